@@ -987,6 +987,20 @@ fn bench_table_render_wide(cols: usize) -> impl IntoBenchmarks {
     })]
 }
 
+// Benchmark the explore pager laying out and drawing a table, a row further down each frame.
+
+fn bench_explore_scroll(rows: usize, cols: usize, frames: usize) -> impl IntoBenchmarks {
+    let name = format!("explore_scroll_{rows}x{cols}_{frames}_frames");
+    [benchmark_fn(name, move |b| {
+        let engine_state = setup_engine();
+        let stack = Stack::new();
+        b.iter(move || {
+            nu_explore::bench::scroll_table(&engine_state, &stack, (rows, cols), (200, 50), frames)
+                .unwrap();
+        })
+    })]
+}
+
 tango_benchmarks!(
     bench_load_standard_lib(),
     bench_load_use_standard_lib(),
@@ -1120,7 +1134,12 @@ tango_benchmarks!(
     bench_table_render_themed(100, 5),
     bench_table_render_themed(1_000, 5),
     bench_table_render_wide(20),
-    bench_table_render_wide(50)
+    bench_table_render_wide(50),
+    // Pager (nu-explore)
+    bench_explore_scroll(1_000, 10, 1),
+    bench_explore_scroll(100_000, 10, 1),
+    bench_explore_scroll(1_000, 10, 50),
+    bench_explore_scroll(100, 200, 50)
 );
 
 tango_main!();
@@ -78,8 +78,9 @@ impl Command for JobSpawn {
 
         let (send, recv) = mpsc::channel();
 
+        let thread_job = ThreadJob::new(job_signals, description, send);
         let id = {
-            let thread_job = ThreadJob::new(job_signals, description, send);
+            let thread_job = thread_job.clone();
 
             let id = jobs.add_job(Job::Thread(thread_job.clone()));
 
@@ -100,14 +101,15 @@ impl Command for JobSpawn {
                     Some(Redirection::Pipe(OutDest::Null)),
                     Some(Redirection::Pipe(OutDest::Null)),
                 );
-                ClosureEvalOnce::new_preserve_out_dest(&job_state, &stack, closure)
+                let outcome = ClosureEvalOnce::new_preserve_out_dest(&job_state, &stack, closure)
                     .run_with_input(Value::nothing(head).into_pipeline_data())
-                    .and_then(|data| data.drain())
-                    .unwrap_or_else(|err| {
-                        if !job_state.signals().interrupted() {
-                            report_shell_error(None, &job_state, &err);
-                        }
-                    });
+                    .and_then(|data| thread_job.output(data));
+                if let Err(err) = &outcome
+                    && !job_state.signals().interrupted()
+                {
+                    report_shell_error(None, &job_state, err);
+                }
+                thread_job.finish(outcome);
 
                 {
                     let mut jobs = job_state.jobs.lock().expect("jobs lock is poisoned!");
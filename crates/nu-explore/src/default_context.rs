@@ -1,12 +1,25 @@
-use crate::explore::Explore;
+use crate::explore::{Explore, ExploreCommand};
 use crate::explore_config::ExploreConfigCommand;
 use crate::explore_regex::ExploreRegex;
-use nu_protocol::engine::{EngineState, StateWorkingSet};
+use nu_protocol::{
+    Record, Value,
+    engine::{EngineState, StateWorkingSet},
+};
+use std::{collections::HashMap, fmt, sync::Arc};
 
-pub fn add_explore_context(mut engine_state: EngineState) -> EngineState {
+pub fn add_explore_context(engine_state: EngineState) -> EngineState {
+    add_explore_context_with(engine_state, ExploreContext::default())
+}
+
+/// Like [`add_explore_context`], with the commands and settings of `context` added to
+/// `explore`, for distributions embedding nushell.
+pub fn add_explore_context_with(
+    mut engine_state: EngineState,
+    context: ExploreContext,
+) -> EngineState {
     let delta = {
         let mut working_set = StateWorkingSet::new(&engine_state);
-        working_set.add_decl(Box::new(Explore));
+        working_set.add_decl(Box::new(Explore::new(context)));
         working_set.add_decl(Box::new(ExploreRegex));
         working_set.add_decl(Box::new(ExploreConfigCommand));
         working_set.render()
@@ -18,3 +31,121 @@ pub fn add_explore_context(mut engine_state: EngineState) -> EngineState {
 
     engine_state
 }
+
+/// What a distribution embedding nushell adds to `explore`: `:` commands of its own, and
+/// settings which take the place of the built-in defaults.
+///
+/// The settings are those of `$env.config.explore`, which still has the last word: a key set
+/// there overrides the same one here, records being merged key by key.
+#[derive(Clone, Default)]
+pub struct ExploreContext {
+    commands: Vec<Arc<dyn ExploreCommand>>,
+    config: Record,
+}
+
+impl ExploreContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the command, run with `:` and its name; one named like a built-in command takes
+    /// its place.
+    pub fn with_command(mut self, command: impl ExploreCommand + 'static) -> Self {
+        self.commands.push(Arc::new(command));
+        self
+    }
+
+    /// Settings as set in `$env.config.explore`, like `{ table: { show_index: true } }`.
+    pub fn with_config(mut self, config: Record) -> Self {
+        merge_record(&mut self.config, config);
+        self
+    }
+
+    /// Colors as set in `$env.config.explore`, like `{ selected_cell: { bg: blue } }` or
+    /// `{ status: { error: red } }`; the same as settings, told apart for readability.
+    pub fn with_theme(self, theme: Record) -> Self {
+        self.with_config(theme)
+    }
+
+    pub(crate) fn commands(&self) -> &[Arc<dyn ExploreCommand>] {
+        &self.commands
+    }
+
+    /// `explore` of the user's config, over the settings of the context.
+    pub(crate) fn apply_config(&self, explore: &mut HashMap<String, Value>) {
+        for (key, default) in self.config.iter() {
+            match explore.get_mut(key) {
+                Some(Value::Record { val, .. }) => {
+                    if let Value::Record { val: default, .. } = default {
+                        let mut merged = (**default).clone();
+                        merge_record(&mut merged, (**val).clone());
+                        *val.to_mut() = merged;
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    explore.insert(key.clone(), default.clone());
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ExploreContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let commands: Vec<_> = self.commands.iter().map(|command| command.name()).collect();
+        f.debug_struct("ExploreContext")
+            .field("commands", &commands)
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+// `over`'s keys replace those of `record`, but records which are merged in turn
+fn merge_record(record: &mut Record, over: Record) {
+    for (key, value) in over {
+        match (record.get_mut(&key), value) {
+            (Some(Value::Record { val, .. }), Value::Record { val: over, .. }) => {
+                merge_record(val.to_mut(), over.into_owned());
+            }
+            (Some(current), value) => *current = value,
+            (None, value) => record.push(key, value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::record;
+
+    #[test]
+    fn user_config_over_the_context() {
+        let context = ExploreContext::new()
+            .with_config(record! {
+                "table" => Value::test_record(record! {
+                    "show_index" => Value::test_bool(true),
+                    "column_padding_left" => Value::test_int(2),
+                }),
+                "mouse" => Value::test_bool(false),
+            })
+            .with_theme(record! {
+                "selected_cell" => Value::test_record(record! { "bg" => Value::test_string("blue") }),
+            });
+
+        let mut explore = HashMap::from([
+            (
+                "table".to_string(),
+                Value::test_record(record! { "show_index" => Value::test_bool(false) }),
+            ),
+            ("mouse".to_string(), Value::test_bool(true)),
+        ]);
+        context.apply_config(&mut explore);
+
+        let table = explore["table"].as_record().unwrap();
+        assert_eq!(table.get("show_index"), Some(&Value::test_bool(false)));
+        assert_eq!(table.get("column_padding_left"), Some(&Value::test_int(2)));
+        assert_eq!(explore["mouse"], Value::test_bool(true));
+        assert!(explore.contains_key("selected_cell"));
+    }
+}
@@ -0,0 +1,74 @@
+//! Entry points for the benchmarks of the pager, in the workspace's `benches`.
+//!
+//! They aren't an API, only a way to reach the views from outside of the crate.
+
+use super::{
+    ExploreConfig,
+    nu_common::create_lscolors,
+    views::{Layout, RecordView, View, ViewConfig},
+};
+use anyhow::Result;
+use nu_color_config::StyleComputer;
+use nu_protocol::{
+    Span, Value,
+    engine::{EngineState, Stack},
+};
+use ratatui::{Terminal, backend::TestBackend};
+
+/// A `rows` by `columns` table mixing the types explore shows: ints, strings, floats,
+/// booleans and file sizes, one after the other across the columns.
+pub fn synthesize_table(rows: usize, columns: usize) -> (Vec<String>, Vec<Vec<Value>>) {
+    let span = Span::unknown();
+    let names = (0..columns)
+        .map(|column| format!("column_{column}"))
+        .collect();
+    let rows = (0..rows)
+        .map(|row| {
+            (0..columns)
+                .map(|column| {
+                    let n = (row * columns + column) as i64;
+                    match column % 5 {
+                        0 => Value::int(n, span),
+                        1 => Value::string(format!("value_{row}_{column}"), span),
+                        2 => Value::float(n as f64 / 7.0, span),
+                        3 => Value::bool(n % 2 == 0, span),
+                        _ => Value::filesize(n * 1024, span),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    (names, rows)
+}
+
+/// Open a synthesized table in a `width` by `height` screen and draw `frames` frames of it,
+/// a row further down each time, the way the pager lays out and renders it while scrolling.
+pub fn scroll_table(
+    engine_state: &EngineState,
+    stack: &Stack,
+    (rows, columns): (usize, usize),
+    (width, height): (u16, u16),
+    frames: usize,
+) -> Result<()> {
+    let nu_config = stack.get_config(engine_state);
+    let explore_config = ExploreConfig::from_nu_config(&nu_config);
+    let style_computer = StyleComputer::from_config(engine_state, stack);
+    let (lscolors, _) = create_lscolors(engine_state, stack);
+
+    let (names, values) = synthesize_table(rows, columns);
+    let mut view = RecordView::new(names, values, explore_config.clone());
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+
+    for _ in 0..frames {
+        terminal.draw(|f| {
+            let cfg = ViewConfig::new(&nu_config, &explore_config, &style_computer, &lscolors, "");
+            let mut layout = Layout::default();
+            view.draw(f, f.area(), cfg, &mut layout);
+        })?;
+
+        view.get_top_layer_mut().cursor.next_row();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,717 @@
+//! The keymap: the keys the pager and its views handle, and the ones bound in
+//! `$env.config.explore.keybindings` to commands or to the default actions, listed by
+//! `explore --list-bindings` and `:bindings`.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use nu_protocol::{IntoValue, Value};
+
+/// Modifiers as they're written in keys, like `ctrl+alt+x`.
+pub const MODIFIERS: [(KeyModifiers, &str); 3] = [
+    (KeyModifiers::CONTROL, "ctrl"),
+    (KeyModifiers::ALT, "alt"),
+    (KeyModifiers::SHIFT, "shift"),
+];
+
+// the views moving a cursor, which all have the keys of `CURSOR_KEYS`
+const CURSOR_VIEWS: &[&str] = &["table", "preview", "binary", "histogram"];
+
+const CURSOR_KEYS: &[(&str, &str)] = &[
+    ("up", "move up"),
+    ("k", "move up"),
+    ("ctrl+p", "move up"),
+    ("down", "move down"),
+    ("j", "move down"),
+    ("ctrl+n", "move down"),
+    ("left", "move left"),
+    ("h", "move left"),
+    ("right", "move right"),
+    ("l", "move right"),
+    ("home", "go to the top"),
+    ("g", "go to the top"),
+    ("end", "go to the bottom"),
+    ("G", "go to the bottom"),
+    ("pageup", "page up"),
+    ("ctrl+b", "page up"),
+    ("alt+v", "page up"),
+    ("pagedown", "page down"),
+    ("ctrl+f", "page down"),
+    ("ctrl+v", "page down"),
+    ("esc", "go back"),
+    ("q", "go back"),
+];
+
+const VIEW_KEYS: &[(&str, &str, &str)] = &[
+    ("enter", "select the cell, then drill into it", "table"),
+    ("i", "select the cell, then drill into it", "table"),
+    ("t", "transpose", "table:view"),
+    ("e", "expand nested data", "table"),
+    ("a", "show all rows of an abbreviated table", "table"),
+    ("c", ":col", "table"),
+    ("tab", "switch between the cursor and view modes", "table"),
+    ("f", ":filter by the selected cell's column", "table:cursor"),
+    ("o", "sort by the column, again to reverse", "table:cursor"),
+    (
+        "o",
+        "sort the fields of a record by name, again to reverse",
+        "table",
+    ),
+    ("O", ":open the link of the selected cell", "table:cursor"),
+    ("x", ":menu of the selected cell", "table:cursor"),
+    ("y", ":copy the selected cell", "table:cursor"),
+    ("Y", ":copy the row of the selected cell", "table:cursor"),
+    (
+        "alt+y",
+        ":copy the column of the selected cell",
+        "table:cursor",
+    ),
+    ("F", "facets", "table"),
+    ("r", ":rotate", "table"),
+    ("p", "pin the selected row", "table:cursor"),
+    (
+        "P",
+        "freeze the selected row to compare the others to",
+        "table:cursor",
+    ),
+    ("m", "mark the selected row", "table:cursor"),
+    ("v", "select a range", "table:cursor"),
+    (
+        "shift+left",
+        "scroll the text of the selected cell back",
+        "table:cursor",
+    ),
+    (
+        "shift+right",
+        "scroll the text of the selected cell",
+        "table:cursor",
+    ),
+    ("alt+left", "move the selected column left", "table:cursor"),
+    (
+        "alt+right",
+        "move the selected column right",
+        "table:cursor",
+    ),
+    ("alt+up", "move the selected row up", "table:cursor"),
+    ("alt+down", "move the selected row down", "table:cursor"),
+    ("+", "expand a level more", "preview"),
+    ("-", "expand a level less", "preview"),
+    ("enter", "run the pipeline", "try"),
+    ("tab", "switch between the pipeline and the results", "try"),
+    ("ctrl+r", "paste a text from :registers", "try"),
+    (
+        "ctrl+l",
+        "switch between the stacked and side by side layouts",
+        "try",
+    ),
+    ("ctrl+left", "give the pipeline less room", "try"),
+    ("ctrl+up", "give the pipeline less room", "try"),
+    ("ctrl+right", "give the pipeline more room", "try"),
+    ("ctrl+down", "give the pipeline more room", "try"),
+    ("ctrl+o", "maximize the results, again to restore", "try"),
+    ("esc", "go back", "try"),
+    ("i", "edit the bytes", "binary:view"),
+    ("u", "undo the last edit", "binary:view"),
+    (
+        "d",
+        "show the bytes at the cursor as numbers, text and a time",
+        "binary:view",
+    ),
+    (
+        "tab",
+        "switch between the hex and ASCII panes",
+        "binary:edit",
+    ),
+    ("backspace", "undo the last edit", "binary:edit"),
+    ("esc", "stop editing", "binary:edit"),
+    ("+", "zoom in", "chart"),
+    ("up", "zoom in", "chart"),
+    ("-", "zoom out", "chart"),
+    ("down", "zoom out", "chart"),
+    ("left", "pan left", "chart"),
+    ("right", "pan right", "chart"),
+    ("0", "show all of it", "chart"),
+    ("b", "switch between lines and bars", "chart"),
+    ("esc", "go back", "chart"),
+    (":", "type a command", "all"),
+    ("ctrl+k", ":palette of the commands", "all"),
+    ("s", "switch what the status bar shows", "all"),
+    ("/", "search", "all"),
+    ("?", "search backwards", "all"),
+    ("n", "next search result", "all"),
+    ("N", "previous search result", "all"),
+    ("ctrl+c", "quit", "all"),
+    ("ctrl+d", "quit", "all"),
+    ("ctrl+q", "quit", "all"),
+];
+
+// the keys of the `vim` preset; the defaults already move with hjkl, go to the bottom with G,
+// search with / and quit with :q
+const VIM_KEYS: &[(&str, &str)] = &[
+    ("g g", "go to the top"),
+    ("ctrl+d", "page down"),
+    ("ctrl+u", "page up"),
+    ("ctrl+e", "move down"),
+    ("ctrl+y", "move up"),
+];
+
+#[cfg(unix)]
+const SUSPEND_KEYS: &[(&str, &str, &str)] = &[("ctrl+z", "suspend to the shell", "all")];
+#[cfg(not(unix))]
+const SUSPEND_KEYS: &[(&str, &str, &str)] = &[];
+
+/// Keys bound in `explore.keybindings`, pressed one after the other: to a command run as if
+/// typed after `:`, to one of the default actions, or to nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    /// One key, or a sequence of them, like `g g`
+    pub keys: Vec<KeyEvent>,
+    pub action: KeyAction,
+    /// The kind of view it's bound in, `all` for any, and maybe the mode of the view too,
+    /// like `table:cursor`
+    pub view: String,
+}
+
+/// What keys bound in `explore.keybindings` do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyAction {
+    /// A command, run as if typed after `:`
+    Command(String),
+    /// A default action, by its name in the keymap, like `move down` or `quit`
+    Default(String),
+    /// Nothing, turning the default action of the keys off
+    Nothing,
+}
+
+impl KeyBinding {
+    /// Read an entry of `explore.keybindings`, like `{ key: ctrl+s, command: "nu save a.json" }`
+    /// or `{ key: "g g", action: "go to the top" }`.
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let record = value.as_record().ok()?;
+        let keys = record.get("key")?.coerce_str().ok()?;
+        let keys = keys
+            .split_whitespace()
+            .map(parse_key)
+            .collect::<Option<Vec<_>>>()
+            .filter(|keys| !keys.is_empty())?;
+
+        let action = match (record.get("command"), record.get("action")) {
+            (Some(command), None) => KeyAction::Command(command.coerce_string().ok()?),
+            (None, Some(action)) => match action.coerce_str().ok()?.as_ref() {
+                "none" => KeyAction::Nothing,
+                action if is_default_action(action) => KeyAction::Default(action.to_string()),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let view = match record.get("view") {
+            Some(view) => view.coerce_string().ok()?,
+            None => String::from("all"),
+        };
+
+        Some(Self { keys, action, view })
+    }
+
+    /// Whether it applies to `keys` in a view of the given kind, followed by its mode if it
+    /// has one, like `table:view`.
+    pub fn matches(&self, keys: &[KeyEvent], view: &str) -> bool {
+        self.keys.len() == keys.len() && self.starts_with(keys, view)
+    }
+
+    /// Whether `keys` are its first keys, or all of them, in a view of the given kind.
+    pub fn starts_with(&self, keys: &[KeyEvent], view: &str) -> bool {
+        applies(&self.view, view)
+            && self.keys.len() >= keys.len()
+            && self.keys.iter().zip(keys).all(|(a, b)| same_key(a, b))
+    }
+}
+
+/// The bindings of a preset of `explore.keybinding_preset`, like `vim`, in the views moving
+/// a cursor.
+pub fn preset(name: &str) -> Option<Vec<KeyBinding>> {
+    let keys = match name {
+        "vim" => VIM_KEYS,
+        _ => return None,
+    };
+
+    let bindings = CURSOR_VIEWS.iter().flat_map(|view| {
+        keys.iter().map(move |(keys, action)| KeyBinding {
+            keys: keys.split(' ').filter_map(parse_key).collect(),
+            action: KeyAction::Default(action.to_string()),
+            view: view.to_string(),
+        })
+    });
+    Some(bindings.collect())
+}
+
+// whether something bound in `bound`, like `all` or `table`, is in the view `view`
+fn applies(bound: &str, view: &str) -> bool {
+    let kind = view.split_once(':').map_or(view, |(kind, _)| kind);
+    bound == "all" || bound == view || bound == kind
+}
+
+fn same_key(a: &KeyEvent, b: &KeyEvent) -> bool {
+    // a shifted character says so by its case already
+    let modifiers = |key: &KeyEvent| match key.code {
+        KeyCode::Char(_) => key.modifiers.difference(KeyModifiers::SHIFT),
+        _ => key.modifiers,
+    };
+
+    a.code == b.code && modifiers(a) == modifiers(b)
+}
+
+/// What a key pressed after those of `pending` does, by `bindings`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Pressed<'a> {
+    /// The keys complete a binding.
+    Bound(&'a KeyAction),
+    /// The keys start a sequence, which the next key may complete.
+    Pending,
+    /// No binding has the key, which does what it does by default.
+    Unbound,
+}
+
+/// Add `key` to the keys pressed so far, `pending`, and tell what they're bound to in `view`.
+/// A binding of fewer keys is found before the longer ones it starts, and a sequence broken
+/// off is dropped, its last key pressed on its own.
+pub fn press<'a>(
+    bindings: &'a [KeyBinding],
+    pending: &mut Vec<KeyEvent>,
+    key: KeyEvent,
+    view: &str,
+) -> Pressed<'a> {
+    pending.push(key);
+    loop {
+        if let Some(binding) = bindings
+            .iter()
+            .find(|binding| binding.matches(pending, view))
+        {
+            pending.clear();
+            return Pressed::Bound(&binding.action);
+        }
+        if bindings
+            .iter()
+            .any(|binding| binding.starts_with(pending, view))
+        {
+            return Pressed::Pending;
+        }
+        if pending.len() == 1 {
+            pending.clear();
+            return Pressed::Unbound;
+        }
+        pending.drain(..pending.len() - 1);
+    }
+}
+
+// the default keys of every view, with the actions they do
+fn default_keys() -> impl Iterator<Item = (&'static str, &'static str, &'static str)> {
+    let cursor_keys = CURSOR_VIEWS.iter().flat_map(|view| {
+        CURSOR_KEYS
+            .iter()
+            .map(move |(key, action)| (*key, *action, *view))
+    });
+    cursor_keys.chain(VIEW_KEYS.iter().chain(SUSPEND_KEYS).copied())
+}
+
+fn is_default_action(action: &str) -> bool {
+    default_keys().any(|(_, default, _)| default == action)
+}
+
+/// The first of the default keys doing `action` in `view`, pressed for keys bound to it.
+pub fn default_key(action: &str, view: &str) -> Option<KeyEvent> {
+    default_keys()
+        .filter(|(_, default, bound)| *default == action && applies(bound, view))
+        .find_map(|(key, _, _)| parse_key(key))
+}
+
+/// What became of a default key once the bindings of the config are in effect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rebound {
+    /// It still does its action.
+    Same,
+    /// Its action is done by these keys instead.
+    Moved(String),
+    /// No key does its action anymore.
+    Off,
+}
+
+/// What became of `key`, a default key doing `action` in `view`, by `config_bindings`: for
+/// what tells of the keys, the hint bar and the help.
+pub fn rebound(key: &str, action: &str, view: &str, config_bindings: &[KeyBinding]) -> Rebound {
+    let shadowed = |key: KeyEvent| {
+        config_bindings
+            .iter()
+            .any(|binding| binding.starts_with(&[key], view))
+    };
+    if parse_key(key).is_none_or(|key| !shadowed(key)) {
+        return Rebound::Same;
+    }
+
+    let bound = config_bindings.iter().find(|binding| {
+        applies(&binding.view, view) && binding.action == KeyAction::Default(action.to_string())
+    });
+    if let Some(binding) = bound {
+        let keys: Vec<_> = binding.keys.iter().map(key_text).collect();
+        return Rebound::Moved(keys.join(" "));
+    }
+
+    default_keys()
+        .filter(|(_, default, bound)| *default == action && applies(bound, view))
+        .find(|(key, _, _)| parse_key(key).is_some_and(|key| !shadowed(key)))
+        .map_or(Rebound::Off, |(key, _, _)| Rebound::Moved(key.to_string()))
+}
+
+/// A row of the keymap.
+#[derive(Debug, Clone, PartialEq, Eq, IntoValue)]
+pub struct Binding {
+    pub key: String,
+    pub command: String,
+    pub view: String,
+    pub source: Source,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoValue)]
+pub enum Source {
+    Default,
+    Config,
+}
+
+/// Every key in effect: the defaults that aren't bound to something else in the config,
+/// or the start of a sequence, then the config's bindings.
+pub fn keymap(config_bindings: &[KeyBinding]) -> Vec<Binding> {
+    let defaults = default_keys()
+        .filter(|(key, _, view)| {
+            let Some(key) = parse_key(key) else {
+                return false;
+            };
+            !config_bindings
+                .iter()
+                .any(|binding| binding.starts_with(&[key], view))
+        })
+        .map(|(key, command, view)| Binding {
+            key: key.to_string(),
+            command: command.to_string(),
+            view: view.to_string(),
+            source: Source::Default,
+        });
+
+    let configured = config_bindings.iter().map(|binding| Binding {
+        key: binding
+            .keys
+            .iter()
+            .map(key_text)
+            .collect::<Vec<_>>()
+            .join(" "),
+        command: match &binding.action {
+            KeyAction::Command(command) => format!(":{command}"),
+            KeyAction::Default(action) => action.clone(),
+            KeyAction::Nothing => String::from("nothing"),
+        },
+        view: binding.view.clone(),
+        source: Source::Config,
+    });
+
+    defaults.chain(configured).collect()
+}
+
+/// Read a key like `g`, `enter`, `f5` or `ctrl+alt+x`.
+pub fn parse_key(text: &str) -> Option<KeyEvent> {
+    // `+` is a key too, as in `ctrl++`
+    let (modifiers, code) = if text == "+" {
+        ("", text)
+    } else if let Some(modifiers) = text.strip_suffix("++") {
+        (modifiers, "+")
+    } else {
+        text.rsplit_once('+').unwrap_or(("", text))
+    };
+
+    let mut key = KeyEvent::new(key_code(code)?, KeyModifiers::NONE);
+    for name in modifiers.split('+').filter(|name| !name.is_empty()) {
+        let (modifier, _) = MODIFIERS
+            .iter()
+            .find(|(_, known)| known.eq_ignore_ascii_case(name))?;
+        key.modifiers |= *modifier;
+    }
+
+    Some(key)
+}
+
+/// Write a key the way [`parse_key`] reads it.
+pub fn key_text(key: &KeyEvent) -> String {
+    let mut text = String::new();
+    for (modifier, name) in MODIFIERS {
+        if key.modifiers.contains(modifier) {
+            text.push_str(name);
+            text.push('+');
+        }
+    }
+    text.push_str(&key_name(key.code).unwrap_or_default());
+
+    text
+}
+
+/// The name of a key without modifiers, if it's one explore can bind.
+pub fn key_name(code: KeyCode) -> Option<String> {
+    let name = match code {
+        // as keys of a sequence are written between spaces
+        KeyCode::Char(' ') => "space",
+        KeyCode::Char(c) => return Some(c.to_string()),
+        KeyCode::F(n) => return Some(format!("f{n}")),
+        KeyCode::Enter => "enter",
+        KeyCode::Esc => "esc",
+        KeyCode::Backspace => "backspace",
+        KeyCode::Delete => "delete",
+        KeyCode::Insert => "insert",
+        KeyCode::Tab => "tab",
+        KeyCode::BackTab => "backtab",
+        KeyCode::Up => "up",
+        KeyCode::Down => "down",
+        KeyCode::Left => "left",
+        KeyCode::Right => "right",
+        KeyCode::Home => "home",
+        KeyCode::End => "end",
+        KeyCode::PageUp => "pageup",
+        KeyCode::PageDown => "pagedown",
+        _ => return None,
+    };
+
+    Some(name.to_string())
+}
+
+pub fn key_code(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    let code = match name.to_ascii_lowercase().as_str() {
+        "space" => KeyCode::Char(' '),
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        name => return name.strip_prefix('f')?.parse().ok().map(KeyCode::F),
+    };
+
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::Record;
+
+    #[test]
+    fn keys_round_trip() {
+        for text in [
+            "g",
+            "G",
+            "enter",
+            "f5",
+            "ctrl+alt+x",
+            "+",
+            "ctrl++",
+            "shift+tab",
+            "ctrl+space",
+        ] {
+            let key = parse_key(text).unwrap();
+            assert_eq!(key_text(&key), text);
+        }
+
+        assert_eq!(parse_key("hyper+x"), None);
+        assert_eq!(parse_key("ctrl+"), None);
+    }
+
+    #[test]
+    fn config_bindings_replace_defaults() {
+        let binding = KeyBinding {
+            keys: vec![parse_key("ctrl+f").unwrap()],
+            action: KeyAction::Command(String::from("filter status open")),
+            view: String::from("table"),
+        };
+        let sequence = KeyBinding {
+            keys: vec![parse_key("g").unwrap(), parse_key("g").unwrap()],
+            action: KeyAction::Default(String::from("go to the top")),
+            view: String::from("all"),
+        };
+
+        let keymap = keymap(&[binding, sequence]);
+        let bound = |key: &str, view: &str| {
+            keymap
+                .iter()
+                .filter(|binding| binding.key == key && binding.view == view)
+                .map(|binding| (binding.command.as_str(), binding.source))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            bound("ctrl+f", "table"),
+            [(":filter status open", Source::Config)]
+        );
+        assert_eq!(bound("ctrl+f", "preview"), [("page down", Source::Default)]);
+        assert_eq!(bound("/", "all"), [("search", Source::Default)]);
+        assert_eq!(bound("g g", "all"), [("go to the top", Source::Config)]);
+        assert!(bound("g", "table").is_empty());
+    }
+
+    #[test]
+    fn bindings_of_a_mode() {
+        let binding = |view: &str| KeyBinding {
+            keys: vec![parse_key("x").unwrap()],
+            action: KeyAction::Command(String::from("unpin")),
+            view: String::from(view),
+        };
+        let x = [parse_key("x").unwrap()];
+
+        assert!(binding("table").matches(&x, "table:cursor"));
+        assert!(binding("table").matches(&x, "table:view"));
+        assert!(binding("table:cursor").matches(&x, "table:cursor"));
+        assert!(!binding("table:cursor").matches(&x, "table:view"));
+        assert!(!binding("table:cursor").matches(&x, "preview"));
+    }
+
+    #[test]
+    fn read_bindings() {
+        let binding = |record: Record| KeyBinding::from_value(&Value::test_record(record));
+        let mut record = Record::new();
+        record.push("key", Value::test_string("g g"));
+        record.push("action", Value::test_string("go to the top"));
+        let top = binding(record.clone()).unwrap();
+        assert_eq!(top.keys.len(), 2);
+        assert_eq!(
+            top.action,
+            KeyAction::Default(String::from("go to the top"))
+        );
+
+        record.insert("action", Value::test_string("none"));
+        assert_eq!(binding(record.clone()).unwrap().action, KeyAction::Nothing);
+
+        record.insert("action", Value::test_string("fly"));
+        assert_eq!(binding(record.clone()), None);
+
+        record.push("command", Value::test_string("unpin"));
+        assert_eq!(binding(record), None, "a command or an action, not both");
+    }
+
+    #[test]
+    fn press_sequences() {
+        let bind = |keys: &str, action: &str| KeyBinding {
+            keys: keys.split(' ').map(|key| parse_key(key).unwrap()).collect(),
+            action: KeyAction::Default(String::from(action)),
+            view: String::from("all"),
+        };
+        let bindings = [bind("g g", "go to the top"), bind("z", "quit")];
+        let key = |key: &str| parse_key(key).unwrap();
+        let mut pending = Vec::new();
+
+        assert_eq!(
+            press(&bindings, &mut pending, key("g"), "table"),
+            Pressed::Pending
+        );
+        assert_eq!(
+            press(&bindings, &mut pending, key("g"), "table"),
+            Pressed::Bound(&bindings[0].action)
+        );
+        assert!(pending.is_empty());
+
+        // broken off by a key bound on its own
+        press(&bindings, &mut pending, key("g"), "table");
+        assert_eq!(
+            press(&bindings, &mut pending, key("z"), "table"),
+            Pressed::Bound(&bindings[1].action)
+        );
+        press(&bindings, &mut pending, key("g"), "table");
+        assert_eq!(
+            press(&bindings, &mut pending, key("j"), "table"),
+            Pressed::Unbound
+        );
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn vim_preset() {
+        let bindings = preset("vim").unwrap();
+        let mut pending = Vec::new();
+        // the default key a key stands for
+        let mut key_of = |key: &str, view: &str| {
+            let key = parse_key(key).unwrap();
+            match press(&bindings, &mut pending, key, view) {
+                Pressed::Bound(KeyAction::Default(action)) => default_key(action, view),
+                _ => None,
+            }
+        };
+
+        assert_eq!(key_of("ctrl+d", "table:cursor"), parse_key("pagedown"));
+        assert_eq!(key_of("ctrl+u", "binary:view"), parse_key("pageup"));
+        assert_eq!(key_of("g", "preview"), None);
+        assert_eq!(key_of("g", "preview"), parse_key("home"));
+        assert_eq!(key_of("ctrl+d", "try"), None, "quits where nothing scrolls");
+
+        // every action of it is one of the views
+        for binding in &bindings {
+            let KeyAction::Default(action) = &binding.action else {
+                panic!("{binding:?} isn't an action");
+            };
+            assert!(default_key(action, &binding.view).is_some(), "{action}");
+        }
+        assert!(preset("emacs").is_none());
+    }
+
+    #[test]
+    fn default_keys_of_actions() {
+        assert_eq!(
+            default_key("go to the top", "table:cursor"),
+            parse_key("home")
+        );
+        assert_eq!(default_key("transpose", "table:view"), parse_key("t"));
+        assert_eq!(default_key("transpose", "preview"), None);
+        assert_eq!(default_key("quit", "try"), parse_key("ctrl+c"));
+    }
+
+    #[test]
+    fn rebound_default_keys() {
+        let bind = |key: &str, action: KeyAction| KeyBinding {
+            keys: vec![parse_key(key).unwrap()],
+            action,
+            view: String::from("table"),
+        };
+        let bindings = [
+            bind("t", KeyAction::Command(String::from("unpin"))),
+            bind("T", KeyAction::Default(String::from("transpose"))),
+            bind("enter", KeyAction::Nothing),
+            bind("f", KeyAction::Nothing),
+        ];
+        let rebound = |key: &str, action: &str, view: &str| rebound(key, action, view, &bindings);
+
+        assert_eq!(
+            rebound("t", "transpose", "table:view"),
+            Rebound::Moved(String::from("T"))
+        );
+        assert_eq!(
+            rebound("e", "expand nested data", "table:view"),
+            Rebound::Same
+        );
+        assert_eq!(
+            rebound(
+                "enter",
+                "select the cell, then drill into it",
+                "table:cursor"
+            ),
+            Rebound::Moved(String::from("i"))
+        );
+        assert_eq!(
+            rebound("f", ":filter by the selected cell's column", "table:cursor"),
+            Rebound::Off
+        );
+        assert_eq!(rebound("enter", "run the pipeline", "try"), Rebound::Same);
+    }
+}
@@ -1,17 +1,50 @@
 //! The explore command implementation.
 
-use crate::explore::config::ExploreConfig;
-use crate::explore::nu_common::create_lscolors;
-use crate::explore::pager::PagerConfig;
-use crate::explore::run_pager;
+use crate::ExploreContext;
+use crate::explore::bindings::keymap;
+use crate::explore::commands::ExtensionCmd;
+use crate::explore::config::{ExploreConfig, Preset};
+use crate::explore::deterministic;
+use crate::explore::l10n::{self, tr};
+use crate::explore::nu_common::{create_lscolors, create_style_computer, without_color_closures};
+use crate::explore::pager::{PagerConfig, session::Session, trace::Trace};
+use crate::explore::recent::{Recent, RecentEntry, SourceKind};
+use crate::explore::{help_reference, run_pager};
 use nu_ansi_term::Style;
 use nu_color_config::StyleComputer;
 use nu_engine::command_prelude::*;
-use nu_protocol::shell_error::generic::GenericError;
+use nu_protocol::{
+    DataSource, JobId,
+    engine::{Job, StateWorkingSet},
+    shell_error::generic::GenericError,
+};
+use std::{
+    path::PathBuf,
+    sync::{Arc, LazyLock},
+};
+
+// the commands and keys are only listed once they're asked for
+static EXTRA_DESCRIPTION: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        "Press `:` then `h` to get a help menu.\n\nOnce explore is done, `$env.EXPLORE_LAST` \
+         tells how: the exit (quit, back, interrupted or error), the time it took, whether a \
+         value was peeked, and the view left open with its filters, sort and selection.\n\n{}",
+        help_reference()
+    )
+});
 
 /// A `less` like program to render a [`Value`] as a table.
-#[derive(Clone)]
-pub struct Explore;
+#[derive(Clone, Default)]
+pub struct Explore {
+    // what the distribution embedding nushell added, see `add_explore_context_with`
+    context: ExploreContext,
+}
+
+impl Explore {
+    pub fn new(context: ExploreContext) -> Self {
+        Self { context }
+    }
+}
 
 impl Command for Explore {
     fn name(&self) -> &str {
@@ -45,11 +78,74 @@ impl Command for Explore {
                 "When quitting, output the value of the cell the cursor was on.",
                 Some('p'),
             )
+            .named(
+                "try",
+                SyntaxShape::String,
+                "Start by trying a pipeline on the input, '' for none yet; the input is only read once one runs.",
+                None,
+            )
+            .named(
+                "expand",
+                SyntaxShape::Int,
+                "Start with the nested data expanded this many levels, like `:expand <depth>`; `+` and `-` expand a level more or less.",
+                None,
+            )
+            .named(
+                "preset",
+                SyntaxShape::String,
+                "Start with the filters, sort and columns of a preset saved with `:preset save`.",
+                None,
+            )
+            .named(
+                "on-start",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "Run these `:` commands once the table is open, in order, like `[':sort time' ':filter level ERROR']`.",
+                None,
+            )
+            .named(
+                "record",
+                SyntaxShape::Filepath,
+                "Save the keys pressed, the terminal sizes and the commands run to a JSON trace, without the data.",
+                None,
+            )
+            .named(
+                "replay",
+                SyntaxShape::Filepath,
+                "Play the keys of a trace saved with --record, before reading any from the terminal.",
+                None,
+            )
+            .named(
+                "recent",
+                SyntaxShape::Int,
+                "Explore a recent source again, 0 being the latest; `:recent` lists them.",
+                None,
+            )
+            .switch(
+                "watch-file",
+                "Read the file explored again whenever it changes, keeping the filters, sort, columns and position.",
+                None,
+            )
+            .named(
+                "follow",
+                SyntaxShape::Int,
+                "Explore what a background job outputs, or sends with `job send 0 --tag <id>`, the rows showing up as they come, until it's done or fails.",
+                None,
+            )
+            .switch(
+                "no-eval",
+                "Disable what runs code, like :nu, :try and closures, keeping navigation and export; `explore.no_eval` does it for good.",
+                None,
+            )
+            .switch(
+                "list-bindings",
+                "Output the keys explore handles and the commands bound in the config, instead of opening it.",
+                None,
+            )
             .category(Category::Viewers)
     }
 
     fn extra_description(&self) -> &str {
-        "Press `:` then `h` to get a help menu."
+        &EXTRA_DESCRIPTION
     }
 
     fn run(
@@ -63,21 +159,154 @@ impl Command for Explore {
         let show_index: bool = call.has_flag(engine_state, stack, "index")?;
         let tail: bool = call.has_flag(engine_state, stack, "tail")?;
         let peek_value: bool = call.has_flag(engine_state, stack, "peek")?;
+        let expand: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "expand")?;
+        let preset: Option<Spanned<String>> = call.get_flag(engine_state, stack, "preset")?;
+        let try_command: Option<String> = call.get_flag(engine_state, stack, "try")?;
+        let on_start: Option<Vec<String>> = call.get_flag(engine_state, stack, "on-start")?;
+        let record: Option<Spanned<String>> = call.get_flag(engine_state, stack, "record")?;
+        let replay: Option<Spanned<String>> = call.get_flag(engine_state, stack, "replay")?;
+        let list_bindings: bool = call.has_flag(engine_state, stack, "list-bindings")?;
+        let reopened: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "recent")?;
+        let no_eval: bool = call.has_flag(engine_state, stack, "no-eval")?;
+        let watch_file: bool = call.has_flag(engine_state, stack, "watch-file")?;
+        let follow: Option<Spanned<i64>> = call.get_flag(engine_state, stack, "follow")?;
 
-        let nu_config = stack.get_config(engine_state);
-        let style_computer = StyleComputer::from_config(engine_state, stack);
-
+        let mut nu_config = stack.get_config(engine_state);
+        self.context
+            .apply_config(&mut Arc::make_mut(&mut nu_config).explore);
         let mut explore_config = ExploreConfig::from_nu_config(&nu_config);
+        // the flag can only add to the config, which locks it on
+        explore_config.no_eval |= no_eval;
+
+        // the closures of color_config are code as well
+        let style_stack = explore_config
+            .no_eval
+            .then(|| without_color_closures(engine_state, stack));
+        let (style_computer, style_warning) =
+            create_style_computer(engine_state, style_stack.as_ref().unwrap_or(stack));
         explore_config.table.show_header = show_head;
         explore_config.table.show_index = show_index;
         explore_config.table.separator_style = lookup_color(&style_computer, "separator");
+        l10n::set_catalog(explore_config.catalog.clone());
+
+        if list_bindings {
+            let bindings = keymap(&explore_config.keybindings).into_value(call.head);
+            return Ok(PipelineData::value(bindings, None));
+        }
 
-        let lscolors = create_lscolors(engine_state, stack);
+        let (lscolors, lscolors_warning) = create_lscolors(engine_state, stack);
         let cwd = engine_state.cwd(Some(stack)).map_or(String::new(), |path| {
             path.to_str().unwrap_or("").to_string()
         });
 
-        let config = PagerConfig::new(
+        // a recent source is read again in place of the input, with its preset if it's still
+        // saved
+        let (input, recent, preset) = match reopened {
+            Some(index) => {
+                let no_eval = explore_config.no_eval;
+                // the styles borrow the stack, so the source is read on a copy of it
+                let mut stack = stack.clone();
+                let (input, entry) = reopen_recent(engine_state, &mut stack, index.item, no_eval)
+                    .map_err(|err| {
+                    ShellError::Generic(GenericError::new(
+                        "Can't explore the recent source",
+                        format!("{err:#}"),
+                        index.span,
+                    ))
+                })?;
+                let preset = preset.or_else(|| {
+                    let name = entry.preset.clone()?;
+                    Preset::find(&nu_config, &name).ok()?;
+                    Some(Spanned {
+                        item: name,
+                        span: index.span,
+                    })
+                });
+                (input, Some(entry), preset)
+            }
+            None => {
+                let recent =
+                    explored_source(engine_state, &input, call.head).map(|(kind, source)| {
+                        let name = preset.as_ref().map(|name| name.item.clone());
+                        RecentEntry::new(kind, source, name)
+                    });
+                (input, recent, preset)
+            }
+        };
+
+        let preset = preset
+            .map(|name| {
+                Preset::find(&nu_config, &name.item).map_err(|err| {
+                    ShellError::Generic(GenericError::new(
+                        "Unknown explore preset",
+                        err.to_string(),
+                        name.span,
+                    ))
+                })
+            })
+            .transpose()?;
+
+        if explore_config.no_eval && try_command.is_some() {
+            return Err(ShellError::Generic(GenericError::new(
+                "Can't try pipelines without evaluation",
+                "--try runs a pipeline, which --no-eval disables",
+                call.head,
+            )));
+        }
+
+        let expand = expand
+            .map(|depth| {
+                usize::try_from(depth.item).map_err(|_| {
+                    ShellError::Generic(GenericError::new(
+                        "Invalid expand depth",
+                        "the depth is a number of levels, 0 or more",
+                        depth.span,
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let is_file = matches!(&recent, Some(entry) if entry.kind == SourceKind::File);
+        if watch_file && !is_file {
+            return Err(ShellError::Generic(GenericError::new(
+                "Can't watch the input",
+                "--watch-file needs the input opened from a file, like `open data.csv | explore --watch-file`",
+                call.head,
+            )));
+        }
+
+        let follow = follow
+            .map(|id| {
+                followed_job(engine_state, id.item).map_err(|err| {
+                    ShellError::Generic(GenericError::new(
+                        "Can't follow the job",
+                        err.to_string(),
+                        id.span,
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let in_cwd = |path: &str| -> Result<PathBuf, ShellError> {
+            Ok(engine_state
+                .cwd(Some(stack))?
+                .join(path)
+                .into_std_path_buf())
+        };
+        let replay = replay
+            .map(|path| {
+                Trace::load(&in_cwd(&path.item)?).map_err(|err| {
+                    ShellError::Generic(GenericError::new(
+                        "Invalid explore trace",
+                        format!("{err:#}"),
+                        path.span,
+                    ))
+                })
+            })
+            .transpose()?;
+        let record = record.map(|path| in_cwd(&path.item)).transpose()?;
+
+        let mut config = PagerConfig::new(
             &nu_config,
             &explore_config,
             &style_computer,
@@ -86,8 +315,38 @@ impl Command for Explore {
             tail,
             &cwd,
         );
+        config.preset = preset;
+        config.try_command = try_command;
+        config.on_start = on_start.unwrap_or_default();
+        config.record = record;
+        config.replay = replay;
+        config.recent = recent;
+        config.watch_file = watch_file;
+        config.follow = follow;
+        config.warning = style_warning.or(lscolors_warning);
+        config.expand = expand;
+        let extensions: Vec<ExtensionCmd> = self
+            .context
+            .commands()
+            .iter()
+            .map(|command| ExtensionCmd::new(command.clone()))
+            .collect();
+        config.commands = &extensions;
+
+        let mut pager_stack = stack.clone();
+        let mut session = Session::default();
+        let started = deterministic::now();
+        let result = run_pager(engine_state, &mut pager_stack, input, config, &mut session);
 
-        let result = run_pager(engine_state, &mut stack.clone(), input, config);
+        keep_presets(engine_state, &pager_stack, stack)?;
+
+        let peeked = matches!(result, Ok(Some(_)));
+        let last = session.to_value(
+            deterministic::now().saturating_duration_since(started),
+            peeked,
+            call.head,
+        );
+        stack.add_env_var("EXPLORE_LAST".to_string(), last);
 
         match result {
             Ok(Some(value)) => Ok(PipelineData::value(value, None)),
@@ -128,10 +387,231 @@ impl Command for Explore {
                 example: "open file.json | explore --peek | to json | save part.json",
                 result: None,
             },
+            Example {
+                description: "Explore a table, then tell how the session ended and the filters it left",
+                example: "ls | explore; $env.EXPLORE_LAST | select exit elapsed filters sort",
+                result: None,
+            },
+            Example {
+                description: "Try pipelines on a long listing, which shows up before it's all read",
+                example: "ls **/* | explore --try ''",
+                result: None,
+            },
+            Example {
+                description: "Explore the big files, with the pipeline to tweak it at hand",
+                example: "ls | explore --try 'where size > 1mb'",
+                result: None,
+            },
+            Example {
+                description: "Explore a log with only its errors, the latest last",
+                example: "open app.log.json | explore --on-start [':filter $it.level == \"ERROR\"' ':sort time']",
+                result: None,
+            },
+            Example {
+                description: "Record a session that goes wrong, to replay it later on the same input",
+                example: "open data.json | explore --record trace.json",
+                result: None,
+            },
+            Example {
+                description: "Replay a recorded session, then carry on from where it ended",
+                example: "open data.json | explore --replay trace.json",
+                result: None,
+            },
+            Example {
+                description: "Explore nested data with two levels of it expanded in the table",
+                example: "open data.json | explore --expand 2",
+                result: None,
+            },
+            Example {
+                description: "Explore a table with the filters saved earlier as the `triage` preset",
+                example: "open issues.json | explore --preset triage",
+                result: None,
+            },
+            Example {
+                description: "Explore the latest source explored again, a file or a pipeline",
+                example: "explore --recent 0",
+                result: None,
+            },
+            Example {
+                description: "Explore a CSV file as it's written, the new rows showing up as they come",
+                example: "open requests.csv | explore --watch-file --tail",
+                result: None,
+            },
+            Example {
+                description: "Explore the files a background job finds, as it finds them",
+                example: "let id = job spawn { glob **/*.rs | each { ls $in } | flatten }; explore --follow $id",
+                result: None,
+            },
+            Example {
+                description: "Explore data of unknown origin, with nothing able to run code",
+                example: "open untrusted.json | explore --no-eval",
+                result: None,
+            },
+            Example {
+                description: "Find the keys that move to the bottom of a table",
+                example: "explore --list-bindings | where view == table and command =~ bottom",
+                result: None,
+            },
         ]
     }
 }
 
+/// Read the recent source at `index` again; without evaluation, only if it's a file.
+fn reopen_recent(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    index: i64,
+    no_eval: bool,
+) -> anyhow::Result<(PipelineData, RecentEntry)> {
+    let index = usize::try_from(index)
+        .map_err(|_| anyhow::anyhow!(tr!("{index} isn't an index", index = index)))?;
+    let path = Recent::path().ok_or_else(|| anyhow::anyhow!(tr!("there's no config directory")))?;
+    let entry = Recent::load(&path)?.get(index)?.clone();
+    if no_eval && entry.kind == SourceKind::Pipeline {
+        anyhow::bail!(tr!("it's a pipeline, which --no-eval keeps from running"));
+    }
+    let input = entry.reopen(engine_state, stack)?;
+
+    Ok((input, entry))
+}
+
+/// The background job `id`, if it's running and can send anything, which a frozen one can't.
+fn followed_job(engine_state: &EngineState, id: i64) -> anyhow::Result<JobId> {
+    let job_id = usize::try_from(id)
+        .map(JobId::new)
+        .map_err(|_| anyhow::anyhow!(tr!("{id} isn't a job id", id = id)))?;
+    let jobs = engine_state
+        .jobs
+        .lock()
+        .map_err(|_| anyhow::anyhow!(tr!("the jobs are locked")))?;
+    match jobs.lookup(job_id) {
+        Some(Job::Thread(_)) => Ok(job_id),
+        Some(Job::Frozen(_)) => {
+            anyhow::bail!(tr!("job {id} is frozen, `job unfreeze` it first", id = id))
+        }
+        None => anyhow::bail!(tr!("there's no job {id}, `job list` lists them", id = id)),
+    }
+}
+
+/// Where the input comes from: the file it was opened from, or else the pipeline before
+/// explore as it was written, like `ls | where size > 1mb`.
+fn explored_source(
+    engine_state: &EngineState,
+    input: &PipelineData,
+    head: Span,
+) -> Option<(SourceKind, String)> {
+    if let Some(DataSource::FilePath(path)) = input.metadata_ref().map(|m| &m.data_source) {
+        return Some((SourceKind::File, path.to_string_lossy().into_owned()));
+    }
+
+    let file = engine_state
+        .files()
+        .find(|file| file.covered_span.contains_span(head))?;
+    let working_set = StateWorkingSet::new(engine_state);
+    let span = pipeline_before(&working_set, file.covered_span, head)?;
+    let pipeline = String::from_utf8_lossy(working_set.get_span_contents(span));
+    let pipeline = pipeline.trim();
+    (!pipeline.is_empty()).then(|| (SourceKind::Pipeline, pipeline.to_string()))
+}
+
+// the span of what's before the call of explore at `head` in its pipeline, found as the
+// parser splits the code of `span` into pipelines, and then the blocks and subexpressions
+// the call is in
+fn pipeline_before(working_set: &StateWorkingSet, span: Span, head: Span) -> Option<Span> {
+    let code = working_set.get_span_contents(span);
+    let (tokens, _) = nu_parser::lex(code, span.start, &[], &[], false);
+    let (block, _) = nu_parser::lite_parse(&tokens, working_set);
+
+    for pipeline in &block.block {
+        let Some(position) = pipeline
+            .commands
+            .iter()
+            .position(|command| command.parts.iter().any(|part| part.contains_span(head)))
+        else {
+            continue;
+        };
+
+        let command = &pipeline.commands[position];
+        if command.parts.first() == Some(&head) {
+            let before = &pipeline.commands[..position];
+            return Some(
+                before
+                    .first()?
+                    .parts
+                    .first()?
+                    .merge(*before.last()?.parts.last()?),
+            );
+        }
+
+        // further in, between brackets, after the parameters of a closure
+        let part = command.parts.iter().find(|part| part.contains_span(head))?;
+        let inner = working_set.get_span_contents(*part);
+        if !matches!(inner.first(), Some(b'{' | b'(' | b'[')) || part.len() < 2 {
+            return None;
+        }
+        let mut start = part.start + 1;
+        let body = &inner[1..inner.len() - 1];
+        let trimmed = body.trim_ascii_start();
+        if let Some(params) = trimmed.strip_prefix(b"|") {
+            let end = params.iter().position(|&byte| byte == b'|')?;
+            start += body.len() - trimmed.len() + end + 2;
+        }
+        return pipeline_before(working_set, Span::new(start, part.end - 1), head);
+    }
+
+    None
+}
+
+/// Carry presets saved with `:preset save` over from the stack explore ran with.
+fn keep_presets(
+    engine_state: &EngineState,
+    pager_stack: &Stack,
+    stack: &mut Stack,
+) -> Result<(), ShellError> {
+    let pager_config = pager_stack.get_config(engine_state);
+    let saved = Preset::all(&pager_config);
+    if saved == Preset::all(&stack.get_config(engine_state)) {
+        return Ok(());
+    }
+
+    match saved {
+        Some(presets) => Preset::set_all(engine_state, stack, presets.clone()),
+        None => Ok(()),
+    }
+}
+
 fn lookup_color(style_computer: &StyleComputer, key: &str) -> Style {
     style_computer.compute(key, &Value::nothing(Span::unknown()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source_of(code: &str, nth: usize) -> Option<String> {
+        let mut engine_state = EngineState::new();
+        let mut working_set = StateWorkingSet::new(&engine_state);
+        let file = working_set.add_file("source", code.as_bytes());
+        let offset = working_set.get_span_for_file(file).start;
+        let delta = working_set.render();
+        engine_state.merge_delta(delta).unwrap();
+
+        let start = offset + code.match_indices("explore").nth(nth)?.0;
+        let head = Span::new(start, start + "explore".len());
+        explored_source(&engine_state, &PipelineData::empty(), head).map(|(_, source)| source)
+    }
+
+    #[test]
+    fn source_is_the_pipeline_parsed() {
+        let code = "let a = 'x;y|z'; ls | where name != 'a;b|c'  |  explore --index";
+        assert_eq!(
+            source_of(code, 0).as_deref(),
+            Some("ls | where name != 'a;b|c'")
+        );
+
+        let code = "explore; do { |it| ps | explore }; [(open a.json | explore)]";
+        assert_eq!(source_of(code, 0), None);
+        assert_eq!(source_of(code, 1).as_deref(), Some("ps"));
+        assert_eq!(source_of(code, 2).as_deref(), Some("open a.json"));
+    }
+}
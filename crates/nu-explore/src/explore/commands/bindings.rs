@@ -0,0 +1,75 @@
+use super::super::{
+    bindings::keymap,
+    views::{RecordView, ViewConfig},
+};
+use super::ViewCommand;
+use anyhow::Result;
+use nu_protocol::{
+    IntoValue, Span, Value,
+    engine::{EngineState, Stack},
+};
+
+/// The keymap as a table, the rows of `explore --list-bindings`.
+#[derive(Debug, Default, Clone)]
+pub struct BindingsCmd {
+    // only the keys whose key or command contains it
+    text: String,
+}
+
+impl BindingsCmd {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub const NAME: &'static str = "bindings";
+}
+
+impl ViewCommand for BindingsCmd {
+    type View = RecordView;
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "list the keys and the commands bound to them"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        self.text = args.trim().to_lowercase();
+        Ok(())
+    }
+
+    fn spawn(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        _: Option<Value>,
+        config: &ViewConfig,
+    ) -> Result<Self::View> {
+        let columns = ["key", "command", "view", "source"]
+            .map(String::from)
+            .to_vec();
+        let rows = keymap(&config.explore_config.keybindings)
+            .into_iter()
+            .filter(|binding| {
+                binding.key.to_lowercase().contains(&self.text)
+                    || binding.command.to_lowercase().contains(&self.text)
+            })
+            .map(|binding| {
+                vec![
+                    Value::string(binding.key, Span::unknown()),
+                    Value::string(binding.command, Span::unknown()),
+                    Value::string(binding.view, Span::unknown()),
+                    binding.source.into_value(Span::unknown()),
+                ]
+            })
+            .collect();
+
+        Ok(RecordView::new(
+            columns,
+            rows,
+            config.explore_config.clone(),
+        ))
+    }
+}
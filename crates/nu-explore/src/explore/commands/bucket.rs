@@ -0,0 +1,334 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::{SimpleCommand, filter::split_column};
+use anyhow::Result;
+use chrono::DateTime;
+use nu_protocol::{
+    Span, Value,
+    engine::{EngineState, Stack},
+};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Clone)]
+pub struct BucketCmd {
+    bucket: Option<Bucket>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bucket {
+    column: String,
+    // in seconds
+    interval: i64,
+    aggregation: Aggregation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Aggregation {
+    Count,
+    Sum(String),
+}
+
+impl BucketCmd {
+    pub const NAME: &'static str = "bucket";
+
+    /// Empty buckets between the first and the last are only filled in up to this many buckets.
+    const MAX_FILLED_BUCKETS: i64 = 10_000;
+}
+
+impl SimpleCommand for BucketCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "group rows into time buckets of a date column, counting or summing them"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let usage = || {
+            anyhow::anyhow!(tr!(
+                "expected <date-column> <interval> [--agg count|sum:<column>]"
+            ))
+        };
+
+        let (column, rest) = split_column(args.trim()).ok_or_else(usage)?;
+        let mut words = rest.split_whitespace();
+        let interval = words.next().ok_or_else(usage)?;
+        let interval = parse_interval(interval)?;
+
+        let aggregation = match (words.next(), words.next(), words.next()) {
+            (None, ..) => Aggregation::Count,
+            (Some("--agg"), Some("count"), None) => Aggregation::Count,
+            (Some("--agg"), Some(agg), None) => match agg.strip_prefix("sum:") {
+                Some(column) if !column.is_empty() => Aggregation::Sum(column.to_string()),
+                _ => {
+                    return Err(anyhow::anyhow!(tr!(
+                        "unknown aggregation {agg}",
+                        agg = format!("{agg:?}")
+                    )));
+                }
+            },
+            _ => return Err(usage()),
+        };
+
+        self.bucket = Some(Bucket {
+            column,
+            interval,
+            aggregation,
+        });
+
+        Ok(())
+    }
+
+    fn flags(&self) -> &'static [&'static str] {
+        &["--agg"]
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only tables can be bucketed")))?;
+        let Some(bucket) = self.bucket.take() else {
+            return Ok(Transition::Ok);
+        };
+
+        let (columns, rows) = view
+            .top_table()
+            .ok_or_else(|| anyhow::anyhow!(tr!("only tables can be bucketed")))?;
+        let (columns, rows, skipped) = bucket.apply(columns, rows)?;
+
+        let mut message = tr!("{count} buckets", count = rows.len());
+        if skipped > 0 {
+            message.push_str(&tr!(
+                ", {skipped} rows without a date skipped",
+                skipped = skipped
+            ));
+        }
+
+        view.push_table(Self::NAME, columns, rows);
+        pager.show_message(message);
+
+        Ok(Transition::Ok)
+    }
+}
+
+impl Bucket {
+    /// The rows grouped by bucket, oldest first, and how many rows had no date.
+    fn apply(
+        &self,
+        columns: &[String],
+        rows: &[Vec<Value>],
+    ) -> Result<(Vec<String>, Vec<Vec<Value>>, usize)> {
+        let find = |name: &str| {
+            columns
+                .iter()
+                .position(|column| column == name)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(tr!("There is no column {name}", name = format!("{name:?}")))
+                })
+        };
+
+        let date_column = find(&self.column)?;
+        let sum_column = match &self.aggregation {
+            Aggregation::Count => None,
+            Aggregation::Sum(column) => Some(find(column)?),
+        };
+
+        let span = Span::unknown();
+        let mut skipped = 0;
+        let mut offset = None;
+        // bucket start (as a UTC timestamp) => (row count, sum)
+        let mut buckets: BTreeMap<i64, (i64, Value)> = BTreeMap::new();
+        for row in rows {
+            let Some(Value::Date { val, .. }) = row.get(date_column) else {
+                skipped += 1;
+                continue;
+            };
+
+            // buckets start at round times in the timezone of the dates, not in UTC
+            let utc_offset = i64::from(val.offset().local_minus_utc());
+            let local = val.timestamp() + utc_offset;
+            let start = local.div_euclid(self.interval) * self.interval - utc_offset;
+            offset.get_or_insert(*val.offset());
+
+            let (count, sum) = buckets
+                .entry(start)
+                .or_insert_with(|| (0, Value::nothing(span)));
+            *count += 1;
+
+            if let Some(value) = sum_column.and_then(|column| row.get(column))
+                && !value.is_nothing()
+            {
+                *sum = match sum {
+                    Value::Nothing { .. } => value.clone(),
+                    _ => sum.add(span, value, span)?,
+                };
+            }
+        }
+
+        let (Some(offset), Some(&first), Some(&last)) =
+            (offset, buckets.keys().next(), buckets.keys().next_back())
+        else {
+            return Err(anyhow::anyhow!(tr!(
+                "No value of {column} is a date",
+                column = format!("{:?}", self.column)
+            )));
+        };
+
+        // a rate table with the quiet periods left out would be misleading
+        if (last - first) / self.interval < BucketCmd::MAX_FILLED_BUCKETS {
+            let mut start = first;
+            while start < last {
+                buckets
+                    .entry(start)
+                    .or_insert_with(|| (0, Value::nothing(span)));
+                start += self.interval;
+            }
+        }
+
+        let rows = buckets
+            .into_iter()
+            .map(|(start, (count, sum))| {
+                let date = DateTime::from_timestamp(start, 0)
+                    .map(|date| date.with_timezone(&offset))
+                    .map_or_else(|| Value::nothing(span), |date| Value::date(date, span));
+                let value = match self.aggregation {
+                    Aggregation::Count => Value::int(count, span),
+                    Aggregation::Sum(_) => sum,
+                };
+
+                vec![date, value]
+            })
+            .collect();
+
+        let value_column = match &self.aggregation {
+            Aggregation::Count => String::from("count"),
+            Aggregation::Sum(column) => format!("{column}_sum"),
+        };
+
+        Ok((vec![self.column.clone(), value_column], rows, skipped))
+    }
+}
+
+/// Parses intervals like `30sec`, `5min`, `1hr`, `1day` or `1wk` into seconds.
+fn parse_interval(text: &str) -> Result<i64> {
+    let unit_start = text.find(|c: char| !c.is_ascii_digit()).ok_or_else(|| {
+        anyhow::anyhow!(tr!(
+            "the interval {text} needs a unit, like 1hr",
+            text = format!("{text:?}")
+        ))
+    })?;
+    let (count, unit) = text.split_at(unit_start);
+
+    let count: i64 = match count {
+        "" => 1,
+        count => count.parse()?,
+    };
+    let unit = match unit {
+        "sec" => 1,
+        "min" => 60,
+        "hr" => 60 * 60,
+        "day" => 24 * 60 * 60,
+        "wk" => 7 * 24 * 60 * 60,
+        _ => {
+            return Err(anyhow::anyhow!(tr!(
+                "unknown interval unit {unit}, use sec, min, hr, day or wk",
+                unit = format!("{unit:?}")
+            )));
+        }
+    };
+
+    match count * unit {
+        0 => Err(anyhow::anyhow!(tr!("the interval can't be empty"))),
+        seconds => Ok(seconds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> Value {
+        let date = DateTime::parse_from_rfc3339(text).unwrap();
+        Value::date(date, Span::test_data())
+    }
+
+    #[test]
+    fn parse_args() {
+        let mut cmd = BucketCmd::default();
+
+        cmd.parse("time 5min").unwrap();
+        assert_eq!(
+            cmd.bucket,
+            Some(Bucket {
+                column: "time".to_string(),
+                interval: 300,
+                aggregation: Aggregation::Count,
+            })
+        );
+
+        cmd.parse("time day --agg sum:bytes").unwrap();
+        assert_eq!(
+            cmd.bucket.as_ref().map(|bucket| &bucket.aggregation),
+            Some(&Aggregation::Sum("bytes".to_string()))
+        );
+
+        assert!(cmd.parse("time").is_err());
+        assert!(cmd.parse("time 5").is_err());
+        assert!(cmd.parse("time 0min").is_err());
+        assert!(cmd.parse("time 1hr --agg avg:bytes").is_err());
+    }
+
+    #[test]
+    fn count_per_bucket_with_gaps_filled() {
+        let rows = vec![
+            vec![date("2024-01-01T10:05:00+02:00")],
+            vec![date("2024-01-01T10:55:00+02:00")],
+            vec![date("2024-01-01T12:01:00+02:00")],
+            vec![Value::string("not a date", Span::test_data())],
+        ];
+        let bucket = Bucket {
+            column: "time".to_string(),
+            interval: 60 * 60,
+            aggregation: Aggregation::Count,
+        };
+
+        let (columns, rows, skipped) = bucket.apply(&["time".to_string()], &rows).unwrap();
+        assert_eq!(columns, vec!["time", "count"]);
+        assert_eq!(skipped, 1);
+
+        let counts: Vec<_> = rows.iter().map(|row| row[1].as_int().unwrap()).collect();
+        assert_eq!(counts, vec![2, 0, 1]);
+        assert_eq!(rows[0][0], date("2024-01-01T10:00:00+02:00"));
+    }
+
+    #[test]
+    fn sum_per_bucket() {
+        let span = Span::test_data();
+        let rows = vec![
+            vec![date("2024-01-01T00:00:00Z"), Value::int(3, span)],
+            vec![date("2024-01-01T00:00:30Z"), Value::int(4, span)],
+        ];
+        let bucket = Bucket {
+            column: "time".to_string(),
+            interval: 60,
+            aggregation: Aggregation::Sum("n".to_string()),
+        };
+
+        let columns = ["time".to_string(), "n".to_string()];
+        let (columns, rows, _) = bucket.apply(&columns, &rows).unwrap();
+        assert_eq!(columns, vec!["time", "n_sum"]);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0][1].as_int().unwrap(), 7);
+    }
+}
@@ -0,0 +1,56 @@
+use super::super::{
+    l10n::tr,
+    nu_common::base_value,
+    views::{BinaryView, ViewConfig},
+};
+use super::ViewCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct BytesCmd;
+
+impl BytesCmd {
+    pub const NAME: &'static str = "bytes";
+}
+
+impl ViewCommand for BytesCmd {
+    type View = BinaryView;
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "show the bytes of the selected cell, binary or text, in a binary view"
+    }
+
+    fn parse(&mut self, _: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn spawn(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        value: Option<Value>,
+        config: &ViewConfig,
+    ) -> Result<Self::View> {
+        let value = value.unwrap_or_default();
+        let bytes = match base_value(&value).into_owned() {
+            Value::Binary { val, .. } => val,
+            Value::String { val, .. } => val.into_bytes(),
+            value => {
+                return Err(anyhow::anyhow!(tr!(
+                    "a {kind} has no bytes to show",
+                    kind = value.get_type()
+                )));
+            }
+        };
+
+        Ok(BinaryView::new(bytes, config.explore_config))
+    }
+}
@@ -0,0 +1,204 @@
+use super::super::{
+    l10n::tr,
+    nu_common::{Unit, collect_input},
+    views::{ChartView, Scale, Series, ViewConfig},
+};
+use super::{ViewCommand, filter::split_column};
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+use std::cmp::Ordering;
+
+/// Plots columns of a table against a date or number column, in a chart view which zooms
+/// and pans along it.
+#[derive(Debug, Default, Clone)]
+pub struct ChartCmd {
+    // the x column, then the y ones
+    columns: Vec<String>,
+    bars: bool,
+}
+
+impl ChartCmd {
+    pub const NAME: &'static str = "chart";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ViewCommand for ChartCmd {
+    type View = ChartView;
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "plot number columns against a date or number column, as lines or --bar"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let usage = || anyhow::anyhow!(tr!("expected <x-column> <y-column>... [--bar]"));
+
+        self.columns.clear();
+        self.bars = false;
+
+        let mut rest = args.trim();
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix("--bar")
+                && (after.is_empty() || after.starts_with(' '))
+            {
+                self.bars = true;
+                rest = after.trim_start();
+                continue;
+            }
+
+            let (column, after) = split_column(rest).ok_or_else(usage)?;
+            self.columns.push(column);
+            rest = after.trim_start();
+        }
+
+        if self.columns.len() < 2 {
+            return Err(usage());
+        }
+
+        Ok(())
+    }
+
+    fn flags(&self) -> &'static [&'static str] {
+        &["--bar"]
+    }
+
+    fn spawn(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        value: Option<Value>,
+        _: &ViewConfig,
+    ) -> Result<Self::View> {
+        let value = value.unwrap_or_default();
+        if !matches!(value, Value::List { .. }) {
+            return Err(anyhow::anyhow!(tr!(
+                "Only tables can be charted, this is a {kind}",
+                kind = value.get_type().to_string()
+            )));
+        }
+
+        let (names, rows) = collect_input(value.clone())?;
+        let columns = std::mem::take(&mut self.columns)
+            .into_iter()
+            .map(
+                |column| match names.iter().position(|name| *name == column) {
+                    Some(i) => Ok((column, i)),
+                    None => Err(anyhow::anyhow!(tr!(
+                        "There is no column {column}",
+                        column = column
+                    ))),
+                },
+            )
+            .collect::<Result<Vec<_>>>()?;
+        let Some(((x_name, x), ys)) = columns.split_first() else {
+            return Err(anyhow::anyhow!(tr!(
+                "expected <x-column> <y-column>... [--bar]"
+            )));
+        };
+
+        let (x_scale, y_unit) = scales(&rows, *x, ys).ok_or_else(|| {
+            anyhow::anyhow!(tr!(
+                "Nothing to chart, {x} needs dates or numbers and the other columns numbers",
+                x = x_name
+            ))
+        })?;
+
+        let series = ys
+            .iter()
+            .map(|(name, y)| Series {
+                name: name.clone(),
+                points: points(&rows, *x, *y, x_scale, y_unit),
+            })
+            .collect();
+
+        let view = ChartView::new(x_name.clone(), x_scale, y_unit, series)
+            .with_bars(self.bars)
+            .with_source(value);
+
+        Ok(view)
+    }
+}
+
+// what the x column holds, dates or numbers, and the unit of the y columns, as told by the
+// first row with both
+fn scales(rows: &[Vec<Value>], x: usize, ys: &[(String, usize)]) -> Option<(Scale, Unit)> {
+    rows.iter().find_map(|row| {
+        let x_scale = match row.get(x)? {
+            Value::Date { val, .. } => Scale::Date(*val.offset()),
+            value => Scale::Unit(Unit::of(value)?.1),
+        };
+        let y_unit = ys.iter().find_map(|(_, y)| Unit::of(row.get(*y)?))?.1;
+        Some((x_scale, y_unit))
+    })
+}
+
+// the points of a y column, in order of x; the rows without an x and a y of the scales are
+// left out
+fn points(
+    rows: &[Vec<Value>],
+    x: usize,
+    y: usize,
+    x_scale: Scale,
+    y_unit: Unit,
+) -> Vec<(f64, f64)> {
+    let y_scale = Scale::Unit(y_unit);
+    let mut points: Vec<(f64, f64)> = rows
+        .iter()
+        .filter_map(|row| {
+            let x = x_scale.number(row.get(x)?)?;
+            let y = y_scale.number(row.get(y)?)?;
+            Some((x, y))
+        })
+        .collect();
+    points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::Span;
+
+    #[test]
+    fn parse_the_columns() {
+        let mut cmd = ChartCmd::new();
+        cmd.parse(r#"date "cpu load" mem --bar"#).unwrap();
+        assert_eq!(cmd.columns, ["date", "cpu load", "mem"]);
+        assert!(cmd.bars);
+
+        cmd.parse("date cpu").unwrap();
+        assert!(!cmd.bars);
+        assert!(cmd.parse("date").is_err());
+        assert!(cmd.parse("--bar").is_err());
+    }
+
+    #[test]
+    fn points_in_order_of_x() {
+        let span = Span::test_data();
+        let row = |x: i64, y: Value| vec![Value::int(x, span), y];
+        let rows = [
+            row(3, Value::float(1.5, span)),
+            row(1, Value::int(4, span)),
+            row(2, Value::nothing(span)),
+            vec![Value::string("later", span), Value::int(9, span)],
+        ];
+        let ys = [("y".to_string(), 1)];
+
+        let (x_scale, y_unit) = scales(&rows, 0, &ys).unwrap();
+        assert_eq!(x_scale, Scale::Unit(Unit::Number));
+        assert_eq!(
+            points(&rows, 0, 1, x_scale, y_unit),
+            [(1.0, 4.0), (3.0, 1.5)]
+        );
+    }
+}
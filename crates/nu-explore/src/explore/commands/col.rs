@@ -0,0 +1,152 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct ColCmd {
+    name: String,
+}
+
+impl ColCmd {
+    pub const NAME: &'static str = "col";
+
+    /// Where the column name starts in a partially typed `:col <name>` line, for completion.
+    pub fn split_partial(line: &str) -> Option<usize> {
+        let args = line.strip_prefix(Self::NAME)?;
+        if !args.starts_with(' ') {
+            return None;
+        }
+
+        Some(line.len() - args.trim_start().len())
+    }
+
+    /// The column names matching `typed`, the best first: an exact match, then the names
+    /// starting with it, containing it, and containing its characters in order.
+    ///
+    /// Case is ignored.
+    pub fn fuzzy_matches<'a>(names: &'a [String], typed: &str) -> Vec<&'a String> {
+        let typed = typed.to_lowercase();
+
+        let mut matches: Vec<(usize, &String)> = names
+            .iter()
+            .filter_map(|name| {
+                let lower = name.to_lowercase();
+                let rank = if lower == typed {
+                    0
+                } else if lower.starts_with(&typed) {
+                    1
+                } else if lower.contains(&typed) {
+                    2
+                } else if is_subsequence(&typed, &lower) {
+                    3
+                } else {
+                    return None;
+                };
+
+                Some((rank, name))
+            })
+            .collect();
+        // stable, so names of the same rank keep the order of the table
+        matches.sort_by_key(|(rank, _)| *rank);
+
+        matches.into_iter().map(|(_, name)| name).collect()
+    }
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle.chars().all(|c| haystack.any(|h| h == c))
+}
+
+impl SimpleCommand for ColCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "move the cursor to the column, or the field of a record, best matching a name"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let name = args.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!(tr!("expected a column name")));
+        }
+
+        self.name = name.to_string();
+
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only tables have columns to jump to")))?;
+
+        let column = Self::fuzzy_matches(view.column_names(), &self.name)
+            .first()
+            .map(|name| name.to_string())
+            .ok_or_else(|| {
+                anyhow::anyhow!(tr!(
+                    "There is no column matching {name}",
+                    name = format!("{:?}", self.name)
+                ))
+            })?;
+
+        view.go_to_column(&column);
+        pager.show_message(tr!("Column {column}", column = column));
+
+        Ok(Transition::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_are_ranked() {
+        let names: Vec<String> = ["modified", "name", "size", "Type", "named_size"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(
+            ColCmd::fuzzy_matches(&names, "name"),
+            vec!["name", "named_size"]
+        );
+        assert_eq!(ColCmd::fuzzy_matches(&names, "type"), vec!["Type"]);
+        assert_eq!(
+            ColCmd::fuzzy_matches(&names, "size"),
+            vec!["size", "named_size"]
+        );
+        assert_eq!(
+            ColCmd::fuzzy_matches(&names, "md"),
+            vec!["modified", "named_size"]
+        );
+        assert!(ColCmd::fuzzy_matches(&names, "xyz").is_empty());
+    }
+
+    #[test]
+    fn split_partial_line() {
+        assert_eq!(ColCmd::split_partial("col na"), Some(4));
+        assert_eq!(ColCmd::split_partial("col  "), Some(5));
+        assert_eq!(ColCmd::split_partial("col"), None);
+        assert_eq!(ColCmd::split_partial("cols x"), None);
+    }
+}
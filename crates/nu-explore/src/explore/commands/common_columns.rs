@@ -0,0 +1,55 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct CommonColumnsCmd;
+
+impl CommonColumnsCmd {
+    pub const NAME: &'static str = "common-columns";
+}
+
+impl SimpleCommand for CommonColumnsCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "show only the columns every row has, in a table of rows of different shapes; again to show them all"
+    }
+
+    fn parse(&mut self, _: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only tables have columns")))?;
+
+        pager.show_message(match view.toggle_common_columns()? {
+            Some(count) => tr!(
+                "Left out {count} columns some rows don't have",
+                count = count
+            ),
+            None => tr!("Showing all the columns"),
+        });
+
+        Ok(Transition::Ok)
+    }
+}
@@ -0,0 +1,49 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct CompareCmd;
+
+impl CompareCmd {
+    pub const NAME: &'static str = "compare";
+}
+
+impl SimpleCommand for CompareCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "show the marked rows side by side, highlighting the fields that differ"
+    }
+
+    fn parse(&mut self, _: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        _: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only table rows can be compared")))?;
+
+        view.compare_marked()?;
+
+        Ok(Transition::Ok)
+    }
+}
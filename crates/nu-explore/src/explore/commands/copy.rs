@@ -0,0 +1,213 @@
+use super::super::{
+    l10n::tr,
+    nu_common::run_nu_command,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use crossterm::{clipboard::CopyToClipboard, execute};
+use nu_protocol::{
+    Config, PipelineData, Span, Value,
+    engine::{EngineState, Stack},
+};
+use std::io;
+
+#[derive(Debug, Default, Clone)]
+pub struct CopyCmd {
+    what: Selected,
+    // the path of the cell rather than its value
+    path: bool,
+    // as NUON rather than plain text
+    nuon: bool,
+}
+
+/// What of the table `:copy` copies, from the selected cell.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Selected {
+    #[default]
+    Cell,
+    Row,
+    Column,
+}
+
+impl Selected {
+    // as pasted in a spreadsheet: a row's values between tabs, a column's one to a line
+    fn plain_text(self, value: &Value) -> String {
+        let values: Vec<String> = match (self, value) {
+            (Self::Row, Value::Record { val, .. }) => val.values().map(cell_text).collect(),
+            (Self::Column, Value::List { vals, .. }) => vals.iter().map(cell_text).collect(),
+            (_, value) => return cell_text(value),
+        };
+
+        let separator = match self {
+            Self::Row => "\t",
+            _ => "\n",
+        };
+        values.join(separator)
+    }
+}
+
+// strings as they are, without quotes
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::String { val, .. } => val.clone(),
+        value => value.to_expanded_string(", ", &Config::default()),
+    }
+}
+
+impl CopyCmd {
+    pub const NAME: &'static str = "copy";
+}
+
+impl SimpleCommand for CopyCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "copy the selected cell, its row or its column to the clipboard, as text or --nuon; or the path of the cell with --path"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        *self = Self::default();
+
+        let mut what = None;
+        for arg in args.split_whitespace() {
+            match arg {
+                "--path" => self.path = true,
+                "--nuon" => self.nuon = true,
+                "cell" | "row" | "column" if what.is_none() => {
+                    what = Some(match arg {
+                        "row" => Selected::Row,
+                        "column" => Selected::Column,
+                        _ => Selected::Cell,
+                    });
+                }
+                arg => {
+                    return Err(anyhow::anyhow!(tr!(
+                        "unexpected {arg}, expected cell, row or column, --nuon or --path",
+                        arg = format!("{arg:?}")
+                    )));
+                }
+            }
+        }
+        self.what = what.unwrap_or_default();
+
+        if self.path && (self.nuon || self.what != Selected::Cell) {
+            return Err(anyhow::anyhow!(tr!(
+                "--path only copies the path of a cell"
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn flags(&self) -> &'static [&'static str] {
+        &["--nuon", "--path"]
+    }
+
+    fn menu_entries(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("Copy value", "copy"),
+            ("Copy path", "copy --path"),
+            ("Copy row", "copy row"),
+            ("Copy column", "copy column"),
+        ]
+    }
+
+    fn react(
+        &mut self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only table cells can be copied")))?;
+
+        let path = view.cell_path();
+        let value = match self.what {
+            Selected::Cell => view.get_current_value().clone(),
+            Selected::Row => view.current_row_value(),
+            Selected::Column => view.current_column_value(),
+        };
+        let text = match (self.path, self.nuon) {
+            (true, _) => path.clone(),
+            (false, true) => to_nuon(engine_state, stack, value)?,
+            (false, false) => self.what.plain_text(&value),
+        };
+
+        // OSC 52: the terminal puts it on the clipboard, over ssh too
+        execute!(
+            io::stdout(),
+            CopyToClipboard::to_clipboard_from(text.clone())
+        )?;
+        pager.yank(text);
+
+        let message = match (self.path, self.what) {
+            (true, _) => tr!("Copied {path}", path = path),
+            (false, Selected::Cell) => tr!("Copied the value of {path}", path = path),
+            (false, Selected::Row) => tr!("Copied the row of {path}", path = path),
+            (false, Selected::Column) => tr!("Copied the column of {path}", path = path),
+        };
+        pager.show_message(match self.nuon {
+            true => tr!("{copied} as NUON", copied = message),
+            false => message,
+        });
+
+        Ok(Transition::Ok)
+    }
+}
+
+pub(super) fn to_nuon(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    value: Value,
+) -> Result<String> {
+    let input = PipelineData::value(value, None);
+    let text = run_nu_command(engine_state, stack, "to nuon", input)
+        .and_then(|output| output.into_value(Span::unknown()))
+        .and_then(Value::coerce_into_string)?;
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_what_to_copy() {
+        let mut cmd = CopyCmd::default();
+        cmd.parse("row --nuon").unwrap();
+        assert_eq!(cmd.what, Selected::Row);
+        assert!(cmd.nuon);
+
+        cmd.parse("--path").unwrap();
+        assert_eq!(cmd.what, Selected::Cell);
+        assert!(cmd.path && !cmd.nuon);
+
+        assert!(cmd.parse("column --path").is_err());
+        assert!(cmd.parse("row column").is_err());
+        assert!(cmd.parse("table").is_err());
+    }
+
+    #[test]
+    fn plain_text_for_spreadsheets() {
+        let span = Span::test_data();
+        let row = Value::test_record(nu_protocol::record! {
+            "name" => Value::string("a b", span),
+            "size" => Value::int(3, span),
+        });
+        assert_eq!(Selected::Row.plain_text(&row), "a b\t3");
+
+        let column = Value::list(vec![Value::int(1, span), Value::int(2, span)], span);
+        assert_eq!(Selected::Column.plain_text(&column), "1\n2");
+
+        // a cell is copied whole, whatever it holds
+        assert_eq!(Selected::Cell.plain_text(&column), "[1, 2]");
+    }
+}
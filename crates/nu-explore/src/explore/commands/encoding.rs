@@ -0,0 +1,94 @@
+use super::super::{
+    l10n::tr,
+    nu_common::{decode_text, find_encoding},
+    views::{Preview, ViewConfig},
+};
+use super::ViewCommand;
+use anyhow::Result;
+use encoding_rs::Encoding;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+/// Decodes bytes shown in the hex view or as text in an encoding of the user's choice, for
+/// when the one detected is wrong.
+#[derive(Debug, Default, Clone)]
+pub struct EncodingCmd {
+    encoding: Option<&'static Encoding>,
+}
+
+impl EncodingCmd {
+    pub const NAME: &'static str = "encoding";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ViewCommand for EncodingCmd {
+    type View = Preview;
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "decode the bytes shown as text in another encoding, like latin1, shift_jis or utf-16be"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let label = args.trim();
+        if label.is_empty() {
+            return Err(anyhow::anyhow!(tr!(
+                "expected an encoding, like latin1 or shift_jis"
+            )));
+        }
+
+        self.encoding = Some(find_encoding(label).ok_or_else(|| {
+            anyhow::anyhow!(tr!(
+                "{label} isn't an encoding, see https://encoding.spec.whatwg.org/#names-and-labels",
+                label = format!("{label:?}")
+            ))
+        })?);
+
+        Ok(())
+    }
+
+    fn spawn(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        value: Option<Value>,
+        _: &ViewConfig,
+    ) -> Result<Self::View> {
+        let Some(encoding) = self.encoding.take() else {
+            return Ok(Preview::new(""));
+        };
+
+        match value {
+            Some(Value::Binary { val, .. }) => {
+                let text = decode_text(&val, encoding);
+                Ok(Preview::new(&text).with_source(val))
+            }
+            _ => Err(anyhow::anyhow!(tr!(
+                "Only bytes can be decoded, open a file with --raw to get them"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_the_encoding() {
+        let mut cmd = EncodingCmd::new();
+        cmd.parse(" latin1 ").unwrap();
+        assert_eq!(cmd.encoding.map(Encoding::name), Some("windows-1252"));
+
+        assert!(cmd.parse("").is_err());
+        assert!(cmd.parse("klingon").is_err());
+    }
+}
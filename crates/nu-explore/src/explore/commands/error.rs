@@ -0,0 +1,245 @@
+use super::super::{
+    config::ExploreConfig,
+    l10n::tr,
+    nu_common::{base_value, collect_input},
+    views::{RecordView, ViewConfig},
+};
+use super::ViewCommand;
+use anyhow::Result;
+use nu_protocol::{
+    LabeledError, Record, ShellError, Span, Value,
+    engine::{EngineState, Stack, StateWorkingSet},
+    format_cli_error,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Browses an error: its message, where in the source it happened, the diagnostic nushell
+/// would print for it, and the errors that caused it, each drilled into with Enter.
+#[derive(Debug, Default, Clone)]
+pub struct ErrorCmd;
+
+impl ErrorCmd {
+    pub const NAME: &'static str = "error";
+
+    /// The page of the first error in `value`, itself or in a cell of it, with the value it
+    /// was found in when there's one.
+    pub fn view(
+        engine_state: &EngineState,
+        stack: &Stack,
+        value: &Value,
+        config: &ExploreConfig,
+    ) -> Result<RecordView> {
+        let (error, offending) = find_error(value)
+            .ok_or_else(|| anyhow::anyhow!(tr!("There's no error in the selected value")))?;
+
+        let mut record = error_record(engine_state, stack, error);
+        if let Some(offending) = offending {
+            record.push("value", offending);
+        }
+
+        let (columns, data) = collect_input(Value::record(record, Span::unknown()))?;
+        let mut view = RecordView::new(columns, data, config.clone());
+        view.set_top_layer_record();
+
+        Ok(view)
+    }
+}
+
+impl ViewCommand for ErrorCmd {
+    type View = RecordView;
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "browse the error of the cell, or the first one in the table: its source, diagnostic and the errors causing it"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        match args.trim() {
+            "" => Ok(()),
+            args => Err(anyhow::anyhow!(tr!(
+                "unexpected {args}, :error takes no arguments",
+                args = format!("{args:?}")
+            ))),
+        }
+    }
+
+    fn uses_selection(&self) -> bool {
+        true
+    }
+
+    fn spawn(
+        &mut self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        value: Option<Value>,
+        config: &ViewConfig,
+    ) -> Result<Self::View> {
+        let value = value.unwrap_or_default();
+        Self::view(
+            engine_state,
+            stack,
+            &base_value(&value),
+            config.explore_config,
+        )
+    }
+}
+
+/// Whether there's an error in `value`, itself or in a cell of it.
+pub fn has_error(value: &Value) -> bool {
+    find_error(value).is_some()
+}
+
+// the first error, depth first, and the value it's in: the row or list holding it, or the
+// value of a `return` out of place
+fn find_error(value: &Value) -> Option<(&ShellError, Option<Value>)> {
+    let children: Box<dyn Iterator<Item = &Value>> = match value {
+        Value::Error { error, .. } => {
+            let attached = match error.as_ref() {
+                ShellError::Return { value, .. } => Some(value.as_ref().clone()),
+                _ => None,
+            };
+            return Some((error.as_ref(), attached));
+        }
+        Value::Record { val, .. } => Box::new(val.values()),
+        Value::List { vals, .. } => Box::new(vals.iter()),
+        _ => return None,
+    };
+
+    children
+        .filter_map(|child| match child {
+            Value::Error { error, .. } => Some((error.as_ref(), Some(value.clone()))),
+            child => find_error(child),
+        })
+        .next()
+}
+
+// the fields of an error, with those of the errors causing it in `inner`
+fn error_record(engine_state: &EngineState, stack: &Stack, error: &ShellError) -> Record {
+    let span = Span::unknown();
+    let labeled = LabeledError::from(error.clone());
+    let working_set = StateWorkingSet::new(engine_state);
+
+    let mut record = Record::new();
+    record.push("error", Value::string(&labeled.msg, span));
+    if let Some(code) = &labeled.code {
+        record.push("code", Value::string(code, span));
+    }
+
+    let excerpts: Vec<_> = labeled
+        .labels
+        .iter()
+        .filter_map(|label| {
+            let file = working_set.find_file_by_span(label.span)?;
+            let start = label.span.start - file.covered_span.start;
+            let end = label.span.end - file.covered_span.start;
+            Some(excerpt(&file.name, &file.content, start..end, &label.text))
+        })
+        .collect();
+    if !excerpts.is_empty() {
+        record.push("source", Value::string(excerpts.join("\n\n"), span));
+    }
+
+    if let Some(help) = &labeled.help {
+        record.push("help", Value::string(help, span));
+    }
+
+    let diagnostic = format_cli_error(Some(stack), &working_set, error, None);
+    record.push(
+        "diagnostic",
+        Value::string(nu_utils::strip_ansi_string_likely(diagnostic), span),
+    );
+
+    if !labeled.inner.is_empty() {
+        let inner = labeled
+            .inner
+            .iter()
+            .map(|inner| Value::record(error_record(engine_state, stack, inner), span))
+            .collect();
+        record.push("inner", Value::list(inner, span));
+    }
+
+    record
+}
+
+// the line of `content` the bytes at `range` start on, marked under them with their label,
+// after where it is: `name:line:column`
+fn excerpt(name: &str, content: &[u8], range: std::ops::Range<usize>, label: &str) -> String {
+    let start = range.start.min(content.len());
+    let line_start = content[..start]
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |newline| newline + 1);
+    let line_end = content[start..]
+        .iter()
+        .position(|&byte| byte == b'\n')
+        .map_or(content.len(), |newline| start + newline);
+    let line_number = content[..line_start]
+        .iter()
+        .filter(|&&byte| byte == b'\n')
+        .count()
+        + 1;
+
+    let before = String::from_utf8_lossy(&content[line_start..start]);
+    let marked_end = range.end.clamp(start, line_end);
+    let marked = String::from_utf8_lossy(&content[start..marked_end]);
+    let line = String::from_utf8_lossy(&content[line_start..line_end]);
+
+    let mut text = format!(
+        "{name}:{line_number}:{}\n{line}\n{}{}",
+        before.chars().count() + 1,
+        " ".repeat(before.width()),
+        "^".repeat(marked.width().max(1)),
+    );
+    if !label.is_empty() {
+        text.push(' ');
+        text.push_str(label);
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::shell_error::generic::GenericError;
+
+    #[test]
+    fn excerpt_marks_the_span() {
+        let source = b"let a = 1\nls | get nme\n";
+        assert_eq!(
+            excerpt("repl", source, 19..22, "not a column"),
+            "repl:2:10\nls | get nme\n         ^^^ not a column"
+        );
+        // spans over lines are marked to the end of their first
+        assert_eq!(
+            excerpt("repl", source, 4..14, ""),
+            "repl:1:5\nlet a = 1\n    ^^^^^"
+        );
+    }
+
+    #[test]
+    fn find_the_first_error() {
+        let span = Span::test_data();
+        let error = ShellError::Generic(GenericError::new_internal("bad", "it went wrong"));
+        let row = Value::test_record(nu_protocol::record! {
+            "name" => Value::test_string("a"),
+            "size" => Value::error(error.clone(), span),
+        });
+        let table = Value::test_list(vec![
+            Value::test_record(nu_protocol::record! { "name" => Value::test_string("b") }),
+            row.clone(),
+        ]);
+
+        let (found, offending) = find_error(&table).unwrap();
+        assert_eq!(found, &error);
+        assert_eq!(offending, Some(row));
+
+        let (_, offending) = find_error(&Value::error(error, span)).unwrap();
+        assert!(offending.is_none());
+
+        assert!(!has_error(&Value::test_string("fine")));
+    }
+}
@@ -1,26 +1,48 @@
 use super::super::{
+    l10n::tr,
     nu_common::{self, collect_input},
     views::{Preview, ViewConfig},
 };
 use super::ViewCommand;
 use anyhow::Result;
-use nu_color_config::StyleComputer;
 use nu_protocol::{
     Value,
     engine::{EngineState, Stack},
 };
 
 #[derive(Default, Clone)]
-pub struct ExpandCmd;
+pub struct ExpandCmd {
+    // levels of nested tables to expand
+    depth: Option<usize>,
+}
 
 impl ExpandCmd {
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
 }
 
 impl ExpandCmd {
     pub const NAME: &'static str = "expand";
+
+    /// Deeper levels are expanded one at a time, as expanding deeply nested data all at
+    /// once makes for huge tables.
+    const DEFAULT_DEPTH: usize = 1;
+
+    /// `value` expanded `depth` levels, or as many as it has, in a preview where `+` and `-`
+    /// expand a level more or less.
+    pub fn view(
+        value: Value,
+        depth: usize,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+    ) -> Result<Preview> {
+        let max_depth = nu_common::nesting_depth(&value).saturating_sub(1);
+        let depth = depth.min(max_depth);
+
+        let value_as_string = convert_value_to_string(value.clone(), depth, engine_state, stack)?;
+        Ok(Preview::new(&value_as_string).with_depth(value, depth, max_depth))
+    }
 }
 
 impl ViewCommand for ExpandCmd {
@@ -31,13 +53,27 @@ impl ViewCommand for ExpandCmd {
     }
 
     fn description(&self) -> &'static str {
-        ""
+        "expand the nested data of the table a level"
     }
 
-    fn parse(&mut self, _: &str) -> Result<()> {
+    fn parse(&mut self, args: &str) -> Result<()> {
+        self.depth = match args.trim() {
+            "" => None,
+            depth => Some(depth.parse().map_err(|_| {
+                anyhow::anyhow!(tr!(
+                    "expected a depth, got {depth}",
+                    depth = format!("{depth:?}")
+                ))
+            })?),
+        };
+
         Ok(())
     }
 
+    fn menu_entries(&self) -> &'static [(&'static str, &'static str)] {
+        &[("Expand", "expand")]
+    }
+
     fn spawn(
         &mut self,
         engine_state: &EngineState,
@@ -45,17 +81,18 @@ impl ViewCommand for ExpandCmd {
         value: Option<Value>,
         _: &ViewConfig,
     ) -> Result<Self::View> {
-        if let Some(value) = value {
-            let value_as_string = convert_value_to_string(value, engine_state, stack)?;
-            Ok(Preview::new(&value_as_string))
-        } else {
-            Ok(Preview::new(""))
-        }
+        let Some(value) = value else {
+            return Ok(Preview::new(""));
+        };
+
+        let depth = self.depth.take().unwrap_or(Self::DEFAULT_DEPTH);
+        Self::view(value, depth, engine_state, stack)
     }
 }
 
 fn convert_value_to_string(
     value: Value,
+    depth: usize,
     engine_state: &EngineState,
     stack: &mut Stack,
 ) -> Result<String> {
@@ -65,12 +102,70 @@ fn convert_value_to_string(
     let has_single_value = vals.len() == 1 && vals[0].len() == 1;
     let config = stack.get_config(engine_state);
     if !has_no_head && has_single_value {
-        Ok(vals[0][0].to_abbreviated_string(&config))
+        Ok(nu_common::simple_value_text(
+            engine_state,
+            &vals[0][0],
+            &config,
+        ))
     } else {
-        let style_computer = StyleComputer::from_config(engine_state, stack);
-        let table =
-            nu_common::try_build_table(value, engine_state.signals(), &config, style_computer);
+        // the warning was shown as explore started
+        let (style_computer, _) = nu_common::create_style_computer(engine_state, stack);
+        let table = nu_common::try_build_table(
+            value,
+            engine_state.signals(),
+            &config,
+            style_computer,
+            Some(depth),
+        );
 
         Ok(table)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explore::views::View;
+    use nu_protocol::{Span, record};
+
+    #[test]
+    fn parse_depth() {
+        let mut cmd = ExpandCmd::new();
+
+        cmd.parse(" 3 ").unwrap();
+        assert_eq!(cmd.depth, Some(3));
+
+        cmd.parse("").unwrap();
+        assert_eq!(cmd.depth, None);
+
+        assert!(cmd.parse("deep").is_err());
+    }
+
+    #[test]
+    fn depth_is_at_most_the_levels_there_are() {
+        let span = Span::test_data();
+        let inner = Value::list(vec![Value::int(1, span)], span);
+        let value = Value::list(vec![Value::test_record(record! { "a" => inner })], span);
+
+        let mut stack = Stack::new();
+        let mut view = ExpandCmd::view(value.clone(), 10, &EngineState::new(), &mut stack).unwrap();
+        assert_eq!(view.exit(), Some(value));
+        assert!(view.key_hints().iter().any(|hint| hint.key == "+- 2/2"));
+    }
+
+    #[test]
+    fn nesting_depth_counts_levels() {
+        let span = Span::test_data();
+        let inner = Value::list(vec![Value::int(1, span)], span);
+        let value = Value::list(
+            vec![
+                Value::test_record(record! { "a" => inner }),
+                Value::int(2, span),
+            ],
+            span,
+        );
+
+        assert_eq!(nu_common::nesting_depth(&value), 3);
+        assert_eq!(nu_common::nesting_depth(&Value::int(1, span)), 0);
+    }
+}
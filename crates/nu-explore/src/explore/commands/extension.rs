@@ -0,0 +1,160 @@
+use super::super::{
+    nu_common::{collect_input, simple_value_text},
+    views::{BinaryView, Preview, RecordView, View, ViewConfig},
+};
+use super::ViewCommand;
+use anyhow::Result;
+use nu_protocol::{
+    ShellError, Value,
+    engine::{EngineState, Stack},
+};
+use std::{fmt, sync::Arc};
+
+/// A `:` command added to explore by a distribution embedding nushell, with
+/// [`ExploreContext::with_command`](crate::ExploreContext::with_command).
+///
+/// It's given what's typed after its name and the selection of the current view, or all it
+/// shows, and returns the value explored on a page of its own.
+pub trait ExploreCommand: Send + Sync {
+    /// What's typed after `:` to run it, without spaces.
+    fn name(&self) -> &'static str;
+
+    /// What it does, as listed by `:help`.
+    fn description(&self) -> &'static str;
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        args: &str,
+        input: Value,
+    ) -> Result<Value, ShellError>;
+}
+
+/// An [`ExploreCommand`] among the view commands, its value shown the way explore shows its
+/// input: a table, bytes or text.
+#[derive(Clone)]
+pub struct ExtensionCmd {
+    command: Arc<dyn ExploreCommand>,
+    args: String,
+}
+
+impl fmt::Debug for ExtensionCmd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtensionCmd")
+            .field("name", &self.command.name())
+            .field("args", &self.args)
+            .finish()
+    }
+}
+
+impl ExtensionCmd {
+    pub fn new(command: Arc<dyn ExploreCommand>) -> Self {
+        Self {
+            command,
+            args: String::new(),
+        }
+    }
+}
+
+impl ViewCommand for ExtensionCmd {
+    type View = Box<dyn View>;
+
+    fn name(&self) -> &'static str {
+        self.command.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.command.description()
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        args.trim().clone_into(&mut self.args);
+        Ok(())
+    }
+
+    fn uses_selection(&self) -> bool {
+        true
+    }
+
+    fn spawn(
+        &mut self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        value: Option<Value>,
+        config: &ViewConfig,
+    ) -> Result<Self::View> {
+        let input = value.unwrap_or_default();
+        let value = self.command.run(engine_state, stack, &self.args, input)?;
+
+        let view: Box<dyn View> = match value {
+            Value::List { .. } | Value::Record { .. } => {
+                let is_record = matches!(value, Value::Record { .. });
+                let (columns, data) = collect_input(value)?;
+                let mut view = RecordView::new(columns, data, config.explore_config.clone());
+                if is_record {
+                    view.set_top_layer_record();
+                }
+                Box::new(view)
+            }
+            Value::Binary { val, .. } => Box::new(BinaryView::new(val, config.explore_config)),
+            value => {
+                let text = simple_value_text(engine_state, &value, config.nu_config);
+                Box::new(Preview::new(&text))
+            }
+        };
+
+        Ok(view)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::Span;
+
+    struct Shout;
+
+    impl ExploreCommand for Shout {
+        fn name(&self) -> &'static str {
+            "shout"
+        }
+
+        fn description(&self) -> &'static str {
+            "upper case the text"
+        }
+
+        fn run(
+            &self,
+            _: &EngineState,
+            _: &mut Stack,
+            args: &str,
+            input: Value,
+        ) -> Result<Value, ShellError> {
+            let text = input.coerce_into_string()?;
+            Ok(Value::string(
+                format!("{}{args}", text.to_uppercase()),
+                Span::unknown(),
+            ))
+        }
+    }
+
+    #[test]
+    fn run_with_the_arguments() {
+        let mut cmd = ExtensionCmd::new(Arc::new(Shout));
+        cmd.parse(" !! ").unwrap();
+        assert_eq!(cmd.name(), "shout");
+
+        let mut stack = Stack::new();
+        let value = cmd
+            .command
+            .run(
+                &EngineState::new(),
+                &mut stack,
+                &cmd.args,
+                Value::test_string("hey"),
+            )
+            .unwrap();
+        assert_eq!(value, Value::test_string("HEY!!"));
+    }
+}
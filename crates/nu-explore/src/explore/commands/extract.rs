@@ -0,0 +1,83 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::{SimpleCommand, filter::split_column};
+use anyhow::Result;
+use fancy_regex::Regex;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct ExtractCmd {
+    extract: Option<(String, Regex)>,
+}
+
+impl ExtractCmd {
+    pub const NAME: &'static str = "extract";
+}
+
+impl SimpleCommand for ExtractCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "add the named captures of a regex applied to a column as new columns"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let (column, regex) = split_column(args.trim())
+            .ok_or_else(|| anyhow::anyhow!(tr!("expected a column name and a regex")))?;
+        let regex = regex.trim();
+        if column.is_empty() || regex.is_empty() {
+            return Err(anyhow::anyhow!(tr!("expected a column name and a regex")));
+        }
+
+        let regex = Regex::new(regex)?;
+        self.extract = Some((column, regex));
+
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only tables can be extracted from")))?;
+
+        if let Some((column, regex)) = self.extract.take() {
+            let count = view.extract(&column, &regex)?;
+            pager.show_message(tr!("{count} rows match", count = count));
+        }
+
+        Ok(Transition::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_column_and_regex() {
+        let mut cmd = ExtractCmd::default();
+        cmd.parse(r"name (?<stem>\w+)\.(?<ext>\w+)").unwrap();
+
+        let (column, regex) = cmd.extract.as_ref().unwrap();
+        assert_eq!(column, "name");
+        assert_eq!(regex.as_str(), r"(?<stem>\w+)\.(?<ext>\w+)");
+
+        assert!(cmd.parse("name").is_err());
+        assert!(cmd.parse("name (?<open").is_err());
+    }
+}
@@ -0,0 +1,322 @@
+use super::super::{
+    l10n::tr,
+    nu_common::run_nu_command,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    PipelineData, Span, Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct FilterCmd {
+    // `None` clears all filters
+    filter: Option<(String, String)>,
+    // `{closure}`, or an expression of `$in`, run on each row
+    closure: Option<String>,
+    // with `--edit`: the filter replaced by `filter`, counted from 1, or `None` to have the
+    // last one typed in for editing
+    edit: Option<Option<usize>>,
+}
+
+impl FilterCmd {
+    pub const NAME: &'static str = "filter";
+
+    /// Upper bound of distinct values offered for completion of a single column.
+    pub const COMPLETION_LIMIT: usize = 1000;
+
+    /// Splits a partially typed `:filter <column> <value>` command line into the column
+    /// and the byte offset where the value begins, for completing the value.
+    pub fn split_partial(line: &str) -> Option<(String, usize)> {
+        let args = line.strip_prefix(Self::NAME)?;
+        if !args.starts_with(' ') {
+            return None;
+        }
+
+        // `--edit <n>` comes before the column
+        let mut args = args.trim_start();
+        if let Some(rest) = args.strip_prefix("--edit ") {
+            let (_, rest) = rest.trim_start().split_once(' ')?;
+            args = rest;
+        }
+
+        // a closure has no values to complete
+        let args = args.trim_start();
+        if args.starts_with(['{', '$']) {
+            return None;
+        }
+
+        let (column, rest) = split_column(args)?;
+        if !rest.starts_with(' ') {
+            return None;
+        }
+
+        let value_start = line.len() - rest.trim_start().len();
+
+        Some((column, value_start))
+    }
+
+    /// The column as it's typed in `:filter`, quoted when it has spaces.
+    pub fn quote_column(column: &str) -> String {
+        if column.is_empty() || column.contains(char::is_whitespace) {
+            format!("{column:?}")
+        } else {
+            column.to_string()
+        }
+    }
+}
+
+impl SimpleCommand for FilterCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "keep only the rows where a column has the given value, or a closure returns true"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let mut args = args.trim();
+        self.edit = None;
+        self.closure = None;
+        if args.starts_with('{') || args.starts_with('$') {
+            self.closure = Some(match args.starts_with('{') {
+                true => args.to_string(),
+                false => format!("{{ {args} }}"),
+            });
+            self.filter = None;
+            return Ok(());
+        }
+
+        if let Some(rest) = args.strip_prefix("--edit") {
+            let rest = rest.trim_start();
+            if rest.is_empty() {
+                self.edit = Some(None);
+                self.filter = None;
+                return Ok(());
+            }
+
+            let (index, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+            let index = index
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index > 0)
+                .ok_or_else(|| anyhow::anyhow!(tr!("expected the number of a filter to edit")))?;
+            self.edit = Some(Some(index));
+            args = rest.trim();
+        }
+
+        if args.is_empty() {
+            if self.edit.is_some() {
+                return Err(anyhow::anyhow!(tr!("expected a column name and a value")));
+            }
+            self.filter = None;
+            return Ok(());
+        }
+
+        let (column, value) = split_column(args)
+            .ok_or_else(|| anyhow::anyhow!(tr!("expected a column name and a value")))?;
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(anyhow::anyhow!(tr!(
+                "expected a value to filter {column} by",
+                column = format!("{column:?}")
+            )));
+        }
+
+        self.filter = Some((column, value.to_string()));
+
+        Ok(())
+    }
+
+    fn flags(&self) -> &'static [&'static str] {
+        &["--edit"]
+    }
+
+    fn menu_entries(&self) -> &'static [(&'static str, &'static str)] {
+        &[("Filter to this value", "filter {column} {value}")]
+    }
+
+    fn react(
+        &mut self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only tables can be filtered")))?;
+
+        if let Some(closure) = self.closure.take() {
+            let count = view.set_closure_filter(closure.clone(), |columns, rows| {
+                eval_closure(&closure, columns, rows, engine_state, stack)
+            })?;
+            pager.show_message(tr!("{count} rows match", count = count));
+            return Ok(Transition::Ok);
+        }
+
+        match (self.edit.take(), self.filter.take()) {
+            (Some(None), _) => {
+                let filters = view.filters();
+                let Some((column, value, _)) = filters.last() else {
+                    let closure = view
+                        .closure_filter()
+                        .ok_or_else(|| anyhow::anyhow!(tr!("There are no filters to edit")))?;
+                    return Ok(Transition::Prompt(format!("{} {closure}", Self::NAME)));
+                };
+                return Ok(Transition::Prompt(format!(
+                    "{} --edit {} {} {value}",
+                    Self::NAME,
+                    filters.len(),
+                    Self::quote_column(column)
+                )));
+            }
+            (Some(Some(index)), Some((column, value))) => {
+                let count = view.replace_filter(index - 1, &column, value)?;
+                pager.show_message(tr!("{count} rows match", count = count));
+            }
+            (_, Some((column, value))) => {
+                let count = view.add_filter(&column, value)?;
+                pager.show_message(tr!("{count} rows match", count = count));
+            }
+            (_, None) => {
+                if view.clear_filters() {
+                    pager.show_message(tr!("Filters cleared"));
+                }
+            }
+        }
+
+        Ok(Transition::Ok)
+    }
+}
+
+/// Runs `closure` on each row, as a record given both as input and as argument, and tells
+/// the rows it returns true for.
+fn eval_closure(
+    closure: &str,
+    columns: &[String],
+    rows: &[Vec<Value>],
+    engine_state: &EngineState,
+    stack: &mut Stack,
+) -> Result<Vec<bool>> {
+    let span = Span::unknown();
+    let records = rows
+        .iter()
+        .map(|row| {
+            let record = columns.iter().cloned().zip(row.iter().cloned()).collect();
+            Value::record(record, span)
+        })
+        .collect();
+
+    let command = format!("each {{|row| ($row | do {closure} $row) == true }}");
+    let input = PipelineData::value(Value::list(records, span), None);
+    let kept = run_nu_command(engine_state, stack, &command, input)
+        .and_then(|output| output.into_value(span))
+        .and_then(Value::into_list)
+        .map_err(|err| anyhow::anyhow!(tr!("The filter failed: {err}", err = err)))?;
+    if kept.len() != rows.len() {
+        return Err(anyhow::anyhow!(tr!("The filter failed on some rows")));
+    }
+
+    Ok(kept
+        .iter()
+        .map(|kept| kept.as_bool().unwrap_or(false))
+        .collect())
+}
+
+/// Takes the leading column name off `args`, which may be double-quoted to include spaces.
+pub(super) fn split_column(args: &str) -> Option<(String, &str)> {
+    if let Some(quoted) = args.strip_prefix('"') {
+        let end = quoted.find('"')?;
+        return Some((quoted[..end].to_string(), &quoted[end + 1..]));
+    }
+
+    let end = args.find(' ').unwrap_or(args.len());
+    Some((args[..end].to_string(), &args[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_column_and_value() {
+        let mut cmd = FilterCmd::default();
+        cmd.parse(" status  open issue ").unwrap();
+        assert_eq!(
+            cmd.filter,
+            Some(("status".to_string(), "open issue".to_string()))
+        );
+
+        cmd.parse(r#""last status" done"#).unwrap();
+        assert_eq!(
+            cmd.filter,
+            Some(("last status".to_string(), "done".to_string()))
+        );
+
+        cmd.parse("").unwrap();
+        assert_eq!(cmd.filter, None);
+
+        assert!(cmd.parse("status").is_err());
+    }
+
+    #[test]
+    fn parse_closure() {
+        let mut cmd = FilterCmd::default();
+        cmd.parse(" {|row| $row.size > 1mb } ").unwrap();
+        assert_eq!(cmd.closure.as_deref(), Some("{|row| $row.size > 1mb }"));
+        assert_eq!(cmd.filter, None);
+
+        cmd.parse(r#"$in.status == "open""#).unwrap();
+        assert_eq!(cmd.closure.as_deref(), Some(r#"{ $in.status == "open" }"#));
+
+        cmd.parse("status open").unwrap();
+        assert_eq!(cmd.closure, None);
+    }
+
+    #[test]
+    fn parse_edit() {
+        let mut cmd = FilterCmd::default();
+        cmd.parse("--edit").unwrap();
+        assert_eq!(cmd.edit, Some(None));
+        assert_eq!(cmd.filter, None);
+
+        cmd.parse(r#"--edit 2 "last status" done"#).unwrap();
+        assert_eq!(cmd.edit, Some(Some(2)));
+        assert_eq!(
+            cmd.filter,
+            Some(("last status".to_string(), "done".to_string()))
+        );
+
+        cmd.parse("status open").unwrap();
+        assert_eq!(cmd.edit, None);
+
+        assert!(cmd.parse("--edit 0 status open").is_err());
+        assert!(cmd.parse("--edit 1").is_err());
+    }
+
+    #[test]
+    fn split_partial_line() {
+        assert_eq!(
+            FilterCmd::split_partial("filter status op"),
+            Some(("status".to_string(), 14))
+        );
+        assert_eq!(
+            FilterCmd::split_partial(r#"filter "a b" "#),
+            Some(("a b".to_string(), 13))
+        );
+        assert_eq!(
+            FilterCmd::split_partial("filter --edit 2 status op"),
+            Some(("status".to_string(), 23))
+        );
+        assert_eq!(FilterCmd::split_partial("filter status"), None);
+        assert_eq!(FilterCmd::split_partial("filter {|row| $row.a "), None);
+        assert_eq!(FilterCmd::split_partial("filters status x"), None);
+        assert_eq!(FilterCmd::split_partial("nu ls"), None);
+    }
+}
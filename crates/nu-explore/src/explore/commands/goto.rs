@@ -0,0 +1,87 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct GotoCmd {
+    offset: usize,
+}
+
+impl GotoCmd {
+    pub const NAME: &'static str = "goto";
+}
+
+impl SimpleCommand for GotoCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "go to the byte at an offset, in decimal or 0x hex"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        self.offset = parse_offset(args)
+            .ok_or_else(|| anyhow::anyhow!(tr!("expected an offset, like 1024 or 0x400")))?;
+
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_binary_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only bytes are gone to by offset")))?;
+
+        if !view.go_to(self.offset) {
+            return Err(anyhow::anyhow!(tr!(
+                "There are only {size} bytes",
+                size = view.data().len()
+            )));
+        }
+        pager.show_message(tr!("At {position}", position = view.position_text()));
+
+        Ok(Transition::Ok)
+    }
+}
+
+// decimal, or hex after `0x`
+fn parse_offset(text: &str) -> Option<usize> {
+    let text = text.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_decimal_or_hex() {
+        let mut cmd = GotoCmd::default();
+        cmd.parse(" 0x4F0 ").unwrap();
+        assert_eq!(cmd.offset, 0x4f0);
+        cmd.parse("1024").unwrap();
+        assert_eq!(cmd.offset, 1024);
+
+        assert!(cmd.parse("").is_err());
+        assert!(cmd.parse("0x").is_err());
+        assert!(cmd.parse("-1").is_err());
+    }
+}
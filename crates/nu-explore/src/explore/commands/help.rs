@@ -1,4 +1,9 @@
-use super::super::views::{Preview, ViewConfig};
+use super::super::{
+    bindings::{self, Binding, KeyBinding, Rebound},
+    l10n::{self, tr},
+    registry::CommandRegistry,
+    views::{Preview, ViewConfig},
+};
 use super::ViewCommand;
 use anyhow::Result;
 use nu_ansi_term::Color;
@@ -6,84 +11,399 @@ use nu_protocol::{
     Value,
     engine::{EngineState, Stack},
 };
-
-use std::sync::LazyLock;
+use std::fmt::Write;
+use unicode_width::UnicodeWidthStr;
 
 #[derive(Debug, Default, Clone)]
 pub struct HelpCmd {}
 
 impl HelpCmd {
     pub const NAME: &'static str = "help";
-    pub fn view() -> Preview {
-        Preview::new(&HELP_MESSAGE)
+    /// The help page, with the keys as `config_bindings` rebind them.
+    pub fn view(config_bindings: &[KeyBinding]) -> Preview {
+        Preview::new(&help_message(config_bindings))
     }
 }
 
-static HELP_MESSAGE: LazyLock<String> = LazyLock::new(|| {
+// the width of the keys column
+const KEY_WIDTH: usize = 19;
+
+const NAVIGATION: &[(&str, &str)] = &[
+    ("↑ ↓ ← →", "Move cursor up/down/left/right"),
+    ("Enter", "Drill into a cell (select it)"),
+    ("Tab", "Switch between cursor and view (scroll) mode"),
+    ("Shift ← →", "Scroll the text of the selected cell"),
+    ("Esc / q", "Go back / exit current view"),
+    ("PgUp / PgDn", "Page up / Page down"),
+    ("c", "Jump to a column by name"),
+    ("Ctrl k", "Palette of the commands, found by typing"),
+    ("Click", "Select a cell, or sort by the column of a header"),
+    ("Right click", "Menu of the cell clicked, as x opens"),
+    ("Drag", "Move a column by its header, resize it by the edge"),
+    (
+        "Minimap click",
+        "Scroll to the rows of the line, or drag along",
+    ),
+    ("Wheel", "Scroll up/down"),
+    ("Ctrl z", "Suspend to the shell, fg to resume"),
+];
+
+const DATA_MANIPULATION: &[(&str, &str)] = &[
+    ("t", "Transpose (flip rows and columns)"),
+    ("e", "Expand nested data a level, + and - for more or less"),
+    ("a", "Show all rows of an abbreviated table"),
+    ("f", "Filter by the selected cell's column"),
+    ("o", "Sort by the selected column, again to reverse"),
+    ("o (record)", "Sort the fields by name, again to reverse"),
+    ("O", "Open the link of the selected cell, see :open"),
+    ("x", "Menu of the selected cell: open, copy, filter, ..."),
+    (
+        "y / Y / Alt y",
+        "Copy the selected cell / its row / its column",
+    ),
+    ("Alt ← →", "Move the selected column left/right"),
+    ("Alt ↑ ↓", "Move the selected row up/down"),
+    ("p", "Pin the selected row on top (again to unpin)"),
+    (
+        "P",
+        "Freeze the selected row on top, cells differing from it highlighted",
+    ),
+    ("m", "Mark the selected row for :compare"),
+    ("v", "Select a range, its sum/avg/min/max in the status bar"),
+    ("F", "Facets: top values per column, Enter filters"),
+    ("r", "Rotate the table, as :rotate does"),
+    ("s", "Status bar: position, selection, perf, source"),
+];
+
+// the keys of the navigation and data manipulation sections that are default keys of the
+// keymap: as they're shown, their keys in the keymap, their actions there and the views they
+// do them in
+const KEYMAP_KEYS: &[(&str, &str, &str, &str)] = &[
+    (
+        "Enter",
+        "enter",
+        "select the cell, then drill into it",
+        "table:cursor",
+    ),
+    (
+        "Tab",
+        "tab",
+        "switch between the cursor and view modes",
+        "table",
+    ),
+    ("c", "c", ":col", "table"),
+    ("Ctrl k", "ctrl+k", ":palette of the commands", "all"),
+    ("Ctrl z", "ctrl+z", "suspend to the shell", "all"),
+    ("t", "t", "transpose", "table:view"),
+    ("e", "e", "expand nested data", "table"),
+    ("a", "a", "show all rows of an abbreviated table", "table"),
+    (
+        "f",
+        "f",
+        ":filter by the selected cell's column",
+        "table:cursor",
+    ),
+    (
+        "o",
+        "o",
+        "sort by the column, again to reverse",
+        "table:cursor",
+    ),
+    (
+        "O",
+        "O",
+        ":open the link of the selected cell",
+        "table:cursor",
+    ),
+    ("x", "x", ":menu of the selected cell", "table:cursor"),
+    ("p", "p", "pin the selected row", "table:cursor"),
+    (
+        "P",
+        "P",
+        "freeze the selected row to compare the others to",
+        "table:cursor",
+    ),
+    ("m", "m", "mark the selected row", "table:cursor"),
+    ("v", "v", "select a range", "table:cursor"),
+    ("F", "F", "facets", "table"),
+    ("r", "r", ":rotate", "table"),
+    ("s", "s", "switch what the status bar shows", "all"),
+];
+
+const COMMANDS: &[(&str, &str)] = &[
+    (":help", "Show this help page"),
+    (
+        ":palette",
+        "Find a command by name or description, and run it",
+    ),
+    (
+        "Tab",
+        "Complete a command, its flags, a column, or a path of :save",
+    ),
+    (":try [expr]", "Open interactive REPL, running expr in it"),
+    (
+        ":nu <cmd>",
+        "Run a Nushell command on the rows shown or the selection (or --all)",
+    ),
+    (
+        ":nu! <cmd>",
+        "Run a Nushell command on the cell under the cursor",
+    ),
+    (
+        ":filter <col> <v>",
+        "Keep rows where column is value (Tab completes)",
+    ),
+    (
+        ":filter {closure}",
+        "Keep the rows a closure returns true for",
+    ),
+    (":filter", "Clear all filters"),
+    (":filter --edit", "Edit the last filter, in the command bar"),
+    (":filters", "List the filters, to turn each on/off"),
+    (
+        ":sort <col> [desc]",
+        "Sort the rows by a column, --nulls first/last",
+    ),
+    (
+        ":copy [row|column]",
+        "Copy the cell, its row or column, --nuon or --path",
+    ),
+    (":open", "Open the link of the cell, as its column sets"),
+    (
+        ":yank-column",
+        "Copy the column as a list, or exit returning it: --return",
+    ),
+    (
+        ":registers",
+        "The last texts copied, Ctrl r and a number pastes in :try",
+    ),
+    (
+        ":encoding <name>",
+        "Decode the bytes as text, like latin1 or shift_jis",
+    ),
+    (
+        ":bytes",
+        "Show the bytes of the cell in a binary view, as Enter does",
+    ),
+    (
+        ":error",
+        "Browse an error: its source, diagnostic, the errors causing it",
+    ),
+    (
+        ":goto <offset>",
+        "In bytes, go to an offset, 1024 or 0x400; G to the end",
+    ),
+    (
+        ":set hex.<key> <v>",
+        "Lay bytes out: hex.bytes_per_line 32/auto, group, ascii",
+    ),
+    (
+        ":save [--force] <p>",
+        "Save the table as shown to .csv/.json/.nuon/.yaml, or the bytes",
+    ),
+    (":preset save <n>", "Save the current filters as a preset"),
+    (":preset <n>", "Apply a saved preset"),
+    (":preset", "List the saved presets"),
+    (":unpin", "Unpin all rows, the frozen one too"),
+    (
+        ":rotate",
+        "Turn the table round: a record across, or a field a line",
+    ),
+    (":compare", "Show the marked rows side by side"),
+    (
+        ":stats",
+        "Sum up each column: count, nulls, min, max, mean, ...",
+    ),
+    (
+        ":hist <col> [bins]",
+        "Histogram of a column, numbers binned into ranges",
+    ),
+    (
+        ":chart <x> <y>...",
+        "Plot columns over a date or number column, --bar",
+    ),
+    (
+        ":extract <c> <re>",
+        "Add a regex's named captures as columns",
+    ),
+    (
+        ":split-col <c> <d>",
+        "Split a column by a delimiter into columns",
+    ),
+    (
+        ":rename <old> <new>",
+        "Rename a column, as it's saved, copied and returned",
+    ),
+    (
+        ":common-columns",
+        "Only the columns every row has, again for all; % is how full",
+    ),
+    (
+        ":bucket <c> <ivl>",
+        "Count rows per time bucket (--agg sum:<col>)",
+    ),
+    (":tag <text>", "Tag the selected row (no text: untag)"),
+    (
+        ":replace <p> <r>",
+        "Regex replace in string cells (--column c)",
+    ),
+    (":pages", "List the drilled down pages, Enter goes back"),
+    (":title [name]", "Name the page shown (no name: unname)"),
+    (
+        ":info",
+        "Tell where the data came from, its size, read time",
+    ),
+    (
+        ":col <name>",
+        "Move to the column, or field of a record, best matching name",
+    ),
+    (":bindings [text]", "List the keys, or those matching text"),
+    (
+        ":inline [col]",
+        "Show the small tables of col in their cells",
+    ),
+    (
+        ":recent [n]",
+        "List the recent sources, or explore one again",
+    ),
+    (":q", "Exit Explore"),
+];
+
+const SEARCH: &[(&str, &str)] = &[
+    (
+        "/",
+        "Search forward, every match highlighted (a regex, or text)",
+    ),
+    ("?", "Start reverse search"),
+    (
+        "/deadbeef",
+        "In bytes, search hex digits; /text:hello searches text",
+    ),
+    (
+        "Tab",
+        "While typing a search, switch to fuzzy search: the closest matches first",
+    ),
+    ("n N Enter", "Navigate search results"),
+    ("Esc", "Stop a search of many lines, still running"),
+];
+
+/// The help page, in the language of the messages.
+fn help_message(config_bindings: &[KeyBinding]) -> String {
     let title = nu_ansi_term::Style::new().bold();
     let section = nu_ansi_term::Style::new().bold().fg(Color::Cyan);
     let code = nu_ansi_term::Style::new().bold().fg(Color::Blue);
     let key = nu_ansi_term::Style::new().bold().fg(Color::Green);
     let dim = nu_ansi_term::Style::new().dimmed();
 
-    format!(
+    let mut text = format!(
         "
-  {} Explore Help {}
+  {} {} {}
 
-  Explore helps you dynamically navigate through your data.
-  Launch it by piping data into the command: {}
+  {}
+  {} {}
+",
+        title.paint("━━"),
+        tr!("Explore Help"),
+        title.paint("━━"),
+        tr!("Explore helps you dynamically navigate through your data."),
+        tr!("Launch it by piping data into the command:"),
+        code.paint("ls | explore"),
+    );
 
-  {} Navigation
+    // the sections, and whether their keys are those of the keymap
+    let sections = [
+        (tr!("Navigation"), None, NAVIGATION, true),
+        (tr!("Data Manipulation"), None, DATA_MANIPULATION, true),
+        (
+            tr!("Commands"),
+            Some(tr!("(type : then command)")),
+            COMMANDS,
+            false,
+        ),
+        (tr!("Search"), None, SEARCH, false),
+    ];
+    for (name, note, entries, keymap) in sections {
+        let _ = write!(text, "\n  {} {name}", section.paint("▸"));
+        if let Some(note) = note {
+            let _ = write!(text, " {}", dim.paint(note));
+        }
+        text.push_str("\n\n");
 
-    {}            Move cursor up/down/left/right
-    {}              Drill into a cell (select it)
-    {}            Go back / exit current view
-    {}        Page up / Page down
+        for (keys, description) in entries {
+            let keys = match keymap {
+                true => match shown_keys(keys, config_bindings) {
+                    Some(keys) => keys,
+                    None => continue,
+                },
+                false => keys.to_string(),
+            };
+            let padding = KEY_WIDTH.saturating_sub(keys.width()).max(1);
+            let _ = writeln!(
+                text,
+                "    {}{}{}",
+                key.paint(&keys),
+                " ".repeat(padding),
+                l10n::translate(description, &[]),
+            );
+        }
+    }
+    text.push('\n');
 
-  {} Data Manipulation
+    text
+}
 
-    {}                  Transpose (flip rows and columns)
-    {}                  Expand (show all nested data)
+// the keys of an entry of the help as `config_bindings` rebind them, none when no key does
+// what they did anymore
+fn shown_keys(keys: &str, config_bindings: &[KeyBinding]) -> Option<String> {
+    let Some((_, key, action, view)) = KEYMAP_KEYS.iter().find(|(shown, ..)| *shown == keys) else {
+        return Some(keys.to_string());
+    };
 
-  {} Commands {}
+    match bindings::rebound(key, action, view, config_bindings) {
+        Rebound::Same => Some(keys.to_string()),
+        Rebound::Moved(keys) => Some(keys),
+        Rebound::Off => None,
+    }
+}
 
-    {}              Show this help page
-    {}               Open interactive REPL
-    {}          Run a Nushell command on current data
-    {}                 Exit Explore
+/// The commands of `registry` and the keys of `bindings` as plain text, for the help of
+/// `explore` in nushell: `help explore` and `explore --help`.
+pub fn help_reference(registry: &CommandRegistry, bindings: &[Binding]) -> String {
+    let mut commands: Vec<_> = registry.get_commands().collect();
+    commands.sort_by_key(|command| command.name());
 
-  {} Search
+    let mut text = String::from("Commands, typed after `:` in explore:\n");
+    for command in commands {
+        let name = command.name();
+        let mut line = format!("  :{name}");
+        let aliases = registry.aliases_of(name);
+        if !aliases.is_empty() {
+            let _ = write!(line, " (:{})", aliases.join(", :"));
+        }
+        let padding = KEY_WIDTH.saturating_sub(line.width()).max(1);
+        let _ = writeln!(
+            text,
+            "{line}{}{}",
+            " ".repeat(padding),
+            command.description()
+        );
+    }
 
-    {}                  Start forward search
-    {}                  Start reverse search
-    {} {} {}          Navigate search results
+    text.push_str("\nKeys:\n");
+    for binding in bindings {
+        let key = format!("  {}", binding.key);
+        let padding = KEY_WIDTH.saturating_sub(key.width()).max(1);
+        let _ = writeln!(
+            text,
+            "{key}{}{} ({})",
+            " ".repeat(padding),
+            binding.command,
+            binding.view
+        );
+    }
 
-",
-        title.paint("━━"),
-        title.paint("━━"),
-        code.paint("ls | explore"),
-        section.paint("▸"),
-        key.paint("↑ ↓ ← →"),
-        key.paint("Enter"),
-        key.paint("Esc / q"),
-        key.paint("PgUp / PgDn"),
-        section.paint("▸"),
-        key.paint("t"),
-        key.paint("e"),
-        section.paint("▸"),
-        dim.paint("(type : then command)"),
-        key.paint(":help"),
-        key.paint(":try"),
-        key.paint(":nu <cmd>"),
-        key.paint(":q"),
-        section.paint("▸"),
-        key.paint("/"),
-        key.paint("?"),
-        key.paint("n"),
-        key.paint("N"),
-        key.paint("Enter"),
-    )
-});
+    text
+}
 
 // TODO: search help could use some updating... search results get shown immediately after typing, don't need to press Enter
 // const HELP_MESSAGE: &str = r#"# Explore
@@ -122,7 +442,7 @@ impl ViewCommand for HelpCmd {
     }
 
     fn description(&self) -> &'static str {
-        ""
+        "show the keys and commands of explore"
     }
 
     fn parse(&mut self, _: &str) -> Result<()> {
@@ -134,8 +454,54 @@ impl ViewCommand for HelpCmd {
         _: &EngineState,
         _: &mut Stack,
         _: Option<Value>,
-        _: &ViewConfig,
+        config: &ViewConfig,
     ) -> Result<Self::View> {
-        Ok(HelpCmd::view())
+        Ok(HelpCmd::view(&config.explore_config.keybindings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::QuitCmd;
+    use super::*;
+    use crate::explore::{bindings::KeyAction, pager::PagePolicy};
+
+    #[test]
+    fn help_reference_lists_commands_and_aliases() {
+        let mut registry = CommandRegistry::new();
+        registry.register_command_view(HelpCmd::default(), PagePolicy::Modal);
+        registry.register_command_reactive(QuitCmd);
+        registry.create_aliases("q!", QuitCmd::NAME);
+        registry.create_aliases("q", QuitCmd::NAME);
+
+        let text = help_reference(&registry, &[]);
+        let lines: Vec<_> = text.lines().collect();
+        assert!(lines[1].starts_with("  :help "));
+        assert!(lines[1].ends_with("show the keys and commands of explore"));
+        assert!(lines[2].starts_with("  :quit (:q, :q!) "));
+        assert!(lines[2].ends_with("exit explore"));
+        assert_eq!(lines[4], "Keys:");
+    }
+
+    #[test]
+    fn help_shows_rebound_keys() {
+        let bind = |key: &str, action: KeyAction| KeyBinding {
+            keys: vec![bindings::parse_key(key).unwrap()],
+            action,
+            view: String::from("table"),
+        };
+        let bindings = [
+            bind("T", KeyAction::Default(String::from("transpose"))),
+            bind("t", KeyAction::Nothing),
+            bind("m", KeyAction::Nothing),
+        ];
+
+        assert_eq!(shown_keys("t", &bindings), Some(String::from("T")));
+        assert_eq!(shown_keys("m", &bindings), None);
+        assert_eq!(shown_keys("e", &bindings), Some(String::from("e")));
+        assert_eq!(
+            shown_keys("Alt ← →", &bindings),
+            Some(String::from("Alt ← →"))
+        );
     }
 }
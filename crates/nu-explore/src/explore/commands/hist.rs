@@ -0,0 +1,275 @@
+use super::super::{
+    l10n::tr,
+    nu_common::{NuConfig, Unit, collect_input, is_missing_cell},
+    views::{Bar, HistogramView, ViewConfig},
+};
+use super::{ViewCommand, filter::split_column};
+use anyhow::Result;
+use nu_protocol::{
+    Span, Value,
+    engine::{EngineState, Stack},
+};
+use std::collections::HashMap;
+
+/// Shows how the values of a column are spread in a histogram view: numbers, sizes and
+/// durations binned into ranges, anything else counted by value.
+#[derive(Debug, Default, Clone)]
+pub struct HistCmd {
+    column: Option<String>,
+    bins: Option<usize>,
+}
+
+impl HistCmd {
+    pub const NAME: &'static str = "hist";
+
+    /// The ranges numbers are binned into, unless told otherwise.
+    const BINS: usize = 10;
+    const MAX_BINS: usize = 1000;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ViewCommand for HistCmd {
+    type View = HistogramView;
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "show a histogram of a column: numbers binned into ranges, other values counted"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        self.column = None;
+        self.bins = None;
+
+        let args = args.trim();
+        if args.is_empty() {
+            return Ok(());
+        }
+
+        let (column, rest) =
+            split_column(args).ok_or_else(|| anyhow::anyhow!(tr!("expected <column> [bins]")))?;
+        let bins = match rest.trim() {
+            "" => None,
+            bins => match bins.parse::<usize>() {
+                Ok(bins) if (1..=Self::MAX_BINS).contains(&bins) => Some(bins),
+                _ => {
+                    return Err(anyhow::anyhow!(tr!(
+                        "{bins} isn't a number of bins from 1 to {max}",
+                        bins = format!("{bins:?}"),
+                        max = Self::MAX_BINS
+                    )));
+                }
+            },
+        };
+
+        self.column = Some(column);
+        self.bins = bins;
+
+        Ok(())
+    }
+
+    fn spawn(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        value: Option<Value>,
+        config: &ViewConfig,
+    ) -> Result<Self::View> {
+        let value = value.unwrap_or_default();
+        if !matches!(value, Value::List { .. } | Value::Record { .. }) {
+            return Err(anyhow::anyhow!(tr!(
+                "Only tables and lists have histograms, this is a {kind}",
+                kind = value.get_type().to_string()
+            )));
+        }
+
+        let (columns, rows) = collect_input(value)?;
+        let (name, cells): (_, Vec<&Value>) = match self.column.take() {
+            Some(column) => {
+                let i = columns
+                    .iter()
+                    .position(|name| *name == column)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(tr!("There is no column {column}", column = column))
+                    })?;
+                (column, rows.iter().filter_map(|row| row.get(i)).collect())
+            }
+            // a list of plain values is a table of one column without a name
+            None if columns.is_empty() || columns == [""] => {
+                (String::new(), rows.iter().flatten().collect())
+            }
+            None => {
+                return Err(anyhow::anyhow!(tr!(
+                    "expected a column, one of {columns}",
+                    columns = columns.join(", ")
+                )));
+            }
+        };
+
+        let (empty, values): (Vec<&Value>, Vec<&Value>) = cells
+            .into_iter()
+            .partition(|value| matches!(value, Value::Nothing { .. }) || is_missing_cell(value));
+        let bins = self.bins.take().unwrap_or(Self::BINS);
+        let bars = histogram(&values, bins, config.nu_config);
+
+        let mut title = match name.is_empty() {
+            true => tr!("{count} values", count = values.len()),
+            false => tr!(
+                "{column}: {count} values",
+                column = name,
+                count = values.len()
+            ),
+        };
+        if !empty.is_empty() {
+            title.push_str(&tr!(", {count} empty left out", count = empty.len()));
+        }
+
+        Ok(HistogramView::new(title, bars))
+    }
+}
+
+/// The bars of `values`: `bins` ranges of equal width if they're all numbers of one unit, a
+/// bar a distinct value otherwise, the most common first.
+fn histogram(values: &[&Value], bins: usize, config: &NuConfig) -> Vec<Bar> {
+    let numbers: Option<Vec<(f64, Unit)>> = values.iter().map(|value| Unit::of(value)).collect();
+    match numbers {
+        Some(numbers) if !numbers.is_empty() && numbers.iter().all(|(_, u)| *u == numbers[0].1) => {
+            let integers = values
+                .iter()
+                .all(|value| !matches!(value, Value::Float { .. }));
+            let unit = numbers[0].1;
+            let numbers: Vec<f64> = numbers.into_iter().map(|(n, _)| n).collect();
+            number_bars(&numbers, unit, integers, bins, config)
+        }
+        _ => value_bars(values, config),
+    }
+}
+
+fn value_bars(values: &[&Value], config: &NuConfig) -> Vec<Bar> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for value in values {
+        *counts
+            .entry(value.to_abbreviated_string(config))
+            .or_default() += 1;
+    }
+
+    let mut bars: Vec<Bar> = counts
+        .into_iter()
+        .map(|(label, count)| Bar::new(label, count))
+        .collect();
+    bars.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.label.cmp(&b.label)));
+
+    bars
+}
+
+fn number_bars(
+    numbers: &[f64],
+    unit: Unit,
+    integers: bool,
+    bins: usize,
+    config: &NuConfig,
+) -> Vec<Bar> {
+    let min = numbers.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = Span::unknown();
+    let text = |number: f64| match (unit, integers) {
+        (Unit::Number, true) => Value::int(number as i64, span).to_abbreviated_string(config),
+        (unit, _) => unit.text(number, (max - min) / bins as f64, config),
+    };
+
+    // integers are binned in whole ranges, each value its own bar if there are few enough
+    let (width, bins) = match integers {
+        true => {
+            let width = ((max - min + 1.0) / bins as f64).ceil().max(1.0);
+            (width, ((max - min + 1.0) / width).ceil() as usize)
+        }
+        false if max > min => ((max - min) / bins as f64, bins),
+        false => (1.0, 1),
+    };
+
+    let mut counts = vec![0; bins];
+    for number in numbers {
+        let bin = ((number - min) / width) as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let start = min + width * i as f64;
+            let label = match integers {
+                true if width == 1.0 => text(start),
+                // ranges of integers include their end
+                true => format!("{}..{}", text(start), text(start + width - 1.0)),
+                false if bins == 1 => text(start),
+                false if i + 1 == bins => format!("{}..{}", text(start), text(max)),
+                // ranges of floats don't, but the last
+                false => format!("{}..<{}", text(start), text(start + width)),
+            };
+            Bar::new(label, count)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(bars: &[Bar]) -> Vec<(&str, usize)> {
+        bars.iter()
+            .map(|bar| (bar.label.as_str(), bar.count))
+            .collect()
+    }
+
+    #[test]
+    fn bin_numbers() {
+        let span = Span::test_data();
+        let config = NuConfig::default();
+        let values: Vec<Value> = [1, 2, 2, 3, 9].map(|n| Value::int(n, span)).into();
+        let values: Vec<&Value> = values.iter().collect();
+
+        let bars = histogram(&values, 3, &config);
+        assert_eq!(labels(&bars), [("1..3", 4), ("4..6", 0), ("7..9", 1)]);
+
+        // few enough integers are a bar each
+        let bars = histogram(&values[..4], 10, &config);
+        assert_eq!(labels(&bars), [("1", 1), ("2", 2), ("3", 1)]);
+
+        let floats: Vec<Value> = [0.0, 0.5, 1.0].map(|n| Value::float(n, span)).into();
+        let floats: Vec<&Value> = floats.iter().collect();
+        let bars = histogram(&floats, 2, &config);
+        assert_eq!(labels(&bars), [("0.00..<0.50", 1), ("0.50..1.00", 2)]);
+    }
+
+    #[test]
+    fn count_other_values() {
+        let span = Span::test_data();
+        let config = NuConfig::default();
+        let values: Vec<Value> = ["dir", "file", "file", "symlink", "dir", "file"]
+            .map(|text| Value::string(text, span))
+            .into();
+        let values: Vec<&Value> = values.iter().collect();
+
+        let bars = histogram(&values, 10, &config);
+        assert_eq!(labels(&bars), [("file", 3), ("dir", 2), ("symlink", 1)]);
+    }
+
+    #[test]
+    fn parse_column_and_bins() {
+        let mut cmd = HistCmd::new();
+        cmd.parse(r#""file size" 20"#).unwrap();
+        assert_eq!(cmd.column.as_deref(), Some("file size"));
+        assert_eq!(cmd.bins, Some(20));
+
+        cmd.parse("").unwrap();
+        assert_eq!(cmd.column, None);
+        assert!(cmd.parse("size 0").is_err());
+        assert!(cmd.parse("size many").is_err());
+    }
+}
@@ -0,0 +1,124 @@
+use super::super::{
+    l10n::tr,
+    nu_common::{MAX_RANGE_VALUES, format_size},
+    pager::{
+        Pager, Transition,
+        input::{InputInfo, format_duration},
+    },
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+/// Tells what's explored: where it came from, how big it is and how long reading it took.
+#[derive(Debug, Default, Clone)]
+pub struct InfoCmd;
+
+impl InfoCmd {
+    pub const NAME: &'static str = "info";
+}
+
+impl SimpleCommand for InfoCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "tell where the data came from, its rows, columns and size, and how long reading it took"
+    }
+
+    fn parse(&mut self, _: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let table = view
+            .and_then(|view| view.as_record_view())
+            .map(|view| view.data_size());
+        let source = pager.source().map(|entry| entry.short_source());
+
+        let info = describe(pager.input(), source.as_deref(), table);
+        pager.show_message(info);
+
+        Ok(Transition::Ok)
+    }
+}
+
+/// What `:info` says of the `input`, from `source` if it's known, and of the `table` shown:
+/// its rows, columns and bytes.
+fn describe(
+    input: &InputInfo,
+    source: Option<&str>,
+    table: Option<(usize, usize, usize)>,
+) -> String {
+    let mut parts = Vec::new();
+    parts.push(match source {
+        Some(source) => tr!(
+            "{shape} from {source}",
+            shape = input.shape.name(),
+            source = source
+        ),
+        None => input.shape.name().to_string(),
+    });
+    if let Some((rows, columns, bytes)) = table {
+        parts.push(tr!(
+            "{rows} rows × {columns} columns",
+            rows = rows,
+            columns = columns
+        ));
+        parts.push(tr!("~{size} in memory", size = format_size(bytes)));
+    }
+    parts.push(tr!(
+        "read in {time}",
+        time = format_duration(input.collected_in)
+    ));
+    if input.truncated {
+        parts.push(tr!(
+            "cut to its first {count} values",
+            count = MAX_RANGE_VALUES
+        ));
+    }
+
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::pager::input::InputShape;
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn describe_the_input() {
+        let input = InputInfo {
+            shape: InputShape::ListStream,
+            collected_in: Duration::from_millis(120),
+            truncated: false,
+        };
+        assert_eq!(
+            describe(&input, Some("data.json"), Some((3, 2, 2048))),
+            "list stream from data.json, 3 rows × 2 columns, ~2.0 kB in memory, read in 120 ms"
+        );
+
+        let input = InputInfo {
+            shape: InputShape::Value,
+            truncated: true,
+            ..input
+        };
+        assert_eq!(
+            describe(&input, None, None),
+            format!("value, read in 120 ms, cut to its first {MAX_RANGE_VALUES} values")
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct InlineCmd {
+    // the column under the cursor when `None`
+    column: Option<String>,
+}
+
+impl InlineCmd {
+    pub const NAME: &'static str = "inline";
+}
+
+impl SimpleCommand for InlineCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "show the small tables of a column in their cells, or stop showing them so"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let args = args.trim();
+        self.column = (!args.is_empty()).then(|| args.to_string());
+
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only table columns can be shown inline")))?;
+
+        let (column, inline) = view.toggle_inline(self.column.take().as_deref())?;
+        let column = format!("{column:?}");
+        if inline {
+            pager.show_message(tr!("Tables of {column} shown inline", column = column));
+        } else {
+            pager.show_message(tr!("Tables of {column} folded", column = column));
+        }
+
+        Ok(Transition::Ok)
+    }
+}
@@ -0,0 +1,58 @@
+use super::super::{
+    config::MenuEntry,
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+/// Opens the menu of the selected cell, listing what can be done with it.
+#[derive(Debug, Default, Clone)]
+pub struct MenuCmd {
+    // what the commands offer, then what's added in `explore.menu`
+    entries: Vec<MenuEntry>,
+}
+
+impl MenuCmd {
+    pub const NAME: &'static str = "menu";
+
+    pub fn new(entries: Vec<MenuEntry>) -> Self {
+        Self { entries }
+    }
+}
+
+impl SimpleCommand for MenuCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "list what can be done with the selected cell: open, copy, filter, sort, ..."
+    }
+
+    fn parse(&mut self, _: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        _: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only table cells have a menu")))?;
+
+        view.show_menu(&self.entries);
+
+        Ok(Transition::Ok)
+    }
+}
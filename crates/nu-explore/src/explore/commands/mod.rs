@@ -1,24 +1,102 @@
 use super::pager::{Pager, Transition};
-use super::views::ViewConfig;
+use super::views::{View, ViewConfig};
 use anyhow::Result;
 use nu_protocol::{
     Value,
     engine::{EngineState, Stack},
 };
 
+mod bindings;
+mod bucket;
+mod bytes;
+mod chart;
+mod col;
+mod common_columns;
+mod compare;
+mod copy;
+mod encoding;
+mod error;
 mod expand;
+mod extension;
+mod extract;
+mod filter;
+mod filters;
+mod goto;
 mod help;
+mod hist;
+mod info;
+mod inline;
+mod menu;
 mod nu;
+mod open;
+mod pages;
+mod palette;
+mod preset;
 mod quit;
+mod recent;
+mod registers;
+mod rename;
+mod replace;
+mod rotate;
+mod save;
+mod set;
+mod sort;
+mod split_col;
+mod stats;
+#[cfg(any(debug_assertions, feature = "stress"))]
+mod stress;
 mod table;
+mod tag;
+mod title;
 mod r#try;
+mod unpin;
+mod yank_column;
 
+pub use bindings::BindingsCmd;
+pub use bucket::BucketCmd;
+pub use bytes::BytesCmd;
+pub use chart::ChartCmd;
+pub use col::ColCmd;
+pub use common_columns::CommonColumnsCmd;
+pub use compare::CompareCmd;
+pub use copy::CopyCmd;
+pub use encoding::EncodingCmd;
+pub use error::{ErrorCmd, has_error};
 pub use expand::ExpandCmd;
-pub use help::HelpCmd;
+pub use extension::{ExploreCommand, ExtensionCmd};
+pub use extract::ExtractCmd;
+pub use filter::FilterCmd;
+pub use filters::FiltersCmd;
+pub use goto::GotoCmd;
+pub use help::{HelpCmd, help_reference};
+pub use hist::HistCmd;
+pub use info::InfoCmd;
+pub use inline::InlineCmd;
+pub use menu::MenuCmd;
 pub use nu::NuCmd;
+pub use open::OpenCmd;
+pub use pages::PagesCmd;
+pub use palette::PaletteCmd;
+pub use preset::PresetCmd;
 pub use quit::QuitCmd;
+pub use recent::RecentCmd;
+pub use registers::RegistersCmd;
+pub use rename::RenameCmd;
+pub use replace::ReplaceCmd;
+pub use rotate::RotateCmd;
+pub use save::SaveCmd;
+pub use set::SetCmd;
+pub use sort::SortCmd;
+pub use split_col::SplitColCmd;
+pub use stats::StatsCmd;
+#[cfg(any(debug_assertions, feature = "stress"))]
+pub use stress::StressCmd;
 pub use table::TableCmd;
+pub use tag::TagCmd;
+pub use title::TitleCmd;
 pub use r#try::TryCmd;
+pub use unpin::UnpinCmd;
+pub use yank_column::YankColumnCmd;
 
 pub trait SimpleCommand {
     fn name(&self) -> &'static str;
@@ -27,11 +105,23 @@ pub trait SimpleCommand {
 
     fn parse(&mut self, args: &str) -> Result<()>;
 
+    /// The flags the command takes, completed on <Tab> in the command line.
+    fn flags(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// What the command offers in the menu of a cell (`x`): labels and the command lines
+    /// they run, see [`MenuEntry`](super::config::MenuEntry).
+    fn menu_entries(&self) -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
     fn react(
         &mut self,
         engine_state: &EngineState,
         stack: &mut Stack,
         pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
         value: Option<Value>,
     ) -> Result<Transition>;
 }
@@ -45,6 +135,29 @@ pub trait ViewCommand {
 
     fn parse(&mut self, args: &str) -> Result<()>;
 
+    /// The flags the command takes, like [`SimpleCommand::flags`].
+    fn flags(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// What the command offers in the menu of a cell (`x`), like
+    /// [`SimpleCommand::menu_entries`].
+    fn menu_entries(&self) -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Whether [`ViewCommand::spawn`] is given the selection of the current view, when
+    /// there's one, rather than everything it shows.
+    fn uses_selection(&self) -> bool {
+        false
+    }
+
+    /// Whether [`ViewCommand::spawn`] is given the cell under the cursor alone, in place of
+    /// the selection or everything the view shows.
+    fn uses_cell(&self) -> bool {
+        false
+    }
+
     fn spawn(
         &mut self,
         engine_state: &EngineState,
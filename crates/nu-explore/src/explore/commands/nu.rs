@@ -1,11 +1,13 @@
 use super::super::{
     config::ExploreConfig,
+    deterministic,
+    l10n::tr,
     nu_common::{NuText, run_command_with_value},
     pager::{
         Frame, Transition, ViewInfo,
         report::{Report, Severity},
     },
-    views::{Layout, Orientation, Preview, RecordView, View, ViewConfig},
+    views::{KeyHint, Layout, Preview, RecordView, View, ViewConfig},
 };
 use super::ViewCommand;
 use anyhow::Result;
@@ -22,12 +24,18 @@ use std::thread::{self, JoinHandle};
 #[derive(Debug, Default, Clone)]
 pub struct NuCmd {
     command: String,
+    // run on the whole view even when part of it is selected
+    all: bool,
+    // run on the cell under the cursor alone, whatever is selected
+    cell: bool,
 }
 
 impl NuCmd {
     pub fn new() -> Self {
         Self {
             command: String::new(),
+            all: false,
+            cell: false,
         }
     }
 
@@ -42,15 +50,51 @@ impl ViewCommand for NuCmd {
     }
 
     fn description(&self) -> &'static str {
-        ""
+        "run a nushell command on the rows shown or the selection, on all of the data with --all, on the cell under the cursor with --cell (or :nu!)"
     }
 
     fn parse(&mut self, args: &str) -> Result<()> {
-        args.trim().clone_into(&mut self.command);
+        let mut command = args.trim();
+        self.all = false;
+        self.cell = false;
+        loop {
+            let flag = |name: &str| match command.strip_prefix(name) {
+                Some(rest) if rest.is_empty() || rest.starts_with(' ') => Some(rest.trim_start()),
+                _ => None,
+            };
+            if let Some(rest) = flag("--all") {
+                self.all = true;
+                command = rest;
+            } else if let Some(rest) = flag("--cell") {
+                self.cell = true;
+                command = rest;
+            } else {
+                break;
+            }
+        }
+
+        if self.all && self.cell {
+            return Err(anyhow::anyhow!(tr!(
+                "--all and --cell can't be used together"
+            )));
+        }
+        command.clone_into(&mut self.command);
 
         Ok(())
     }
 
+    fn flags(&self) -> &'static [&'static str] {
+        &["--all", "--cell"]
+    }
+
+    fn uses_selection(&self) -> bool {
+        !self.all
+    }
+
+    fn uses_cell(&self) -> bool {
+        self.cell
+    }
+
     fn spawn(
         &mut self,
         engine_state: &EngineState,
@@ -127,7 +171,7 @@ fn stream_command(
     let pipeline = match run_command_with_value(command, value, engine_state, stack) {
         Ok(p) => p,
         Err(e) => {
-            let _ = sender.send(StreamMessage::Error(format!("Command failed: {e}")));
+            let _ = sender.send(StreamMessage::Error(tr!("Command failed: {e}", e = e)));
             return;
         }
     };
@@ -171,9 +215,9 @@ fn stream_command(
                     let _ = sender.send(StreamMessage::SimpleValue(text));
                 }
                 Err(e) => {
-                    let _ = sender.send(StreamMessage::Error(format!(
-                        "Failed to read stream: {}",
-                        Value::error(e, span).to_debug_string()
+                    let _ = sender.send(StreamMessage::Error(tr!(
+                        "Failed to read stream: {e}",
+                        e = Value::error(e, span).to_debug_string()
                     )));
                 }
             }
@@ -281,7 +325,7 @@ enum ViewState {
     /// Streaming/showing a RecordView
     Records(Box<RecordView>),
     /// Showing a simple preview
-    Preview(Preview),
+    Preview(Box<Preview>),
     /// Command completed with no output
     Empty,
 }
@@ -327,7 +371,7 @@ impl NuView {
                     self.is_record = true;
                 }
                 Ok(StreamMessage::SimpleValue(text)) => {
-                    self.state = ViewState::Preview(Preview::new(&text));
+                    self.state = ViewState::Preview(Box::new(Preview::new(&text)));
                     self.stream_done = true;
                     // Don't put receiver back - we're done
                     return;
@@ -411,7 +455,7 @@ impl NuView {
                     RecordView::new(cols, self.rows.clone(), self.explore_config.clone());
 
                 if self.is_record {
-                    view.set_top_layer_orientation(Orientation::Left);
+                    view.set_top_layer_record();
                 }
 
                 self.state = ViewState::Records(Box::new(view));
@@ -421,6 +465,9 @@ impl NuView {
 
     fn spinner_char(&self) -> char {
         const SPINNER: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+        if deterministic::is_enabled() {
+            return SPINNER[0];
+        }
         SPINNER[self.frame_count % SPINNER.len()]
     }
 
@@ -497,7 +544,11 @@ impl View for NuView {
         match &self.state {
             ViewState::Loading => {
                 let spinner = self.spinner_char();
-                let msg = format!("{} Running: {}", spinner, self.command_text);
+                let msg = tr!(
+                    "{spinner} Running: {command}",
+                    spinner = spinner,
+                    command = self.command_text
+                );
                 info.status = Some(Report::message(msg, Severity::Info));
                 true // Keep polling
             }
@@ -505,12 +556,16 @@ impl View for NuView {
                 let row_count = self.row_count();
                 if self.is_streaming() {
                     let spinner = self.spinner_char();
-                    let msg = format!("{} Streaming: {} rows", spinner, row_count);
+                    let msg = tr!(
+                        "{spinner} Streaming: {count} rows",
+                        spinner = spinner,
+                        count = row_count
+                    );
                     info.status = Some(Report::message(msg, Severity::Info));
                     true // Keep polling
                 } else {
                     info.status = Some(Report::new(
-                        format!("{} rows", row_count),
+                        tr!("{count} rows", count = row_count),
                         Severity::Info,
                         String::new(),
                         String::new(),
@@ -520,11 +575,11 @@ impl View for NuView {
                 }
             }
             ViewState::Preview(_) => {
-                info.status = Some(Report::message("Preview", Severity::Info));
+                info.status = Some(Report::message(tr!("Preview"), Severity::Info));
                 false // Done polling
             }
             ViewState::Empty => {
-                info.status = Some(Report::message("No output", Severity::Info));
+                info.status = Some(Report::message(tr!("No output"), Severity::Info));
                 false // Done polling
             }
         }
@@ -553,6 +608,29 @@ impl View for NuView {
             _ => None,
         }
     }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        match &self.state {
+            ViewState::Records(view) => view.key_hints(),
+            ViewState::Preview(view) => view.key_hints(),
+            _ => vec![KeyHint::new("q", "back")],
+        }
+    }
+
+    fn as_record_view(&mut self) -> Option<&mut RecordView> {
+        match &mut self.state {
+            ViewState::Records(view) => Some(&mut **view),
+            _ => None,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match &self.state {
+            ViewState::Records(view) => view.kind(),
+            ViewState::Preview(view) => view.kind(),
+            _ => "",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -560,6 +638,36 @@ mod test {
     use super::*;
     use crossterm::event::KeyModifiers;
 
+    #[test]
+    fn parse_all_flag() {
+        let mut cmd = NuCmd::new();
+
+        cmd.parse("where size > 1kb").unwrap();
+        assert_eq!(cmd.command, "where size > 1kb");
+        assert!(cmd.uses_selection());
+
+        cmd.parse("--all  where size > 1kb").unwrap();
+        assert_eq!(cmd.command, "where size > 1kb");
+        assert!(!cmd.uses_selection());
+
+        cmd.parse("--allow").unwrap();
+        assert_eq!(cmd.command, "--allow");
+        assert!(cmd.uses_selection());
+    }
+
+    #[test]
+    fn parse_cell_flag() {
+        let mut cmd = NuCmd::new();
+        cmd.parse("--cell lines | length").unwrap();
+        assert_eq!(cmd.command, "lines | length");
+        assert!(cmd.uses_cell());
+
+        cmd.parse("lines").unwrap();
+        assert!(!cmd.uses_cell());
+
+        assert!(cmd.parse("--cell --all lines").is_err());
+    }
+
     #[test]
     fn non_interactive_states_exit_on_q_or_esc() {
         let key_q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
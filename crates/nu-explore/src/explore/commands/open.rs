@@ -0,0 +1,97 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_engine::env_to_strings;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+use std::path::PathBuf;
+
+/// Opens the link of the selected cell, made from the template of its column in
+/// `explore.table.columns`, with the opener of the system; `:open &` leaves it in the
+/// background.
+#[derive(Debug, Default, Clone)]
+pub struct OpenCmd {
+    background: bool,
+}
+
+impl OpenCmd {
+    pub const NAME: &'static str = "open";
+}
+
+impl SimpleCommand for OpenCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "open the link of the selected cell, as its column sets in explore.table.columns; with & in the background"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        self.background = match args.trim() {
+            "" => false,
+            "&" => true,
+            args => anyhow::bail!(tr!(
+                "{args} isn't an argument of :open, & opens in the background",
+                args = args
+            )),
+        };
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let link = view
+            .and_then(|view| view.as_record_view())
+            .and_then(|view| view.current_link())
+            .ok_or_else(|| {
+                anyhow::anyhow!(tr!(
+                    "the cell has no link, a link template of its column in explore.table.columns makes one"
+                ))
+            })?;
+
+        // a path is found from the current directory, as `start` does; anything else, like
+        // an url, goes to the opener as it is
+        let cwd = engine_state.cwd(Some(stack))?;
+        let path = nu_path::expand_path_with(&link, &cwd, true);
+        let target = match path.exists() {
+            true => path.into_os_string(),
+            false => PathBuf::from(&link).into_os_string(),
+        };
+        let env = env_to_strings(engine_state, stack)?;
+
+        // the openers of the system, tried in turn until one works
+        let mut failure = None;
+        for mut command in open::commands(&target) {
+            command.envs(&env).current_dir(&cwd);
+            // an opener may run in the terminal, as a browser or an editor there does, unless
+            // it's left in the background
+            let opened = match self.background {
+                true => pager.processes().spawn(command),
+                false => pager.processes().run(engine_state, command),
+            };
+            match opened {
+                Ok(()) => {
+                    pager.show_message(tr!("Opened {link}", link = link));
+                    return Ok(Transition::Ok);
+                }
+                Err(err) => failure = Some(err),
+            }
+        }
+
+        let err = failure.map_or_else(|| tr!("no opener was found"), |err| err.to_string());
+        anyhow::bail!(tr!("Can't open {link}: {err}", link = link, err = err))
+    }
+}
@@ -0,0 +1,72 @@
+use super::super::{
+    registry::CommandRegistry,
+    views::{PaletteEntry, PaletteView, ViewConfig},
+};
+use super::ViewCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+/// Opens the palette of the commands, to find one by typing a few characters of its name or
+/// of what it does.
+#[derive(Debug, Default, Clone)]
+pub struct PaletteCmd {
+    // the commands of the registry, by name
+    entries: Vec<PaletteEntry>,
+}
+
+impl PaletteCmd {
+    pub const NAME: &'static str = "palette";
+
+    /// The palette of the commands registered in `registry`.
+    pub fn new(registry: &CommandRegistry) -> Self {
+        let mut entries: Vec<_> = registry
+            .get_commands()
+            .map(|command| PaletteEntry {
+                name: command.name().to_string(),
+                aliases: registry
+                    .aliases_of(command.name())
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                description: command.description().to_string(),
+            })
+            .collect();
+        entries.extend(registry.get_macros().map(|(name, commands)| PaletteEntry {
+            name: name.to_string(),
+            aliases: Vec::new(),
+            description: format!(":{}", commands.join(", then :")),
+        }));
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self { entries }
+    }
+}
+
+impl ViewCommand for PaletteCmd {
+    type View = PaletteView;
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "find a command by a few characters of its name or of what it does, and run it"
+    }
+
+    fn parse(&mut self, _: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn spawn(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        _: Option<Value>,
+        _: &ViewConfig,
+    ) -> Result<Self::View> {
+        Ok(PaletteView::new(self.entries.clone()))
+    }
+}
@@ -0,0 +1,126 @@
+use super::super::{
+    config::Preset,
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Record, Span, Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PresetCmd {
+    action: PresetAction,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+enum PresetAction {
+    #[default]
+    List,
+    Save(String),
+    Apply(String),
+}
+
+impl PresetCmd {
+    pub const NAME: &'static str = "preset";
+}
+
+impl SimpleCommand for PresetCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "save the current filters under a name, or apply a saved preset"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let args = args.trim();
+
+        self.action = match args.split_once(' ') {
+            None if args.is_empty() => PresetAction::List,
+            None if args == "save" => {
+                return Err(anyhow::anyhow!(tr!(
+                    "expected a name to save the preset as"
+                )));
+            }
+            None => PresetAction::Apply(args.to_string()),
+            Some(("save", name)) => PresetAction::Save(name.trim().to_string()),
+            Some(_) => return Err(anyhow::anyhow!(tr!("preset names can't contain spaces"))),
+        };
+
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let config = stack.get_config(engine_state);
+
+        match std::mem::take(&mut self.action) {
+            PresetAction::List => {
+                let names = Preset::names(&config);
+                if names.is_empty() {
+                    pager.show_message(tr!("No presets saved yet, use :preset save <name>"));
+                } else {
+                    pager.show_message(tr!("Presets: {names}", names = names.join(", ")));
+                }
+            }
+            PresetAction::Save(name) => {
+                let view = view
+                    .and_then(|view| view.as_record_view())
+                    .ok_or_else(|| anyhow::anyhow!(tr!("only tables have presets")))?;
+
+                let mut presets = Preset::all(&config)
+                    .and_then(|presets| presets.as_record().ok())
+                    .cloned()
+                    .unwrap_or_else(Record::new);
+                presets.insert(name.clone(), view.preset().to_value(Span::unknown()));
+
+                Preset::set_all(engine_state, stack, Value::record(presets, Span::unknown()))?;
+                pager.show_message(tr!("Saved preset {name}", name = format!("{name:?}")));
+            }
+            PresetAction::Apply(name) => {
+                let view = view
+                    .and_then(|view| view.as_record_view())
+                    .ok_or_else(|| anyhow::anyhow!(tr!("only tables have presets")))?;
+
+                let preset = Preset::find(&config, &name)?;
+                view.apply_preset(&preset)?;
+                pager.show_message(tr!("Applied preset {name}", name = format!("{name:?}")));
+            }
+        }
+
+        Ok(Transition::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_actions() {
+        let mut cmd = PresetCmd::default();
+
+        cmd.parse(" save triage ").unwrap();
+        assert_eq!(cmd.action, PresetAction::Save("triage".to_string()));
+
+        cmd.parse("triage").unwrap();
+        assert_eq!(cmd.action, PresetAction::Apply("triage".to_string()));
+
+        cmd.parse("").unwrap();
+        assert_eq!(cmd.action, PresetAction::List);
+
+        assert!(cmd.parse("save").is_err());
+        assert!(cmd.parse("my triage").is_err());
+    }
+}
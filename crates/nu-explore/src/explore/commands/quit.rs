@@ -1,4 +1,7 @@
-use super::super::pager::{Pager, Transition};
+use super::super::{
+    pager::{Pager, Transition},
+    views::View,
+};
 use super::SimpleCommand;
 use anyhow::Result;
 use nu_protocol::{
@@ -19,7 +22,7 @@ impl SimpleCommand for QuitCmd {
     }
 
     fn description(&self) -> &'static str {
-        ""
+        "exit explore"
     }
 
     fn parse(&mut self, _: &str) -> Result<()> {
@@ -31,6 +34,7 @@ impl SimpleCommand for QuitCmd {
         _: &EngineState,
         _: &mut Stack,
         _: &mut Pager<'_>,
+        _: Option<&mut Box<dyn View>>,
         _: Option<Value>,
     ) -> Result<Transition> {
         Ok(Transition::Exit)
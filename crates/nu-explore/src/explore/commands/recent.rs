@@ -0,0 +1,95 @@
+use super::super::{
+    config::Preset,
+    l10n::tr,
+    nu_common::{collect_input, collect_pipeline},
+    recent::{Recent, SourceKind},
+    views::{RecordView, ViewConfig},
+};
+use super::ViewCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Span, Value,
+    engine::{EngineState, Stack},
+};
+
+/// The recent sources, or one of them read again.
+#[derive(Debug, Default, Clone)]
+pub struct RecentCmd {
+    // the source to read again, all of them are listed when `None`
+    index: Option<usize>,
+}
+
+impl RecentCmd {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub const NAME: &'static str = "recent";
+}
+
+impl ViewCommand for RecentCmd {
+    type View = RecordView;
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "list the sources explored lately, or explore one of them again"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let args = args.trim();
+        self.index = match args {
+            "" => None,
+            index => Some(
+                index
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!(tr!("expected the index of a recent source")))?,
+            ),
+        };
+
+        Ok(())
+    }
+
+    fn spawn(
+        &mut self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        _: Option<Value>,
+        config: &ViewConfig,
+    ) -> Result<Self::View> {
+        let path = Recent::path().ok_or_else(|| {
+            anyhow::anyhow!(tr!("There's no config directory to keep sources in"))
+        })?;
+        let recent = Recent::load(&path)?;
+
+        let Some(index) = self.index.take() else {
+            let (columns, data) = collect_input(recent.to_value(Span::unknown()))?;
+            return Ok(RecordView::new(
+                columns,
+                data,
+                config.explore_config.clone(),
+            ));
+        };
+
+        let entry = recent.get(index)?;
+        if config.explore_config.no_eval && entry.kind == SourceKind::Pipeline {
+            return Err(anyhow::anyhow!(tr!(
+                "{source} is a pipeline, which --no-eval keeps from running",
+                source = entry.source
+            )));
+        }
+        let (columns, data) = collect_pipeline(entry.reopen(engine_state, stack)?)?;
+        let mut view = RecordView::new(columns, data, config.explore_config.clone());
+        if let Some(preset) = entry
+            .preset
+            .as_ref()
+            .and_then(|name| Preset::find(config.nu_config, name).ok())
+        {
+            view.apply_preset(&preset)?;
+        }
+
+        Ok(view)
+    }
+}
@@ -0,0 +1,67 @@
+use super::super::{
+    l10n::tr,
+    views::{RecordView, ViewConfig},
+};
+use super::ViewCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Span, Value,
+    engine::{EngineState, Stack},
+};
+
+/// Lists the texts copied in the session, numbered as `:try` pastes them with Ctrl-R.
+#[derive(Debug, Default, Clone)]
+pub struct RegistersCmd;
+
+impl RegistersCmd {
+    pub const NAME: &'static str = "registers";
+
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ViewCommand for RegistersCmd {
+    type View = RecordView;
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "list the last values and paths copied, the latest first; Ctrl-R and their number paste them in :try"
+    }
+
+    fn parse(&mut self, _: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn spawn(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        _: Option<Value>,
+        config: &ViewConfig,
+    ) -> Result<Self::View> {
+        if config.registers.is_empty() {
+            return Err(anyhow::anyhow!(tr!(
+                "Nothing was copied yet, y copies the selected cell"
+            )));
+        }
+
+        let span = Span::unknown();
+        let columns = vec!["register".to_string(), "text".to_string()];
+        let rows = config
+            .registers
+            .iter()
+            .enumerate()
+            .map(|(i, text)| vec![Value::int(i as i64, span), Value::string(text, span)])
+            .collect();
+
+        Ok(RecordView::new(
+            columns,
+            rows,
+            config.explore_config.clone(),
+        ))
+    }
+}
@@ -0,0 +1,90 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::{SimpleCommand, filter::split_column};
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct RenameCmd {
+    rename: Option<(String, String)>,
+}
+
+impl RenameCmd {
+    pub const NAME: &'static str = "rename";
+}
+
+impl SimpleCommand for RenameCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "rename a column, in the table shown and the one saved, copied or returned"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let usage = || anyhow::anyhow!(tr!("expected the name of a column and a new one"));
+
+        let (column, rest) = split_column(args.trim_start()).ok_or_else(usage)?;
+        let (name, rest) = split_column(rest.trim_start()).ok_or_else(usage)?;
+        if column.is_empty() || name.is_empty() || !rest.trim().is_empty() {
+            return Err(usage());
+        }
+
+        self.rename = Some((column, name));
+
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only table columns can be renamed")))?;
+
+        if let Some((column, name)) = self.rename.take() {
+            view.rename_column(&column, &name)?;
+            pager.show_message(tr!(
+                "Renamed {column} to {name}",
+                column = format!("{column:?}"),
+                name = format!("{name:?}")
+            ));
+        }
+
+        Ok(Transition::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_the_names() {
+        let mut cmd = RenameCmd::default();
+
+        cmd.parse("size bytes").unwrap();
+        assert_eq!(cmd.rename, Some(("size".to_string(), "bytes".to_string())));
+
+        cmd.parse(r#""full name" name"#).unwrap();
+        assert_eq!(
+            cmd.rename,
+            Some(("full name".to_string(), "name".to_string()))
+        );
+
+        assert!(cmd.parse("size").is_err());
+        assert!(cmd.parse("size bytes more").is_err());
+    }
+}
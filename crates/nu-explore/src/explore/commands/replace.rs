@@ -0,0 +1,115 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::{SimpleCommand, filter::split_column};
+use anyhow::Result;
+use fancy_regex::Regex;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct ReplaceCmd {
+    replace: Option<(Regex, String, Option<String>)>,
+}
+
+impl ReplaceCmd {
+    pub const NAME: &'static str = "replace";
+}
+
+impl SimpleCommand for ReplaceCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "replace regex matches in the string cells, after a preview of the cells it changes"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let usage = || anyhow::anyhow!(tr!("expected <pattern> <replacement> [--column <column>]"));
+
+        let (pattern, rest) = split_column(args.trim()).ok_or_else(usage)?;
+        let rest = rest.trim_start();
+        if pattern.is_empty() || rest.is_empty() {
+            return Err(usage());
+        }
+
+        let (replacement, rest) = split_column(rest).ok_or_else(usage)?;
+        let column = match rest.trim() {
+            "" => None,
+            rest => match rest.strip_prefix("--column") {
+                Some(column) if !column.trim().is_empty() => {
+                    let (column, rest) = split_column(column.trim()).ok_or_else(usage)?;
+                    if !rest.trim().is_empty() {
+                        return Err(usage());
+                    }
+                    Some(column)
+                }
+                _ => return Err(usage()),
+            },
+        };
+
+        let regex = Regex::new(&pattern)?;
+        self.replace = Some((regex, replacement, column));
+
+        Ok(())
+    }
+
+    fn flags(&self) -> &'static [&'static str] {
+        &["--column"]
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only tables can be searched and replaced")))?;
+
+        if let Some((regex, replacement, column)) = self.replace.take() {
+            let count = view.preview_replace(regex, replacement, column)?;
+            pager.show_message(tr!(
+                "{count} cells would change, Enter to replace, Esc to cancel",
+                count = count
+            ));
+        }
+
+        Ok(Transition::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pattern_replacement_and_column() {
+        let mut cmd = ReplaceCmd::default();
+
+        cmd.parse(r"\d+ N").unwrap();
+        let (regex, replacement, column) = cmd.replace.take().unwrap();
+        assert_eq!(regex.as_str(), r"\d+");
+        assert_eq!(replacement, "N");
+        assert_eq!(column, None);
+
+        cmd.parse(r#""foo bar" "" --column "last name""#).unwrap();
+        let (regex, replacement, column) = cmd.replace.take().unwrap();
+        assert_eq!(regex.as_str(), "foo bar");
+        assert_eq!(replacement, "");
+        assert_eq!(column.as_deref(), Some("last name"));
+
+        assert!(cmd.parse("foo").is_err());
+        assert!(cmd.parse("foo bar --column").is_err());
+        assert!(cmd.parse("foo bar baz").is_err());
+        assert!(cmd.parse("(foo bar").is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+/// Shows the table the other way round, for good: a record across as a row or a field a
+/// line, rather than as it's turned to fit the terminal.
+#[derive(Debug, Default, Clone)]
+pub struct RotateCmd;
+
+impl RotateCmd {
+    pub const NAME: &'static str = "rotate";
+}
+
+impl SimpleCommand for RotateCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "show the table the other way round, a record across or a field a line"
+    }
+
+    fn parse(&mut self, _: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        _: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only tables can be rotated")))?;
+
+        view.rotate();
+
+        Ok(Transition::Ok)
+    }
+}
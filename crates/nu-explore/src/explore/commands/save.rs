@@ -0,0 +1,202 @@
+use super::super::{
+    l10n::tr,
+    nu_common::run_nu_command,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    PipelineData, Span, Value,
+    engine::{EngineState, Stack},
+};
+use std::path::Path;
+
+/// Writes the table as it's shown, its filters and sorts applied, to a file; or the bytes of
+/// a binary view, as edited, as they are.
+#[derive(Debug, Default, Clone)]
+pub struct SaveCmd {
+    path: String,
+    // by the extension, which bytes do without
+    format: Option<SaveFormat>,
+    // overwrite the file if there's one
+    force: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SaveFormat {
+    Csv,
+    Json,
+    Nuon,
+    Yaml,
+}
+
+impl SaveFormat {
+    fn from_path(path: &str) -> Option<Self> {
+        let extension = Path::new(path).extension()?.to_str()?;
+        match extension.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "nuon" => Some(Self::Nuon),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    fn command(self) -> &'static str {
+        match self {
+            Self::Csv => "to csv",
+            Self::Json => "to json",
+            Self::Nuon => "to nuon",
+            Self::Yaml => "to yaml",
+        }
+    }
+}
+
+impl SaveCmd {
+    pub const NAME: &'static str = "save";
+}
+
+impl SimpleCommand for SaveCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "save the table as shown to a CSV, JSON, NUON or YAML file by its extension, or the bytes as they are"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let mut args = args.trim();
+        self.force = false;
+        if let Some(rest) = args.strip_prefix("--force")
+            && (rest.is_empty() || rest.starts_with(' '))
+        {
+            self.force = true;
+            args = rest.trim_start();
+        }
+
+        // quotes around a path with spaces are optional
+        let path = match args
+            .strip_prefix('"')
+            .and_then(|path| path.strip_suffix('"'))
+        {
+            Some(path) => path,
+            None => args,
+        };
+        if path.is_empty() {
+            return Err(anyhow::anyhow!(tr!("expected a path to save to")));
+        }
+
+        self.format = SaveFormat::from_path(path);
+        self.path = path.to_string();
+
+        Ok(())
+    }
+
+    fn flags(&self) -> &'static [&'static str] {
+        &["--force"]
+    }
+
+    fn react(
+        &mut self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let path = std::mem::take(&mut self.path);
+        if path.is_empty() {
+            return Ok(Transition::Ok);
+        }
+
+        let view = view.ok_or_else(|| anyhow::anyhow!(tr!("There's nothing to save")))?;
+        let force = if self.force { "--force " } else { "" };
+
+        if let Some(view) = view.as_binary_view() {
+            let bytes = view.data().to_vec();
+            let count = bytes.len();
+            let input = PipelineData::value(Value::binary(bytes, Span::unknown()), None);
+            save(
+                engine_state,
+                stack,
+                &format!("save {force}{path:?}"),
+                input,
+                &path,
+            )?;
+            view.set_saved();
+
+            pager.show_message(tr!(
+                "Saved {bytes} bytes to {path}",
+                bytes = count,
+                path = path
+            ));
+            return Ok(Transition::Ok);
+        }
+
+        let format = self.format.take().ok_or_else(|| {
+            anyhow::anyhow!(tr!(
+                "{path} isn't a .csv, .json, .nuon or .yaml file",
+                path = path
+            ))
+        })?;
+        let (value, rows) = match view.as_record_view() {
+            Some(view) => view.table_value(),
+            None => {
+                let value = view
+                    .exit()
+                    .ok_or_else(|| anyhow::anyhow!(tr!("There's nothing to save")))?;
+                (value, 1)
+            }
+        };
+
+        let command = format!("{} | save {force}{path:?}", format.command());
+        save(
+            engine_state,
+            stack,
+            &command,
+            PipelineData::value(value, None),
+            &path,
+        )?;
+
+        pager.show_message(tr!("Saved {rows} rows to {path}", rows = rows, path = path));
+
+        Ok(Transition::Ok)
+    }
+}
+
+fn save(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    command: &str,
+    input: PipelineData,
+    path: &str,
+) -> Result<()> {
+    run_nu_command(engine_state, stack, command, input)
+        .and_then(|output| output.drain())
+        .map_err(|err| anyhow::anyhow!(tr!("Can't save {path}: {err}", path = path, err = err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_from_extension() {
+        let mut cmd = SaveCmd::default();
+        cmd.parse(" out.JSON").unwrap();
+        assert_eq!(cmd.format, Some(SaveFormat::Json));
+        assert!(!cmd.force);
+
+        cmd.parse("--force \"my rows.yml\"").unwrap();
+        assert_eq!(cmd.path, "my rows.yml");
+        assert_eq!(cmd.format, Some(SaveFormat::Yaml));
+        assert!(cmd.force);
+
+        // bytes are saved to any file
+        cmd.parse("out.bin").unwrap();
+        assert_eq!(cmd.format, None);
+        assert!(cmd.parse("--force").is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use super::super::{
+    config::HexConfig,
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+/// Changes a setting of `explore.hex` for the binary view shown, like `:set hex.group 4`.
+#[derive(Debug, Default, Clone)]
+pub struct SetCmd {
+    key: String,
+    value: String,
+}
+
+impl SetCmd {
+    pub const NAME: &'static str = "set";
+}
+
+impl SimpleCommand for SetCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "change how the bytes are laid out: hex.bytes_per_line (a number or auto), hex.group or hex.ascii"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let usage = || {
+            anyhow::anyhow!(tr!(
+                "expected a setting and its value, like hex.bytes_per_line 32"
+            ))
+        };
+
+        let (key, value) = args.trim().split_once(' ').ok_or_else(usage)?;
+        let key = key.strip_prefix("hex.").ok_or_else(|| {
+            anyhow::anyhow!(tr!(
+                "There is no setting {key}, only {keys}",
+                key = format!("{key:?}"),
+                keys = HexConfig::KEYS.map(|key| format!("hex.{key}")).join(", ")
+            ))
+        })?;
+
+        // checked against a layout here, for a mistake to show as it's typed
+        HexConfig::default().set(key, value)?;
+        key.clone_into(&mut self.key);
+        value.trim().clone_into(&mut self.value);
+
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_binary_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only the layout of bytes can be set")))?;
+
+        let mut hex = view.hex();
+        hex.set(&self.key, &self.value)?;
+        view.set_hex(hex);
+        pager.show_message(tr!(
+            "Set hex.{key} to {value}",
+            key = self.key,
+            value = self.value
+        ));
+
+        Ok(Transition::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_a_setting() {
+        let mut cmd = SetCmd::default();
+        cmd.parse(" hex.bytes_per_line  auto ").unwrap();
+        assert_eq!(
+            (cmd.key.as_str(), cmd.value.as_str()),
+            ("bytes_per_line", "auto")
+        );
+
+        assert!(cmd.parse("hex.group").is_err());
+        assert!(cmd.parse("hex.group none").is_err());
+        assert!(cmd.parse("group 4").is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use super::super::{
+    config::Nulls,
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::{View, sort_message},
+};
+use super::{SimpleCommand, filter::split_column};
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct SortCmd {
+    // empty for the column under the cursor
+    column: String,
+    // `None` reverses the order when sorted by the column already
+    descending: Option<bool>,
+    // with `--nulls first|last`, over `explore.table.sort_nulls`
+    nulls: Option<Nulls>,
+}
+
+impl SortCmd {
+    pub const NAME: &'static str = "sort";
+}
+
+impl SimpleCommand for SortCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "sort the rows by a column, asc or desc, with --nulls first or last"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let args = args.trim();
+        let (column, options) = match args.starts_with("--") {
+            true => (String::new(), args),
+            false => split_column(args).ok_or_else(|| {
+                anyhow::anyhow!(tr!("The column name is missing its closing quote"))
+            })?,
+        };
+
+        self.column = column;
+        self.descending = None;
+        self.nulls = None;
+
+        let mut options = options.split_whitespace();
+        while let Some(option) = options.next() {
+            match option {
+                "asc" => self.descending = Some(false),
+                "desc" => self.descending = Some(true),
+                "--nulls" => {
+                    let nulls = options.next().unwrap_or_default();
+                    self.nulls = Some(Nulls::from_name(nulls).ok_or_else(|| {
+                        anyhow::anyhow!(tr!(
+                            "Put nulls first or last, not {nulls}",
+                            nulls = format!("{nulls:?}")
+                        ))
+                    })?);
+                }
+                option => {
+                    return Err(anyhow::anyhow!(tr!(
+                        "Sort in asc or desc order, not {order}",
+                        order = format!("{option:?}")
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn flags(&self) -> &'static [&'static str] {
+        &["--nulls"]
+    }
+
+    fn menu_entries(&self) -> &'static [(&'static str, &'static str)] {
+        &[("Sort by this column", "sort {column}")]
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("Only tables can be sorted")))?;
+
+        let column = match self.column.is_empty() {
+            true => view
+                .current_column()
+                .ok_or_else(|| anyhow::anyhow!(tr!("There is no column to sort by")))?,
+            false => self.column.clone(),
+        };
+
+        let descending = view.sort_by(&column, self.descending, self.nulls)?;
+        pager.show_message(sort_message(&column, descending));
+
+        Ok(Transition::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_column_and_order() {
+        let mut cmd = SortCmd::default();
+        cmd.parse(" size desc").unwrap();
+        assert_eq!(cmd.column, "size");
+        assert_eq!(cmd.descending, Some(true));
+
+        cmd.parse(r#""last modified" asc"#).unwrap();
+        assert_eq!(cmd.column, "last modified");
+        assert_eq!(cmd.descending, Some(false));
+
+        cmd.parse("").unwrap();
+        assert_eq!(cmd.column, "");
+        assert_eq!(cmd.descending, None);
+
+        cmd.parse("size desc --nulls first").unwrap();
+        assert_eq!(cmd.descending, Some(true));
+        assert_eq!(cmd.nulls, Some(Nulls::First));
+
+        cmd.parse("--nulls last").unwrap();
+        assert_eq!(cmd.column, "");
+        assert_eq!(cmd.nulls, Some(Nulls::Last));
+
+        assert!(cmd.parse("size down").is_err());
+        assert!(cmd.parse("size --nulls middle").is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::{SimpleCommand, filter::split_column};
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct SplitColCmd {
+    split: Option<(String, String)>,
+}
+
+impl SplitColCmd {
+    pub const NAME: &'static str = "split-col";
+}
+
+impl SimpleCommand for SplitColCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "split the values of a column by a delimiter into new columns"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let (column, rest) = split_column(args.trim_start())
+            .ok_or_else(|| anyhow::anyhow!(tr!("expected a column name and a delimiter")))?;
+
+        // a single separating space, so that `" "` and `,` both work as delimiters
+        let delimiter = rest.strip_prefix(' ').unwrap_or(rest);
+        let delimiter = delimiter
+            .strip_prefix('"')
+            .and_then(|quoted| quoted.strip_suffix('"'))
+            .unwrap_or(delimiter);
+        if column.is_empty() || delimiter.is_empty() {
+            return Err(anyhow::anyhow!(tr!(
+                "expected a column name and a delimiter"
+            )));
+        }
+
+        self.split = Some((column, delimiter.to_string()));
+
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only table columns can be split")))?;
+
+        if let Some((column, delimiter)) = self.split.take() {
+            let count = view.split_into_columns(&column, &delimiter)?;
+            pager.show_message(tr!(
+                "Split {column} into {count} columns",
+                column = format!("{column:?}"),
+                count = count
+            ));
+        }
+
+        Ok(Transition::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_column_and_delimiter() {
+        let mut cmd = SplitColCmd::default();
+
+        cmd.parse("tags ,").unwrap();
+        assert_eq!(cmd.split, Some(("tags".to_string(), ",".to_string())));
+
+        cmd.parse(r#""full name" " ""#).unwrap();
+        assert_eq!(cmd.split, Some(("full name".to_string(), " ".to_string())));
+
+        cmd.parse("path ::").unwrap();
+        assert_eq!(cmd.split, Some(("path".to_string(), "::".to_string())));
+
+        assert!(cmd.parse("tags").is_err());
+        assert!(cmd.parse("tags ").is_err());
+    }
+}
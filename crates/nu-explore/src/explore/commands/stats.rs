@@ -0,0 +1,209 @@
+use super::super::{
+    l10n::tr,
+    nu_common::{Unit, collect_input, is_missing_cell},
+    views::{RecordView, ViewConfig},
+};
+use super::ViewCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Span, Value,
+    engine::{EngineState, Stack},
+};
+use std::cmp::Ordering;
+
+/// Sums up each column of the table in a row of its own, on a page over the table.
+#[derive(Debug, Default, Clone)]
+pub struct StatsCmd;
+
+impl StatsCmd {
+    pub const NAME: &'static str = "stats";
+
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+const COLUMNS: [&str; 8] = [
+    "column", "count", "nulls", "distinct", "min", "max", "mean", "median",
+];
+
+impl ViewCommand for StatsCmd {
+    type View = RecordView;
+
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "sum up each column of the table, or of the selection: count, nulls, distinct values, min, max, mean and median"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        match args.trim() {
+            "" => Ok(()),
+            args => Err(anyhow::anyhow!(tr!(
+                "unexpected {args}, :stats takes no arguments",
+                args = format!("{args:?}")
+            ))),
+        }
+    }
+
+    fn uses_selection(&self) -> bool {
+        true
+    }
+
+    fn spawn(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        value: Option<Value>,
+        config: &ViewConfig,
+    ) -> Result<Self::View> {
+        let value = value.unwrap_or_default();
+        if !matches!(value, Value::List { .. } | Value::Record { .. }) {
+            return Err(anyhow::anyhow!(tr!(
+                "Only tables have statistics, this is a {kind}",
+                kind = value.get_type().to_string()
+            )));
+        }
+
+        let (columns, rows) = collect_input(value)?;
+        let span = Span::unknown();
+        let stats = columns
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let cells = rows.iter().filter_map(|row| row.get(i));
+                ColumnStats::new(cells).row(name, span)
+            })
+            .collect();
+
+        let columns = COLUMNS.map(String::from).to_vec();
+        Ok(RecordView::new(
+            columns,
+            stats,
+            config.explore_config.clone(),
+        ))
+    }
+}
+
+/// The cells of a column which aren't empty, in order, and how many are: null or missing
+/// from rows of other shapes.
+struct ColumnStats<'a> {
+    values: Vec<&'a Value>,
+    nulls: usize,
+}
+
+impl<'a> ColumnStats<'a> {
+    fn new(cells: impl Iterator<Item = &'a Value>) -> Self {
+        let (nulls, mut values): (Vec<_>, Vec<_>) = cells
+            .partition(|value| matches!(value, Value::Nothing { .. }) || is_missing_cell(value));
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        Self {
+            values,
+            nulls: nulls.len(),
+        }
+    }
+
+    fn row(&self, name: &str, span: Span) -> Vec<Value> {
+        let int = |n: usize| Value::int(n as i64, span);
+        let value = |value: Option<Value>| value.unwrap_or_else(|| Value::nothing(span));
+
+        vec![
+            Value::string(name, span),
+            int(self.values.len()),
+            int(self.nulls),
+            int(self.distinct()),
+            value(self.values.first().map(|v| (*v).clone())),
+            value(self.values.last().map(|v| (*v).clone())),
+            value(self.mean(span)),
+            value(self.median(span)),
+        ]
+    }
+
+    fn distinct(&self) -> usize {
+        // being sorted, equal values are next to each other
+        match self.values.is_empty() {
+            true => 0,
+            false => 1 + self.values.windows(2).filter(|w| w[0] != w[1]).count(),
+        }
+    }
+
+    fn mean(&self, span: Span) -> Option<Value> {
+        let (numbers, unit) = self.numbers()?;
+        let sum: f64 = numbers.iter().sum();
+        Some(unit.value(sum / numbers.len() as f64, span))
+    }
+
+    fn median(&self, span: Span) -> Option<Value> {
+        let (numbers, unit) = self.numbers()?;
+        let middle = numbers.len() / 2;
+        let median = match numbers.len() % 2 {
+            0 => (numbers[middle - 1] + numbers[middle]) / 2.0,
+            _ => numbers[middle],
+        };
+        Some(unit.value(median, span))
+    }
+
+    // the values as numbers, in order, if they're all numbers, sizes or durations
+    fn numbers(&self) -> Option<(Vec<f64>, Unit)> {
+        let mut unit = None;
+        let mut numbers = Vec::with_capacity(self.values.len());
+        for value in &self.values {
+            let (number, of) = Unit::of(value)?;
+            if *unit.get_or_insert(of) != of {
+                return None;
+            }
+            numbers.push(number);
+        }
+
+        numbers.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        Some((numbers, unit?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sum_up_a_column() {
+        let span = Span::test_data();
+        let cells = [
+            Value::int(4, span),
+            Value::nothing(span),
+            Value::int(1, span),
+            Value::int(4, span),
+            Value::float(3.0, span),
+        ];
+        let row = ColumnStats::new(cells.iter()).row("n", span);
+
+        let expected = [
+            Value::string("n", span),
+            Value::int(4, span),
+            Value::int(1, span),
+            Value::int(3, span),
+            Value::int(1, span),
+            Value::int(4, span),
+            Value::float(3.0, span),
+            Value::float(3.5, span),
+        ];
+        assert_eq!(row, expected);
+    }
+
+    #[test]
+    fn text_has_no_mean() {
+        let span = Span::test_data();
+        let cells = [Value::string("b", span), Value::string("a", span)];
+        let stats = ColumnStats::new(cells.iter());
+
+        assert_eq!(stats.values.first(), Some(&&Value::string("a", span)));
+        assert_eq!(stats.mean(span), None);
+        assert_eq!(stats.median(span), None);
+
+        let sizes = [Value::filesize(1000, span), Value::filesize(2000, span)];
+        let stats = ColumnStats::new(sizes.iter());
+        assert_eq!(stats.mean(span), Some(Value::filesize(1500, span)));
+    }
+}
@@ -0,0 +1,90 @@
+use super::super::{
+    bench::synthesize_table,
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+/// Only in debug builds, or with the `stress` feature: it's for finding slow paths.
+#[derive(Debug, Default, Clone)]
+pub struct StressCmd {
+    size: Option<(usize, usize)>,
+}
+
+impl StressCmd {
+    pub const NAME: &'static str = "stress";
+}
+
+impl SimpleCommand for StressCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "open a synthesized table of the given size, to try the pager on large data"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let usage = || anyhow::anyhow!(tr!("expected <rows> <columns>"));
+
+        let mut words = args.split_whitespace();
+        let (Some(rows), Some(columns), None) = (words.next(), words.next(), words.next()) else {
+            return Err(usage());
+        };
+
+        let rows = rows.parse().map_err(|_| usage())?;
+        let columns = columns.parse().map_err(|_| usage())?;
+        self.size = Some((rows, columns));
+
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view.and_then(|view| view.as_record_view()).ok_or_else(|| {
+            anyhow::anyhow!(tr!("a stress table can only be opened over a table"))
+        })?;
+        let Some((rows, columns)) = self.size.take() else {
+            return Ok(Transition::Ok);
+        };
+
+        let (names, values) = synthesize_table(rows, columns);
+        view.push_table(Self::NAME, names, values);
+        pager.show_message(tr!(
+            "{rows} rows of {columns} columns",
+            rows = rows,
+            columns = columns
+        ));
+
+        Ok(Transition::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_size() {
+        let mut cmd = StressCmd::default();
+
+        cmd.parse("100000 20").unwrap();
+        assert_eq!(cmd.size, Some((100_000, 20)));
+
+        assert!(cmd.parse("100000").is_err());
+        assert!(cmd.parse("100000 20 3").is_err());
+        assert!(cmd.parse("many 20").is_err());
+    }
+}
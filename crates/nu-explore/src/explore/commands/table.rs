@@ -37,7 +37,7 @@ impl ViewCommand for TableCmd {
     }
 
     fn description(&self) -> &'static str {
-        ""
+        "show the input as a table"
     }
 
     fn parse(&mut self, _: &str) -> Result<()> {
@@ -59,7 +59,7 @@ impl ViewCommand for TableCmd {
         let mut view = RecordView::new(columns, data, config.explore_config.clone());
 
         if is_record {
-            view.set_top_layer_orientation(Orientation::Left);
+            view.set_top_layer_record();
         }
 
         if let Some(o) = self.settings.orientation {
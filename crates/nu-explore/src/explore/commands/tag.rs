@@ -0,0 +1,65 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct TagCmd {
+    // `None` removes the tag
+    tag: Option<String>,
+}
+
+impl TagCmd {
+    pub const NAME: &'static str = "tag";
+}
+
+impl SimpleCommand for TagCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "tag the selected row with a note, kept in a column of its own"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let args = args.trim();
+        self.tag = (!args.is_empty()).then(|| args.to_string());
+
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only table rows can be tagged")))?;
+
+        match self.tag.take() {
+            Some(tag) => {
+                let message = tr!("Tagged {tag}", tag = format!("{tag:?}"));
+                view.set_tag(Some(tag))?;
+                pager.show_message(message);
+            }
+            None => {
+                view.set_tag(None)?;
+                pager.show_message(tr!("Tag removed"));
+            }
+        }
+
+        Ok(Transition::Ok)
+    }
+}
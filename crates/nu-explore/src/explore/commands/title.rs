@@ -0,0 +1,60 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::SimpleCommand;
+use anyhow::Result;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct TitleCmd {
+    // `None` takes the title away
+    title: Option<String>,
+}
+
+impl TitleCmd {
+    pub const NAME: &'static str = "title";
+}
+
+impl SimpleCommand for TitleCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "name the page shown, in the title bar and in :pages"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        let args = args.trim();
+        self.title = (!args.is_empty()).then(|| args.to_string());
+
+        Ok(())
+    }
+
+    fn react(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only table pages can be named")))?;
+
+        let message = match &self.title {
+            Some(title) => tr!("Page named {title}", title = format!("{title:?}")),
+            None => tr!("Page name removed"),
+        };
+        view.set_title(self.title.take());
+        pager.show_message(message);
+
+        Ok(Transition::Ok)
+    }
+}
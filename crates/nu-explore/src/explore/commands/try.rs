@@ -29,7 +29,7 @@ impl ViewCommand for TryCmd {
     }
 
     fn description(&self) -> &'static str {
-        ""
+        "open a pipeline to try on the input, its output shown as it's typed"
     }
 
     fn parse(&mut self, args: &str) -> Result<()> {
@@ -46,7 +46,9 @@ impl ViewCommand for TryCmd {
         config: &ViewConfig,
     ) -> Result<Self::View> {
         let value = value.unwrap_or_default();
-        let mut view = TryView::new(value, config.explore_config.clone());
+        let mut view = TryView::new(value, config.explore_config.clone())
+            .with_registers(config.registers)
+            .with_history(config.try_history);
         view.init(self.command.clone());
         view.try_run(engine_state, stack)?;
 
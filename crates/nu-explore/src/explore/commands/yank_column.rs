@@ -0,0 +1,110 @@
+use super::super::{
+    l10n::tr,
+    pager::{Pager, Transition},
+    views::View,
+};
+use super::{SimpleCommand, copy::to_nuon};
+use anyhow::Result;
+use crossterm::{clipboard::CopyToClipboard, execute};
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+use std::io;
+
+#[derive(Debug, Default, Clone)]
+pub struct YankColumnCmd {
+    // exit explore returning the column, rather than copying it
+    ret: bool,
+}
+
+impl YankColumnCmd {
+    pub const NAME: &'static str = "yank-column";
+}
+
+impl SimpleCommand for YankColumnCmd {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        "copy the selected column, its filtered rows, as a nushell list; or exit returning it with --return"
+    }
+
+    fn parse(&mut self, args: &str) -> Result<()> {
+        self.ret = match args.trim() {
+            "" => false,
+            "--return" => true,
+            args => {
+                return Err(anyhow::anyhow!(tr!(
+                    "unexpected {args}, expected nothing or --return",
+                    args = format!("{args:?}")
+                )));
+            }
+        };
+
+        Ok(())
+    }
+
+    fn flags(&self) -> &'static [&'static str] {
+        &["--return"]
+    }
+
+    fn menu_entries(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("Copy column as a list", "yank-column"),
+            ("Return column", "yank-column --return"),
+        ]
+    }
+
+    fn react(
+        &mut self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        pager: &mut Pager<'_>,
+        view: Option<&mut Box<dyn View>>,
+        _: Option<Value>,
+    ) -> Result<Transition> {
+        let view = view
+            .and_then(|view| view.as_record_view())
+            .ok_or_else(|| anyhow::anyhow!(tr!("only the columns of a table can be yanked")))?;
+
+        let column = view.current_column_value();
+        if self.ret {
+            pager.return_value(column);
+            return Ok(Transition::Exit);
+        }
+
+        let count = column.as_list().map_or(0, <[Value]>::len);
+        let text = to_nuon(engine_state, stack, column)?;
+        execute!(
+            io::stdout(),
+            CopyToClipboard::to_clipboard_from(text.clone())
+        )?;
+        pager.yank(text);
+        pager.show_message(tr!(
+            "Copied the column of {path} as a list of {count}",
+            path = view.cell_path(),
+            count = count
+        ));
+
+        Ok(Transition::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_return() {
+        let mut cmd = YankColumnCmd::default();
+        cmd.parse("--return").unwrap();
+        assert!(cmd.ret);
+
+        cmd.parse(" ").unwrap();
+        assert!(!cmd.ret);
+
+        assert!(cmd.parse("--nuon").is_err());
+    }
+}
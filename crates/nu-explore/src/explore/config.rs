@@ -1,9 +1,23 @@
 //! Configuration types for the explore command.
 
-use crate::explore::nu_common::create_map;
+use crate::explore::{
+    bindings::{self, KeyBinding},
+    l10n::{Catalog, tr},
+    nu_common::create_map,
+    pager::{
+        PagePolicy,
+        compat::{self, ColorDepth},
+        status_bar::StatusMode,
+    },
+};
+use anyhow::Result;
 use nu_ansi_term::{Color, Style};
-use nu_color_config::get_color_map;
-use nu_protocol::Config;
+use nu_color_config::{Alignment, get_color_map};
+use nu_protocol::{
+    Config, IntoValue, Record, ShellError, Span, Value,
+    engine::{EngineState, Stack},
+};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub struct ExploreConfig {
@@ -22,6 +36,67 @@ pub struct ExploreConfig {
     pub title_bar_text: Style,
     /// if true, the explore view will immediately try to run the command as it is typed
     pub try_reactive: bool,
+    /// if true, the pipelines run in `:try` are kept across sessions, not only within one
+    pub try_history: bool,
+    /// how `:try` lays out the pipeline and its results, set in `explore.try.layout`
+    pub try_layout: TryLayout,
+    /// the percent of the height, or of the width side by side, the pipeline of `:try` takes
+    /// at most, set in `explore.try.split`
+    pub try_split: u16,
+    /// if true, a line above the status bar lists the keys relevant to the current view
+    pub show_hints: bool,
+    /// if true, frames are drawn with ASCII symbols and 16 colors, for consoles without ANSI
+    /// support like the legacy Windows one, where it's on by default
+    pub compat: bool,
+    /// the colors the terminal has, as detected or set with `explore.colors`; frames are
+    /// brought down to them
+    pub colors: ColorDepth,
+    /// if true, frames are sent as synchronized updates, shown once complete
+    pub synchronized_output: bool,
+    /// how many bytes of a string or binary cell are shown in tables, the rest only in a
+    /// preview of the cell
+    pub cell_budget: usize,
+    /// how many rows of a small table are shown in its cell, once its column is `:inline`
+    pub inline_rows: usize,
+    /// what the status bar shows on its right at first, until `s` switches it
+    pub status_mode: StatusMode,
+    /// if true, a minimap on the right of tables and previews marks where the window is, the
+    /// search matches, the marked rows and the errors
+    pub minimap: bool,
+    /// if true, the mouse clicks cells and column headers, scrolls and resizes columns;
+    /// off, the terminal selects text with it as usual
+    pub mouse: bool,
+    /// if set, a search in a preview shows only the matching lines with this many lines of
+    /// context around each, like `grep -C`
+    pub search_context: Option<usize>,
+    /// how columns are shown wherever they appear, set in `explore.table.columns`, by name
+    pub columns: HashMap<String, ColumnOverride>,
+    /// if true, the columns of tables are reordered as they're opened: identifiers first,
+    /// long text last
+    pub auto_order: bool,
+    /// where sorting puts the nulls and the cells missing from rows of other shapes
+    pub sort_nulls: Nulls,
+    /// how the binary view lays the bytes out, set in `explore.hex` or with `:set`
+    pub hex: HexConfig,
+    /// the page policies set in `explore.pages`, by command name
+    pub page_policies: HashMap<String, PagePolicy>,
+    /// the keys bound in `explore.keybindings`, then those of the preset named in
+    /// `explore.keybinding_preset`, over the default ones
+    pub keybindings: Vec<KeyBinding>,
+    /// the entries added to the menu of a cell in `explore.menu`, after the commands' own
+    pub menu: Vec<MenuEntry>,
+    /// the commands defined in `explore.aliases`, by name: the `:` command lines each runs,
+    /// in order
+    pub aliases: HashMap<String, Vec<String>>,
+    /// if true, nothing in the session runs code: `:nu`, `:try`, closures of `:filter`,
+    /// pipelines of `:recent` and the entries of `explore.menu` are disabled; set with
+    /// `explore --no-eval`, or for good with `explore.no_eval`
+    pub no_eval: bool,
+    /// if false, the sources explored aren't kept for `explore --recent` and `:recent`, from
+    /// `explore.recent`
+    pub recent: bool,
+    /// the translations in `explore.translations` for `explore.locale`, or the system's locale
+    pub catalog: Catalog,
 }
 
 impl Default for ExploreConfig {
@@ -43,6 +118,30 @@ impl Default for ExploreConfig {
             title_bar_background: color(None, None),
             title_bar_text: color(None, None),
             try_reactive: false,
+            try_history: false,
+            try_layout: TryLayout::default(),
+            try_split: 40,
+            show_hints: true,
+            compat: compat::is_legacy_console(),
+            colors: ColorDepth::detect(),
+            synchronized_output: true,
+            cell_budget: 4096,
+            inline_rows: 3,
+            status_mode: StatusMode::Position,
+            search_context: None,
+            minimap: false,
+            mouse: true,
+            columns: HashMap::new(),
+            auto_order: false,
+            sort_nulls: Nulls::default(),
+            hex: HexConfig::default(),
+            page_policies: HashMap::new(),
+            keybindings: Vec::new(),
+            menu: Vec::new(),
+            aliases: HashMap::new(),
+            no_eval: false,
+            recent: true,
+            catalog: Catalog::default(),
         }
     }
 }
@@ -54,6 +153,7 @@ impl ExploreConfig {
 
         ret.table.column_padding_left = config.table.padding.left;
         ret.table.column_padding_right = config.table.padding.right;
+        ret.table.abbreviated_row_count = config.table.abbreviated_row_count;
 
         let explore_cfg_hash_map = config.explore.clone();
         let colors = get_color_map(&explore_cfg_hash_map);
@@ -110,11 +210,213 @@ impl ExploreConfig {
             }
         }
 
-        if let Some(hm) = explore_cfg_hash_map.get("try").and_then(create_map)
-            && let Some(reactive) = hm.get("reactive")
-            && let Ok(b) = reactive.as_bool()
+        if let Some(show_hints) = explore_cfg_hash_map.get("show_hints")
+            && let Ok(b) = show_hints.as_bool()
         {
-            ret.try_reactive = b;
+            ret.show_hints = b;
+        }
+
+        if let Some(compat) = explore_cfg_hash_map.get("compat")
+            && let Ok(b) = compat.as_bool()
+        {
+            ret.compat = b;
+        }
+
+        match explore_cfg_hash_map
+            .get("colors")
+            .and_then(|colors| colors.coerce_string().ok())
+            .and_then(|name| ColorDepth::from_name(&name))
+        {
+            Some(colors) => ret.colors = colors,
+            None if ret.compat => ret.colors = ColorDepth::Ansi16,
+            None => {}
+        }
+
+        if let Some(zebra) = explore_cfg_hash_map.get("zebra")
+            && let Ok(true) = zebra.as_bool()
+        {
+            let background = colors
+                .get("zebra_background")
+                .copied()
+                .unwrap_or_else(|| zebra_background(ret.colors));
+            ret.table.zebra = Some(background);
+        }
+
+        if let Some(synchronized) = explore_cfg_hash_map.get("synchronized_output")
+            && let Ok(b) = synchronized.as_bool()
+        {
+            ret.synchronized_output = b;
+        }
+
+        match explore_cfg_hash_map.get("cell_budget") {
+            Some(Value::Int { val, .. }) if *val > 0 => ret.cell_budget = *val as usize,
+            Some(Value::Filesize { val, .. }) if val.get() > 0 => {
+                ret.cell_budget = val.get() as usize
+            }
+            _ => {}
+        }
+
+        if let Some(Value::Int { val, .. }) = explore_cfg_hash_map.get("inline_rows")
+            && *val > 0
+        {
+            ret.inline_rows = *val as usize;
+        }
+
+        if let Some(mode) = explore_cfg_hash_map
+            .get("status_mode")
+            .and_then(|mode| mode.coerce_str().ok())
+            .and_then(|name| StatusMode::from_name(&name))
+        {
+            ret.status_mode = mode;
+        }
+
+        if let Some(minimap) = explore_cfg_hash_map.get("minimap")
+            && let Ok(b) = minimap.as_bool()
+        {
+            ret.minimap = b;
+        }
+
+        if let Some(mouse) = explore_cfg_hash_map.get("mouse")
+            && let Ok(b) = mouse.as_bool()
+        {
+            ret.mouse = b;
+        }
+
+        if let Some(no_eval) = explore_cfg_hash_map.get("no_eval")
+            && let Ok(b) = no_eval.as_bool()
+        {
+            ret.no_eval = b;
+        }
+
+        if let Some(recent) = explore_cfg_hash_map.get("recent")
+            && let Ok(b) = recent.as_bool()
+        {
+            ret.recent = b;
+        }
+
+        if let Some(Value::Int { val, .. }) = explore_cfg_hash_map.get("search_context")
+            && *val >= 0
+        {
+            ret.search_context = Some(*val as usize);
+        }
+
+        if let Some(hm) = explore_cfg_hash_map.get("try").and_then(create_map) {
+            if let Some(reactive) = hm.get("reactive")
+                && let Ok(b) = reactive.as_bool()
+            {
+                ret.try_reactive = b;
+            }
+            if let Some(history) = hm.get("history")
+                && let Ok(b) = history.as_bool()
+            {
+                ret.try_history = b;
+            }
+            if let Some(layout) = hm
+                .get("layout")
+                .and_then(|layout| layout.coerce_str().ok())
+                .and_then(|name| TryLayout::from_name(&name))
+            {
+                ret.try_layout = layout;
+            }
+            if let Some(Value::Int { val, .. }) = hm.get("split") {
+                ret.try_split = (*val).clamp(TryLayout::MIN_SPLIT, TryLayout::MAX_SPLIT) as u16;
+            }
+        }
+
+        if let Some(columns) = explore_cfg_hash_map
+            .get("table")
+            .and_then(create_map)
+            .and_then(|table| table.get("columns").and_then(create_map))
+        {
+            for (name, column) in columns {
+                if let Some(column) = ColumnOverride::from_value(&column) {
+                    ret.columns.insert(name, column);
+                }
+            }
+        }
+
+        if let Some(auto_order) = explore_cfg_hash_map
+            .get("table")
+            .and_then(create_map)
+            .and_then(|table| table.get("auto_order").cloned())
+            && let Ok(b) = auto_order.as_bool()
+        {
+            ret.auto_order = b;
+        }
+
+        if let Some(nulls) = explore_cfg_hash_map
+            .get("table")
+            .and_then(create_map)
+            .and_then(|table| table.get("sort_nulls").cloned())
+            && let Ok(nulls) = nulls.coerce_str()
+            && let Some(nulls) = Nulls::from_name(&nulls)
+        {
+            ret.sort_nulls = nulls;
+        }
+
+        if let Some(hm) = explore_cfg_hash_map.get("hex").and_then(create_map) {
+            for (key, value) in hm {
+                // what isn't a setting, or a value of it, leaves the default
+                if let Ok(value) = value.coerce_str() {
+                    let _ = ret.hex.set(&key, &value);
+                }
+            }
+        }
+
+        if let Some(hm) = explore_cfg_hash_map.get("pages").and_then(create_map) {
+            for (command, policy) in hm {
+                if let Ok(policy) = policy.coerce_str()
+                    && let Some(policy) = PagePolicy::from_name(&policy)
+                {
+                    ret.page_policies.insert(command, policy);
+                }
+            }
+        }
+
+        if let Some(Ok(bindings)) = explore_cfg_hash_map.get("keybindings").map(Value::as_list) {
+            ret.keybindings = bindings.iter().filter_map(KeyBinding::from_value).collect();
+        }
+
+        if let Some(preset) = explore_cfg_hash_map.get("keybinding_preset")
+            && let Ok(preset) = preset.coerce_str()
+            && let Some(preset) = bindings::preset(&preset)
+        {
+            ret.keybindings.extend(preset);
+        }
+
+        if let Some(Ok(entries)) = explore_cfg_hash_map.get("menu").map(Value::as_list) {
+            ret.menu = entries.iter().filter_map(MenuEntry::from_value).collect();
+        }
+
+        if let Some(hm) = explore_cfg_hash_map.get("aliases").and_then(create_map) {
+            for (name, commands) in hm {
+                // one command line, or a list of them
+                let commands = match &commands {
+                    Value::List { vals, .. } => vals.iter().map(Value::coerce_string).collect(),
+                    value => value.coerce_string().map(|command| vec![command]),
+                };
+                let Ok(commands) = commands else {
+                    continue;
+                };
+
+                let commands: Vec<_> = commands
+                    .iter()
+                    .map(|command| command.trim().trim_start_matches(':').to_string())
+                    .filter(|command| !command.is_empty())
+                    .collect();
+                if !commands.is_empty() {
+                    ret.aliases.insert(name, commands);
+                }
+            }
+        }
+
+        if let Some(translations) = explore_cfg_hash_map.get("translations") {
+            let locale = explore_cfg_hash_map
+                .get("locale")
+                .and_then(|locale| locale.coerce_string().ok())
+                .or_else(nu_utils::locale::get_system_locale_string)
+                .unwrap_or_default();
+            ret.catalog = Catalog::select(translations, &locale);
         }
 
         ret
@@ -128,6 +430,398 @@ pub struct TableConfig {
     pub show_header: bool,
     pub column_padding_left: usize,
     pub column_padding_right: usize,
+    /// Mirrors `table.abbreviated_row_count`: when set, large tables open showing only
+    /// this many rows from the head and the tail
+    pub abbreviated_row_count: Option<usize>,
+    /// The background of every other row, when `explore.zebra` is on
+    pub zebra: Option<Style>,
+}
+
+/// How a column is shown in every table it's in, from `explore.table.columns.<name>`, like
+/// `{ width: 10, align: right, style: { fg: green }, format: "{} ms", hidden: false }`, or
+/// `{ link: "https://tracker/issue/{value}" }` to make its cells links.
+#[derive(Debug, Default, Clone)]
+pub struct ColumnOverride {
+    /// The width of the column, whatever the width of its cells
+    pub width: Option<usize>,
+    pub align: Option<Alignment>,
+    pub style: Option<Style>,
+    /// Shown in place of each cell's text, `{}` standing for it
+    pub format: Option<String>,
+    /// Hidden columns are left out of tables as they're opened
+    pub hidden: bool,
+    /// Where each cell links to, `{value}` standing for its value, opened with `:open`
+    pub link: Option<String>,
+}
+
+impl ColumnOverride {
+    fn from_value(value: &Value) -> Option<Self> {
+        let map = create_map(value)?;
+        let text = |name: &str| map.get(name).and_then(|value| value.coerce_string().ok());
+
+        Some(Self {
+            width: match map.get("width") {
+                Some(Value::Int { val, .. }) if *val > 0 => Some(*val as usize),
+                _ => None,
+            },
+            align: text("align").and_then(|align| match align.as_str() {
+                "left" => Some(Alignment::Left),
+                "right" => Some(Alignment::Right),
+                "center" => Some(Alignment::Center),
+                _ => None,
+            }),
+            style: get_color_map(&map).get("style").copied(),
+            format: text("format"),
+            hidden: map.get("hidden").and_then(|hidden| hidden.as_bool().ok()) == Some(true),
+            link: text("link").filter(|link| link.contains("{value}")),
+        })
+    }
+
+    /// The text of a cell as the column shows it.
+    pub fn format(&self, text: String) -> String {
+        match &self.format {
+            Some(format) => format.replace("{}", &text),
+            None => text,
+        }
+    }
+
+    /// Where a cell of the column links to, if the column has links and the cell a value
+    /// which fits in one.
+    pub fn link(&self, value: &Value) -> Option<String> {
+        let link = self.link.as_ref()?;
+        let text = match value {
+            Value::Nothing { .. }
+            | Value::Error { .. }
+            | Value::List { .. }
+            | Value::Record { .. }
+            | Value::Closure { .. } => return None,
+            value => value.coerce_string().ok()?,
+        };
+        let text = text.trim();
+
+        (!text.is_empty()).then(|| link.replace("{value}", text))
+    }
+}
+
+/// An entry of the menu of a cell (`x`): a label, and the `:` command line it runs in which
+/// `{column}`, `{value}` and `{path}` stand for those of the selected cell. The commands offer
+/// some; more are added in `explore.menu`, like `[{ label: "Rows like it", command: "filter
+/// {column} {value}" }]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuEntry {
+    pub label: String,
+    pub command: String,
+}
+
+impl MenuEntry {
+    pub fn new(label: impl Into<String>, command: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            command: command.into(),
+        }
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        let map = create_map(value)?;
+        let text = |name: &str| map.get(name)?.coerce_string().ok();
+
+        Some(Self::new(text("label")?, text("command")?))
+    }
+
+    /// Whether the command needs the cell's column, which only the cells of tables have.
+    pub fn needs_column(&self) -> bool {
+        self.command.contains("{column}") || self.command.contains("{value}")
+    }
+
+    /// The command line for the cell `value` of `column`, at `path`.
+    pub fn command_for(&self, column: &str, value: &str, path: &str) -> String {
+        self.command
+            .replace("{column}", column)
+            .replace("{value}", value)
+            .replace("{path}", path)
+    }
+}
+
+/// Where sorting by a column puts its empty cells, its nulls and those of rows without the
+/// column, whether the order is ascending or descending.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Nulls {
+    First,
+    #[default]
+    Last,
+}
+
+impl Nulls {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "first" => Some(Self::First),
+            "last" => Some(Self::Last),
+            _ => None,
+        }
+    }
+}
+
+/// How `:try` lays out the pipeline and the results of running it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TryLayout {
+    /// the pipeline on top of the results
+    #[default]
+    Stacked,
+    /// the pipeline on the left of the results, for wide terminals
+    SideBySide,
+}
+
+impl TryLayout {
+    /// The least and most percent of the area the pipeline takes.
+    pub const MIN_SPLIT: i64 = 10;
+    pub const MAX_SPLIT: i64 = 90;
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "stacked" => Some(Self::Stacked),
+            "side_by_side" => Some(Self::SideBySide),
+            _ => None,
+        }
+    }
+}
+
+/// How the binary view lays out the bytes, from `explore.hex`, like
+/// `{ bytes_per_line: 16, group: 2, ascii: true }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexConfig {
+    /// the bytes on a line, or as many groups as fit the width for `auto`
+    pub bytes_per_line: Option<usize>,
+    /// the bytes grouped together, between spaces
+    pub group: usize,
+    /// whether the bytes are shown as characters too, on the right
+    pub ascii: bool,
+}
+
+impl Default for HexConfig {
+    fn default() -> Self {
+        Self {
+            bytes_per_line: Some(16),
+            group: 2,
+            ascii: true,
+        }
+    }
+}
+
+impl HexConfig {
+    pub const KEYS: [&'static str; 3] = ["bytes_per_line", "group", "ascii"];
+
+    /// Set `key`, one of [`HexConfig::KEYS`], to `value` as it's typed: a number, `auto` for
+    /// the bytes of a line, `true` or `false` for the characters.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let value = value.trim();
+        let count = || match value.parse::<usize>() {
+            Ok(count) if count > 0 => Ok(count),
+            _ => Err(anyhow::anyhow!(tr!(
+                "expected a number of bytes, not {value}",
+                value = format!("{value:?}")
+            ))),
+        };
+
+        match key {
+            "bytes_per_line" if value == "auto" => self.bytes_per_line = None,
+            "bytes_per_line" => self.bytes_per_line = Some(count()?),
+            "group" => self.group = count()?,
+            "ascii" => {
+                self.ascii = match value {
+                    "true" | "on" => true,
+                    "false" | "off" => false,
+                    _ => return Err(anyhow::anyhow!(tr!("expected true or false"))),
+                }
+            }
+            _ => {
+                return Err(anyhow::anyhow!(tr!(
+                    "There is no setting {key}, only {keys}",
+                    key = format!("{key:?}"),
+                    keys = Self::KEYS.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The groups of a line: those of `bytes_per_line`, a part of a group left out, or
+    /// `fitting` for `auto`.
+    pub fn groups(&self, fitting: usize) -> usize {
+        match self.bytes_per_line {
+            Some(bytes) => (bytes / self.group).max(1),
+            None => fitting.max(1),
+        }
+    }
+}
+
+/// A named set of table adjustments saved with `:preset save <name>`.
+///
+/// Presets live in `$env.config.explore.presets`, keyed by name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Preset {
+    /// `(column, value)` pairs, as given to `:filter`
+    pub filters: Vec<(String, String)>,
+    /// The column the rows are sorted by with `:sort`, and whether it's from Z to A
+    pub sort: Option<(String, bool)>,
+    /// The columns left out of the table
+    pub hidden: Vec<String>,
+    /// The columns in the order they're shown
+    pub order: Vec<String>,
+}
+
+impl Preset {
+    pub const CONFIG_KEY: &'static str = "presets";
+
+    /// Look a preset up by name in the nushell config.
+    pub fn find(config: &Config, name: &str) -> Result<Self> {
+        let preset = Self::all(config)
+            .and_then(|presets| presets.as_record().ok())
+            .and_then(|presets| presets.get(name))
+            .ok_or_else(|| {
+                anyhow::anyhow!(tr!(
+                    "There is no preset named {name}",
+                    name = format!("{name:?}")
+                ))
+            })?;
+
+        Self::from_value(preset)
+    }
+
+    /// The names of all presets in the nushell config.
+    pub fn names(config: &Config) -> Vec<String> {
+        Self::all(config)
+            .and_then(|presets| presets.as_record().ok())
+            .map(|presets| presets.columns().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// All presets in the nushell config, as stored in `$env.config.explore.presets`.
+    pub fn all(config: &Config) -> Option<&Value> {
+        config.explore.get(Self::CONFIG_KEY)
+    }
+
+    /// Replace the presets of the config in `stack`, the same way assigning to
+    /// `$env.config.explore.presets` would.
+    pub fn set_all(
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        presets: Value,
+    ) -> Result<(), ShellError> {
+        let mut config = (*stack.get_config(engine_state)).clone();
+        config.explore.insert(Self::CONFIG_KEY.to_string(), presets);
+
+        stack.add_env_var("config".into(), config.into_value(Span::unknown()));
+        stack.update_config(engine_state)
+    }
+
+    pub fn from_value(value: &Value) -> Result<Self> {
+        let record = value.as_record()?;
+        let mut preset = Self::default();
+
+        if let Some(filters) = record.get("filters") {
+            for filter in filters.as_list()? {
+                let filter = filter.as_record()?;
+                let column = preset_field(filter, "column")?;
+                let value = preset_field(filter, "value")?;
+
+                preset.filters.push((column, value));
+            }
+        }
+
+        if let Some(sort) = record.get("sort")
+            && !sort.is_nothing()
+        {
+            let sort = sort.as_record()?;
+            let descending = match sort.get("descending") {
+                Some(descending) => descending.as_bool()?,
+                None => false,
+            };
+            preset.sort = Some((preset_field(sort, "column")?, descending));
+        }
+
+        preset.hidden = preset_names(record, "hidden")?;
+        preset.order = preset_names(record, "order")?;
+
+        Ok(preset)
+    }
+
+    pub fn to_value(&self, span: Span) -> Value {
+        let names = |names: &[String]| {
+            let names = names.iter().map(|name| Value::string(name, span)).collect();
+            Value::list(names, span)
+        };
+
+        let mut record = Record::new();
+        record.push("filters", self.filters_value(span));
+        record.push("sort", self.sort_value(span));
+        record.push("hidden", names(&self.hidden));
+        record.push("order", names(&self.order));
+
+        Value::record(record, span)
+    }
+
+    /// The sort as a `{column, descending}` record, or nothing when the rows aren't sorted.
+    pub fn sort_value(&self, span: Span) -> Value {
+        let Some((column, descending)) = &self.sort else {
+            return Value::nothing(span);
+        };
+
+        let mut record = Record::new();
+        record.push("column", Value::string(column, span));
+        record.push("descending", Value::bool(*descending, span));
+        Value::record(record, span)
+    }
+
+    /// The filters as a list of `{column, value}` records.
+    pub fn filters_value(&self, span: Span) -> Value {
+        let filters = self
+            .filters
+            .iter()
+            .map(|(column, value)| {
+                let mut record = Record::new();
+                record.push("column", Value::string(column, span));
+                record.push("value", Value::string(value, span));
+                Value::record(record, span)
+            })
+            .collect();
+
+        Value::list(filters, span)
+    }
+}
+
+fn preset_field(record: &Record, name: &str) -> Result<String> {
+    let value = record.get(name).ok_or_else(|| {
+        anyhow::anyhow!(tr!(
+            "A preset entry is missing {name}",
+            name = format!("{name:?}")
+        ))
+    })?;
+
+    Ok(value.coerce_string()?)
+}
+
+// the column names of the list `name` of a preset, none when it has no such list
+fn preset_names(record: &Record, name: &str) -> Result<Vec<String>> {
+    let Some(names) = record.get(name) else {
+        return Ok(Vec::new());
+    };
+
+    names
+        .as_list()?
+        .iter()
+        .map(|name| Ok(name.coerce_string()?))
+        .collect()
+}
+
+// a shade just off the usual dark backgrounds; the closest of the 16 colors would be black
+fn zebra_background(colors: ColorDepth) -> Style {
+    let background = match colors {
+        ColorDepth::Ansi16 => Color::DarkGray,
+        _ => Color::Rgb(38, 38, 38),
+    };
+
+    color(None, Some(background))
 }
 
 const fn color(foreground: Option<Color>, background: Option<Color>) -> Style {
@@ -145,3 +839,257 @@ const fn color(foreground: Option<Color>, background: Option<Color>) -> Style {
         prefix_with_reset: false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preset_round_trips_through_value() {
+        let mut preset = Preset {
+            filters: vec![
+                ("status".to_string(), "open".to_string()),
+                ("owner".to_string(), "me".to_string()),
+            ],
+            sort: Some(("updated".to_string(), true)),
+            hidden: vec!["body".to_string()],
+            order: vec!["status".to_string(), "title".to_string()],
+        };
+
+        let value = preset.to_value(Span::test_data());
+        assert_eq!(Preset::from_value(&value).unwrap(), preset);
+
+        preset.sort = None;
+        let value = preset.to_value(Span::test_data());
+        assert_eq!(Preset::from_value(&value).unwrap(), preset);
+    }
+
+    #[test]
+    fn page_policies_from_nu_config() {
+        let span = Span::test_data();
+        let mut pages = Record::new();
+        pages.insert("help", Value::string("stack", span));
+        pages.insert("expand", Value::string("sideways", span));
+
+        let mut config = Config::default();
+        config
+            .explore
+            .insert("pages".to_string(), Value::record(pages, span));
+
+        let config = ExploreConfig::from_nu_config(&config);
+        assert_eq!(config.page_policies.get("help"), Some(&PagePolicy::Stack));
+        assert_eq!(config.page_policies.get("expand"), None);
+    }
+
+    #[test]
+    fn colors_from_nu_config() {
+        let span = Span::test_data();
+        let explore_config = |settings: &[(&str, Value)]| {
+            let mut config = Config::default();
+            for (name, value) in settings {
+                config.explore.insert(name.to_string(), value.clone());
+            }
+            ExploreConfig::from_nu_config(&config)
+        };
+
+        let config = explore_config(&[("colors", Value::int(256, span))]);
+        assert_eq!(config.colors, ColorDepth::Ansi256);
+
+        let config = explore_config(&[("compat", Value::bool(true, span))]);
+        assert_eq!(config.colors, ColorDepth::Ansi16);
+
+        let config = explore_config(&[
+            ("compat", Value::bool(true, span)),
+            ("colors", Value::string("truecolor", span)),
+        ]);
+        assert_eq!(config.colors, ColorDepth::TrueColor);
+    }
+
+    #[test]
+    fn column_overrides_from_nu_config() {
+        let span = Span::test_data();
+        let mut style = Record::new();
+        style.push("fg", Value::string("green", span));
+        let mut duration = Record::new();
+        duration.push("width", Value::int(8, span));
+        duration.push("align", Value::string("right", span));
+        duration.push("style", Value::record(style, span));
+        duration.push("format", Value::string("{} ms", span));
+        let mut secret = Record::new();
+        secret.push("hidden", Value::bool(true, span));
+        let mut columns = Record::new();
+        columns.push("duration", Value::record(duration, span));
+        columns.push("secret", Value::record(secret, span));
+        let mut issue = Record::new();
+        issue.push("link", Value::string("https://tracker/issue/{value}", span));
+        columns.push("issue", Value::record(issue, span));
+        columns.push("bad", Value::int(1, span));
+        let mut table = Record::new();
+        table.push("columns", Value::record(columns, span));
+
+        let mut config = Config::default();
+        config
+            .explore
+            .insert("table".to_string(), Value::record(table, span));
+        let config = ExploreConfig::from_nu_config(&config);
+
+        let duration = &config.columns["duration"];
+        assert_eq!(duration.width, Some(8));
+        assert!(matches!(duration.align, Some(Alignment::Right)));
+        assert_eq!(
+            duration.style.and_then(|s| s.foreground),
+            Some(Color::Green)
+        );
+        assert_eq!(duration.format("12".to_string()), "12 ms");
+        assert!(!duration.hidden);
+        assert!(config.columns["secret"].hidden);
+        assert!(duration.link(&Value::test_int(12)).is_none());
+
+        let issue = &config.columns["issue"];
+        assert_eq!(
+            issue.link(&Value::test_int(42)).as_deref(),
+            Some("https://tracker/issue/42")
+        );
+        assert!(issue.link(&Value::test_string(" ")).is_none());
+        assert!(issue.link(&Value::test_nothing()).is_none());
+        assert!(!config.columns.contains_key("bad"));
+    }
+
+    #[test]
+    fn menu_from_nu_config() {
+        let span = Span::test_data();
+        let mut entry = Record::new();
+        entry.push("label", Value::string("Rows like it", span));
+        entry.push("command", Value::string("filter {column} {value}", span));
+        let mut incomplete = Record::new();
+        incomplete.push("label", Value::string("Nothing to run", span));
+
+        let mut config = Config::default();
+        config.explore.insert(
+            "menu".to_string(),
+            Value::list(
+                vec![Value::record(entry, span), Value::record(incomplete, span)],
+                span,
+            ),
+        );
+        let config = ExploreConfig::from_nu_config(&config);
+
+        assert_eq!(config.menu.len(), 1);
+        let entry = &config.menu[0];
+        assert!(entry.needs_column());
+        assert_eq!(
+            entry.command_for("status", "open", "3.status"),
+            "filter status open"
+        );
+        assert!(!MenuEntry::new("Copy path", "copy --path").needs_column());
+    }
+
+    #[test]
+    fn aliases_from_nu_config() {
+        let span = Span::test_data();
+        let mut aliases = Record::new();
+        aliases.push(
+            "errs",
+            Value::string(":filter $it.level == \"error\"", span),
+        );
+        aliases.push(
+            "triage",
+            Value::list(
+                vec![
+                    Value::string("filter status open", span),
+                    Value::string(":sort time", span),
+                ],
+                span,
+            ),
+        );
+        aliases.push("none", Value::list(vec![], span));
+        aliases.push("bad", Value::record(Record::new(), span));
+
+        let mut config = Config::default();
+        config
+            .explore
+            .insert("aliases".to_string(), Value::record(aliases, span));
+        let config = ExploreConfig::from_nu_config(&config);
+
+        assert_eq!(config.aliases["errs"], ["filter $it.level == \"error\""]);
+        assert_eq!(
+            config.aliases["triage"],
+            ["filter status open", "sort time"]
+        );
+        assert!(!config.aliases.contains_key("none"));
+        assert!(!config.aliases.contains_key("bad"));
+    }
+
+    #[test]
+    fn hex_from_nu_config() {
+        let span = Span::test_data();
+        let mut config = Config::default();
+        let mut hex = Record::new();
+        hex.push("bytes_per_line", Value::string("auto", span));
+        hex.push("group", Value::int(4, span));
+        hex.push("ascii", Value::bool(false, span));
+        hex.push("colour", Value::string("red", span));
+        config
+            .explore
+            .insert("hex".to_string(), Value::record(hex, span));
+
+        let hex = ExploreConfig::from_nu_config(&config).hex;
+        assert_eq!(
+            hex,
+            HexConfig {
+                bytes_per_line: None,
+                group: 4,
+                ascii: false
+            }
+        );
+        assert_eq!(hex.groups(5), 5);
+
+        let mut hex = HexConfig::default();
+        hex.set("bytes_per_line", "20").unwrap();
+        hex.set("group", "8").unwrap();
+        assert_eq!(hex.groups(5), 2);
+        assert!(hex.set("group", "0").is_err());
+        assert!(hex.set("ascii", "maybe").is_err());
+        assert!(hex.set("width", "8").is_err());
+    }
+
+    #[test]
+    fn sort_nulls_from_nu_config() {
+        let span = Span::test_data();
+        let explore_config = |nulls: &str| {
+            let mut table = Record::new();
+            table.push("sort_nulls", Value::string(nulls, span));
+            let mut config = Config::default();
+            config
+                .explore
+                .insert("table".to_string(), Value::record(table, span));
+            ExploreConfig::from_nu_config(&config)
+        };
+
+        assert_eq!(explore_config("first").sort_nulls, Nulls::First);
+        assert_eq!(explore_config("middle").sort_nulls, Nulls::Last);
+    }
+
+    #[test]
+    fn try_layout_from_nu_config() {
+        let span = Span::test_data();
+        let explore_config = |layout: &str, split: i64| {
+            let mut try_config = Record::new();
+            try_config.push("layout", Value::string(layout, span));
+            try_config.push("split", Value::int(split, span));
+            let mut config = Config::default();
+            config
+                .explore
+                .insert("try".to_string(), Value::record(try_config, span));
+            ExploreConfig::from_nu_config(&config)
+        };
+
+        let config = explore_config("side_by_side", 60);
+        assert_eq!(config.try_layout, TryLayout::SideBySide);
+        assert_eq!(config.try_split, 60);
+
+        let config = explore_config("diagonal", 200);
+        assert_eq!(config.try_layout, TryLayout::Stacked);
+        assert_eq!(config.try_split, 90);
+    }
+}
@@ -0,0 +1,130 @@
+//! A deterministic mode of the pager, for tests to have it draw the same frames every run: in
+//! a terminal of a fixed size, resizes ignored, by a clock only moving a tick at a time, and
+//! with the spinners standing still.
+//!
+//! It's built for explore's own tests, which turn it on by [`enable`], and with the
+//! `deterministic` feature, forwarded by nushell's `explore-deterministic`: a nu built with it
+//! turns it on when `NU_EXPLORE_DETERMINISTIC` is set to a size, like `80x24`. Otherwise these
+//! are the terminal's size and the time.
+
+use nu_utils::time::Instant;
+use std::{io, time::Duration};
+
+pub use imp::*;
+
+/// The variable of the environment turning the mode on, set to the size of the terminal.
+pub const ENV_VAR: &str = "NU_EXPLORE_DETERMINISTIC";
+
+#[cfg(any(test, feature = "deterministic"))]
+mod imp {
+    use super::{Duration, ENV_VAR, Instant, io};
+    use std::sync::{Mutex, OnceLock, PoisonError};
+
+    #[derive(Debug, Clone, Copy)]
+    struct State {
+        size: (u16, u16),
+        started: Instant,
+        elapsed: Duration,
+    }
+
+    fn state() -> &'static Mutex<Option<State>> {
+        static STATE: OnceLock<Mutex<Option<State>>> = OnceLock::new();
+        STATE.get_or_init(|| {
+            let size = std::env::var(ENV_VAR)
+                .ok()
+                .and_then(|size| parse_size(&size));
+            Mutex::new(size.map(start))
+        })
+    }
+
+    fn with_state<T>(f: impl FnOnce(&mut Option<State>) -> T) -> T {
+        f(&mut state().lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn start(size: (u16, u16)) -> State {
+        State {
+            size,
+            started: Instant::now(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// Turn the mode on, in a terminal of `size` (columns, rows), the clock back at its start.
+    pub fn enable(size: (u16, u16)) {
+        with_state(|state| *state = Some(start(size)));
+    }
+
+    /// Turn the mode off, back to the terminal's size and the time.
+    pub fn disable() {
+        with_state(|state| *state = None);
+    }
+
+    pub fn is_enabled() -> bool {
+        with_state(|state| state.is_some())
+    }
+
+    /// The size of the terminal, (columns, rows).
+    pub fn terminal_size() -> io::Result<(u16, u16)> {
+        match with_state(|state| state.map(|state| state.size)) {
+            Some(size) => Ok(size),
+            None => crossterm::terminal::size(),
+        }
+    }
+
+    /// The time, as far as the clock has been moved on since the mode was turned on.
+    pub fn now() -> Instant {
+        with_state(|state| match state {
+            Some(state) => state.started + state.elapsed,
+            None => Instant::now(),
+        })
+    }
+
+    /// Move the clock on by `duration`, when a tick went by without input.
+    pub fn advance(duration: Duration) {
+        with_state(|state| {
+            if let Some(state) = state {
+                state.elapsed += duration;
+            }
+        });
+    }
+
+    // a size like 80x24
+    pub(super) fn parse_size(text: &str) -> Option<(u16, u16)> {
+        let (width, height) = text.trim().split_once(['x', 'X'])?;
+        let size = (width.trim().parse().ok()?, height.trim().parse().ok()?);
+        (size.0 > 0 && size.1 > 0).then_some(size)
+    }
+}
+
+#[cfg(not(any(test, feature = "deterministic")))]
+mod imp {
+    use super::{Duration, Instant, io};
+
+    pub fn is_enabled() -> bool {
+        false
+    }
+
+    pub fn terminal_size() -> io::Result<(u16, u16)> {
+        crossterm::terminal::size()
+    }
+
+    pub fn now() -> Instant {
+        Instant::now()
+    }
+
+    pub fn advance(_: Duration) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::imp::parse_size;
+
+    #[test]
+    fn parse_a_size() {
+        assert_eq!(parse_size("80x24"), Some((80, 24)));
+        assert_eq!(parse_size(" 120 X 40 "), Some((120, 40)));
+        assert_eq!(parse_size("80"), None);
+        assert_eq!(parse_size("0x24"), None);
+        assert_eq!(parse_size("80x-1"), None);
+    }
+}
@@ -0,0 +1,111 @@
+//! The commands typed after `:`, gone through with Up and Down in the command bar, kept
+//! across sessions in `explore_history.txt` in the nushell data directory, one a line; and
+//! the pipelines run in `:try`, in `explore_try_history.txt` when `explore.try.history` is on.
+
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+/// How many commands are kept.
+pub const LIMIT: usize = 500;
+
+/// Where they're kept, if there's a data directory.
+pub fn path() -> Option<PathBuf> {
+    data_file("explore_history.txt")
+}
+
+/// Where the pipelines run in `:try` are kept, if there's a data directory.
+pub fn try_path() -> Option<PathBuf> {
+    data_file("explore_try_history.txt")
+}
+
+fn data_file(name: &str) -> Option<PathBuf> {
+    nu_path::data_dir().map(|dir| dir.join("nushell").join(name).into_std_path_buf())
+}
+
+/// Read the commands kept at `path`, the oldest first; none if nothing was typed yet.
+pub fn load(path: &Path) -> Result<Vec<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(text.lines().map(String::from).collect()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err).with_context(|| format!("can't read {}", path.display())),
+    }
+}
+
+pub fn save(path: &Path, history: &[String]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("can't create {}", dir.display()))?;
+    }
+
+    let mut text = history.join("\n");
+    text.push('\n');
+    fs::write(path, text).with_context(|| format!("can't write {}", path.display()))
+}
+
+/// Put `commands` last in `history`, in place of the same ones earlier, forgetting the
+/// oldest once there are more than [`LIMIT`].
+pub fn append(history: &mut Vec<String>, commands: &[String]) {
+    for command in commands {
+        // a command on several lines would be read back as several
+        if command.trim().is_empty() || command.contains('\n') {
+            continue;
+        }
+        history.retain(|known| known != command);
+        history.push(command.clone());
+    }
+
+    let over = history.len().saturating_sub(LIMIT);
+    history.drain(..over);
+}
+
+/// Add the commands typed in a session to those kept at `path`, as read again, for sessions
+/// run at the same time to keep theirs too.
+pub fn remember(path: &Path, commands: &[String]) -> Result<()> {
+    if commands.is_empty() {
+        return Ok(());
+    }
+
+    let mut history = load(path).unwrap_or_default();
+    append(&mut history, commands);
+    save(path, &history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(commands: &[&str]) -> Vec<String> {
+        commands.iter().map(|command| command.to_string()).collect()
+    }
+
+    #[test]
+    fn latest_last_without_repeats() {
+        let mut history = commands(&["nu ls", "try", "sort size"]);
+        append(
+            &mut history,
+            &commands(&["try", "", "filter a\nb", "nu ps"]),
+        );
+        assert_eq!(history, commands(&["nu ls", "sort size", "try", "nu ps"]));
+
+        let many: Vec<_> = (0..LIMIT).map(|n| n.to_string()).collect();
+        append(&mut history, &many);
+        assert_eq!(history.len(), LIMIT);
+        assert_eq!(history[0], "0");
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let dir = std::env::temp_dir().join(format!("explore-history-{}", std::process::id()));
+        let path = dir.join("explore_history.txt");
+        assert!(load(&path).unwrap().is_empty());
+
+        let history = commands(&["nu ls | where size > 1mb", "help"]);
+        save(&path, &history).unwrap();
+        assert_eq!(load(&path).unwrap(), history);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
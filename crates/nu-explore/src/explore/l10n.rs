@@ -0,0 +1,163 @@
+//! Translations of the pager's own text: the help, the key hints, the status messages and
+//! the errors of its commands.
+//!
+//! Messages are looked up by their English text, which is what's shown when there's no
+//! translation, so they stay written where they're used, wrapped in [`tr!`]. Catalogs come
+//! from `$env.config.explore.translations`, keyed by locale; the one in use is picked with
+//! `$env.config.explore.locale`, or the system's locale when that isn't set.
+
+use nu_protocol::Value;
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{PoisonError, RwLock},
+};
+
+// the catalog of the running pager, none until it starts
+static CATALOG: RwLock<Option<Catalog>> = RwLock::new(None);
+
+/// Translations of messages, by their English text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Pick the translations for `locale` out of `explore.translations`: the ones for its
+    /// exact name if there are some, else the ones for its language, so `pt_BR.UTF-8` falls
+    /// back to `pt`.
+    pub fn select(translations: &Value, locale: &str) -> Self {
+        let Ok(translations) = translations.as_record() else {
+            return Self::default();
+        };
+
+        let locale = locale
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .replace('_', "-");
+        let language = locale.split('-').next().unwrap_or_default();
+
+        let find = |wanted: &str| {
+            translations
+                .iter()
+                .find(|(name, _)| name.replace('_', "-").eq_ignore_ascii_case(wanted))
+                .and_then(|(_, messages)| messages.as_record().ok())
+        };
+        let Some(messages) = find(&locale).or_else(|| find(language)) else {
+            return Self::default();
+        };
+
+        let messages = messages
+            .iter()
+            .filter_map(|(text, translation)| {
+                Some((text.clone(), translation.coerce_string().ok()?))
+            })
+            .collect();
+
+        Self { messages }
+    }
+
+    fn get<'a>(&'a self, text: &'a str) -> &'a str {
+        self.messages.get(text).map_or(text, String::as_str)
+    }
+}
+
+/// Use `catalog` for the messages from now on.
+pub fn set_catalog(catalog: Catalog) {
+    *CATALOG.write().unwrap_or_else(PoisonError::into_inner) = Some(catalog);
+}
+
+/// The translation of `text`, with its `{name}` placeholders filled in from `args`.
+///
+/// [`tr!`] is the shorter way to call it.
+pub fn translate(text: &str, args: &[(&str, &dyn Display)]) -> String {
+    let catalog = CATALOG.read().unwrap_or_else(PoisonError::into_inner);
+    let template = catalog.as_ref().map_or(text, |catalog| catalog.get(text));
+
+    fill(template, args)
+}
+
+fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let arg = rest.find('}').and_then(|end| {
+            let (_, value) = args.iter().find(|(name, _)| *name == &rest[1..end])?;
+            Some((end, value))
+        });
+        match arg {
+            Some((end, value)) => {
+                text.push_str(&value.to_string());
+                rest = &rest[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+
+    text
+}
+
+/// Translate a message, filling in its placeholders by name:
+/// `tr!("{count} rows match", count = rows.len())`.
+macro_rules! tr {
+    ($text:literal $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::explore::l10n::translate(
+            $text,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),*],
+        )
+    };
+}
+
+pub(crate) use tr;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::{Record, Span};
+
+    fn translations() -> Value {
+        let span = Span::test_data();
+        let mut pt = Record::new();
+        pt.insert("{count} rows match", Value::string("{count} linhas", span));
+        let mut pt_br = Record::new();
+        pt_br.insert("Ready", Value::string("Pronto", span));
+
+        let mut translations = Record::new();
+        translations.insert("pt", Value::record(pt, span));
+        translations.insert("pt_BR", Value::record(pt_br, span));
+        Value::record(translations, span)
+    }
+
+    #[test]
+    fn catalog_is_selected_by_locale_then_language() {
+        let translations = translations();
+
+        let catalog = Catalog::select(&translations, "pt-BR.UTF-8");
+        assert_eq!(catalog.get("Ready"), "Pronto");
+        assert_eq!(catalog.get("{count} rows match"), "{count} rows match");
+
+        let catalog = Catalog::select(&translations, "pt_PT");
+        assert_eq!(catalog.get("{count} rows match"), "{count} linhas");
+
+        let catalog = Catalog::select(&translations, "de-DE");
+        assert_eq!(catalog, Catalog::default());
+    }
+
+    #[test]
+    fn placeholders_are_filled_by_name() {
+        assert_eq!(
+            fill("{count} rows of {name}", &[("name", &"ls"), ("count", &3)]),
+            "3 rows of ls"
+        );
+        assert_eq!(fill("{unknown} {", &[]), "{unknown} {");
+    }
+}
@@ -3,70 +3,209 @@
 //! This module contains the `explore` command implementation and all
 //! its supporting infrastructure including views, pager, and internal commands.
 
+pub mod bench;
+mod bindings;
 mod command;
 mod commands;
 mod config;
+pub mod deterministic;
+mod history;
+mod l10n;
 mod nu_common;
 mod pager;
+mod recent;
 mod registry;
 mod views;
 
 use anyhow::Result;
 pub use command::Explore;
-use commands::{ExpandCmd, HelpCmd, NuCmd, QuitCmd, TableCmd, TryCmd};
+pub use commands::ExploreCommand;
+use commands::{
+    BindingsCmd, BucketCmd, BytesCmd, ChartCmd, ColCmd, CommonColumnsCmd, CompareCmd, CopyCmd,
+    EncodingCmd, ErrorCmd, ExpandCmd, ExtensionCmd, ExtractCmd, FilterCmd, FiltersCmd, GotoCmd,
+    HelpCmd, HistCmd, InfoCmd, InlineCmd, MenuCmd, NuCmd, OpenCmd, PagesCmd, PaletteCmd, PresetCmd,
+    QuitCmd, RecentCmd, RegistersCmd, RenameCmd, ReplaceCmd, RotateCmd, SaveCmd, SetCmd, SortCmd,
+    SplitColCmd, StatsCmd, TableCmd, TagCmd, TitleCmd, TryCmd, UnpinCmd, ViewCommand,
+    YankColumnCmd, has_error,
+};
 pub use config::ExploreConfig;
-use crossterm::terminal::size;
-use nu_common::{collect_pipeline, has_simple_value};
+use l10n::tr;
+use nu_common::{
+    MAX_RANGE_VALUES, collect_pipeline, decode_text, detect_encoding, has_simple_value,
+    simple_value_text,
+};
 use nu_protocol::{
-    PipelineData, Value,
+    PipelineData, Span, Value,
     engine::{EngineState, Stack},
 };
-use pager::{Page, Pager, PagerConfig};
+use pager::{
+    Page, PagePolicy, Pager, PagerConfig,
+    follow::JobFollower,
+    input::{InputInfo, InputShape},
+    session::{ExitAction, Session},
+};
 use registry::CommandRegistry;
-use views::{BinaryView, Orientation, Preview, RecordView};
+use views::{BinaryView, Preview, RecordView, TryView};
 
+/// Explore `input`, telling in `session` how it ended.
 pub(crate) fn run_pager(
     engine_state: &EngineState,
     stack: &mut Stack,
     input: PipelineData,
     config: PagerConfig,
+    session: &mut Session,
 ) -> Result<Option<Value>> {
     let mut p = Pager::new(config.clone());
-    let commands = create_command_registry();
+    let result = explore(&mut p, engine_state, stack, input, config);
+    *session = p.take_session();
+    if result.is_err() {
+        session.exit = ExitAction::Error;
+    }
+
+    result
+}
+
+fn explore(
+    p: &mut Pager<'_>,
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    input: PipelineData,
+    config: PagerConfig,
+) -> Result<Option<Value>> {
+    let commands = create_command_registry(config.explore_config, config.commands);
+
+    // a job followed is shown as a table from the start, even before it sends anything
+    if let Some(id) = config.follow {
+        let mut follower = JobFollower::new(engine_state, id, input.into_value(Span::unknown())?);
+        let (columns, data) = follower.rows();
+        p.show_message(tr!(
+            "Following job {id}, what it outputs or sends tagged with its id shows up as it comes",
+            id = id.get()
+        ));
+        p.follow(follower);
+
+        let view = create_record_view(p, columns, data, false, config);
+        return p.run(engine_state, stack, view, commands);
+    }
+
+    // pipelines are composed first with `--try`, or without input, as there's nothing to
+    // explore yet; unless they can't be run
+    let no_eval = config.explore_config.no_eval;
+    if config.try_command.is_some() || (matches!(input, PipelineData::Empty) && !no_eval) {
+        let command = config.try_command.clone().unwrap_or_default();
+        let mut view =
+            TryView::from_pipeline(input, engine_state.signals(), config.explore_config.clone());
+        view.init(command.clone());
+
+        if command.is_empty() {
+            p.show_message(tr!(
+                "Type a pipeline to explore what it returns, :help for help"
+            ));
+        } else if let Err(err) = view.try_run(engine_state, stack) {
+            p.show_message(tr!("Error: {err}", err = err));
+        }
+
+        return p.run(
+            engine_state,
+            stack,
+            Some(Page::new(view, PagePolicy::Replace)),
+            commands,
+        );
+    }
 
     let is_record = matches!(input, PipelineData::Value(Value::Record { .. }, ..));
+    let is_range = matches!(input, PipelineData::Value(Value::Range { .. }, ..));
     let is_binary = matches!(
         input,
         PipelineData::Value(Value::Binary { .. }, ..) | PipelineData::ByteStream(..)
     );
+    let shape = InputShape::of(&input);
+    let started = deterministic::now();
 
     if is_binary {
-        p.show_message("Viewing binary data");
-
-        let view = binary_view(input, config.explore_config)?;
+        let view = binary_view(p, input, config.explore_config)?;
+        p.set_input(InputInfo {
+            shape,
+            collected_in: deterministic::now().saturating_duration_since(started),
+            truncated: false,
+        });
         return p.run(engine_state, stack, Some(view), commands);
     }
 
     let (columns, data) = collect_pipeline(input)?;
+    p.set_input(InputInfo {
+        shape,
+        collected_in: deterministic::now().saturating_duration_since(started),
+        truncated: is_range && data.len() == MAX_RANGE_VALUES,
+    });
 
     let has_no_input = columns.is_empty() && data.is_empty();
     if has_no_input {
-        return p.run(engine_state, stack, help_view(), commands);
+        return p.run(
+            engine_state,
+            stack,
+            help_view(config.explore_config),
+            commands,
+        );
     }
 
-    p.show_message("Ready");
+    p.show_message(tr!("Ready"));
+
+    if let Some(entry) = config.recent.clone()
+        && config.explore_config.recent
+    {
+        match recent::remember(entry, recent::fingerprint(&columns, &data)) {
+            Ok(true) => p.show_message(tr!("The data changed since it was last explored")),
+            Ok(false) => {}
+            Err(err) => log::warn!("Failed to remember the explored source: {err:#}"),
+        }
+    }
+
+    // an error is browsed on a page of its own, with what caused it
+    if let Some(value @ Value::Error { .. }) = has_simple_value(&data) {
+        let view = ErrorCmd::view(engine_state, stack, value, config.explore_config)?;
+        let view = Some(Page::new(view, PagePolicy::Stack));
+        return p.run(engine_state, stack, view, commands);
+    }
 
     if let Some(value) = has_simple_value(&data) {
-        let text = value.to_abbreviated_string(config.nu_config);
-        let view = Some(Page::new(Preview::new(&text), false));
+        let text = simple_value_text(engine_state, value, config.nu_config);
+        let view = Some(Page::new(Preview::new(&text), PagePolicy::Replace));
         return p.run(engine_state, stack, view, commands);
     }
 
-    let view = create_record_view(columns, data, is_record, config);
+    if data.iter().flatten().any(has_error) {
+        p.show_message(tr!(
+            "There are errors in the data, Enter on one or :error browses it"
+        ));
+    }
+
+    let expand = config.expand;
+    let mut view = create_record_view(p, columns, data, is_record, config);
+    if let Some(depth) = expand {
+        view = expand_view(engine_state, stack, view, depth)?;
+    }
+
     p.run(engine_state, stack, view, commands)
 }
 
+// the table of `page` expanded `depth` levels in its place, as `:expand <depth>` does it
+fn expand_view(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    page: Option<Page>,
+    depth: usize,
+) -> Result<Option<Page>> {
+    let Some(value) = page.and_then(|mut page| page.view.exit()) else {
+        return Ok(None);
+    };
+
+    let view = ExpandCmd::view(value, depth, engine_state, stack)?;
+    Ok(Some(Page::new(view, PagePolicy::Replace)))
+}
+
 fn create_record_view(
+    pager: &mut Pager<'_>,
     columns: Vec<String>,
     data: Vec<Vec<Value>>,
     // wait, why would we use RecordView for something that isn't a record?
@@ -75,51 +214,161 @@ fn create_record_view(
 ) -> Option<Page> {
     let mut view = RecordView::new(columns, data, config.explore_config.clone());
     if is_record {
-        view.set_top_layer_orientation(Orientation::Left);
+        view.set_top_layer_record();
+    }
+
+    if let Some(count) = config.explore_config.table.abbreviated_row_count {
+        view.abbreviate(count);
+    }
+
+    if let Some(preset) = &config.preset
+        && let Err(err) = view.apply_preset(preset)
+    {
+        pager.show_message(tr!("The preset could not be applied: {err}", err = err));
     }
 
     if config.tail
-        && let Ok((w, h)) = size()
+        && let Ok((w, h)) = deterministic::terminal_size()
     {
         view.tail(w, h);
     }
 
-    Some(Page::new(view, true))
+    Some(Page::new(view, PagePolicy::Stack))
 }
 
-fn help_view() -> Option<Page> {
-    Some(Page::new(HelpCmd::view(), false))
+fn help_view(config: &ExploreConfig) -> Option<Page> {
+    Some(Page::new(
+        HelpCmd::view(&config.keybindings),
+        PagePolicy::Modal,
+    ))
 }
 
-fn binary_view(input: PipelineData, config: &ExploreConfig) -> Result<Page> {
+fn binary_view(pager: &mut Pager<'_>, input: PipelineData, config: &ExploreConfig) -> Result<Page> {
     let data = match input {
         PipelineData::Value(Value::Binary { val, .. }, _) => val,
         PipelineData::ByteStream(bs, _) => bs.into_bytes()?,
         _ => unreachable!("checked beforehand"),
     };
 
+    // bytes that aren't UTF-8 may still be text in another encoding
+    if std::str::from_utf8(&data).is_err()
+        && let Some(encoding) = detect_encoding(&data)
+    {
+        pager.show_message(tr!(
+            "Decoded as {encoding}, :encoding <name> for another",
+            encoding = encoding.name()
+        ));
+
+        let view = Preview::new(&decode_text(&data, encoding)).with_source(data);
+        return Ok(Page::new(view, PagePolicy::Stack));
+    }
+
+    pager.show_message(tr!("Viewing binary data"));
     let view = BinaryView::new(data, config);
 
-    Ok(Page::new(view, true))
+    Ok(Page::new(view, PagePolicy::Stack))
 }
 
-fn create_command_registry() -> CommandRegistry {
+fn create_command_registry(config: &ExploreConfig, extensions: &[ExtensionCmd]) -> CommandRegistry {
     let mut registry = CommandRegistry::new();
     create_commands(&mut registry);
     create_aliases(&mut registry);
 
+    // after the built-in commands, to take the place of those of the same name
+    for command in extensions {
+        registry.register_command_view(command.clone(), PagePolicy::Stack);
+    }
+
+    for (name, commands) in &config.aliases {
+        registry.define_macro(name, commands.clone());
+    }
+
+    let mut entries = registry.menu_entries();
+    if config.no_eval {
+        registry.disable(NuCmd::NAME);
+        registry.disable(TryCmd::NAME);
+        registry.disable(OpenCmd::NAME);
+        // the commands of a distribution run its code, and macros run commands which may
+        let names: Vec<String> = extensions
+            .iter()
+            .map(|command| command.name().to_owned())
+            .chain(registry.get_macros().map(|(name, _)| name.to_owned()))
+            .collect();
+        for name in names {
+            registry.disable(&name);
+        }
+    } else {
+        entries.extend(config.menu.iter().cloned());
+    }
+    registry.register_command_reactive(MenuCmd::new(entries));
+
+    // last, to list all the commands
+    let palette = PaletteCmd::new(&registry);
+    registry.register_command_view(palette, PagePolicy::Modal);
+
+    for (command, policy) in &config.page_policies {
+        registry.set_page_policy(command, *policy);
+    }
+
     registry
 }
 
+/// The `:` commands, their aliases and the default keys, for `help explore`.
+pub(crate) fn help_reference() -> String {
+    let mut registry = CommandRegistry::new();
+    create_commands(&mut registry);
+    create_aliases(&mut registry);
+
+    commands::help_reference(&registry, &bindings::keymap(&[]))
+}
+
 fn create_commands(registry: &mut CommandRegistry) {
-    registry.register_command_view(NuCmd::new(), true);
-    registry.register_command_view(TableCmd::new(), true);
+    registry.register_command_view(NuCmd::new(), PagePolicy::Stack);
+    registry.register_command_view(TableCmd::new(), PagePolicy::Stack);
+    registry.register_command_view(BindingsCmd::new(), PagePolicy::Stack);
+    registry.register_command_view(RecentCmd::new(), PagePolicy::Stack);
+    registry.register_command_view(StatsCmd::new(), PagePolicy::Stack);
+    registry.register_command_view(HistCmd::new(), PagePolicy::Stack);
+    registry.register_command_view(ChartCmd::new(), PagePolicy::Stack);
+    registry.register_command_view(RegistersCmd::new(), PagePolicy::Stack);
 
-    registry.register_command_view(ExpandCmd::new(), false);
-    registry.register_command_view(TryCmd::new(), false);
-    registry.register_command_view(HelpCmd::default(), false);
+    registry.register_command_view(ExpandCmd::new(), PagePolicy::Replace);
+    registry.register_command_view(EncodingCmd::new(), PagePolicy::Replace);
+    registry.register_command_view(BytesCmd, PagePolicy::Stack);
+    registry.register_command_view(ErrorCmd, PagePolicy::Stack);
+    registry.register_command_view(TryCmd::new(), PagePolicy::Replace);
+    registry.register_command_view(HelpCmd::default(), PagePolicy::Modal);
 
     registry.register_command_reactive(QuitCmd);
+    registry.register_command_reactive(FilterCmd::default());
+    registry.register_command_reactive(FiltersCmd);
+    registry.register_command_reactive(SortCmd::default());
+    registry.register_command_reactive(PresetCmd::default());
+    registry.register_command_reactive(UnpinCmd);
+    registry.register_command_reactive(RotateCmd);
+    registry.register_command_reactive(CompareCmd);
+    registry.register_command_reactive(ExtractCmd::default());
+    registry.register_command_reactive(SplitColCmd::default());
+    registry.register_command_reactive(RenameCmd::default());
+    registry.register_command_reactive(CommonColumnsCmd);
+    registry.register_command_reactive(BucketCmd::default());
+    registry.register_command_reactive(TagCmd::default());
+    registry.register_command_reactive(ReplaceCmd::default());
+    registry.register_command_reactive(PagesCmd);
+    registry.register_command_reactive(TitleCmd::default());
+    registry.register_command_reactive(ColCmd::default());
+    registry.register_command_reactive(InlineCmd::default());
+    registry.register_command_reactive(CopyCmd::default());
+    registry.register_command_reactive(OpenCmd::default());
+    registry.register_command_reactive(YankColumnCmd::default());
+    registry.register_command_reactive(SaveCmd::default());
+    registry.register_command_reactive(GotoCmd::default());
+    registry.register_command_reactive(SetCmd::default());
+    registry.register_command_reactive(InfoCmd);
+    registry.register_command_reactive(MenuCmd::default());
+    registry.register_command_view(PaletteCmd::default(), PagePolicy::Modal);
+    #[cfg(any(debug_assertions, feature = "stress"))]
+    registry.register_command_reactive(commands::StressCmd::default());
 }
 
 fn create_aliases(registry: &mut CommandRegistry) {
@@ -127,4 +376,29 @@ fn create_aliases(registry: &mut CommandRegistry) {
     registry.create_aliases("e", ExpandCmd::NAME);
     registry.create_aliases("q", QuitCmd::NAME);
     registry.create_aliases("q!", QuitCmd::NAME);
+    registry.define_macro("nu!", vec![format!("{} --cell", NuCmd::NAME)]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nothing_runs_code_without_eval() {
+        let mut config = ExploreConfig::default();
+        config
+            .aliases
+            .insert("errs".to_string(), vec![":filter level error".to_string()]);
+
+        let registry = create_command_registry(&config, &[]);
+        assert!(registry.expand_macro("errs").is_some());
+        assert!(matches!(registry.find(OpenCmd::NAME), Some(Ok(_))));
+
+        config.no_eval = true;
+        let registry = create_command_registry(&config, &[]);
+        for name in [NuCmd::NAME, TryCmd::NAME, OpenCmd::NAME, "errs", "nu!"] {
+            assert!(matches!(registry.find(name), Some(Err(_))), "{name}");
+        }
+        assert!(registry.expand_macro("errs").is_none());
+    }
 }
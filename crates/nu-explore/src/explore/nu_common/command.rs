@@ -68,10 +68,11 @@ fn eval_source2(
         );
 
         if let Some(err) = working_set.parse_errors.first() {
-            return Err(ShellError::Generic(GenericError::new_internal(
-                "Parse error",
-                err.to_string(),
-            )));
+            // kept inner, its spans pointing into the source
+            return Err(ShellError::Generic(
+                GenericError::new_internal("Parse error", err.to_string())
+                    .with_inner([ShellError::from_diagnostic(err)]),
+            ));
         }
 
         (output, working_set.render())
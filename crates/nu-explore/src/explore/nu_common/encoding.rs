@@ -0,0 +1,143 @@
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE};
+
+// only the start of the bytes is looked at, that's plenty to tell text apart
+const SAMPLE: usize = 64 * 1024;
+
+// the share of control or undecodable characters above which bytes aren't taken as text, in
+// thousandths
+const MAX_GARBAGE: usize = 10;
+
+/// The encoding `bytes` are most likely text in, if they look like text at all: one their byte
+/// order mark names, UTF-16 if every other byte is 0 as with mostly ASCII text, or else the
+/// guess of chardetng.
+pub fn detect_encoding(bytes: &[u8]) -> Option<&'static Encoding> {
+    let sample = &bytes[..bytes.len().min(SAMPLE)];
+    if sample.is_empty() {
+        return None;
+    }
+
+    let encoding = match Encoding::for_bom(sample) {
+        Some((encoding, _)) => encoding,
+        None => match utf16_without_bom(sample) {
+            Some(encoding) => encoding,
+            // anything else with NULs is binary
+            None if sample.contains(&0) => return None,
+            None => {
+                let mut detector = EncodingDetector::new();
+                detector.feed(sample, sample.len() == bytes.len());
+                detector.guess(None, true)
+            }
+        },
+    };
+
+    let (text, _) = encoding.decode_without_bom_handling(sample);
+    looks_like_text(&text).then_some(encoding)
+}
+
+/// The encoding named `label`, like `latin1`, `shift_jis` or `utf-16le`; `utf16` is taken as
+/// `utf-16` too.
+pub fn find_encoding(label: &str) -> Option<&'static Encoding> {
+    let label = match label.eq_ignore_ascii_case("utf16") {
+        true => "utf-16",
+        false => label,
+    };
+
+    Encoding::for_label_no_replacement(label.as_bytes())
+}
+
+/// `bytes` decoded as text of `encoding`, unless a byte order mark says otherwise; what can't be
+/// decoded is replaced with �.
+pub fn decode_text(bytes: &[u8], encoding: &'static Encoding) -> String {
+    let (text, ..) = encoding.decode(bytes);
+    text.into_owned()
+}
+
+// mostly ASCII text in UTF-16 has a 0 for every other byte: the second of each pair in little
+// endian, the first in big endian
+fn utf16_without_bom(sample: &[u8]) -> Option<&'static Encoding> {
+    let pairs = sample.len() / 2;
+    if pairs == 0 {
+        return None;
+    }
+
+    let count = |zeros_at: usize| {
+        sample
+            .chunks_exact(2)
+            .filter(|pair| pair[zeros_at] == 0)
+            .count()
+    };
+    let (first, second) = (count(0), count(1));
+
+    let mostly = |zeros: usize| zeros * 10 >= pairs * 4;
+    let hardly = |zeros: usize| zeros * 20 <= pairs;
+    if mostly(second) && hardly(first) {
+        Some(UTF_16LE)
+    } else if mostly(first) && hardly(second) {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+fn looks_like_text(text: &str) -> bool {
+    let mut count = 0;
+    let mut garbage = 0;
+    for c in text.chars() {
+        count += 1;
+        let is_control = c.is_control() && !matches!(c, '\n' | '\r' | '\t' | '\x0c');
+        if is_control || c == char::REPLACEMENT_CHARACTER {
+            garbage += 1;
+        }
+    }
+
+    count > 0 && garbage * 1000 <= count * MAX_GARBAGE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::{SHIFT_JIS, UTF_8, WINDOWS_1252};
+
+    #[test]
+    fn detect_the_encoding_of_text() {
+        let (latin1, ..) = WINDOWS_1252.encode("Le café était déjà fermé, à côté de l'hôtel.");
+        assert_eq!(detect_encoding(&latin1), Some(WINDOWS_1252));
+
+        let utf16le: Vec<u8> = "name,size\nexplore,12\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        assert_eq!(detect_encoding(&utf16le), Some(UTF_16LE));
+        assert_eq!(decode_text(&utf16le, UTF_16LE), "name,size\nexplore,12\n");
+
+        let mut bom = vec![0xef, 0xbb, 0xbf];
+        bom.extend_from_slice("text".as_bytes());
+        assert_eq!(detect_encoding(&bom), Some(UTF_8));
+    }
+
+    #[test]
+    fn binary_is_not_text() {
+        let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR\x00\x00\x01\x00";
+        assert_eq!(detect_encoding(png), None);
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(detect_encoding(&bytes), None);
+        assert_eq!(detect_encoding(&[]), None);
+    }
+
+    #[test]
+    fn encodings_by_name() {
+        assert_eq!(find_encoding("latin1"), Some(WINDOWS_1252));
+        assert_eq!(find_encoding("UTF16").map(Encoding::name), Some("UTF-16LE"));
+        assert_eq!(find_encoding("klingon"), None);
+
+        // what isn't told apart well enough, like short Japanese text, is decoded as asked
+        let text = "何だと？……無駄な努力だ？……百も承知だ！";
+        let (sjis, ..) = SHIFT_JIS.encode(text);
+        assert_eq!(
+            decode_text(&sjis, find_encoding("shift_jis").unwrap()),
+            text
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use super::super::l10n::tr;
 use super::NuText;
 use lscolors::LsColors;
 use nu_ansi_term::{Color, Style};
@@ -8,12 +9,22 @@ use nu_path::{expand_path_with, expand_to_real_path};
 use nu_protocol::engine::{EngineState, Stack};
 use nu_utils::get_ls_colors;
 
-pub fn create_lscolors(engine_state: &EngineState, stack: &Stack) -> LsColors {
-    let colors = stack
-        .get_env_var(engine_state, "LS_COLORS")
-        .and_then(|v| env_to_string("LS_COLORS", v, engine_state, stack).ok());
+/// The colors of `$env.LS_COLORS`, or the default ones with a warning when it can't be read.
+pub fn create_lscolors(engine_state: &EngineState, stack: &Stack) -> (LsColors, Option<String>) {
+    let Some(value) = stack.get_env_var(engine_state, "LS_COLORS") else {
+        return (get_ls_colors(None), None);
+    };
 
-    get_ls_colors(colors)
+    match env_to_string("LS_COLORS", value, engine_state, stack) {
+        Ok(colors) => (get_ls_colors(Some(colors)), None),
+        Err(err) => {
+            let warning = tr!(
+                "LS_COLORS can't be read, the default colors are used: {err}",
+                err = err
+            );
+            (get_ls_colors(None), Some(warning))
+        }
+    }
 }
 
 /// Colorizes any columns named "name" in the table using LS_COLORS
@@ -0,0 +1,80 @@
+use super::detect_encoding;
+
+// the bytes files of a kind start with, at an offset
+const MAGIC: &[(usize, &[u8], &str)] = &[
+    (0, b"\x89PNG\r\n\x1a\n", "png"),
+    (0, b"\xff\xd8\xff", "jpeg"),
+    (0, b"GIF87a", "gif"),
+    (0, b"GIF89a", "gif"),
+    (0, b"\x00\x00\x01\x00", "ico"),
+    (0, b"%PDF-", "pdf"),
+    (0, b"PK\x03\x04", "zip"),
+    (0, b"PK\x05\x06", "zip"),
+    (0, b"\x1f\x8b", "gzip"),
+    (0, b"BZh", "bzip2"),
+    (0, b"\xfd7zXZ\x00", "xz"),
+    (0, b"\x28\xb5\x2f\xfd", "zstd"),
+    (0, b"7z\xbc\xaf\x27\x1c", "7z"),
+    (257, b"ustar", "tar"),
+    (0, b"\x7fELF", "elf"),
+    (0, b"MZ", "exe"),
+    (0, b"\xca\xfe\xba\xbe", "mach-o"),
+    (0, b"\xcf\xfa\xed\xfe", "mach-o"),
+    (0, b"\x00asm", "wasm"),
+    (0, b"SQLite format 3\x00", "sqlite"),
+    (0, b"OggS", "ogg"),
+    (0, b"fLaC", "flac"),
+    (0, b"ID3", "mp3"),
+    (4, b"ftyp", "mp4"),
+    (0, b"\x1a\x45\xdf\xa3", "matroska"),
+    (0, b"wOFF", "woff"),
+    (0, b"wOF2", "woff2"),
+    (0, b"ARROW1", "arrow"),
+    (0, b"PAR1", "parquet"),
+];
+
+// formats in a RIFF container, by the kind after its size
+const RIFF: &[(&[u8], &str)] = &[(b"WEBP", "webp"), (b"WAVE", "wav"), (b"AVI ", "avi")];
+
+/// A guess at what kind of file `bytes` are, by the magic bytes they start with, like `png`
+/// or `gzip`; `text` if they have none but look like text.
+pub fn guess_kind(bytes: &[u8]) -> Option<&'static str> {
+    let starts = |offset: usize, magic: &[u8]| {
+        bytes
+            .get(offset..offset + magic.len())
+            .is_some_and(|start| start == magic)
+    };
+
+    if starts(0, b"RIFF") {
+        return RIFF
+            .iter()
+            .find(|(kind, _)| starts(8, kind))
+            .map(|(_, name)| *name);
+    }
+    if let Some((_, _, name)) = MAGIC
+        .iter()
+        .find(|(offset, magic, _)| starts(*offset, magic))
+    {
+        return Some(name);
+    }
+
+    detect_encoding(bytes).map(|_| "text")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kinds_of_files() {
+        assert_eq!(
+            guess_kind(b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR"),
+            Some("png")
+        );
+        assert_eq!(guess_kind(b"RIFF\x24\x08\x00\x00WAVEfmt "), Some("wav"));
+        assert_eq!(guess_kind(b"\x00\x00\x00\x18ftypmp42"), Some("mp4"));
+        assert_eq!(guess_kind(b"just some words"), Some("text"));
+        assert_eq!(guess_kind(b"\x00\x01\x02\x03\xfe"), None);
+        assert_eq!(guess_kind(b""), None);
+    }
+}
@@ -1,22 +1,41 @@
 mod command;
+mod encoding;
 mod lscolor;
+mod magic;
+mod pipeline;
 mod string;
+mod style;
 mod table;
 mod value;
 
 use nu_color_config::TextStyle;
-use nu_protocol::Value;
+use nu_protocol::{Value, engine::EngineState};
 
 pub use nu_ansi_term::{Color as NuColor, Style as NuStyle};
 pub use nu_protocol::{Config as NuConfig, Span as NuSpan};
 
 pub type NuText = (String, TextStyle);
 
-pub use command::run_command_with_value;
+pub use command::{run_command_with_value, run_nu_command};
+pub use encoding::{decode_text, detect_encoding, find_encoding};
 pub use lscolor::{create_lscolors, lscolorize};
-pub use string::{string_width, truncate_str};
+pub use magic::guess_kind;
+pub use pipeline::{complete_pipeline, highlight_pipeline, pipeline_word_start};
+pub use string::{format_size, left_out, scroll_str, string_width, truncate_bytes, truncate_str};
+pub use style::{create_style_computer, without_color_closures};
 pub use table::try_build_table;
-pub use value::{collect_input, collect_pipeline, create_map};
+pub use value::{
+    MAX_RANGE_VALUES, Unit, base_value, closure_source, collect_input, collect_pipeline,
+    create_map, is_missing_cell, nesting_depth, record_create_row,
+};
+
+/// The text of a value shown on its own: the source of a closure, anything else abbreviated.
+pub fn simple_value_text(engine_state: &EngineState, value: &Value, config: &NuConfig) -> String {
+    match value {
+        Value::Closure { val, .. } => closure_source(engine_state, val),
+        value => value.to_abbreviated_string(config),
+    }
+}
 
 pub fn has_simple_value(data: &[Vec<Value>]) -> Option<&Value> {
     if data.len() == 1
@@ -0,0 +1,204 @@
+//! What `:try` knows of the pipeline being typed: the styles of its parts, as nushell
+//! highlights them, and the words completing it.
+
+use super::NuStyle;
+use nu_color_config::get_shape_color;
+use nu_parser::{flatten_block, parse};
+use nu_protocol::{
+    DeclId, Value,
+    engine::{EngineState, Stack, StateWorkingSet},
+};
+use std::ops::Range;
+
+// the commands whose arguments are cell paths of their input
+const CELL_PATH_COMMANDS: &[&str] = &["get", "select", "reject"];
+
+// the rows of a list whose columns are offered, the first ones
+const COLUMN_ROWS: usize = 100;
+
+/// The parts of `line` and their style, by byte range, with the `shape_` colors of
+/// `color_config`; what isn't parsed into anything is left out.
+pub fn highlight_pipeline(
+    engine_state: &EngineState,
+    stack: &Stack,
+    line: &str,
+) -> Vec<(Range<usize>, NuStyle)> {
+    let config = stack.get_config(engine_state);
+    let mut working_set = StateWorkingSet::new(engine_state);
+    let block = parse(&mut working_set, None, line.as_bytes(), false);
+    // spans count from the end of what the engine parsed so far
+    let offset = engine_state.next_span_start();
+
+    flatten_block(&working_set, &block)
+        .into_iter()
+        .filter_map(|(span, shape)| {
+            let start = span.start.checked_sub(offset)?;
+            let end = span.end.checked_sub(offset)?.min(line.len());
+            let style = get_shape_color(shape.as_str(), &config);
+            (start < end).then_some((start..end, style))
+        })
+        .collect()
+}
+
+/// Where the word typed last in `line` starts, which is completed.
+pub fn pipeline_word_start(line: &str) -> usize {
+    line.rfind([' ', '|', '(']).map_or(0, |at| at + 1)
+}
+
+/// What the word typed last in `line` may be: a command at the start of a pipeline element,
+/// a flag of the command it's given to, or a cell path of `input` after `$in.` or as the
+/// argument of `get`, `select` and `reject`.
+pub fn complete_pipeline(engine_state: &EngineState, line: &str, input: &Value) -> Vec<String> {
+    let start = pipeline_word_start(line);
+    let word = &line[start..];
+    let element = line[..start].rsplit(['|', '(']).next().unwrap_or_default();
+    let before: Vec<_> = element.split_whitespace().collect();
+
+    let candidates = if let Some(path) = word.strip_prefix("$in.") {
+        cell_paths(input, path)
+            .into_iter()
+            .map(|path| format!("$in.{path}"))
+            .collect()
+    } else if before.is_empty() {
+        engine_state
+            .get_decls_sorted(false)
+            .into_iter()
+            .filter_map(|(name, _)| String::from_utf8(name).ok())
+            // the subcommands are offered after their command
+            .filter(|name| !name.contains(' '))
+            .collect()
+    } else if let Some((name, decl_id)) = find_command(engine_state, &before) {
+        if word.starts_with('-') {
+            let signature = engine_state.get_decl(decl_id).signature();
+            signature
+                .named
+                .iter()
+                .filter(|flag| !flag.long.is_empty())
+                .map(|flag| format!("--{}", flag.long))
+                .collect()
+        } else if CELL_PATH_COMMANDS.contains(&name.as_str()) {
+            cell_paths(input, word)
+        } else {
+            subcommands(engine_state, &name)
+        }
+    } else {
+        Vec::new()
+    };
+
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(word))
+        .collect()
+}
+
+// the command `words` start with, the longest name first as in `str trim`
+fn find_command(engine_state: &EngineState, words: &[&str]) -> Option<(String, DeclId)> {
+    (1..=words.len()).rev().find_map(|count| {
+        let name = words[..count].join(" ");
+        let decl_id = engine_state.find_decl(name.as_bytes(), &[])?;
+        Some((name, decl_id))
+    })
+}
+
+// the last word of the subcommands of `name`, only those right under it
+fn subcommands(engine_state: &EngineState, name: &str) -> Vec<String> {
+    let prefix = format!("{name} ");
+    engine_state
+        .get_decls_sorted(false)
+        .into_iter()
+        .filter_map(|(decl, _)| {
+            let decl = String::from_utf8(decl).ok()?;
+            let rest = decl.strip_prefix(&prefix)?;
+            (!rest.contains(' ')).then(|| rest.to_string())
+        })
+        .collect()
+}
+
+// the cell paths of `value` going on from `path`, what's before its last `.` being followed
+fn cell_paths(value: &Value, path: &str) -> Vec<String> {
+    let (parents, prefix) = match path.rsplit_once('.') {
+        Some((parents, _)) => (parents.split('.').collect(), format!("{parents}.")),
+        None => (Vec::new(), String::new()),
+    };
+
+    let mut value = value;
+    for name in parents {
+        value = match value {
+            Value::Record { val, .. } => match val.get(name) {
+                Some(value) => value,
+                None => return Vec::new(),
+            },
+            Value::List { vals, .. } => match name.parse::<usize>() {
+                Ok(index) if index < vals.len() => &vals[index],
+                _ => return Vec::new(),
+            },
+            _ => return Vec::new(),
+        };
+    }
+
+    columns(value)
+        .into_iter()
+        .map(|column| format!("{prefix}{column}"))
+        .collect()
+}
+
+// the columns of a record, or those of the records of a list
+fn columns(value: &Value) -> Vec<String> {
+    match value {
+        Value::Record { val, .. } => val.columns().cloned().collect(),
+        Value::List { vals, .. } => {
+            let mut columns: Vec<String> = Vec::new();
+            for row in vals.iter().take(COLUMN_ROWS) {
+                if let Value::Record { val, .. } = row {
+                    for column in val.columns() {
+                        if !columns.contains(column) {
+                            columns.push(column.clone());
+                        }
+                    }
+                }
+            }
+            columns
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::record;
+
+    #[test]
+    fn highlight_the_parts() {
+        let engine_state = EngineState::new();
+        let stack = Stack::new();
+        let line = "1 + 20";
+
+        let parts = highlight_pipeline(&engine_state, &stack, line);
+        let ranges: Vec<_> = parts.iter().map(|(range, _)| range.clone()).collect();
+        assert_eq!(ranges, [0..1, 2..3, 4..6]);
+        assert_eq!(parts[0].1, parts[2].1);
+    }
+
+    #[test]
+    fn complete_cell_paths() {
+        let engine_state = EngineState::new();
+        let file = Value::test_record(record! {
+            "name" => Value::test_string("a"),
+            "meta" => Value::test_record(record! { "size" => Value::test_int(1) }),
+        });
+        let input = Value::test_list(vec![file]);
+
+        assert_eq!(
+            complete_pipeline(&engine_state, "$in.", &input),
+            ["$in.name", "$in.meta"]
+        );
+        assert_eq!(
+            complete_pipeline(&engine_state, "where x | $in.0.meta.s", &input),
+            ["$in.0.meta.size"]
+        );
+        assert!(complete_pipeline(&engine_state, "$in.nope.", &input).is_empty());
+        // not a command this engine knows
+        assert!(complete_pipeline(&engine_state, "get n", &input).is_empty());
+    }
+}
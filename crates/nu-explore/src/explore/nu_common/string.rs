@@ -1,3 +1,4 @@
+use nu_protocol::{Filesize, FilesizeFormatter};
 use nu_table::string_truncate;
 
 pub use nu_table::string_width;
@@ -14,3 +15,59 @@ pub fn truncate_str(text: &mut String, width: usize) {
         text.push('…');
     }
 }
+
+/// The first `budget` bytes of a long text, ending at a character, with the size of the rest:
+/// `start…(+1.2 MB)`.
+pub fn truncate_bytes(text: &str, budget: usize) -> String {
+    if text.len() <= budget {
+        return text.to_string();
+    }
+
+    let end = text.floor_char_boundary(budget);
+    format!("{}…{}", &text[..end], left_out(text.len() - end))
+}
+
+/// The text scrolled `offset` characters to the left, what's scrolled out marked by `…`.
+pub fn scroll_str(text: &str, offset: usize) -> String {
+    if offset == 0 {
+        return text.to_string();
+    }
+
+    let rest: String = text.chars().skip(offset).collect();
+    format!("…{rest}")
+}
+
+/// How much of a cell is left out, like `(+123.0 kB)`.
+pub fn left_out(bytes: usize) -> String {
+    format!("(+{})", format_size(bytes))
+}
+
+/// A number of bytes as a size, like `123.0 kB`.
+pub fn format_size(bytes: usize) -> String {
+    FilesizeFormatter::new()
+        .precision(1)
+        .format(Filesize::new(bytes as i64))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_texts_are_cut_at_a_character() {
+        assert_eq!(truncate_bytes("short", 10), "short");
+        assert_eq!(truncate_bytes("ab€cd", 3), "ab…(+5 B)");
+        assert_eq!(
+            truncate_bytes(&"x".repeat(2500), 500),
+            format!("{}…(+2.0 kB)", "x".repeat(500))
+        );
+    }
+
+    #[test]
+    fn scrolled_texts() {
+        assert_eq!(scroll_str("/usr/local/bin", 0), "/usr/local/bin");
+        assert_eq!(scroll_str("/usr/local/bin", 5), "…local/bin");
+        assert_eq!(scroll_str("né€", 1), "…é€");
+    }
+}
@@ -0,0 +1,110 @@
+use super::super::l10n::tr;
+use nu_color_config::StyleComputer;
+use nu_engine::ClosureEvalOnce;
+use nu_protocol::{
+    Span, Value,
+    engine::{EngineState, Stack},
+};
+use std::{collections::HashMap, sync::Arc};
+
+/// The styles of `color_config`, or a neutral palette, without any color, when one of its
+/// closures fails, with a warning telling which. A failing closure would otherwise print its
+/// error over the pager for every cell it styles.
+pub fn create_style_computer<'a>(
+    engine_state: &'a EngineState,
+    stack: &'a Stack,
+) -> (StyleComputer<'a>, Option<String>) {
+    let config = stack.get_config(engine_state);
+    for (name, value) in &config.color_config {
+        let Value::Closure { val, .. } = value else {
+            continue;
+        };
+
+        let result = ClosureEvalOnce::new(engine_state, stack, *val.clone())
+            .debug(false)
+            .run_with_value(sample_value(name))
+            .and_then(|data| data.into_value(Span::unknown()));
+        if let Err(err) = result {
+            let warning = tr!(
+                "The color_config closure of {name} failed, colors are off: {err}",
+                name = name,
+                err = err
+            );
+            return (
+                StyleComputer::new(engine_state, stack, HashMap::new()),
+                Some(warning),
+            );
+        }
+    }
+
+    (StyleComputer::from_config(engine_state, stack), None)
+}
+
+/// A copy of `stack` whose `color_config` has no closures, for styles which run no code: what
+/// a closure styled gets the default style.
+pub fn without_color_closures(engine_state: &EngineState, stack: &Stack) -> Stack {
+    let mut config = stack.get_config(engine_state).as_ref().clone();
+    config
+        .color_config
+        .retain(|_, value| !matches!(value, Value::Closure { .. }));
+
+    let mut stack = stack.clone();
+    stack.config = Some(Arc::new(config));
+    stack
+}
+
+// a value of the kind the closure of the style `name` is given
+fn sample_value(name: &str) -> Value {
+    let span = Span::unknown();
+    match name {
+        "bool" => Value::bool(false, span),
+        "int" => Value::int(0, span),
+        "float" => Value::float(0.0, span),
+        "filesize" => Value::filesize(0i64, span),
+        "duration" => Value::duration(0, span),
+        "string" | "header" => Value::string("", span),
+        "binary" => Value::binary(Vec::new(), span),
+        "list" => Value::list(Vec::new(), span),
+        "record" => Value::record(Default::default(), span),
+        _ => Value::nothing(span),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_without_closures() {
+        let engine_state = EngineState::new();
+        let stack = Stack::new();
+        let (style_computer, warning) = create_style_computer(&engine_state, &stack);
+
+        assert!(warning.is_none());
+        let header = style_computer.compute("header", &Value::test_string("name"));
+        assert_ne!(header, Default::default());
+    }
+
+    #[test]
+    fn styles_without_running_closures() {
+        let engine_state = EngineState::new();
+        let mut config = nu_protocol::Config::default();
+        let closure = nu_protocol::engine::Closure {
+            block_id: nu_protocol::BlockId::new(0),
+            captures: Vec::new(),
+        };
+        config
+            .color_config
+            .insert("int".into(), Value::test_closure(closure));
+        config
+            .color_config
+            .insert("string".into(), Value::test_string("red"));
+        let mut stack = Stack::new();
+        stack.config = Some(Arc::new(config));
+
+        let stack = without_color_closures(&engine_state, &stack);
+        let color_config = &stack.get_config(&engine_state).color_config;
+        assert!(!color_config.contains_key("int"));
+        assert_eq!(color_config["string"], Value::test_string("red"));
+    }
+}
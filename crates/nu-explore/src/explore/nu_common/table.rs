@@ -11,6 +11,8 @@ pub fn try_build_table(
     signals: &Signals,
     config: &NuConfig,
     style_computer: StyleComputer,
+    // how many levels of nested tables are expanded, `None` for all of them
+    expand_limit: Option<usize>,
 ) -> String {
     let span = value.span();
     let opts = TableOpts::new(
@@ -25,8 +27,8 @@ pub fn try_build_table(
         vec![],
     );
     match value {
-        Value::List { vals, .. } => try_build_list(vals, opts),
-        Value::Record { val, .. } => try_build_map(&val, opts),
+        Value::List { vals, .. } => try_build_list(vals, opts, expand_limit),
+        Value::Record { val, .. } => try_build_map(&val, opts, expand_limit),
         val @ Value::String { .. } => {
             nu_value_to_string_clean(&val, config, &opts.style_computer).0
         }
@@ -34,8 +36,9 @@ pub fn try_build_table(
     }
 }
 
-fn try_build_map(record: &Record, opts: TableOpts<'_>) -> String {
-    let result = ExpandedTable::new(None, false, String::new()).build_map(record, opts.clone());
+fn try_build_map(record: &Record, opts: TableOpts<'_>, expand_limit: Option<usize>) -> String {
+    let result =
+        ExpandedTable::new(expand_limit, false, String::new()).build_map(record, opts.clone());
     match result {
         Ok(Some(result)) => result,
         _ => {
@@ -45,8 +48,9 @@ fn try_build_map(record: &Record, opts: TableOpts<'_>) -> String {
     }
 }
 
-fn try_build_list(vals: Vec<Value>, opts: TableOpts<'_>) -> String {
-    let result = ExpandedTable::new(None, false, String::new()).build_list(&vals, opts.clone());
+fn try_build_list(vals: Vec<Value>, opts: TableOpts<'_>, expand_limit: Option<usize>) -> String {
+    let result =
+        ExpandedTable::new(expand_limit, false, String::new()).build_list(&vals, opts.clone());
     match result {
         Ok(Some(out)) => out,
         _ => {
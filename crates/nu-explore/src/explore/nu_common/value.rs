@@ -1,8 +1,15 @@
 use super::NuSpan;
 use anyhow::Result;
 use nu_engine::get_columns;
-use nu_protocol::{ByteStream, ListStream, PipelineData, PipelineMetadata, Value, record};
-use std::collections::HashMap;
+use nu_protocol::{
+    ByteStream, Config, ListStream, PipelineData, PipelineMetadata, Signals, Value,
+    engine::{Closure, EngineState},
+    record,
+};
+use std::{borrow::Cow, collections::HashMap};
+
+/// At most this many values of a range are shown, as ranges can be unbounded.
+pub const MAX_RANGE_VALUES: usize = 100_000;
 
 pub fn collect_pipeline(input: PipelineData) -> Result<(Vec<String>, Vec<Vec<Value>>)> {
     match input {
@@ -122,6 +129,15 @@ pub fn collect_input(value: Value) -> Result<(Vec<String>, Vec<Vec<Value>>)> {
             Ok((vec![String::from("")], lines))
         }
         Value::Nothing { .. } => Ok((vec![], vec![])),
+        Value::Range { val, .. } => {
+            let values = (*val)
+                .into_range_iter(span, Signals::empty())
+                .take(MAX_RANGE_VALUES)
+                .map(|value| vec![value])
+                .collect();
+
+            Ok((vec![String::from("")], values))
+        }
         Value::Custom { val, .. } => {
             let materialized = val.to_base_value(span)?;
             collect_input(materialized)
@@ -130,6 +146,35 @@ pub fn collect_input(value: Value) -> Result<(Vec<String>, Vec<Vec<Value>>)> {
     }
 }
 
+/// What a custom value stands for, like the table of a dataframe; other values as they are.
+pub fn base_value(value: &Value) -> Cow<'_, Value> {
+    match value {
+        Value::Custom { val, .. } => match val.to_base_value(value.span()) {
+            Ok(base) => Cow::Owned(base),
+            Err(_) => Cow::Borrowed(value),
+        },
+        value => Cow::Borrowed(value),
+    }
+}
+
+/// How many levels of lists and records are nested in `value`, counting itself.
+pub fn nesting_depth(value: &Value) -> usize {
+    match value {
+        Value::Record { val, .. } => 1 + val.values().map(nesting_depth).max().unwrap_or(0),
+        Value::List { vals, .. } => 1 + vals.iter().map(nesting_depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// The source code of a closure, as it was written.
+pub fn closure_source(engine_state: &EngineState, closure: &Closure) -> String {
+    let block = engine_state.get_block(closure.block_id);
+    match block.span {
+        Some(span) => String::from_utf8_lossy(engine_state.get_span_contents(span)).into_owned(),
+        None => format!("<Closure {}>", closure.block_id.get()),
+    }
+}
+
 fn convert_records_to_dataset(cols: &[String], records: Vec<Value>) -> Vec<Vec<Value>> {
     if !cols.is_empty() {
         create_table_for_record(cols, &records)
@@ -159,7 +204,8 @@ fn create_table_for_record(headers: &[String], items: &[Value]) -> Vec<Vec<Value
     data
 }
 
-fn record_create_row(headers: &[String], item: &Value) -> Vec<Value> {
+/// The row of `item` in a table of records with the columns `headers`.
+pub fn record_create_row(headers: &[String], item: &Value) -> Vec<Value> {
     if let Value::Record { val, .. } = item {
         headers
             .iter()
@@ -183,6 +229,63 @@ pub fn create_map(value: &Value) -> Option<HashMap<String, Value>> {
     )
 }
 
+// what a row shows in a column it doesn't have
+const MISSING_CELL: &str = "❎";
+
 fn unknown_error_value() -> Value {
-    Value::string(String::from("❎"), NuSpan::unknown())
+    Value::string(MISSING_CELL, NuSpan::unknown())
+}
+
+/// Whether the cell stands for a column its row doesn't have, in a table of rows of
+/// different shapes.
+pub fn is_missing_cell(value: &Value) -> bool {
+    matches!(value, Value::String { val, .. } if val == MISSING_CELL)
+        && value.span() == NuSpan::unknown()
+}
+
+/// What numbers of a column are: plain numbers, sizes or durations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Number,
+    Filesize,
+    Duration,
+}
+
+impl Unit {
+    /// `value` as a number, with its unit, if it's one.
+    pub fn of(value: &Value) -> Option<(f64, Self)> {
+        match value {
+            Value::Int { val, .. } => Some((*val as f64, Self::Number)),
+            Value::Float { val, .. } => Some((*val, Self::Number)),
+            Value::Filesize { val, .. } => Some((val.get() as f64, Self::Filesize)),
+            Value::Duration { val, .. } => Some((*val as f64, Self::Duration)),
+            _ => None,
+        }
+    }
+
+    /// `number` as a value of the unit; sizes and durations are rounded.
+    pub fn value(self, number: f64, span: NuSpan) -> Value {
+        match self {
+            Self::Number => Value::float(number, span),
+            Self::Filesize => Value::filesize(number.round() as i64, span),
+            Self::Duration => Value::duration(number.round() as i64, span),
+        }
+    }
+
+    /// `number` as text, with as many decimals as telling it apart from numbers `step` away
+    /// takes.
+    pub fn text(self, number: f64, step: f64, config: &Config) -> String {
+        match self {
+            Self::Number => {
+                let decimals = match step > 0.0 {
+                    true => (1.0 - step.log10().floor()).clamp(0.0, 6.0) as usize,
+                    false => 2,
+                };
+                format!("{number:.decimals$}")
+            }
+            unit => unit
+                .value(number, NuSpan::unknown())
+                .to_abbreviated_string(config),
+        }
+    }
 }
@@ -0,0 +1,252 @@
+use ratatui::{buffer::Buffer, style::Color};
+
+/// Whether the terminal is a console without ANSI support, which can't be relied on for more
+/// than ASCII and 16 colors.
+#[cfg(windows)]
+pub fn is_legacy_console() -> bool {
+    !crossterm::ansi_support::supports_ansi()
+}
+
+#[cfg(not(windows))]
+pub fn is_legacy_console() -> bool {
+    false
+}
+
+/// How many colors a terminal shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColorDepth {
+    /// What the terminal says it has through `COLORTERM` and `TERM`, the way terminfo names
+    /// them, 16 colors in legacy consoles.
+    pub fn detect() -> Self {
+        let colorterm = std::env::var("COLORTERM").ok();
+        let term = std::env::var("TERM").ok();
+        let windows_terminal = std::env::var_os("WT_SESSION").is_some();
+
+        Self::from_env(colorterm.as_deref(), term.as_deref(), windows_terminal)
+    }
+
+    fn from_env(colorterm: Option<&str>, term: Option<&str>, windows_terminal: bool) -> Self {
+        if is_legacy_console() {
+            return Self::Ansi16;
+        }
+
+        if matches!(colorterm, Some("truecolor" | "24bit")) || windows_terminal {
+            return Self::TrueColor;
+        }
+
+        match term.unwrap_or_default() {
+            term if term.ends_with("-direct") || term.ends_with("-truecolor") => Self::TrueColor,
+            term if term.contains("256color") => Self::Ansi256,
+            "" | "dumb" | "linux" | "ansi" | "vt100" | "vt220" | "xterm-color" | "screen"
+            | "cons25" => Self::Ansi16,
+            // most terminals have 256 colors, whatever they're called
+            _ => Self::Ansi256,
+        }
+    }
+
+    /// Read `explore.colors`: `truecolor`, `256` or `16`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "truecolor" | "24bit" => Some(Self::TrueColor),
+            "256" => Some(Self::Ansi256),
+            "16" => Some(Self::Ansi16),
+            _ => None,
+        }
+    }
+}
+
+/// Rewrite a drawn frame for the terminal: with ASCII in place of box drawing and other
+/// symbols if `ascii`, and with every color brought down to the ones it has.
+pub fn downgrade(buf: &mut Buffer, ascii: bool, colors: ColorDepth) {
+    let quantize: fn(Color) -> Color = match colors {
+        ColorDepth::TrueColor => |color| color,
+        ColorDepth::Ansi256 => indexed_color,
+        ColorDepth::Ansi16 => basic_color,
+    };
+
+    for cell in &mut buf.content {
+        if ascii && let Some(symbol) = ascii_symbol(cell.symbol()) {
+            cell.set_symbol(symbol);
+        }
+        cell.fg = quantize(cell.fg);
+        cell.bg = quantize(cell.bg);
+    }
+}
+
+fn ascii_symbol(symbol: &str) -> Option<&'static str> {
+    let mut chars = symbol.chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+
+    let ascii = match c {
+        '─' | '━' | '═' | '┄' | '┈' | '╌' => "-",
+        '│' | '┃' | '║' | '┆' | '┊' | '╎' => "|",
+        // corners, tees and crosses
+        '\u{2500}'..='\u{257F}' => "+",
+        '▸' | '▶' | '→' => ">",
+        '◂' | '◀' | '←' => "<",
+        '↑' => "^",
+        '↓' => "v",
+        '✓' => "*",
+        '…' => "~",
+        '█' | '▌' | '▐' => "#",
+        // the spinner
+        '\u{2800}'..='\u{28FF}' => "*",
+        _ => return None,
+    };
+
+    Some(ascii)
+}
+
+// the usual values of the 16 basic colors
+const BASIC_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (128, 0, 0)),
+    (Color::Green, (0, 128, 0)),
+    (Color::Yellow, (128, 128, 0)),
+    (Color::Blue, (0, 0, 128)),
+    (Color::Magenta, (128, 0, 128)),
+    (Color::Cyan, (0, 128, 128)),
+    (Color::Gray, (192, 192, 192)),
+    (Color::DarkGray, (128, 128, 128)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (0, 0, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+// the levels of a channel in the 6x6x6 color cube
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn indexed_color(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+
+    let nearest_level = |n: u8| {
+        (0..6)
+            .min_by_key(|&i| CUBE_LEVELS[i].abs_diff(n))
+            .unwrap_or_default()
+    };
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+
+    // the 24 grays go from 8 to 238, by 10
+    let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let gray_index = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + gray_index * 10;
+
+    let distance = |(r2, g2, b2): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+        d(r, r2) + d(g, g2) + d(b, b2)
+    };
+
+    if distance((gray, gray, gray)) < distance(cube) {
+        Color::Indexed(232 + gray_index)
+    } else {
+        Color::Indexed(16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8)
+    }
+}
+
+fn basic_color(color: Color) -> Color {
+    let (r, g, b) = match color {
+        Color::Indexed(i) if i < 16 => return BASIC_COLORS[i as usize].0,
+        Color::Indexed(i) if i < 232 => {
+            // the 6x6x6 color cube
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let i = i - 16;
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        Color::Indexed(i) => {
+            let gray = 8 + (i - 232) * 10;
+            (gray, gray, gray)
+        }
+        Color::Rgb(r, g, b) => (r, g, b),
+        color => return color,
+    };
+
+    let distance = |(r2, g2, b2): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+        d(r, r2) + d(g, g2) + d(b, b2)
+    };
+
+    BASIC_COLORS
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map_or(color, |(basic, _)| *basic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn frame_is_downgraded() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buf[(0, 0)].set_symbol("┼").set_fg(Color::Rgb(250, 10, 10));
+        buf[(1, 0)].set_symbol("│").set_bg(Color::Indexed(21));
+        buf[(2, 0)].set_symbol("a").set_fg(Color::Indexed(244));
+        buf[(3, 0)].set_symbol("⠋").set_fg(Color::Reset);
+
+        downgrade(&mut buf, true, ColorDepth::Ansi16);
+
+        assert_eq!(buf[(0, 0)].symbol(), "+");
+        assert_eq!(buf[(0, 0)].fg, Color::LightRed);
+        assert_eq!(buf[(1, 0)].symbol(), "|");
+        assert_eq!(buf[(1, 0)].bg, Color::LightBlue);
+        assert_eq!(buf[(2, 0)].symbol(), "a");
+        assert_eq!(buf[(2, 0)].fg, Color::DarkGray);
+        assert_eq!(buf[(3, 0)].symbol(), "*");
+        assert_eq!(buf[(3, 0)].fg, Color::Reset);
+    }
+
+    #[test]
+    fn rgb_colors_are_brought_to_256() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buf[(0, 0)].set_symbol("│").set_fg(Color::Rgb(255, 0, 0));
+        buf[(1, 0)].set_bg(Color::Rgb(38, 38, 38));
+        buf[(2, 0)]
+            .set_fg(Color::Rgb(90, 130, 180))
+            .set_bg(Color::Blue);
+
+        downgrade(&mut buf, false, ColorDepth::Ansi256);
+
+        assert_eq!(buf[(0, 0)].symbol(), "│");
+        assert_eq!(buf[(0, 0)].fg, Color::Indexed(196));
+        assert_eq!(buf[(1, 0)].bg, Color::Indexed(235));
+        assert_eq!(buf[(2, 0)].fg, Color::Indexed(67));
+        assert_eq!(buf[(2, 0)].bg, Color::Blue);
+    }
+
+    #[test]
+    fn color_depth_is_detected() {
+        let detect = ColorDepth::from_env;
+
+        assert_eq!(
+            detect(Some("truecolor"), Some("xterm"), false),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            detect(None, Some("xterm-256color"), false),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(
+            detect(None, Some("tmux-direct"), false),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect(None, Some("linux"), false), ColorDepth::Ansi16);
+        assert_eq!(detect(None, None, true), ColorDepth::TrueColor);
+        assert_eq!(detect(None, Some("alacritty"), false), ColorDepth::Ansi256);
+    }
+}
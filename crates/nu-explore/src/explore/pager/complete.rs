@@ -0,0 +1,215 @@
+//! Completion of the words typed after `:`, on <Tab>: the names of the commands, their
+//! flags, the columns `:sort` and `:filter` take and the paths `:save` writes to.
+
+use super::super::{
+    commands::{FilterCmd, SaveCmd, SortCmd},
+    registry::CommandRegistry,
+};
+use std::{fs, path::PathBuf};
+
+/// The commands of a [`CommandRegistry`], by their names and aliases, with the directory
+/// paths are relative to.
+#[derive(Debug, Clone, Default)]
+pub struct Completer {
+    commands: Vec<Entry>,
+    cwd: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    // the name it's typed by, the command's or an alias of it
+    name: String,
+    command: String,
+    flags: &'static [&'static str],
+}
+
+impl Completer {
+    pub fn new(registry: &CommandRegistry, cwd: &str) -> Self {
+        let mut commands: Vec<_> = registry
+            .get_commands()
+            .map(|command| Entry {
+                name: command.name().to_string(),
+                command: command.name().to_string(),
+                flags: command.flags(),
+            })
+            .collect();
+
+        let aliases: Vec<_> = registry
+            .get_aliases()
+            .filter_map(|(alias, name)| {
+                let command = commands.iter().find(|entry| entry.name == name)?;
+                Some(Entry {
+                    name: alias.to_string(),
+                    ..command.clone()
+                })
+            })
+            .collect();
+        commands.extend(aliases);
+
+        // the flags of a macro are those of the command its typed arguments are added to
+        let macros: Vec<_> = registry
+            .get_macros()
+            .map(|(name, lines)| {
+                let last = lines.last().map_or("", |line| {
+                    line.split_once(' ').map_or(line.as_str(), |(name, _)| name)
+                });
+                let command = commands.iter().find(|entry| entry.name == last);
+                Entry {
+                    name: name.to_string(),
+                    command: command.map_or(String::new(), |entry| entry.command.clone()),
+                    flags: command.map(|entry| entry.flags).unwrap_or_default(),
+                }
+            })
+            .collect();
+        commands.extend(macros);
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            commands,
+            cwd: PathBuf::from(cwd),
+        }
+    }
+
+    /// Where the word typed last in `line` starts, which is completed.
+    pub fn word_start(line: &str) -> usize {
+        line.rfind(' ').map_or(0, |space| space + 1)
+    }
+
+    /// What the last word of `line` may be, starting with what's typed of it; `columns` are
+    /// the names of the columns of the table shown.
+    pub fn words(&self, line: &str, columns: impl FnOnce() -> Vec<String>) -> Vec<String> {
+        let start = Self::word_start(line);
+        let word = &line[start..];
+
+        let candidates = match line.split_once(' ') {
+            None => self
+                .commands
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect(),
+            Some((name, _)) => {
+                let Some(entry) = self.commands.iter().find(|entry| entry.name == name) else {
+                    return Vec::new();
+                };
+
+                let before: Vec<_> = line[..start].split_whitespace().skip(1).collect();
+                if word.starts_with('-') {
+                    entry.flags.iter().map(|flag| flag.to_string()).collect()
+                } else if is_column_argument(&entry.command, &before) {
+                    columns()
+                        .iter()
+                        .map(|column| FilterCmd::quote_column(column))
+                        .collect()
+                } else if entry.command == SaveCmd::NAME {
+                    self.paths(word)
+                } else {
+                    Vec::new()
+                }
+            }
+        };
+
+        // quoted columns are typed without their quote
+        candidates
+            .into_iter()
+            .filter(|candidate| {
+                candidate.starts_with(word)
+                    || candidate
+                        .strip_prefix('"')
+                        .is_some_and(|unquoted| unquoted.starts_with(word))
+            })
+            .collect()
+    }
+
+    // the entries of the directory `word` is in, hidden ones only once a `.` is typed;
+    // directories end with a `/`, to go on into them
+    fn paths(&self, word: &str) -> Vec<String> {
+        let (dir, name) = match word.rfind(['/', std::path::MAIN_SEPARATOR]) {
+            Some(separator) => word.split_at(separator + 1),
+            None => ("", word),
+        };
+        let Ok(entries) = fs::read_dir(self.cwd.join(dir)) else {
+            return Vec::new();
+        };
+
+        let mut paths: Vec<_> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                if file_name.starts_with('.') && !name.starts_with('.') {
+                    return None;
+                }
+                let slash = match entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                    true => "/",
+                    false => "",
+                };
+                Some(format!("{dir}{file_name}{slash}"))
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+}
+
+// whether the word after `before` is the column of `:sort` or `:filter`, the first argument
+// after their flags
+fn is_column_argument(command: &str, before: &[&str]) -> bool {
+    match command {
+        SortCmd::NAME => before.is_empty(),
+        FilterCmd::NAME => matches!(before, [] | ["--edit", _]),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explore::commands::QuitCmd;
+
+    fn completer(cwd: &str) -> Completer {
+        let mut registry = CommandRegistry::new();
+        registry.register_command_reactive(SortCmd::default());
+        registry.register_command_reactive(FilterCmd::default());
+        registry.register_command_reactive(SaveCmd::default());
+        registry.register_command_reactive(QuitCmd);
+        registry.create_aliases("q", QuitCmd::NAME);
+        registry.define_macro("errs", vec!["sort time".to_string(), "filter".to_string()]);
+        Completer::new(&registry, cwd)
+    }
+
+    #[test]
+    fn complete_commands_flags_and_columns() {
+        let completer = completer(".");
+        let columns = || vec!["name".to_string(), "size".to_string(), "my col".to_string()];
+
+        assert_eq!(completer.words("s", columns), ["save", "sort"]);
+        assert_eq!(completer.words("q", columns), ["q", "quit"]);
+        assert_eq!(completer.words("e", columns), ["errs"]);
+        // what's typed after a macro goes to its last command
+        assert_eq!(completer.words("errs --", columns), ["--edit"]);
+        assert_eq!(completer.words("sort --", columns), ["--nulls"]);
+        assert_eq!(completer.words("sort s", columns), ["size"]);
+        assert_eq!(completer.words("sort ", columns).len(), 3);
+        assert_eq!(completer.words("filter --edit 2 n", columns), ["name"]);
+        assert_eq!(completer.words("filter m", columns), ["\"my col\""]);
+        // the values of the column are completed from the data instead
+        assert!(completer.words("sort size d", columns).is_empty());
+        assert!(completer.words("nope ", columns).is_empty());
+    }
+
+    #[test]
+    fn complete_paths() {
+        let dir = std::env::temp_dir().join(format!("explore-complete-{}", std::process::id()));
+        fs::create_dir_all(dir.join("data")).unwrap();
+        fs::write(dir.join("data").join("ls.csv"), "").unwrap();
+        fs::write(dir.join("data.json"), "").unwrap();
+        fs::write(dir.join(".hidden"), "").unwrap();
+
+        let completer = completer(&dir.to_string_lossy());
+        let columns = Vec::new;
+        assert_eq!(completer.words("save da", columns), ["data.json", "data/"]);
+        assert_eq!(completer.words("save data/", columns), ["data/ls.csv"]);
+        assert_eq!(completer.words("save --force .", columns), [".hidden"]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
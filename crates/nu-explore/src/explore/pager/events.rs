@@ -1,65 +1,261 @@
-use std::{io::Result, time::Duration};
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    io::Result,
+    time::Duration,
+};
 
-use nu_utils::time::Instant;
+use crossterm::event::{Event, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind, poll, read};
+use ratatui::layout::Rect;
 
-use crossterm::event::{Event, KeyEvent, KeyEventKind, poll, read};
+use super::super::deterministic;
+use super::trace::{self, Trace, TraceEvent};
 
 pub struct UIEvents {
     tick_rate: Duration,
+    // how long the terminal has to keep its size before a frame is drawn in it, so a pane
+    // being dragged in a multiplexer is drawn once it stops rather than at every step
+    settle_time: Duration,
+    // the size of the terminal, as last reported by a resize event
+    screen: Cell<(u16, u16)>,
+    // the size a replayed trace was recorded in
+    replayed: Cell<Option<(u16, u16)>>,
+    // whether the size to draw in changed since it was last taken
+    resized: Cell<bool>,
+    // events of a replayed trace, handed out before any from the terminal
+    replay: RefCell<VecDeque<TraceEvent>>,
+    // the events read so far, when the session is recorded
+    recording: Option<RefCell<Trace>>,
+}
+
+/// What the user did: pressed a key, or clicked, dragged or scrolled with the mouse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
 }
 
 pub struct Cfg {
     pub tick_rate: Duration,
+    pub settle_time: Duration,
 }
 
 impl Default for Cfg {
     fn default() -> Cfg {
         Cfg {
             tick_rate: Duration::from_millis(250),
+            settle_time: Duration::from_millis(40),
         }
     }
 }
 
 impl UIEvents {
-    pub fn new() -> UIEvents {
-        UIEvents::with_config(Cfg::default())
+    /// Read the events of a terminal of the given size.
+    pub fn new(size: (u16, u16)) -> UIEvents {
+        UIEvents::with_config(size, Cfg::default())
     }
 
-    pub fn with_config(config: Cfg) -> UIEvents {
+    pub fn with_config(size: (u16, u16), config: Cfg) -> UIEvents {
         UIEvents {
             tick_rate: config.tick_rate,
+            settle_time: config.settle_time,
+            screen: Cell::new(size),
+            replayed: Cell::new(None),
+            resized: Cell::new(false),
+            replay: RefCell::default(),
+            recording: None,
         }
     }
 
-    /// Read the next key press event, dropping any other preceding events. Returns None if no
-    /// relevant event is found within the configured tick_rate.
-    pub fn next_key_press(&self) -> Result<Option<KeyEvent>> {
-        let deadline = Instant::now() + self.tick_rate;
+    /// Play the events of a trace first, one key press per call.
+    pub fn replay(&mut self, trace: Trace) {
+        self.replayed.set(Some(trace.size));
+        self.replay = RefCell::new(trace.events.into());
+    }
+
+    /// Keep the events read from now on, starting from a terminal of the given size.
+    pub fn record(&mut self, size: (u16, u16)) {
+        self.recording = Some(RefCell::new(Trace::new(size)));
+    }
+
+    /// The events recorded, if the session is.
+    pub fn into_trace(self) -> Option<Trace> {
+        self.recording.map(RefCell::into_inner)
+    }
+
+    /// Note a command run, in the recording if there's one.
+    pub fn record_command(&self, command: &str) {
+        self.push(TraceEvent::Command(command.to_string()));
+    }
+
+    /// The area to draw in: the whole terminal, or the size of a replayed trace as far as
+    /// the terminal allows.
+    ///
+    /// It's the size from the last resize event rather than the one asked from the terminal,
+    /// which can still be the old one while a multiplexer is resizing its panes.
+    pub fn area(&self) -> Rect {
+        let (columns, rows) = self.screen.get();
+        let (width, height) = self.replayed.get().unwrap_or((columns, rows));
+
+        Rect::new(0, 0, width.min(columns), height.min(rows))
+    }
+
+    /// The area to draw in, if it changed since the last call; any number of resizes in
+    /// between come down to one.
+    pub fn take_resize(&self) -> Option<Rect> {
+        self.resized.take().then(|| self.area())
+    }
+
+    /// Read the next key press or mouse event, dropping any other preceding events but noting
+    /// resizes. Returns None if no relevant event is found within the configured tick_rate, or
+    /// once the terminal has kept its new size for the settle time after a resize.
+    pub fn next_input(&self) -> Result<Option<Input>> {
+        if let Some(key) = self.next_replayed_key_press() {
+            return Ok(Some(Input::Key(key)));
+        }
+
+        let tick = deterministic::now() + self.tick_rate;
+        let mut deadline = tick;
         loop {
-            let timeout = deadline.saturating_duration_since(Instant::now());
+            let timeout = deadline.saturating_duration_since(deterministic::now());
             if !poll(timeout)? {
+                deterministic::advance(timeout);
                 return Ok(None);
             }
-            if let Event::Key(event) = read()?
-                && event.kind == KeyEventKind::Press
-            {
-                return Ok(Some(event));
+            if let Some(input) = self.read_event(read()?) {
+                return Ok(Some(input));
+            }
+            // drawn when the resizes stop, or at the next tick if they go on
+            if self.resized.get() {
+                deadline = tick.min(deterministic::now() + self.settle_time);
             }
         }
     }
 
-    /// Read the next key press event, dropping any other preceding events. If no key event is
-    /// available, returns immediately.
-    pub fn try_next_key_press(&self) -> Result<Option<KeyEvent>> {
+    /// Read the next key press or mouse event, dropping any other preceding events but noting
+    /// resizes. If none is available, returns immediately.
+    pub fn try_next_input(&self) -> Result<Option<Input>> {
+        // a frame is drawn after each replayed key, as when they were typed one by one
+        if !self.replay.borrow().is_empty() {
+            return Ok(None);
+        }
+
         loop {
             if !poll(Duration::ZERO)? {
                 return Ok(None);
             }
-            if let Event::Key(event) = read()?
-                && event.kind == KeyEventKind::Press
-            {
-                return Ok(Some(event));
+            if let Some(input) = self.read_event(read()?) {
+                return Ok(Some(input));
+            }
+        }
+    }
+
+    fn next_replayed_key_press(&self) -> Option<KeyEvent> {
+        loop {
+            let event = self.replay.borrow_mut().pop_front()?;
+            match event {
+                TraceEvent::Key(key) => {
+                    self.push(TraceEvent::Key(key));
+                    return Some(key);
+                }
+                TraceEvent::Resize(width, height) => {
+                    self.push(TraceEvent::Resize(width, height));
+                    self.replayed.set(Some((width, height)));
+                    self.resized.set(true);
+                }
+                // run again by the keys that typed them
+                TraceEvent::Command(_) => {}
             }
         }
     }
+
+    fn read_event(&self, event: Event) -> Option<Input> {
+        match event {
+            Event::Key(event) if event.kind == KeyEventKind::Press => {
+                if trace::is_recorded(event.code) {
+                    self.push(TraceEvent::Key(KeyEvent::new(event.code, event.modifiers)));
+                }
+                Some(Input::Key(event))
+            }
+            // the pointer is reported at every move, which does nothing; the rest isn't
+            // recorded, a click replayed in another layout would land elsewhere
+            Event::Mouse(event) if event.kind != MouseEventKind::Moved => Some(Input::Mouse(event)),
+            // the terminal keeps the size it was given
+            Event::Resize(..) if deterministic::is_enabled() => None,
+            Event::Resize(width, height) => {
+                if self.screen.replace((width, height)) != (width, height) {
+                    self.push(TraceEvent::Resize(width, height));
+                    self.resized.set(true);
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn push(&self, event: TraceEvent) {
+        if let Some(trace) = &self.recording {
+            trace.borrow_mut().events.push(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resizes_are_coalesced() {
+        let events = UIEvents::new((80, 24));
+
+        for (width, height) in [(81, 24), (95, 30), (120, 40)] {
+            assert_eq!(events.read_event(Event::Resize(width, height)), None);
+        }
+        assert_eq!(events.take_resize(), Some(Rect::new(0, 0, 120, 40)));
+        assert_eq!(events.take_resize(), None);
+
+        // a size it already has isn't a resize
+        events.read_event(Event::Resize(120, 40));
+        assert_eq!(events.take_resize(), None);
+    }
+
+    #[test]
+    fn replays_are_drawn_within_the_terminal() {
+        let mut events = UIEvents::new((100, 30));
+        events.replay(Trace {
+            size: (80, 24),
+            events: vec![TraceEvent::Resize(120, 20)],
+        });
+        assert_eq!(events.area(), Rect::new(0, 0, 80, 24));
+
+        assert_eq!(events.next_replayed_key_press(), None);
+        assert_eq!(events.take_resize(), Some(Rect::new(0, 0, 100, 20)));
+
+        events.read_event(Event::Resize(90, 30));
+        assert_eq!(events.take_resize(), Some(Rect::new(0, 0, 90, 20)));
+    }
+
+    #[test]
+    fn mouse_moves_are_dropped() {
+        use crossterm::event::{KeyModifiers, MouseButton};
+
+        let events = UIEvents::new((80, 24));
+        let mouse = |kind| MouseEvent {
+            kind,
+            column: 10,
+            row: 5,
+            modifiers: KeyModifiers::NONE,
+        };
+
+        assert_eq!(
+            events.read_event(Event::Mouse(mouse(MouseEventKind::Moved))),
+            None
+        );
+
+        let click = mouse(MouseEventKind::Down(MouseButton::Left));
+        assert_eq!(
+            events.read_event(Event::Mouse(click)),
+            Some(Input::Mouse(click))
+        );
+    }
 }
@@ -0,0 +1,278 @@
+//! Following a background job, for `explore --follow <id>`: the values it outputs, and those
+//! it sends to explore's job with `job send 0 --tag <id>`, are added to the table as they come,
+//! until the job ends.
+
+use super::super::{l10n::tr, nu_common::record_create_row};
+use super::report::{Report, Severity};
+use nu_protocol::{
+    JobId, ShellError, Span, Value,
+    engine::{EngineState, FilterTag, Job, ThreadJob},
+};
+use std::sync::mpsc::Receiver;
+
+/// The values a job output or sent so far and whether it's still running.
+#[derive(Debug)]
+pub struct JobFollower {
+    id: JobId,
+    // the job, kept to tell how it ended once it's gone from the jobs
+    job: Option<ThreadJob>,
+    output: Option<Receiver<Value>>,
+    values: Vec<Value>,
+    // the fields of the values, all records so far; `None` once anything else came
+    fields: Option<Vec<String>>,
+    // how many of `values` were taken as rows, and the columns they were taken in
+    taken: usize,
+    columns: Vec<String>,
+    done: bool,
+    // the error the job failed with
+    failure: Option<ShellError>,
+}
+
+impl JobFollower {
+    /// Follow the job `id`, after the values explore was given.
+    pub fn new(engine_state: &EngineState, id: JobId, input: Value) -> Self {
+        let job = engine_state
+            .jobs
+            .lock()
+            .ok()
+            .and_then(|jobs| match jobs.lookup(id) {
+                Some(Job::Thread(job)) => Some(job.clone()),
+                _ => None,
+            });
+        let mut follower = Self {
+            id,
+            output: job.as_ref().map(ThreadJob::follow),
+            job,
+            values: Vec::new(),
+            fields: Some(Vec::new()),
+            taken: 0,
+            columns: Vec::new(),
+            done: false,
+            failure: None,
+        };
+        follower.push(input);
+        follower
+    }
+
+    /// Take what the job output or sent since this was last asked; it doesn't wait. Returns
+    /// whether anything changed, new values or the job ending.
+    ///
+    /// Only the mail tagged with the id of the job is taken, the rest is left for `job recv`.
+    pub fn receive(&mut self, engine_state: &EngineState) -> bool {
+        if self.done {
+            return false;
+        }
+
+        // a job removes itself once it output all it had, so it's asked first
+        let running = engine_state
+            .jobs
+            .lock()
+            .is_ok_and(|jobs| jobs.lookup(self.id).is_some());
+
+        let count = self.values.len();
+        let output: Vec<Value> = self.output.iter().flat_map(Receiver::try_iter).collect();
+        output.into_iter().for_each(|value| self.push(value));
+
+        let tag = self.id.get() as FilterTag;
+        if let Ok(mut mailbox) = engine_state.current_job.mailbox.lock() {
+            while let Ok(data) = mailbox.try_recv(Some(tag)) {
+                let value = data
+                    .into_value(Span::unknown())
+                    .unwrap_or_else(|err| Value::error(err, Span::unknown()));
+                self.push(value);
+            }
+        }
+
+        if !running {
+            self.done = true;
+            self.failure = self
+                .job
+                .as_ref()
+                .and_then(ThreadJob::outcome)
+                .and_then(Result::err);
+        }
+        self.values.len() != count || self.done
+    }
+
+    fn push(&mut self, value: Value) {
+        match value {
+            Value::Nothing { .. } => {}
+            Value::List { vals, .. } => vals.into_iter().for_each(|value| self.push(value)),
+            value => self.values.push(value),
+        }
+    }
+
+    /// The columns and rows of all the values so far.
+    pub fn rows(&mut self) -> (Vec<String>, Vec<Vec<Value>>) {
+        self.taken = 0;
+        self.new_rows()
+    }
+
+    /// The columns and rows of the values that came since the rows were last taken, or `None`
+    /// if they change the columns the rows taken earlier are in.
+    pub fn take_rows(&mut self) -> Option<(Vec<String>, Vec<Vec<Value>>)> {
+        let columns = self.columns.clone();
+        let (now, rows) = self.new_rows();
+        (now == columns).then_some((now, rows))
+    }
+
+    // the rows of the values not taken yet, in the columns of all of them, as
+    // `collect_input` reads a list
+    fn new_rows(&mut self) -> (Vec<String>, Vec<Vec<Value>>) {
+        let new = &self.values[self.taken..];
+        let mut fields = self.fields.take();
+        for value in new {
+            fields = match (fields, value) {
+                (Some(mut fields), Value::Record { val, .. }) => {
+                    for column in val.columns() {
+                        if !fields.contains(column) {
+                            fields.push(column.clone());
+                        }
+                    }
+                    Some(fields)
+                }
+                _ => None,
+            };
+        }
+
+        let rows = match fields.as_deref() {
+            Some(fields) if !fields.is_empty() => new
+                .iter()
+                .map(|value| record_create_row(fields, value))
+                .collect(),
+            _ => new.iter().map(|value| vec![value.clone()]).collect(),
+        };
+        self.columns = match fields.as_deref() {
+            Some(fields) if !fields.is_empty() => fields.to_vec(),
+            _ if self.values.is_empty() => Vec::new(),
+            _ => vec![String::new()],
+        };
+        self.fields = fields;
+        self.taken = self.values.len();
+
+        (self.columns.clone(), rows)
+    }
+
+    /// How the job is doing, for the status bar.
+    pub fn report(&self) -> Report {
+        let id = self.id.get();
+        let count = self.values.len();
+        match (&self.failure, self.done) {
+            (Some(err), _) => Report::error(tr!("Job {id} failed: {err}", id = id, err = err)),
+            (None, true) => Report::success(tr!(
+                "Job {id} is done, {count} rows",
+                id = id,
+                count = count
+            )),
+            (None, false) => Report::message(
+                tr!("Following job {id}, {count} rows", id = id, count = count),
+                Severity::Info,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::{
+        ListStream, PipelineData, Signals, engine::Job, shell_error::generic::GenericError,
+    };
+    use std::sync::mpsc;
+
+    fn send(engine_state: &EngineState, tag: Option<FilterTag>, value: Value) {
+        let mail = (tag, PipelineData::value(value, None));
+        engine_state.root_job_sender.send(mail).unwrap();
+    }
+
+    fn spawn(engine_state: &EngineState) -> (JobId, ThreadJob) {
+        let (sender, _) = mpsc::channel();
+        let job = ThreadJob::new(Signals::empty(), None, sender);
+        let id = engine_state
+            .jobs
+            .lock()
+            .unwrap()
+            .add_job(Job::Thread(job.clone()));
+        (id, job)
+    }
+
+    fn output(job: &ThreadJob, values: Vec<Value>) {
+        let stream = ListStream::new(values.into_iter(), Span::test_data(), Signals::empty());
+        job.output(PipelineData::list_stream(stream, None)).unwrap();
+    }
+
+    fn record(fields: &[&str]) -> Value {
+        let fields = fields
+            .iter()
+            .map(|field| (field.to_string(), Value::test_int(1)))
+            .collect();
+        Value::test_record(fields)
+    }
+
+    #[test]
+    fn rows_come_in_until_the_job_ends() {
+        let engine_state = EngineState::new();
+        let (id, job) = spawn(&engine_state);
+
+        let mut follower = JobFollower::new(&engine_state, id, Value::test_nothing());
+        assert!(!follower.receive(&engine_state));
+
+        output(&job, vec![Value::test_int(1)]);
+        send(
+            &engine_state,
+            Some(id.get() as FilterTag),
+            Value::test_list(vec![Value::test_int(2), Value::test_int(3)]),
+        );
+        // mail the job didn't tag is left for `job recv`
+        send(&engine_state, None, Value::test_string("other"));
+        assert!(follower.receive(&engine_state));
+        assert_eq!(follower.rows().1.len(), 3);
+        assert!(matches!(follower.report().level, Severity::Info));
+        let mut mailbox = engine_state.current_job.mailbox.lock().unwrap();
+        assert!(mailbox.try_recv(None).is_ok());
+        drop(mailbox);
+
+        engine_state.jobs.lock().unwrap().remove_job(id);
+        job.finish(Ok(()));
+        assert!(follower.receive(&engine_state));
+        assert!(matches!(follower.report().level, Severity::Success));
+
+        // nothing is taken once it ended
+        output(&job, vec![Value::test_int(5)]);
+        assert!(!follower.receive(&engine_state));
+    }
+
+    #[test]
+    fn failing_job_is_reported() {
+        let engine_state = EngineState::new();
+        let (id, job) = spawn(&engine_state);
+        let mut follower = JobFollower::new(&engine_state, id, Value::test_nothing());
+
+        let error = ShellError::Generic(GenericError::new_internal("bad", "it went wrong"));
+        job.finish(Err(error));
+        engine_state.jobs.lock().unwrap().remove_job(id);
+        assert!(follower.receive(&engine_state));
+        assert!(matches!(follower.report().level, Severity::Err));
+    }
+
+    #[test]
+    fn new_rows_are_taken_alone() {
+        let engine_state = EngineState::new();
+        let (id, job) = spawn(&engine_state);
+        let mut follower = JobFollower::new(&engine_state, id, record(&["a"]));
+        let (columns, rows) = follower.rows();
+        assert_eq!((columns.len(), rows.len()), (1, 1));
+
+        output(&job, vec![record(&["a"]), record(&["a"])]);
+        follower.receive(&engine_state);
+        let (columns, rows) = follower.take_rows().unwrap();
+        assert_eq!((columns.len(), rows.len()), (1, 2));
+
+        // a new field changes the columns of all the rows
+        output(&job, vec![record(&["a", "b"])]);
+        follower.receive(&engine_state);
+        assert!(follower.take_rows().is_none());
+        let (columns, rows) = follower.rows();
+        assert_eq!((columns.len(), rows.len()), (2, 4));
+    }
+}
@@ -0,0 +1,168 @@
+//! Scoring of fuzzy matches, in the manner of fzf: the characters of the pattern have to come
+//! in order, the closer together and the nearer the start of words, the better.
+
+use std::ops::Range;
+
+const SCORE_MATCH: i64 = 16;
+const GAP_START: i64 = -3;
+const GAP_EXTENSION: i64 = -1;
+// a character at the start of a word
+const BONUS_BOUNDARY: i64 = 8;
+// an uppercase character after a lowercase one, or a digit after a letter
+const BONUS_CAMEL: i64 = 7;
+// a character right after the one matched before
+const BONUS_CONSECUTIVE: i64 = 4;
+// the bonus of the first character of the pattern counts this many times
+const FIRST_MULTIPLIER: i64 = 2;
+// of equal matches the shorter text is closer, each character weighing this little
+const LENGTH_WEIGHT: i64 = 1000;
+
+/// A fuzzy match of a pattern in a text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fuzzy {
+    /// The higher, the closer the text is to the pattern
+    pub score: i64,
+    /// The bytes of the text matched, consecutive characters joined
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// Match `pattern` in `text`, if all its characters are there in order; case is ignored
+/// unless the pattern has uppercase characters.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<Fuzzy> {
+    let case_sensitive = pattern.chars().any(char::is_uppercase);
+    let fold = |c: char| match case_sensitive {
+        true => c,
+        false => c.to_lowercase().next().unwrap_or(c),
+    };
+
+    let pattern: Vec<char> = pattern.chars().map(fold).collect();
+    if pattern.is_empty() {
+        return None;
+    }
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let folded: Vec<char> = chars.iter().map(|&(_, c)| fold(c)).collect();
+
+    // the first window holding the whole pattern, narrowed from its end back
+    let mut next = 0;
+    let mut end = None;
+    for (i, &c) in folded.iter().enumerate() {
+        if c == pattern[next] {
+            next += 1;
+            if next == pattern.len() {
+                end = Some(i);
+                break;
+            }
+        }
+    }
+    let end = end?;
+
+    let mut start = end;
+    let mut next = pattern.len();
+    for i in (0..=end).rev() {
+        if folded[i] == pattern[next - 1] {
+            next -= 1;
+            if next == 0 {
+                start = i;
+                break;
+            }
+        }
+    }
+
+    // the characters matched in the window, the earliest ones
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut next = 0;
+    for (i, &c) in folded.iter().enumerate().take(end + 1).skip(start) {
+        if next < pattern.len() && c == pattern[next] {
+            positions.push(i);
+            next += 1;
+        }
+    }
+
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    for (n, &i) in positions.iter().enumerate() {
+        let mut bonus = bonus(&chars, i);
+        if n == 0 {
+            bonus *= FIRST_MULTIPLIER;
+        }
+
+        score += SCORE_MATCH;
+        match previous {
+            Some(previous) if previous + 1 == i => bonus = bonus.max(BONUS_CONSECUTIVE),
+            Some(previous) => score += GAP_START + GAP_EXTENSION * (i - previous - 2) as i64,
+            None => {}
+        }
+        score += bonus;
+        previous = Some(i);
+    }
+
+    let length = (chars.len() as i64).min(LENGTH_WEIGHT - 1);
+    let score = score * LENGTH_WEIGHT - length;
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for i in positions {
+        let (byte, c) = chars[i];
+        match ranges.last_mut() {
+            Some(last) if last.end == byte => last.end = byte + c.len_utf8(),
+            _ => ranges.push(byte..byte + c.len_utf8()),
+        }
+    }
+
+    Some(Fuzzy { score, ranges })
+}
+
+fn bonus(chars: &[(usize, char)], i: usize) -> i64 {
+    let c = chars[i].1;
+    if !c.is_alphanumeric() {
+        return 0;
+    }
+
+    match i.checked_sub(1).map(|i| chars[i].1) {
+        None => BONUS_BOUNDARY,
+        Some(before) if !before.is_alphanumeric() => BONUS_BOUNDARY,
+        Some(before) if before.is_lowercase() && c.is_uppercase() => BONUS_CAMEL,
+        Some(before) if !before.is_numeric() && c.is_numeric() => BONUS_CAMEL,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+
+    fn score(pattern: &str, text: &str) -> i64 {
+        fuzzy_match(pattern, text).unwrap().score
+    }
+
+    #[test]
+    fn characters_in_order() {
+        let found = fuzzy_match("nsh", "nushell").unwrap();
+        assert_eq!(found.ranges, [0..1, 2..4]);
+
+        assert_eq!(fuzzy_match("hsn", "nushell"), None);
+        assert_eq!(fuzzy_match("", "nushell"), None);
+
+        // smart case
+        assert!(fuzzy_match("NU", "nushell").is_none());
+        assert!(fuzzy_match("nu", "NUSHELL").is_some());
+
+        // by bytes, past wide characters
+        let found = fuzzy_match("ab", "ä-ab").unwrap();
+        assert_eq!(found.ranges, [3..5]);
+    }
+
+    #[test]
+    fn closer_matches_score_higher() {
+        // together rather than apart
+        assert!(score("abc", "xabcx") > score("abc", "xaxbxcx"));
+        // at the start of words
+        assert!(score("fb", "foo_bar") > score("fb", "xfxbxx"));
+        assert!(score("fb", "fooBar") > score("fb", "foobar"));
+        // the shorter of equal matches
+        assert!(score("abc", "abc") > score("abc", "abcdef"));
+        // the narrowest window, not the first one
+        let found = fuzzy_match("ab", "a---ab").unwrap();
+        assert_eq!(found.ranges, [4..6]);
+    }
+}
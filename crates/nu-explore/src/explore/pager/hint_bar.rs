@@ -0,0 +1,92 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::{Block, Widget},
+};
+
+use super::super::{
+    nu_common::{NuStyle, string_width},
+    views::{
+        KeyHint,
+        util::{nu_style_to_tui, set_span},
+    },
+};
+
+/// A nano like line listing the keys which are most useful right now
+pub struct HintBar<'a> {
+    hints: &'a [KeyHint],
+    key_style: Style,
+    action_style: Style,
+    background_style: Style,
+}
+
+impl<'a> HintBar<'a> {
+    pub fn new(hints: &'a [KeyHint], text_style: NuStyle, background_style: NuStyle) -> Self {
+        let text_style = nu_style_to_tui(text_style);
+        Self {
+            hints,
+            key_style: text_style.add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            action_style: text_style,
+            background_style: nu_style_to_tui(background_style),
+        }
+    }
+}
+
+impl Widget for HintBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        const PADDING_LEFT: u16 = 1;
+        const HINT_SPACING: u16 = 2;
+
+        if area.height == 0 {
+            return;
+        }
+
+        let block = Block::default().style(self.background_style);
+        block.render(area, buf);
+
+        let mut x = area.x + PADDING_LEFT;
+        for hint in self.hints {
+            // a hint is either shown whole or not at all
+            let width = string_width(&hint.key) + 1 + string_width(&hint.action);
+            if x as usize + width > area.right() as usize {
+                break;
+            }
+
+            x += set_span(buf, (x, area.y), &hint.key, self.key_style, area.width);
+            x += 1;
+            x += set_span(
+                buf,
+                (x, area.y),
+                &hint.action,
+                self.action_style,
+                area.width,
+            );
+            x += HINT_SPACING;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(hints: &[KeyHint], width: u16) -> String {
+        let area = Rect::new(0, 0, width, 1);
+        let mut buf = Buffer::empty(area);
+        HintBar::new(hints, NuStyle::default(), NuStyle::default()).render(area, &mut buf);
+
+        (0..width)
+            .filter_map(|x| buf.cell((x, 0)))
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn hints_that_do_not_fit_are_dropped() {
+        let hints = [KeyHint::new("n", "next"), KeyHint::new("Esc", "cancel")];
+
+        assert_eq!(render(&hints, 20), " n next  Esc cancel ");
+        assert_eq!(render(&hints, 12), " n next     ");
+    }
+}
@@ -0,0 +1,75 @@
+use nu_protocol::{PipelineData, Value};
+use std::time::Duration;
+
+/// What explore was given and how it was read, for `:info`.
+#[derive(Debug, Clone, Default)]
+pub struct InputInfo {
+    pub shape: InputShape,
+    /// How long reading all of it took
+    pub collected_in: Duration,
+    /// Whether only part of it was read, as with the first values of a long range
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputShape {
+    #[default]
+    Empty,
+    /// A value read at once
+    Value,
+    /// A list streamed, read to its end before it's shown
+    ListStream,
+    /// Bytes streamed, from a file or an external command
+    ByteStream,
+}
+
+impl InputShape {
+    pub fn of(input: &PipelineData) -> Self {
+        match input {
+            PipelineData::Empty => Self::Empty,
+            PipelineData::Value(Value::Nothing { .. }, ..) => Self::Empty,
+            PipelineData::Value(..) => Self::Value,
+            PipelineData::ListStream(..) => Self::ListStream,
+            PipelineData::ByteStream(..) => Self::ByteStream,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Empty => "nothing",
+            Self::Value => "value",
+            Self::ListStream => "list stream",
+            Self::ByteStream => "byte stream",
+        }
+    }
+}
+
+/// A duration as it's told to users: milliseconds under a second, seconds otherwise.
+pub fn format_duration(duration: Duration) -> String {
+    match duration.as_millis() {
+        millis @ 0..1000 => format!("{millis} ms"),
+        _ => format!("{:.1} s", duration.as_secs_f64()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::Span;
+
+    #[test]
+    fn shape_of_the_input() {
+        let span = Span::test_data();
+        let shape = |value| InputShape::of(&PipelineData::value(value, None));
+
+        assert_eq!(InputShape::of(&PipelineData::empty()), InputShape::Empty);
+        assert_eq!(shape(Value::nothing(span)), InputShape::Empty);
+        assert_eq!(shape(Value::int(1, span)), InputShape::Value);
+    }
+
+    #[test]
+    fn durations_in_ms_or_s() {
+        assert_eq!(format_duration(Duration::from_micros(2500)), "2 ms");
+        assert_eq!(format_duration(Duration::from_millis(1300)), "1.3 s");
+    }
+}
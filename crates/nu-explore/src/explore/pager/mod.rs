@@ -1,57 +1,114 @@
 mod command_bar;
+pub mod compat;
+mod complete;
 mod events;
+pub mod follow;
+pub mod fuzzy;
+mod hint_bar;
+pub mod input;
+pub mod process;
 pub mod report;
-mod status_bar;
+mod search;
+pub mod session;
+pub mod status_bar;
+pub mod terminal;
 mod title_bar;
+pub mod trace;
+mod watch;
 
 use self::{
     command_bar::CommandBar,
+    complete::Completer,
+    follow::JobFollower,
+    hint_bar::HintBar,
+    input::InputInfo,
+    process::Processes,
     report::{Report, Severity},
-    status_bar::StatusBar,
+    search::{Matcher, SearchJob},
+    session::{ExitAction, Session},
+    status_bar::{StatusBar, StatusMode, StatusSegments},
+    terminal::TerminalGuard,
     title_bar::TitleBar,
+    trace::Trace,
+    watch::FileWatcher,
 };
 use super::{
-    config::ExploreConfig,
-    nu_common::{NuColor, NuConfig, NuStyle},
+    bindings::{self, KeyAction, KeyBinding, Pressed},
+    commands::{ColCmd, ExtensionCmd, FilterCmd, PaletteCmd},
+    config::{ExploreConfig, Preset},
+    deterministic, history,
+    l10n::tr,
+    nu_common::{NuColor, NuConfig, NuStyle, collect_pipeline},
+    recent::{RecentEntry, SourceKind},
     registry::{Command, CommandRegistry},
-    views::{Layout, View, ViewConfig, util::nu_style_to_tui},
+    views::{KeyHint, Layout, View, ViewConfig, WHEEL_ROWS, util::nu_style_to_tui},
 };
 use anyhow::Result;
 use crossterm::{
-    event::{KeyCode, KeyEvent, KeyModifiers},
+    event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind},
     execute,
-    terminal::{
-        Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
-        enable_raw_mode,
-    },
+    terminal::{BeginSynchronizedUpdate, EndSynchronizedUpdate},
 };
-use events::UIEvents;
+use events::{Input, UIEvents};
 use lscolors::LsColors;
 use nu_color_config::StyleComputer;
 use nu_protocol::{
-    Value,
+    JobId, Record, Span, Value,
     engine::{EngineState, Stack},
 };
-use ratatui::{backend::CrosstermBackend, layout::Rect, widgets::Block};
+use ratatui::{
+    TerminalOptions, Viewport,
+    backend::{Backend, CrosstermBackend},
+    layout::Rect,
+    widgets::Block,
+};
 use std::{
     cmp::min,
     io::{self, Stdout},
+    path::{Path, PathBuf},
     result,
+    time::Duration,
 };
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub type Frame<'a> = ratatui::Frame<'a>;
 pub type Terminal = ratatui::Terminal<CrosstermBackend<Stdout>>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Pager<'a> {
     config: PagerConfig<'a>,
     message: Option<String>,
     cmd_buf: CommandBuf,
     search_buf: SearchBuf,
+    // how long the last frame took to draw, and how many were, for the perf status mode
+    draw_time: Duration,
+    frames: usize,
+    // how the session ended, once it did
+    session: Session,
+    // what explore was given, for `:info`
+    input: InputInfo,
+    // the file explored, watched with `--watch-file`
+    watcher: Option<FileWatcher>,
+    // the background job followed with `--follow`
+    follower: Option<JobFollower>,
+    // the programs started by `:open`
+    processes: Processes,
+    // the texts copied so far, the latest first, for `:registers`
+    registers: Vec<String>,
+    // the pipelines run in `:try`, the oldest first
+    try_history: Vec<String>,
+    // how many pipelines of `try_history` are from earlier sessions
+    try_history_kept: usize,
+    // what explore returns in place of the view's value, as `:yank-column --return` asks
+    returned: Option<Value>,
+    // the keys pressed so far of a sequence bound in `explore.keybindings`
+    pending_keys: Vec<KeyEvent>,
 }
 
-#[derive(Debug, Clone, Default)]
+// how many copied texts are kept
+const REGISTERS: usize = 10;
+
+#[derive(Debug, Default)]
 struct SearchBuf {
     buf_cmd: String,
     buf_cmd_input: String,
@@ -59,6 +116,21 @@ struct SearchBuf {
     search_index: usize,
     is_reversed: bool,
     is_search_input: bool,
+    // fuzzy rather than by regex or text, switched with Tab while typing
+    is_fuzzy: bool,
+    // a search of many lines, still running in the background
+    job: Option<SearchJob>,
+    // whether to show the first match once the job finds it
+    show_first: bool,
+}
+
+impl SearchBuf {
+    fn matcher(&self, pattern: &str) -> Matcher {
+        match self.is_fuzzy {
+            true => Matcher::fuzzy(pattern),
+            false => Matcher::new(pattern),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -70,7 +142,47 @@ struct CommandBuf {
     cmd_history: Vec<String>,
     cmd_history_allow: bool,
     cmd_history_pos: usize,
+    // how many commands of `cmd_history` are from earlier sessions
+    cmd_history_kept: usize,
     cmd_exec_info: Option<String>,
+    completion: Option<Completion>,
+    completer: Completer,
+}
+
+impl CommandBuf {
+    /// Open the command bar with `text` already typed in.
+    fn prompt(&mut self, text: String) {
+        self.cursor_pos = text.len();
+        self.buf_cmd2 = text;
+        self.is_cmd_input = true;
+        self.cmd_exec_info = None;
+        self.completion = None;
+    }
+}
+
+/// Values offered on <Tab> while typing a command: the values of a column in
+/// `:filter <column> <value>`, a column in `:col <name>`, or any other word.
+#[derive(Debug, Clone)]
+struct Completion {
+    target: Target,
+    // distinct values of the column (or the column names), collected on the first <Tab>
+    values: Vec<String>,
+    // the candidates for what was typed when <Tab> was first pressed
+    matches: Vec<String>,
+    index: usize,
+    // set while <Tab> cycles through `matches`; cleared by any other key
+    is_cycling: bool,
+}
+
+/// What's completed on <Tab>.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    // the values of a column, in `:filter <column> <value>`
+    Values(String),
+    // the column names, matched fuzzily, in `:col <name>`
+    Columns,
+    // the word starting there: a command, a flag, a column or a path, see `Completer`
+    Word(usize),
 }
 
 impl<'a> Pager<'a> {
@@ -80,9 +192,63 @@ impl<'a> Pager<'a> {
             cmd_buf: CommandBuf::default(),
             search_buf: SearchBuf::default(),
             message: None,
+            draw_time: Duration::ZERO,
+            frames: 0,
+            session: Session::default(),
+            input: InputInfo::default(),
+            watcher: None,
+            follower: None,
+            processes: Processes::default(),
+            registers: Vec::new(),
+            try_history: Vec::new(),
+            try_history_kept: 0,
+            returned: None,
+            pending_keys: Vec::new(),
         }
     }
 
+    /// Keep a copied text in the first register, moving the others down; the oldest is
+    /// forgotten once there are more than fit.
+    pub fn yank(&mut self, text: String) {
+        self.registers.retain(|register| *register != text);
+        self.registers.insert(0, text);
+        self.registers.truncate(REGISTERS);
+    }
+
+    /// Have explore return `value` when it exits, whether it was peeking or not.
+    pub fn return_value(&mut self, value: Value) {
+        self.returned = Some(value);
+    }
+
+    /// Note what explore was given and how it was read.
+    pub fn set_input(&mut self, input: InputInfo) {
+        self.input = input;
+    }
+
+    pub fn input(&self) -> &InputInfo {
+        &self.input
+    }
+
+    /// Where the input comes from, when it's a file or a pipeline.
+    pub fn source(&self) -> Option<&RecentEntry> {
+        self.config.recent.as_ref()
+    }
+
+    /// How the session ended, once [`Pager::run`] returned.
+    pub fn take_session(&mut self) -> Session {
+        std::mem::take(&mut self.session)
+    }
+
+    /// Add what a background job sends to the table as it comes, for `--follow`.
+    pub fn follow(&mut self, follower: JobFollower) {
+        self.follower = Some(follower);
+    }
+
+    /// The programs explore starts, waited for as they end.
+    pub fn processes(&mut self) -> &mut Processes {
+        &mut self.processes
+    }
+
     pub fn show_message(&mut self, text: impl Into<String>) {
         self.message = Some(text.into());
     }
@@ -94,38 +260,116 @@ impl<'a> Pager<'a> {
         view: Option<Page>,
         commands: CommandRegistry,
     ) -> Result<Option<Value>> {
-        // setup terminal
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, Clear(ClearType::All))?;
+        if self.config.watch_file
+            && let Some(entry) = &self.config.recent
+            && entry.kind == SourceKind::File
+        {
+            self.watcher = Some(FileWatcher::new(Path::new(&entry.source)).map_err(|err| {
+                anyhow::anyhow!(tr!(
+                    "Can't watch {source}: {err}",
+                    source = entry.source,
+                    err = err
+                ))
+            })?);
+        }
+
+        self.cmd_buf.completer = Completer::new(&commands, &self.config.cwd);
 
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+        // the same frames for the same keys, whatever was typed before
+        if !deterministic::is_enabled()
+            && let Some(path) = history::path()
+        {
+            let buf = &mut self.cmd_buf;
+            buf.cmd_history = history::load(&path).unwrap_or_default();
+            buf.cmd_history_kept = buf.cmd_history.len();
+            buf.cmd_history_pos = buf.cmd_history.len();
+        }
+        if !deterministic::is_enabled()
+            && self.config.explore_config.try_history
+            && let Some(path) = history::try_path()
+        {
+            self.try_history = history::load(&path).unwrap_or_default();
+            self.try_history_kept = self.try_history.len();
+        }
+
+        // setup terminal, restored when the guard is dropped, even on errors and panics
+        let terminal_guard = TerminalGuard::new(engine_state, self.config.explore_config.mouse)?;
+
+        let mut events = UIEvents::new(deterministic::terminal_size()?);
+        if let Some(trace) = self.config.replay.take() {
+            self.message = Some(tr!("Replaying {count} events", count = trace.events.len()));
+            events.replay(trace);
+        }
+
+        if self.config.record.is_some() {
+            let area = events.area();
+            events.record((area.width, area.height));
+        }
+
+        // the area is only changed by resize events, ratatui would otherwise ask the terminal
+        // for its size at each frame, and get a stale one while a multiplexer resizes it
+        let mut terminal = Terminal::with_options(
+            CrosstermBackend::new(io::stdout()),
+            TerminalOptions {
+                viewport: Viewport::Fixed(events.area()),
+            },
+        )?;
 
         let mut info = ViewInfo {
             status: Some(Report::default()),
+            status_mode: self.config.explore_config.status_mode,
             ..Default::default()
         };
 
         if let Some(text) = self.message.take() {
             info.status = Some(Report::message(text, Severity::Info));
         }
+        if let Some(text) = self.config.warning.take() {
+            info.status = Some(Report::message(text, Severity::Warn));
+        }
 
         let result = render_ui(
             &mut terminal,
             engine_state,
             stack,
             self,
+            &events,
             &mut info,
             view,
             commands,
-        )?;
+        );
+
+        drop(terminal);
+        drop(terminal_guard);
+
+        if !deterministic::is_enabled()
+            && let Some(path) = history::path()
+        {
+            let typed = &self.cmd_buf.cmd_history[self.cmd_buf.cmd_history_kept..];
+            if let Err(err) = history::remember(&path, typed) {
+                log::warn!("Failed to keep the command history: {err:#}");
+            }
+        }
+        if !deterministic::is_enabled()
+            && self.config.explore_config.try_history
+            && let Some(path) = history::try_path()
+        {
+            let tried = &self.try_history[self.try_history_kept..];
+            if let Err(err) = history::remember(&path, tried) {
+                log::warn!("Failed to keep the :try history: {err:#}");
+            }
+        }
 
-        // restore terminal
-        disable_raw_mode()?;
-        execute!(io::stdout(), LeaveAlternateScreen)?;
+        // saved even when the session failed, that's when a trace is wanted the most
+        let saved = match (events.into_trace(), &self.config.record) {
+            (Some(trace), Some(path)) => trace.save(path),
+            _ => Ok(()),
+        };
 
-        Ok(result)
+        let value = result?;
+        saved?;
+
+        Ok(value)
     }
 }
 
@@ -134,6 +378,10 @@ pub enum Transition {
     Ok,
     Exit,
     Cmd(String),
+    /// Open the command bar with the given text already typed in
+    Prompt(String),
+    /// Stop the process until it's resumed from the shell (Ctrl-Z)
+    Suspend,
     None,
 }
 
@@ -155,6 +403,28 @@ pub struct PagerConfig<'a> {
     pub tail: bool,
     // Just a cached dir we are working in used for color manipulations
     pub cwd: String,
+    // Applied to the initial table, set by `explore --preset`
+    pub preset: Option<Preset>,
+    // Start in the try view running this on the input, set by `explore --try`
+    pub try_command: Option<String>,
+    // Where to save a trace of the session, set by `explore --record`
+    pub record: Option<PathBuf>,
+    // Play these events before reading the terminal, set by `explore --replay`
+    pub replay: Option<Trace>,
+    // Where the input comes from, remembered for `explore --recent` once it's read
+    pub recent: Option<RecentEntry>,
+    // Read the file of `recent` again whenever it changes, set by `explore --watch-file`
+    pub watch_file: bool,
+    // Add what this background job sends to the table as it comes, set by `explore --follow`
+    pub follow: Option<JobId>,
+    // Run these `:` commands once the first view is open, set by `explore --on-start`
+    pub on_start: Vec<String>,
+    // Shown once over the first message, when the colors couldn't be set up as configured
+    pub warning: Option<String>,
+    // Open the input expanded this many levels as `:expand` does, set by `explore --expand`
+    pub expand: Option<usize>,
+    // The commands added by the distribution embedding nushell, see `ExploreContext`
+    pub commands: &'a [ExtensionCmd],
 }
 
 impl<'a> PagerConfig<'a> {
@@ -175,40 +445,97 @@ impl<'a> PagerConfig<'a> {
             peek_value,
             tail,
             cwd: cwd.to_string(),
+            preset: None,
+            try_command: None,
+            record: None,
+            replay: None,
+            recent: None,
+            watch_file: false,
+            follow: None,
+            on_start: Vec::new(),
+            warning: None,
+            expand: None,
+            commands: &[],
         }
     }
 }
 
 #[allow(clippy::too_many_arguments)]
-fn render_ui(
-    term: &mut Terminal,
+fn render_ui<B: Backend<Error: Send + Sync + 'static>>(
+    term: &mut ratatui::Terminal<B>,
     engine_state: &EngineState,
     stack: &mut Stack,
     pager: &mut Pager<'_>,
+    events: &UIEvents,
     info: &mut ViewInfo,
     view: Option<Page>,
     commands: CommandRegistry,
 ) -> Result<Option<Value>> {
-    let events = UIEvents::new();
     let mut view_stack = ViewStack::new(view, Vec::new());
 
+    // once the first view is open, as if typed after `:`; the first failing ends it
+    for command in std::mem::take(&mut pager.config.on_start) {
+        let args = command.trim().trim_start_matches(':').to_string();
+        match pager_run_command(engine_state, stack, pager, &mut view_stack, &commands, args) {
+            Ok(result) if result.exit => {
+                end_session(pager, &view_stack.curr_view, ExitAction::Quit);
+                return Ok(peek_value_from_view(&mut view_stack.curr_view, pager));
+            }
+            Ok(_) => {}
+            Err(err) => {
+                info.report = Some(Report::error(err));
+                break;
+            }
+        }
+    }
+
     loop {
         if engine_state.signals().interrupted() {
+            end_session(pager, &view_stack.curr_view, ExitAction::Interrupted);
             break Ok(None);
         }
 
+        if pager.watcher.as_ref().is_some_and(FileWatcher::changed) {
+            info.status = Some(reload_source(engine_state, stack, pager, &mut view_stack));
+        }
+
+        if let Some(follower) = pager.follower.as_mut()
+            && follower.receive(engine_state)
+        {
+            info.status = Some(follow_job(follower, &mut view_stack));
+        }
+
+        if let Some(report) = pager.processes.poll() {
+            info.report = Some(report);
+        }
+
         // Give the view a chance to update its internal state (e.g., receive streaming data)
         // and update the status bar before drawing.
         if let Some(page) = view_stack.curr_view.as_mut() {
             page.view.update(info);
         }
+        poll_search(
+            &mut pager.search_buf,
+            view_stack.curr_view.as_mut().map(|p| &mut p.view),
+        );
+        // kept for the `:try` views opened later
+        pager.try_history.append(&mut info.tried);
+
+        // the screen was left, suspended or to a program run, so nothing on it can be reused
+        if terminal::was_left() {
+            term.clear()?;
+        }
 
         let mut layout = Layout::default();
         {
             let info = info.clone();
-            term.draw(|f| {
+            let synchronized = pager.config.explore_config.synchronized_output;
+            let start = deterministic::now();
+            draw_synchronized(term, synchronized, |f| {
                 draw_frame(f, &mut view_stack.curr_view, pager, &mut layout, info);
             })?;
+            pager.draw_time = deterministic::now().saturating_duration_since(start);
+            pager.frames += 1;
         }
 
         // Note that this will return within the configured tick_rate of events. In particular this
@@ -220,14 +547,28 @@ fn render_ui(
         let transition = handle_events(
             engine_state,
             stack,
-            &events,
+            events,
             &layout,
             info,
             &mut pager.search_buf,
             &mut pager.cmd_buf,
+            &pager.config.explore_config.keybindings,
+            &mut pager.pending_keys,
             view_stack.curr_view.as_mut().map(|p| &mut p.view),
         );
 
+        // consoles like the legacy Windows one can leave parts of the old frame behind, so the
+        // screen is cleared
+        if let Some(area) = events.take_resize() {
+            term.resize(area)?;
+        }
+
+        #[cfg(unix)]
+        if let Transition::Suspend = transition {
+            terminal::suspend()?;
+            continue;
+        }
+
         let (exit, cmd_name) = react_to_event_result(
             transition,
             engine_state,
@@ -242,6 +583,8 @@ fn render_ui(
             break Ok(value);
         }
 
+        // shown with the next frame; drawing it alone would blank the rest of the screen
+        // until then, which flickers
         if !cmd_name.is_empty() {
             if let Some(r) = info.report.as_mut() {
                 r.message = cmd_name;
@@ -249,38 +592,30 @@ fn render_ui(
             } else {
                 info.report = Some(Report::success(cmd_name));
             }
-
-            let info = info.clone();
-            term.draw(|f| {
-                draw_info(f, pager, info);
-            })?;
         }
 
         if pager.cmd_buf.run_cmd {
             let args = pager.cmd_buf.buf_cmd2.clone();
             pager.cmd_buf.run_cmd = false;
             pager.cmd_buf.buf_cmd2.clear();
+            events.record_command(&args);
 
             let out =
                 pager_run_command(engine_state, stack, pager, &mut view_stack, &commands, args);
             match out {
                 Ok(result) => {
                     if result.exit {
+                        end_session(pager, &view_stack.curr_view, ExitAction::Quit);
                         break Ok(peek_value_from_view(&mut view_stack.curr_view, pager));
                     }
 
-                    if result.view_change && !result.cmd_name.is_empty() {
+                    if !result.cmd_name.is_empty() {
                         if let Some(r) = info.report.as_mut() {
                             r.message = result.cmd_name;
                             r.level = Severity::Success;
                         } else {
                             info.report = Some(Report::success(result.cmd_name));
                         }
-
-                        let info = info.clone();
-                        term.draw(|f| {
-                            draw_info(f, pager, info);
-                        })?;
                     }
                 }
                 Err(err) => info.report = Some(Report::error(err)),
@@ -299,13 +634,17 @@ fn react_to_event_result(
     info: &mut ViewInfo,
 ) -> (Option<Option<Value>>, String) {
     match status {
-        Transition::Exit => (
-            Some(peek_value_from_view(&mut view_stack.curr_view, pager)),
-            String::default(),
-        ),
+        Transition::Exit => {
+            end_session(pager, &view_stack.curr_view, ExitAction::Quit);
+            (
+                Some(peek_value_from_view(&mut view_stack.curr_view, pager)),
+                String::default(),
+            )
+        }
         Transition::Ok => {
             let exit = view_stack.stack.is_empty();
             if exit {
+                end_session(pager, &view_stack.curr_view, ExitAction::Back);
                 return (
                     Some(peek_value_from_view(&mut view_stack.curr_view, pager)),
                     String::default(),
@@ -322,10 +661,13 @@ fn react_to_event_result(
         Transition::Cmd(cmd) => {
             let out = pager_run_command(engine_state, stack, pager, view_stack, commands, cmd);
             match out {
-                Ok(result) if result.exit => (
-                    Some(peek_value_from_view(&mut view_stack.curr_view, pager)),
-                    String::default(),
-                ),
+                Ok(result) if result.exit => {
+                    end_session(pager, &view_stack.curr_view, ExitAction::Quit);
+                    (
+                        Some(peek_value_from_view(&mut view_stack.curr_view, pager)),
+                        String::default(),
+                    )
+                }
                 Ok(result) => (None, result.cmd_name),
                 Err(err) => {
                     info.report = Some(Report::error(err));
@@ -333,12 +675,78 @@ fn react_to_event_result(
                 }
             }
         }
-        Transition::None => (None, String::default()),
+        Transition::Prompt(_) | Transition::Suspend | Transition::None => (None, String::default()),
+    }
+}
+
+fn end_session(pager: &mut Pager<'_>, view: &Option<Page>, exit: ExitAction) {
+    pager.session.exit = exit;
+    pager.session.details = Record::new();
+    if let Some(page) = view {
+        let kind = Value::string(page.view.kind(), Span::unknown());
+        pager.session.details.push("view", kind);
+        page.view.exit_details(&mut pager.session.details);
+    }
+}
+
+/// Read the file explored again into the table it was first shown in, for `--watch-file`.
+fn reload_source(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    pager: &Pager<'_>,
+    view_stack: &mut ViewStack,
+) -> Report {
+    let Some(entry) = &pager.config.recent else {
+        return Report::default();
+    };
+
+    let (columns, data) = match entry.reopen(engine_state, stack).and_then(collect_pipeline) {
+        Ok(data) => data,
+        Err(err) => return Report::error(tr!("Can't reload: {err}", err = err)),
+    };
+
+    let root = match view_stack.stack.first_mut() {
+        Some(page) => Some(page),
+        None => view_stack.curr_view.as_mut(),
+    };
+    match root.and_then(|page| page.view.as_record_view()) {
+        Some(view) => {
+            view.reload(columns, data);
+            Report::info(tr!("Reloaded {source}", source = entry.short_source()))
+        }
+        None => Report::message(
+            tr!(
+                "{source} changed, only tables are reloaded",
+                source = entry.short_source()
+            ),
+            Severity::Warn,
+        ),
+    }
+}
+
+/// Add the values the job followed output since to the table it was first shown in, for
+/// `--follow`; the table is loaded again with all of them if they can't just be added.
+fn follow_job(follower: &mut JobFollower, view_stack: &mut ViewStack) -> Report {
+    let root = match view_stack.stack.first_mut() {
+        Some(page) => Some(page),
+        None => view_stack.curr_view.as_mut(),
+    };
+    if let Some(view) = root.and_then(|page| page.view.as_record_view())
+        && !follower
+            .take_rows()
+            .is_some_and(|(columns, rows)| view.append(&columns, rows))
+    {
+        let (columns, data) = follower.rows();
+        view.reload(columns, data);
     }
+
+    follower.report()
 }
 
 fn peek_value_from_view(view: &mut Option<Page>, pager: &mut Pager<'_>) -> Option<Value> {
-    if pager.config.peek_value {
+    if let Some(value) = pager.returned.take() {
+        Some(value)
+    } else if pager.config.peek_value {
         let view = view.as_mut().map(|p| &mut p.view);
         view.and_then(|v| v.exit())
     } else {
@@ -346,6 +754,33 @@ fn peek_value_from_view(view: &mut Option<Page>, pager: &mut Pager<'_>) -> Optio
     }
 }
 
+/// Draw a frame; ratatui only sends the cells that changed since the last one.
+///
+/// With `synchronized` on, the terminal is told to show the frame once it's complete rather than
+/// as it arrives, so it doesn't tear over slow connections. Terminals without synchronized
+/// output ignore it.
+// the terminal drawn in is the output of nushell, which the updates are marked in
+fn draw_synchronized<B, F>(
+    term: &mut ratatui::Terminal<B>,
+    synchronized: bool,
+    render: F,
+) -> Result<()>
+where
+    B: Backend<Error: Send + Sync + 'static>,
+    F: FnOnce(&mut Frame),
+{
+    if synchronized {
+        execute!(io::stdout(), BeginSynchronizedUpdate)?;
+    }
+    let result = term.draw(render).map(|_| ());
+    if synchronized {
+        execute!(io::stdout(), EndSynchronizedUpdate)?;
+    }
+    result?;
+
+    Ok(())
+}
+
 fn draw_frame(
     f: &mut Frame,
     view: &mut Option<Page>,
@@ -354,34 +789,138 @@ fn draw_frame(
     info: ViewInfo,
 ) {
     let area = f.area();
+    let show_hints = pager.config.explore_config.show_hints;
 
     // Reserve space: 1 line for title bar at top, 2 lines for status/cmd bars at bottom
+    // and 1 more above them for the hint bar
     let title_area = Rect::new(area.x, area.y, area.width, 1);
     let content_area = Rect::new(
         area.x,
         area.y + 1,
         area.width,
-        area.height.saturating_sub(3),
+        area.height.saturating_sub(3 + show_hints as u16),
     );
 
     // Render title bar
-    render_title_bar(f, title_area, pager.config.explore_config);
+    let title = view.as_ref().and_then(|page| page.view.title());
+    render_title_bar(f, title_area, title, pager.config.explore_config);
 
     if let Some(page) = view {
         let cfg = create_view_config(pager);
         page.view.draw(f, content_area, cfg, layout);
     }
 
-    draw_info(f, pager, info);
+    if show_hints {
+        let hints = collect_key_hints(pager, view.as_ref().map(|p| p.view.as_ref()));
+        let hint_area = Rect::new(area.x, area.bottom().saturating_sub(3), area.width, 1);
+        let config = pager.config.explore_config;
+        let hint_bar = HintBar::new(&hints, config.status_bar_text, config.status_bar_background);
+        f.render_widget(hint_bar, hint_area);
+    }
+
+    let contexts = match info.status_mode {
+        StatusMode::Position => None,
+        mode => {
+            let mut segments = StatusSegments::new(mode);
+            pager_status_segments(pager, &mut segments);
+            if let Some(page) = view {
+                page.view.status_segments(&mut segments);
+            }
+            Some(segments.into_contexts())
+        }
+    };
+    draw_info(f, pager, info, contexts);
 
     highlight_search_results(f, pager, layout, pager.config.explore_config.highlight);
     set_cursor_cmd_bar(f, area, pager);
+
+    let config = pager.config.explore_config;
+    if config.compat || config.colors != compat::ColorDepth::TrueColor {
+        compat::downgrade(f.buffer_mut(), config.compat, config.colors);
+    }
+}
+
+/// What the pager knows for the status bar: how long frames take and where the data comes from.
+fn pager_status_segments(pager: &Pager, segments: &mut StatusSegments) {
+    match segments.mode() {
+        StatusMode::Perf => {
+            let millis = pager.draw_time.as_secs_f64() * 1000.0;
+            segments.push(&tr!("draw"), format!("{millis:.1}ms"));
+            segments.push(&tr!("frames"), pager.frames);
+        }
+        StatusMode::Source => match &pager.config.recent {
+            Some(entry) => {
+                segments.push_text(entry.kind.name());
+                segments.push_text(entry.short_source());
+            }
+            None => segments.push_text(tr!("input")),
+        },
+        StatusMode::Position | StatusMode::Selection => {}
+    }
+}
+
+// what the view is called in keybindings, with its mode if it has one, like `table:cursor`
+fn bound_in<V: View + ?Sized>(view: &V) -> String {
+    match view.mode() {
+        Some(mode) => format!("{}:{mode}", view.kind()),
+        None => view.kind().to_string(),
+    }
 }
 
-fn render_title_bar(f: &mut Frame, area: Rect, theme: &ExploreConfig) {
-    let mut title_bar = TitleBar::new("Explore")
-        .with_info_left("Navigate: ←↑↓→")
-        .with_info_right(":help for help");
+/// Keys for whatever has the focus: the command bar, the search bar or the view; those of the
+/// keymap as `explore.keybindings` rebinds them.
+fn collect_key_hints(pager: &Pager, view: Option<&dyn View>) -> Vec<KeyHint> {
+    if pager.cmd_buf.is_cmd_input {
+        return vec![
+            KeyHint::new("Enter", "run"),
+            KeyHint::new("Tab", "complete"),
+            KeyHint::new("↑↓", "history"),
+            KeyHint::new("Esc", "cancel"),
+        ];
+    }
+
+    if pager.search_buf.is_search_input {
+        let mode = match pager.search_buf.is_fuzzy {
+            true => "exact",
+            false => "fuzzy",
+        };
+        return vec![
+            KeyHint::new("Enter", "search"),
+            KeyHint::new("Tab", mode),
+            KeyHint::new("Esc", "cancel"),
+        ];
+    }
+
+    let mut hints = Vec::new();
+    if pager.search_buf.job.is_some() {
+        hints.push(KeyHint::new("Esc", "stop search"));
+    }
+    if !pager.search_buf.search_results.is_empty() {
+        hints.push(KeyHint::new("n", "next match").bound("n", "next search result"));
+        hints.push(KeyHint::new("N", "previous match").bound("N", "previous search result"));
+    }
+
+    if let Some(view) = view {
+        hints.extend(view.key_hints());
+    }
+
+    let kind = view.map_or(String::new(), bound_in);
+    let bindings = &pager.config.explore_config.keybindings;
+    hints
+        .into_iter()
+        .filter_map(|hint| hint.rebind(bindings, &kind))
+        .collect()
+}
+
+// the page's title follows the name of explore
+fn render_title_bar(f: &mut Frame, area: Rect, title: Option<String>, theme: &ExploreConfig) {
+    let title = match title {
+        Some(title) => format!("Explore › {title}"),
+        None => String::from("Explore"),
+    };
+    let mut title_bar = TitleBar::new(title)
+        .with_info_left(tr!("Navigate: ←↑↓→"))
+        .with_info_right(tr!(":help for help"));
     title_bar.set_background_style(theme.title_bar_background);
     title_bar.set_title_style(theme.title_bar_text);
     title_bar.set_info_style(theme.title_bar_text);
@@ -389,13 +928,15 @@ fn render_title_bar(f: &mut Frame, area: Rect, theme: &ExploreConfig) {
     f.render_widget(title_bar, area);
 }
 
-fn draw_info(f: &mut Frame, pager: &mut Pager<'_>, info: ViewInfo) {
+/// Draw the status and command bars; `contexts` replace those of the view's report on the right
+/// of the status bar, in a status mode other than position.
+fn draw_info(f: &mut Frame, pager: &mut Pager<'_>, info: ViewInfo, contexts: Option<Vec<String>>) {
     let area = f.area();
 
     if let Some(report) = info.status {
         let last_2nd_line = area.bottom().saturating_sub(2);
         let area = Rect::new(area.left(), last_2nd_line, area.width, 1);
-        render_status_bar(f, area, report, pager.config.explore_config);
+        render_status_bar(f, area, report, contexts, pager.config.explore_config);
     }
 
     {
@@ -414,6 +955,8 @@ fn create_view_config<'a>(pager: &'a Pager<'_>) -> ViewConfig<'a> {
         cfg.lscolors,
         &pager.config.cwd,
     )
+    .with_registers(&pager.registers)
+    .with_try_history(&pager.try_history)
 }
 
 fn pager_run_command(
@@ -423,6 +966,30 @@ fn pager_run_command(
     view_stack: &mut ViewStack,
     commands: &CommandRegistry,
     args: String,
+) -> result::Result<CmdResult, String> {
+    // the commands of a macro run in turn, the first failing or quitting ending it
+    if let Some(lines) = commands.expand_macro(&args) {
+        let mut result = CmdResult::new(false, String::new());
+        for line in lines {
+            result = run_command_line(engine_state, stack, pager, view_stack, commands, line)?;
+            if result.exit {
+                break;
+            }
+        }
+        return Ok(result);
+    }
+
+    run_command_line(engine_state, stack, pager, view_stack, commands, args)
+}
+
+// a macro's commands are looked up among the others alone, so it can't run itself
+fn run_command_line(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    pager: &mut Pager,
+    view_stack: &mut ViewStack,
+    commands: &CommandRegistry,
+    args: String,
 ) -> result::Result<CmdResult, String> {
     let command = commands.find(&args);
     match command {
@@ -430,13 +997,22 @@ fn pager_run_command(
             let result = run_command(engine_state, stack, pager, view_stack, command);
             match result {
                 Ok(value) => Ok(value),
-                Err(err) => Err(format!("Error: command {args:?} failed: {err}")),
+                Err(err) => Err(tr!(
+                    "Error: command {args} failed: {err}",
+                    args = format!("{args:?}"),
+                    err = err
+                )),
             }
         }
-        Some(Err(err)) => Err(format!(
-            "Error: command {args:?} was not provided with correct arguments: {err}"
+        Some(Err(err)) => Err(tr!(
+            "Error: command {args} was not provided with correct arguments: {err}",
+            args = format!("{args:?}"),
+            err = err
+        )),
+        None => Err(tr!(
+            "Error: command {args} was not recognized",
+            args = format!("{args:?}")
         )),
-        None => Err(format!("Error: command {args:?} was not recognized")),
     }
 }
 
@@ -449,35 +1025,74 @@ fn run_command(
 ) -> Result<CmdResult> {
     match command {
         Command::Reactive(mut command) => {
+            close_modal(view_stack);
+
             // what we do we just replace the view.
             let value = view_stack.curr_view.as_mut().and_then(|p| p.view.exit());
-            let transition = command.react(engine_state, stack, pager, value)?;
+            let view = view_stack.curr_view.as_mut().map(|p| &mut p.view);
+            let transition = command.react(engine_state, stack, pager, view, value)?;
             match transition {
-                Transition::Ok => Ok(CmdResult::new(false, false, String::new())),
-                Transition::Exit => Ok(CmdResult::new(true, false, String::new())),
+                Transition::Ok => {
+                    let message = pager.message.take().unwrap_or_default();
+                    Ok(CmdResult::new(false, message))
+                }
+                Transition::Exit => Ok(CmdResult::new(true, String::new())),
+                Transition::Prompt(text) => {
+                    pager.cmd_buf.prompt(text);
+                    Ok(CmdResult::new(false, String::new()))
+                }
+                // drawn again once resumed, as the terminal was left
+                Transition::Suspend => {
+                    #[cfg(unix)]
+                    terminal::suspend()?;
+                    Ok(CmdResult::new(false, String::new()))
+                }
                 Transition::Cmd { .. } => todo!("not used so far"),
                 Transition::None => panic!("Transition::None not expected from command.react()"),
             }
         }
-        Command::View { mut cmd, stackable } => {
+        Command::View { mut cmd, policy } => {
+            close_modal(view_stack);
+
             // what we do we just replace the view.
-            let value = view_stack.curr_view.as_mut().and_then(|p| p.view.exit());
+            let view = view_stack.curr_view.as_mut();
+            let selection = view
+                .filter(|_| cmd.uses_selection() || cmd.uses_cell())
+                .and_then(|p| p.view.as_record_view())
+                .and_then(|view| match cmd.uses_cell() {
+                    true => Some(view.get_current_value().clone()),
+                    false => view.selection_value(),
+                });
+            let value = match selection {
+                Some(selection) => Some(selection),
+                None => view_stack.curr_view.as_mut().and_then(|p| p.view.exit()),
+            };
             let view_cfg = create_view_config(pager);
 
             let new_view = cmd.spawn(engine_state, stack, value, &view_cfg)?;
+            // a modal page is shown over any other, which comes back once it's closed
             if let Some(view) = view_stack.curr_view.take()
-                && view.stackable
+                && (view.policy == PagePolicy::Stack
+                    || (policy == PagePolicy::Modal && view.policy != PagePolicy::Modal))
             {
                 view_stack.stack.push(view);
             }
 
-            view_stack.curr_view = Some(Page::raw(new_view, stackable));
+            view_stack.curr_view = Some(Page::raw(new_view, policy));
 
-            Ok(CmdResult::new(false, true, cmd.name().to_owned()))
+            Ok(CmdResult::new(false, cmd.name().to_owned()))
         }
     }
 }
 
+// a modal page with nothing under it stays, to be replaced
+fn close_modal(view_stack: &mut ViewStack) {
+    let is_modal = matches!(&view_stack.curr_view, Some(page) if page.policy == PagePolicy::Modal);
+    if is_modal && let Some(page) = view_stack.stack.pop() {
+        view_stack.curr_view = Some(page);
+    }
+}
+
 fn set_cursor_cmd_bar(f: &mut Frame, area: Rect, pager: &Pager) {
     // Account for left padding (1) + prefix char like ':' or '/' (1)
     const LEFT_OFFSET: u16 = 2;
@@ -491,25 +1106,32 @@ fn set_cursor_cmd_bar(f: &mut Frame, area: Rect, pager: &Pager) {
     }
 }
 
-fn render_status_bar(f: &mut Frame, area: Rect, report: Report, theme: &ExploreConfig) {
+fn render_status_bar(
+    f: &mut Frame,
+    area: Rect,
+    report: Report,
+    contexts: Option<Vec<String>>,
+    theme: &ExploreConfig,
+) {
     let msg_style = report_msg_style(&report, theme, theme.status_bar_text);
-    let mut status_bar = create_status_bar(report);
+    let mut status_bar = create_status_bar(report, contexts);
     status_bar.set_background_style(theme.status_bar_background);
     status_bar.set_message_style(msg_style);
-    status_bar.set_ctx1_style(theme.status_bar_text);
-    status_bar.set_ctx2_style(theme.status_bar_text);
-    status_bar.set_ctx3_style(theme.status_bar_text);
+    status_bar.set_context_style(theme.status_bar_text);
 
     f.render_widget(status_bar, area);
 }
 
-fn create_status_bar(report: Report) -> StatusBar {
-    StatusBar::new(
-        report.message,
-        report.context1,
-        report.context2,
-        report.context3,
-    )
+fn create_status_bar(report: Report, contexts: Option<Vec<String>>) -> StatusBar {
+    match contexts {
+        Some(contexts) => StatusBar::with_contexts(report.message, contexts),
+        None => StatusBar::new(
+            report.message,
+            report.context1,
+            report.context2,
+            report.context3,
+        ),
+    }
 }
 
 fn report_msg_style(report: &Report, config: &ExploreConfig, style: NuStyle) -> NuStyle {
@@ -550,8 +1172,13 @@ fn render_cmd_bar(
 }
 
 fn render_cmd_bar_search(f: &mut Frame, area: Rect, pager: &Pager<'_>, config: &ExploreConfig) {
-    if pager.search_buf.search_results.is_empty() && !pager.search_buf.is_search_input {
-        let message = format!("Pattern not found: {}", pager.search_buf.buf_cmd_input);
+    let searching = pager.search_buf.job.is_some();
+    if pager.search_buf.search_results.is_empty() && !pager.search_buf.is_search_input && !searching
+    {
+        let message = tr!(
+            "Pattern not found: {pattern}",
+            pattern = pager.search_buf.buf_cmd_input
+        );
         let style = NuStyle {
             background: Some(NuColor::Red),
             foreground: Some(NuColor::White),
@@ -569,13 +1196,23 @@ fn render_cmd_bar_search(f: &mut Frame, area: Rect, pager: &Pager<'_>, config: &
         '/'
     };
     let text = format!("{}{}", prefix, pager.search_buf.buf_cmd_input);
-    let info = if pager.search_buf.search_results.is_empty() {
+    let mut info = if pager.search_buf.search_results.is_empty() {
         String::from("[0/0]")
     } else {
         let index = pager.search_buf.search_index + 1;
         let total = pager.search_buf.search_results.len();
         format!("[{index}/{total}]")
     };
+    if pager.search_buf.is_fuzzy {
+        info = tr!("fuzzy {info}", info = info);
+    }
+    if let Some(job) = &pager.search_buf.job {
+        info = tr!(
+            "{info} {percent}%, Esc stops",
+            info = info,
+            percent = job.progress()
+        );
+    }
 
     let bar = CommandBar::new(&text, &info, config.cmd_bar_text, config.cmd_bar_background);
     f.render_widget(bar, area);
@@ -626,7 +1263,16 @@ fn render_cmd_bar_cmd(f: &mut Frame, area: Rect, pager: &Pager, config: &Explore
     let prefix = ':';
     let text = format!("{prefix}{truncated_display}");
 
-    let bar = CommandBar::new(&text, "", config.cmd_bar_text, config.cmd_bar_background);
+    let info = match &pager.cmd_buf.completion {
+        Some(completion) if completion.is_cycling && !completion.matches.is_empty() => {
+            let index = completion.index + 1;
+            let total = completion.matches.len();
+            format!("[{index}/{total}]")
+        }
+        _ => String::new(),
+    };
+
+    let bar = CommandBar::new(&text, &info, config.cmd_bar_text, config.cmd_bar_background);
     f.render_widget(bar, area);
 
     // Set the terminal cursor position
@@ -654,37 +1300,28 @@ fn highlight_search_results(f: &mut Frame, pager: &Pager, layout: &Layout, style
     }
 
     let highlight_block = Block::default().style(nu_style_to_tui(style));
+    let matcher = pager.search_buf.matcher(&pager.search_buf.buf_cmd_input);
 
+    // every match of every cell or line shown, several in one if need be
     for e in &layout.data {
         let text = ansi_str::AnsiStr::ansi_strip(&e.text);
 
-        if let Some(p) = text.find(&pager.search_buf.buf_cmd_input) {
-            let p = covert_bytes_to_chars(&text, p);
+        for found in matcher.find_all(&text) {
+            // these widths are a best guess
+            let x = text[..found.start].width() as u16;
+            let w = text[found].width() as u16;
+            if x >= e.area.width {
+                break;
+            }
 
-            // this width is a best guess
-            let w = pager.search_buf.buf_cmd_input.width() as u16;
-            let area = Rect::new(e.area.x + p as u16, e.area.y, w, 1);
+            let w = w.min(e.area.width - x);
+            let area = Rect::new(e.area.x + x, e.area.y, w, 1);
 
             f.render_widget(highlight_block.clone(), area);
         }
     }
 }
 
-fn covert_bytes_to_chars(text: &str, p: usize) -> usize {
-    let mut b = 0;
-    let mut i = 0;
-    for c in text.chars() {
-        b += c.len_utf8();
-        if b > p {
-            break;
-        }
-
-        i += 1;
-    }
-
-    i
-}
-
 #[allow(clippy::too_many_arguments)]
 fn handle_events<V: View>(
     engine_state: &EngineState,
@@ -694,14 +1331,16 @@ fn handle_events<V: View>(
     info: &mut ViewInfo,
     search: &mut SearchBuf,
     command: &mut CommandBuf,
+    bindings: &[KeyBinding],
+    pending: &mut Vec<KeyEvent>,
     mut view: Option<&mut V>,
 ) -> Transition {
     // We are only interested in Pressed events;
     // It's crucial because there are cases where terminal MIGHT produce false events;
     // 2 events 1 for release 1 for press.
     // Want to react only on 1 of them so we do.
-    let mut key = match events.next_key_press() {
-        Ok(Some(key)) => key,
+    let mut input = match events.next_input() {
+        Ok(Some(input)) => input,
         Ok(None) => return Transition::None,
         Err(e) => {
             log::error!("Failed to read key event: {e}");
@@ -715,21 +1354,35 @@ fn handle_events<V: View>(
     //
     // To eliminate that we are trying to read all possible commands which we should act upon.
     loop {
-        let result = handle_event(
-            engine_state,
-            stack,
-            layout,
-            info,
-            search,
-            command,
-            view.as_deref_mut(),
-            key,
-        );
+        let result = match input {
+            Input::Key(key) => handle_event(
+                engine_state,
+                stack,
+                layout,
+                info,
+                search,
+                command,
+                bindings,
+                pending,
+                view.as_deref_mut(),
+                key,
+            ),
+            Input::Mouse(mouse) => handle_mouse(
+                engine_state,
+                stack,
+                layout,
+                info,
+                search,
+                command,
+                view.as_deref_mut(),
+                mouse,
+            ),
+        };
         if !matches!(result, Transition::None) {
             return result;
         }
-        match events.try_next_key_press() {
-            Ok(Some(next_key)) => key = next_key,
+        match events.try_next_input() {
+            Ok(Some(next_input)) => input = next_input,
             Ok(None) => return Transition::None,
             Err(e) => {
                 log::error!("Failed to peek key event: {e}");
@@ -747,13 +1400,38 @@ fn handle_event<V: View>(
     info: &mut ViewInfo,
     search: &mut SearchBuf,
     command: &mut CommandBuf,
+    bindings: &[KeyBinding],
+    pending: &mut Vec<KeyEvent>,
     mut view: Option<&mut V>,
-    key: KeyEvent,
+    mut key: KeyEvent,
 ) -> Transition {
+    // keys typed into the command or search bar are text, not bindings
+    if search.is_search_input || command.is_cmd_input {
+        pending.clear();
+    } else {
+        let kind = view.as_deref().map_or(String::new(), bound_in);
+        match bindings::press(bindings, pending, key, &kind) {
+            Pressed::Bound(KeyAction::Command(command)) => return Transition::Cmd(command.clone()),
+            // handled as the default key of the action, whatever it's bound to
+            Pressed::Bound(KeyAction::Default(action)) => {
+                match bindings::default_key(action, &kind) {
+                    Some(default) => key = default,
+                    None => return Transition::None,
+                }
+            }
+            Pressed::Bound(KeyAction::Nothing) | Pressed::Pending => return Transition::None,
+            Pressed::Unbound => {}
+        }
+    }
+
     if handle_exit_key_event(&key) {
         return Transition::Exit;
     }
 
+    if cfg!(unix) && key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('z') {
+        return Transition::Suspend;
+    }
+
     if handle_general_key_events1(&key, search, command, view.as_deref_mut()) {
         return Transition::None;
     }
@@ -763,17 +1441,68 @@ fn handle_event<V: View>(
         match t {
             Transition::Exit => return Transition::Ok,
             Transition::Cmd(cmd) => return Transition::Cmd(cmd),
+            Transition::Prompt(text) => {
+                command.prompt(text);
+                info.report = None;
+                return Transition::None;
+            }
             Transition::Ok => return Transition::None,
+            Transition::Suspend => return Transition::Suspend,
             Transition::None => {}
         }
     }
 
+    // the palette of the commands, in any view which doesn't take the key for itself
+    if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('k') {
+        return Transition::Cmd(PaletteCmd::NAME.to_string());
+    }
+
     // was not handled so we must check our default controls
     handle_general_key_events2(&key, search, command, view, info);
 
     Transition::None
 }
 
+#[allow(clippy::too_many_arguments)]
+fn handle_mouse<V: View>(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    layout: &Layout,
+    info: &mut ViewInfo,
+    search: &SearchBuf,
+    command: &CommandBuf,
+    view: Option<&mut V>,
+    mouse: MouseEvent,
+) -> Transition {
+    // the command and search bars keep the focus while they're typed in
+    let Some(view) = view else {
+        return Transition::None;
+    };
+    if search.is_search_input || command.is_cmd_input {
+        return Transition::None;
+    }
+
+    match view.handle_mouse(layout, info, mouse) {
+        Transition::None => {}
+        Transition::Ok => return Transition::None,
+        Transition::Exit => return Transition::Ok,
+        transition => return transition,
+    }
+
+    // the views which don't handle the wheel scroll with the arrow keys
+    let code = match mouse.kind {
+        MouseEventKind::ScrollDown => KeyCode::Down,
+        MouseEventKind::ScrollUp => KeyCode::Up,
+        _ => return Transition::None,
+    };
+    for _ in 0..WHEEL_ROWS {
+        let key = KeyEvent::new(code, KeyModifiers::NONE);
+        view.handle_input(engine_state, stack, layout, info, key);
+    }
+
+    Transition::None
+}
+
 fn handle_exit_key_event(key: &KeyEvent) -> bool {
     if key.modifiers == KeyModifiers::CONTROL {
         // these are all common things people might try, might as well handle them all
@@ -797,8 +1526,13 @@ where
         return search_input_key_event(search, view, key);
     }
 
+    // Esc stops a search still running, rather than leaving the view
+    if key.code == KeyCode::Esc && search.job.take().is_some() {
+        return true;
+    }
+
     if command.is_cmd_input {
-        return cmd_input_key_event(command, key);
+        return cmd_input_key_event(command, view, key);
     }
 
     false
@@ -828,6 +1562,7 @@ fn handle_general_key_events2<V>(
 
             info.report = None;
         }
+        KeyCode::Char('s') => info.status_mode = info.status_mode.next(),
         KeyCode::Char(':') => {
             command.buf_cmd2.clear();
             command.is_cmd_input = true;
@@ -887,9 +1622,8 @@ fn search_input_key_event(
             if let Some(view) = view
                 && !buf.buf_cmd.is_empty()
             {
-                let data = view.collect_data().into_iter().map(|(text, _)| text);
-                buf.search_results = search_pattern(data, &buf.buf_cmd, buf.is_reversed);
-                buf.search_index = 0;
+                let pattern = buf.buf_cmd.clone();
+                start_search(buf, view, &pattern, false);
             }
 
             buf.is_search_input = false;
@@ -903,14 +1637,20 @@ fn search_input_key_event(
             if let Some(view) = view
                 && !buf.buf_cmd.is_empty()
             {
-                let data = view.collect_data().into_iter().map(|(text, _)| text);
-                buf.search_results = search_pattern(data, &buf.buf_cmd, buf.is_reversed);
-                buf.search_index = 0;
+                let pattern = buf.buf_cmd.clone();
+                start_search(buf, view, &pattern, true);
+            }
 
-                if !buf.search_results.is_empty() {
-                    let pos = buf.search_results[buf.search_index];
-                    view.show_data(pos);
-                }
+            true
+        }
+        KeyCode::Tab => {
+            buf.is_fuzzy = !buf.is_fuzzy;
+
+            if let Some(view) = view
+                && !buf.buf_cmd_input.is_empty()
+            {
+                let pattern = buf.buf_cmd_input.clone();
+                start_search(buf, view, &pattern, true);
             }
 
             true
@@ -925,14 +1665,8 @@ fn search_input_key_event(
                 if let Some(view) = view
                     && !buf.buf_cmd_input.is_empty()
                 {
-                    let data = view.collect_data().into_iter().map(|(text, _)| text);
-                    buf.search_results = search_pattern(data, &buf.buf_cmd_input, buf.is_reversed);
-                    buf.search_index = 0;
-
-                    if !buf.search_results.is_empty() {
-                        let pos = buf.search_results[buf.search_index];
-                        view.show_data(pos);
-                    }
+                    let pattern = buf.buf_cmd_input.clone();
+                    start_search(buf, view, &pattern, true);
                 }
             }
 
@@ -944,14 +1678,8 @@ fn search_input_key_event(
             if let Some(view) = view
                 && !buf.buf_cmd_input.is_empty()
             {
-                let data = view.collect_data().into_iter().map(|(text, _)| text);
-                buf.search_results = search_pattern(data, &buf.buf_cmd_input, buf.is_reversed);
-                buf.search_index = 0;
-
-                if !buf.search_results.is_empty() {
-                    let pos = buf.search_results[buf.search_index];
-                    view.show_data(pos);
-                }
+                let pattern = buf.buf_cmd_input.clone();
+                start_search(buf, view, &pattern, true);
             }
 
             true
@@ -960,31 +1688,91 @@ fn search_input_key_event(
     }
 }
 
-fn search_pattern(data: impl Iterator<Item = String>, pat: &str, rev: bool) -> Vec<usize> {
-    let mut matches = Vec::new();
-    for (row, text) in data.enumerate() {
-        if text.contains(pat) {
-            matches.push(row);
+/// Search the view for `pattern`, showing the first match if `show_first`; a search of many
+/// lines goes on in the background, replacing one still running. Fuzzy matches come the
+/// closest first. Views searching their data themselves, like bytes, do so instead.
+fn start_search(buf: &mut SearchBuf, view: &mut impl View, pattern: &str, show_first: bool) {
+    buf.job = None;
+    buf.search_index = 0;
+
+    if let Some(mut matches) = view.search(pattern) {
+        if buf.is_reversed {
+            matches.reverse();
         }
+        buf.search_results = matches;
+    } else {
+        search_lines(buf, view, pattern, show_first);
     }
 
-    if rev {
-        matches.reverse();
+    view.show_matches(&buf.search_results);
+    if show_first && let Some(&row) = buf.search_results.first() {
+        view.show_data(row);
     }
+}
 
-    matches
+// search the lines of `collect_data`, in the background if there are many
+fn search_lines(buf: &mut SearchBuf, view: &mut impl View, pattern: &str, show_first: bool) {
+    let matcher = buf.matcher(pattern);
+
+    let lines: Vec<_> = view
+        .collect_data()
+        .into_iter()
+        .map(|(text, _)| text)
+        .collect();
+    if lines.len() > search::BACKGROUND_LINES {
+        buf.search_results.clear();
+        buf.job = Some(SearchJob::spawn(lines, matcher, buf.is_reversed));
+        buf.show_first = show_first;
+    } else {
+        buf.search_results = search::search(lines.into_iter(), &matcher, buf.is_reversed);
+    }
 }
 
-fn cmd_input_key_event(buf: &mut CommandBuf, key: &KeyEvent) -> bool {
+/// Take the matches a background search found since the last frame, jumping to the first one.
+fn poll_search(buf: &mut SearchBuf, view: Option<&mut Box<dyn View>>) {
+    let Some(job) = &mut buf.job else {
+        return;
+    };
+
+    let matches = job.poll().then(|| job.matches());
+    if job.is_done() {
+        buf.job = None;
+    }
+    let Some(matches) = matches else {
+        return;
+    };
+
+    let first = buf.search_results.is_empty();
+    buf.search_results = matches;
+    if let Some(view) = view {
+        view.show_matches(&buf.search_results);
+        if first && buf.show_first {
+            view.show_data(buf.search_results[0]);
+        }
+    }
+}
+
+fn cmd_input_key_event(buf: &mut CommandBuf, view: Option<&mut impl View>, key: &KeyEvent) -> bool {
+    if key.code == KeyCode::Tab {
+        complete_cmd_input(buf, view);
+        return true;
+    }
+
+    if let Some(completion) = &mut buf.completion {
+        completion.is_cycling = false;
+    }
+
     match &key.code {
         KeyCode::Esc => {
             buf.is_cmd_input = false;
+            buf.completion = None;
             buf.buf_cmd2 = String::new();
             buf.cursor_pos = 0;
             true
         }
         KeyCode::Enter => {
             buf.is_cmd_input = false;
+            buf.completion = None;
             buf.run_cmd = true;
             buf.cmd_history.push(buf.buf_cmd2.clone());
             buf.cmd_history_pos = buf.cmd_history.len();
@@ -1055,6 +1843,84 @@ fn cmd_input_key_event(buf: &mut CommandBuf, key: &KeyEvent) -> bool {
     }
 }
 
+/// Complete the value of a `:filter <column> <value>` command, the name of a `:col <name>`
+/// one, or the word typed last of others; repeated presses cycle through the candidates.
+fn complete_cmd_input(buf: &mut CommandBuf, view: Option<&mut impl View>) {
+    let line = &buf.buf_cmd2;
+    let (target, value_start) = match ColCmd::split_partial(line) {
+        Some(value_start) => (Target::Columns, value_start),
+        None => match FilterCmd::split_partial(line) {
+            Some((column, value_start)) => (Target::Values(column), value_start),
+            None => {
+                let start = Completer::word_start(line);
+                (Target::Word(start), start)
+            }
+        },
+    };
+
+    // the candidates for a word depend on all of it, so they're only kept while cycling
+    let completion = match buf.completion.take() {
+        Some(completion)
+            if completion.target == target
+                && (completion.is_cycling || !matches!(target, Target::Word(_))) =>
+        {
+            completion
+        }
+        _ => {
+            let view = view.and_then(|view| view.as_record_view());
+            let values = match (&target, view) {
+                (Target::Values(column), Some(view)) => {
+                    view.column_values(column, FilterCmd::COMPLETION_LIMIT)
+                }
+                (Target::Columns, Some(view)) => view.column_names().to_vec(),
+                (Target::Word(_), view) => buf.completer.words(line, || {
+                    view.map(|view| view.column_names().to_vec())
+                        .unwrap_or_default()
+                }),
+                (_, None) => Vec::new(),
+            };
+            Completion {
+                target,
+                values,
+                matches: Vec::new(),
+                index: 0,
+                is_cycling: false,
+            }
+        }
+    };
+
+    let mut completion = completion;
+    if completion.is_cycling && !completion.matches.is_empty() {
+        completion.index = (completion.index + 1) % completion.matches.len();
+    } else {
+        let typed = &buf.buf_cmd2[value_start..];
+        completion.matches = match completion.target {
+            Target::Values(_) => completion
+                .values
+                .iter()
+                .filter(|value| value.starts_with(typed))
+                .cloned()
+                .collect(),
+            Target::Columns => ColCmd::fuzzy_matches(&completion.values, typed)
+                .into_iter()
+                .cloned()
+                .collect(),
+            Target::Word(_) => completion.values.clone(),
+        };
+        completion.index = 0;
+        completion.is_cycling = true;
+    }
+
+    if let Some(value) = completion.matches.get(completion.index) {
+        buf.buf_cmd2.truncate(value_start);
+        buf.buf_cmd2.push_str(value);
+        buf.cursor_pos = buf.buf_cmd2.len();
+        buf.cmd_history_allow = false;
+    }
+
+    buf.completion = Some(completion);
+}
+
 fn report_level_style(level: Severity, config: &ExploreConfig) -> NuStyle {
     match level {
         Severity::Info => config.status_info,
@@ -1069,6 +1935,10 @@ pub struct ViewInfo {
     pub cursor: Option<Position>,
     pub status: Option<Report>,
     pub report: Option<Report>,
+    /// What the right of the status bar shows
+    pub status_mode: StatusMode,
+    /// The pipelines run in `:try` since the last event, for the pager to keep
+    pub tried: Vec<String>,
 }
 
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -1085,21 +1955,45 @@ impl Position {
 
 pub struct Page {
     pub view: Box<dyn View>,
-    /// Controls what happens when this view is the current view and a new view is created.
-    /// If true, view will be pushed to the stack, otherwise, it will be deleted.
-    pub stackable: bool,
+    pub policy: PagePolicy,
 }
 
 impl Page {
-    pub fn raw(view: Box<dyn View>, stackable: bool) -> Self {
-        Self { view, stackable }
+    pub fn raw(view: Box<dyn View>, policy: PagePolicy) -> Self {
+        Self { view, policy }
     }
 
-    pub fn new<V>(view: V, stackable: bool) -> Self
+    pub fn new<V>(view: V, policy: PagePolicy) -> Self
     where
         V: View + 'static,
     {
-        Self::raw(Box::new(view), stackable)
+        Self::raw(Box::new(view), policy)
+    }
+}
+
+/// What happens to a page when it's the current one and a command runs.
+///
+/// Commands declare the policy of the pages they open, which can be overridden per
+/// command in `$env.config.explore.pages`, e.g. `{ help: stack }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PagePolicy {
+    /// The page is closed when a new one is opened, which takes its place.
+    Replace,
+    /// The page is kept under a new one, and shown again when that one is closed.
+    Stack,
+    /// The page is shown over the others: it's closed before any command runs, so the
+    /// command acts on the page underneath.
+    Modal,
+}
+
+impl PagePolicy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "replace" => Some(Self::Replace),
+            "stack" => Some(Self::Stack),
+            "modal" => Some(Self::Modal),
+            _ => None,
+        }
     }
 }
 
@@ -1119,16 +2013,95 @@ impl ViewStack {
 
 struct CmdResult {
     exit: bool,
-    view_change: bool,
     cmd_name: String,
 }
 
 impl CmdResult {
-    fn new(exit: bool, view_change: bool, cmd_name: String) -> Self {
-        Self {
-            exit,
-            view_change,
-            cmd_name,
-        }
+    fn new(exit: bool, cmd_name: String) -> Self {
+        Self { exit, cmd_name }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{bench::synthesize_table, nu_common::create_lscolors, views::RecordView};
+    use super::trace::TraceEvent;
+    use super::*;
+    use ratatui::{backend::TestBackend, buffer::Buffer};
+
+    // the last frame of a session on a table, with `keys` pressed and then Ctrl-Q
+    fn replay_session(keys: &[KeyCode]) -> Buffer {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        let nu_config = NuConfig::default();
+        let explore_config = ExploreConfig {
+            status_mode: StatusMode::Perf,
+            synchronized_output: false,
+            ..Default::default()
+        };
+        let colors = stack.clone();
+        let style_computer = StyleComputer::from_config(&engine_state, &colors);
+        let (lscolors, _) = create_lscolors(&engine_state, &colors);
+        let config = PagerConfig::new(
+            &nu_config,
+            &explore_config,
+            &style_computer,
+            &lscolors,
+            false,
+            false,
+            "",
+        );
+        let mut pager = Pager::new(config);
+
+        let size = deterministic::terminal_size().unwrap();
+        let mut events = UIEvents::new(size);
+        let quit = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        let keys = keys.iter().map(|&code| KeyEvent::from(code)).chain([quit]);
+        events.replay(Trace {
+            size,
+            events: keys.map(TraceEvent::Key).collect(),
+        });
+
+        let mut term = ratatui::Terminal::new(TestBackend::new(size.0, size.1)).unwrap();
+        let mut info = ViewInfo {
+            status: Some(Report::default()),
+            status_mode: explore_config.status_mode,
+            ..Default::default()
+        };
+        let (columns, rows) = synthesize_table(100, 8);
+        let view = RecordView::new(columns, rows, explore_config.clone());
+        render_ui(
+            &mut term,
+            &engine_state,
+            &mut stack,
+            &mut pager,
+            &events,
+            &mut info,
+            Some(Page::new(view, PagePolicy::Replace)),
+            CommandRegistry::new(),
+        )
+        .unwrap();
+
+        term.backend().buffer().clone()
+    }
+
+    #[test]
+    fn deterministic_sessions_draw_the_same() {
+        deterministic::enable((60, 16));
+        let keys = [
+            KeyCode::Char('i'),
+            KeyCode::Down,
+            KeyCode::Down,
+            KeyCode::Right,
+        ];
+        let first = replay_session(&keys);
+        let second = replay_session(&keys);
+        deterministic::disable();
+
+        assert_eq!(first.area, Rect::new(0, 0, 60, 16));
+        assert_eq!(first, second);
+        // frames take no time on a clock only moved on by ticks
+        let screen: String = first.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("0.0ms"), "{screen}");
     }
 }
@@ -0,0 +1,203 @@
+//! The programs explore starts, for `:open`. One in the foreground is handed the terminal
+//! until it ends; those in the background have the lines they write shown in the status bar
+//! as they come. Each is waited for, so that none is left a zombie, and how it ended is told.
+
+use super::super::l10n::tr;
+use super::{report::Report, terminal};
+use anyhow::{Result, anyhow};
+use nu_protocol::engine::EngineState;
+use std::{
+    ffi::OsStr,
+    io::{BufRead, BufReader, Read},
+    mem,
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+/// The programs started in the background and still running.
+#[derive(Debug, Default)]
+pub struct Processes {
+    running: Vec<Background>,
+}
+
+#[derive(Debug)]
+struct Background {
+    name: String,
+    child: Child,
+    // the lines it writes, to its output and errors, read on threads
+    lines: Receiver<String>,
+}
+
+impl Processes {
+    /// Run `command` with the terminal handed to it, the pager being set up again once it
+    /// ended; fails if it can't start or ends with an error.
+    pub fn run(&mut self, engine_state: &EngineState, mut command: Command) -> Result<()> {
+        let name = program_name(&command);
+        let interrupted = engine_state.signals().interrupted();
+        let status = terminal::handed_back(|| command.status())?;
+        // Ctrl-C while it ran was meant for it, not for the pager
+        if !interrupted {
+            engine_state.signals().reset();
+        }
+
+        let status = status
+            .map_err(|err| anyhow!(tr!("Can't run {name}: {err}", name = name, err = err)))?;
+        match failure(&name, status) {
+            Some(failure) => Err(anyhow!(failure)),
+            None => Ok(()),
+        }
+    }
+
+    /// Start `command` in the background, the lines it writes and how it ends being told by
+    /// [`Processes::poll`].
+    pub fn spawn(&mut self, mut command: Command) -> Result<()> {
+        let name = program_name(&command);
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| anyhow!(tr!("Can't run {name}: {err}", name = name, err = err)))?;
+
+        let (sender, lines) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            read_lines(stdout, sender.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            read_lines(stderr, sender);
+        }
+        self.running.push(Background { name, child, lines });
+
+        Ok(())
+    }
+
+    /// What the programs in the background did since the last poll: the last line one wrote
+    /// or, as each that ended is reaped, how it did; `None` if nothing happened.
+    pub fn poll(&mut self) -> Option<Report> {
+        let mut report = None;
+        self.running.retain_mut(|process| {
+            if let Some(line) = process.lines.try_iter().last() {
+                report = Some(Report::info(format!("{}: {line}", process.name)));
+            }
+
+            let status = match process.child.try_wait() {
+                Ok(None) => return true,
+                Ok(Some(status)) => status,
+                Err(err) => {
+                    report = Some(Report::error(tr!(
+                        "Can't wait for {name}: {err}",
+                        name = process.name,
+                        err = err
+                    )));
+                    return false;
+                }
+            };
+            report = Some(match failure(&process.name, status) {
+                Some(failure) => Report::error(failure),
+                None => Report::success(tr!("{name} is done", name = process.name)),
+            });
+            false
+        });
+
+        report
+    }
+
+    /// How many programs run in the background.
+    pub fn count(&self) -> usize {
+        self.running.len()
+    }
+}
+
+impl Drop for Processes {
+    fn drop(&mut self) {
+        // those still running once explore is left are waited for all the same
+        let running = mem::take(&mut self.running);
+        if !running.is_empty() {
+            thread::spawn(move || {
+                for mut process in running {
+                    let _ = process.child.wait();
+                }
+            });
+        }
+    }
+}
+
+// send each line `pipe` gives until it's closed, which its program does as it ends
+fn read_lines(pipe: impl Read + Send + 'static, sender: Sender<String>) {
+    thread::spawn(move || {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+fn program_name(command: &Command) -> String {
+    let program = command.get_program();
+    std::path::Path::new(program)
+        .file_name()
+        .unwrap_or(OsStr::new(program))
+        .to_string_lossy()
+        .into_owned()
+}
+
+// what went wrong with `name`, unless it succeeded
+fn failure(name: &str, status: ExitStatus) -> Option<String> {
+    if status.success() {
+        return None;
+    }
+
+    Some(match status.code() {
+        Some(code) => tr!("{name} exited with {code}", name = name, code = code),
+        None => tr!("{name} was stopped by a signal", name = name),
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::super::report::Severity;
+    use super::*;
+
+    // the reports of the programs in the background, until none is left
+    fn wait_for_all(processes: &mut Processes) -> Vec<Report> {
+        let mut reports = Vec::new();
+        while processes.count() > 0 {
+            reports.extend(processes.poll());
+            thread::yield_now();
+        }
+        reports
+    }
+
+    #[test]
+    fn background_programs_are_reaped() {
+        let mut processes = Processes::default();
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo hello; exit 3"]);
+        processes.spawn(command).unwrap();
+
+        let reports = wait_for_all(&mut processes);
+        let last = reports.last().unwrap();
+        assert!(matches!(last.level, Severity::Err));
+        assert!(last.message.contains("sh exited with 3"));
+
+        let mut command = Command::new("true");
+        command.arg("ok");
+        processes.spawn(command).unwrap();
+        let reports = wait_for_all(&mut processes);
+        assert!(matches!(reports.last().unwrap().level, Severity::Success));
+    }
+
+    #[test]
+    fn foreground_program_fails_with_its_exit_code() {
+        let engine_state = EngineState::new();
+        let mut processes = Processes::default();
+        let mut command = Command::new("sh");
+        command.args(["-c", "exit 2"]);
+
+        let err = processes.run(&engine_state, command).unwrap_err();
+        assert!(err.to_string().contains("sh exited with 2"));
+        assert!(processes.run(&engine_state, Command::new("true")).is_ok());
+    }
+}
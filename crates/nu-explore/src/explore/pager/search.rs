@@ -0,0 +1,273 @@
+use super::fuzzy::fuzzy_match;
+use fancy_regex::Regex;
+use std::{
+    ops::Range,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+};
+
+/// Searches of more lines than this run on a background thread, so the UI stays responsive.
+pub const BACKGROUND_LINES: usize = 50_000;
+
+// lines searched between two batches of matches sent to the UI
+const CHUNK: usize = 4096;
+
+/// What `/` and `?` look for: a regular expression, or the text as typed if it isn't one
+/// (yet, while it's being typed); or, in fuzzy mode, the characters of the text in order.
+#[derive(Debug, Clone)]
+pub enum Matcher {
+    Regex(Regex),
+    Text(String),
+    Fuzzy(String),
+}
+
+impl Matcher {
+    pub fn new(pattern: &str) -> Self {
+        match Regex::new(pattern) {
+            Ok(regex) => Self::Regex(regex),
+            Err(_) => Self::Text(pattern.to_string()),
+        }
+    }
+
+    pub fn fuzzy(pattern: &str) -> Self {
+        Self::Fuzzy(pattern.to_string())
+    }
+
+    /// How close `text` is to what's looked for, if it matches: every match of a regex or
+    /// a text is as close as another, fuzzy matches are scored.
+    pub fn score(&self, text: &str) -> Option<i64> {
+        match self {
+            Self::Regex(regex) => regex.is_match(text).unwrap_or(false).then_some(0),
+            Self::Text(pattern) => text.contains(pattern.as_str()).then_some(0),
+            Self::Fuzzy(pattern) => fuzzy_match(pattern, text).map(|found| found.score),
+        }
+    }
+
+    /// The bytes of every match in `text`, in order; empty matches are left out.
+    pub fn find_all(&self, text: &str) -> Vec<Range<usize>> {
+        let found: Vec<Range<usize>> = match self {
+            Self::Regex(regex) => regex
+                .find_iter(text)
+                .filter_map(Result::ok)
+                .map(|found| found.range())
+                .collect(),
+            Self::Text(pattern) if pattern.is_empty() => Vec::new(),
+            Self::Text(pattern) => text
+                .match_indices(pattern.as_str())
+                .map(|(start, found)| start..start + found.len())
+                .collect(),
+            Self::Fuzzy(pattern) => fuzzy_match(pattern, text)
+                .map(|found| found.ranges)
+                .unwrap_or_default(),
+        };
+
+        found
+            .into_iter()
+            .filter(|found| !found.is_empty())
+            .collect()
+    }
+}
+
+/// The rows of `lines` the `matcher` matches, the closest first, then the last first if
+/// `reversed`.
+pub fn search(
+    lines: impl Iterator<Item = String>,
+    matcher: &Matcher,
+    reversed: bool,
+) -> Vec<usize> {
+    let mut matches: Vec<(usize, i64)> = lines
+        .enumerate()
+        .filter_map(|(row, text)| matcher.score(&text).map(|score| (row, score)))
+        .collect();
+
+    if reversed {
+        matches.reverse();
+    }
+
+    rank(matches)
+}
+
+// the rows by score, the highest first, keeping the order of those of the same score
+fn rank(mut matches: Vec<(usize, i64)>) -> Vec<usize> {
+    matches.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    matches.into_iter().map(|(row, _)| row).collect()
+}
+
+/// A search running on a background thread, its matches sent in batches as they're found.
+/// Dropping it cancels the search.
+#[derive(Debug)]
+pub struct SearchJob {
+    receiver: Receiver<Found>,
+    cancelled: Arc<AtomicBool>,
+    total: usize,
+    scanned: usize,
+    // the matches so far, with their scores, in the order of the search
+    found: Vec<(usize, i64)>,
+}
+
+#[derive(Debug)]
+struct Found {
+    rows: Vec<(usize, i64)>,
+    // lines searched so far
+    scanned: usize,
+}
+
+impl SearchJob {
+    /// Search `lines` with `matcher`, from the last one back if `reversed`, like [`search`].
+    pub fn spawn(lines: Vec<String>, matcher: Matcher, reversed: bool) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let total = lines.len();
+
+        let stop = cancelled.clone();
+        thread::spawn(move || {
+            let order: Box<dyn Iterator<Item = usize>> = if reversed {
+                Box::new((0..lines.len()).rev())
+            } else {
+                Box::new(0..lines.len())
+            };
+
+            let mut rows = Vec::new();
+            for (scanned, row) in order.enumerate() {
+                if scanned > 0 && scanned % CHUNK == 0 {
+                    if stop.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let rows = std::mem::take(&mut rows);
+                    if sender.send(Found { rows, scanned }).is_err() {
+                        return;
+                    }
+                }
+
+                if let Some(score) = matcher.score(&lines[row]) {
+                    rows.push((row, score));
+                }
+            }
+
+            let _ = sender.send(Found {
+                rows,
+                scanned: lines.len(),
+            });
+        });
+
+        Self {
+            receiver,
+            cancelled,
+            total,
+            scanned: 0,
+            found: Vec::new(),
+        }
+    }
+
+    /// Take the matches found since the last poll, telling whether there were any.
+    pub fn poll(&mut self) -> bool {
+        let known = self.found.len();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(found) => {
+                    self.found.extend(found.rows);
+                    self.scanned = found.scanned;
+                }
+                Err(TryRecvError::Empty) => break,
+                // the thread is gone, there's nothing more to come
+                Err(TryRecvError::Disconnected) => {
+                    self.scanned = self.total;
+                    break;
+                }
+            }
+        }
+
+        self.found.len() > known
+    }
+
+    /// The matches found so far, the closest first, like [`search`].
+    pub fn matches(&self) -> Vec<usize> {
+        rank(self.found.clone())
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.scanned == self.total
+    }
+
+    /// How much of the lines were searched, in percent.
+    pub fn progress(&self) -> usize {
+        (self.scanned * 100).checked_div(self.total).unwrap_or(100)
+    }
+}
+
+impl Drop for SearchJob {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+    use nu_utils::time::Instant;
+    use std::time::Duration;
+
+    fn lines(count: usize) -> Vec<String> {
+        (0..count).map(|n| format!("line {n}")).collect()
+    }
+
+    #[test]
+    fn patterns_are_regexes_or_text() {
+        let regex = Matcher::new(r"line \d+7$");
+        assert!(regex.score("line 17").is_some());
+        assert!(regex.score("line 71").is_none());
+        assert_eq!(regex.find_all("a line 27, line 37"), [11..18]);
+
+        // not a regex, at least not yet
+        let text = Matcher::new("line (");
+        assert!(text.score("a line (1)").is_some());
+        assert_eq!(text.find_all("a line (1)"), [2..8]);
+
+        // empty matches have nothing to highlight
+        assert!(Matcher::new("x*").find_all("abc").is_empty());
+
+        let fuzzy = Matcher::fuzzy("lne7");
+        assert!(fuzzy.score("line 17").is_some());
+        assert_eq!(fuzzy.find_all("a line 17"), [2..3, 4..6, 8..9]);
+    }
+
+    #[test]
+    fn fuzzy_search_ranks_closest_first() {
+        let lines = ["nxuxsxh", "nushell", "bash", "annushell"].map(String::from);
+        let matcher = Matcher::fuzzy("nush");
+        assert_eq!(
+            search(lines.clone().into_iter(), &matcher, false),
+            [1, 3, 0]
+        );
+
+        // a regex or a text finds rows in order
+        let matcher = Matcher::new("sh");
+        assert_eq!(search(lines.into_iter(), &matcher, true), [3, 2, 1]);
+    }
+
+    #[test]
+    fn background_search_finds_what_search_does() {
+        let lines = lines(3 * CHUNK + 7);
+        for reversed in [false, true] {
+            for matcher in [Matcher::new("7$"), Matcher::fuzzy("l17")] {
+                let expected = search(lines.clone().into_iter(), &matcher, reversed);
+
+                let mut job = SearchJob::spawn(lines.clone(), matcher, reversed);
+                let deadline = Instant::now() + Duration::from_secs(10);
+                while !job.is_done() && Instant::now() < deadline {
+                    job.poll();
+                    thread::sleep(Duration::from_millis(1));
+                }
+                job.poll();
+
+                assert_eq!(job.matches(), expected);
+                assert_eq!(job.progress(), 100);
+            }
+        }
+    }
+}
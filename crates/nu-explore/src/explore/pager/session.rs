@@ -0,0 +1,79 @@
+use nu_protocol::{Record, Span, Value};
+use std::time::Duration;
+
+/// How a session ended, set as `$env.EXPLORE_LAST` so scripts wrapping explore can tell.
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    pub exit: ExitAction,
+    /// What the view left open tells of itself: its filters, the size of the selection, ...
+    pub details: Record,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExitAction {
+    /// With `q`, `:q` or a key bound to quitting
+    #[default]
+    Quit,
+    /// With `Esc` out of the first view
+    Back,
+    /// With Ctrl-C
+    Interrupted,
+    /// The session failed
+    Error,
+}
+
+impl ExitAction {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::Back => "back",
+            Self::Interrupted => "interrupted",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl Session {
+    /// The record of `$env.EXPLORE_LAST`, for a session that took `elapsed` and output a
+    /// value if `peeked`.
+    pub fn to_value(&self, elapsed: Duration, peeked: bool, span: Span) -> Value {
+        let mut record = Record::new();
+        record.push("exit", Value::string(self.exit.name(), span));
+        record.push(
+            "elapsed",
+            Value::duration(elapsed.as_nanos().try_into().unwrap_or(i64::MAX), span),
+        );
+        record.push("peeked", Value::bool(peeked, span));
+        for (name, value) in self.details.iter() {
+            record.push(name, value.clone().with_span(span));
+        }
+
+        Value::record(record, span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_as_a_record() {
+        let span = Span::test_data();
+        let mut details = Record::new();
+        details.push("rows", Value::int(3, Span::unknown()));
+        let session = Session {
+            exit: ExitAction::Back,
+            details,
+        };
+
+        let value = session.to_value(Duration::from_millis(1500), false, span);
+        let record = value.as_record().unwrap();
+        assert_eq!(record.get("exit"), Some(&Value::string("back", span)));
+        assert_eq!(
+            record.get("elapsed"),
+            Some(&Value::duration(1_500_000_000, span))
+        );
+        assert_eq!(record.get("peeked"), Some(&Value::bool(false, span)));
+        assert_eq!(record.get("rows"), Some(&Value::int(3, span)));
+    }
+}
@@ -4,27 +4,33 @@ use ratatui::{
     style::{Modifier, Style},
     widgets::{Block, Widget},
 };
+use std::fmt::Display;
 
 use super::super::{
+    l10n::tr,
     nu_common::{NuStyle, string_width},
     views::util::{nu_style_to_tui, set_span},
 };
 
 pub struct StatusBar {
     text: (String, Style),
-    ctx1: (String, Style),
-    ctx2: (String, Style),
-    ctx3: (String, Style),
+    // left to right
+    contexts: Vec<(String, Style)>,
     back_s: Style,
 }
 
 impl StatusBar {
     pub fn new(text: String, ctx1: String, ctx2: String, ctx3: String) -> Self {
+        Self::with_contexts(text, vec![ctx1, ctx2, ctx3])
+    }
+
+    pub fn with_contexts(text: String, contexts: Vec<String>) -> Self {
         Self {
             text: (text, Style::default()),
-            ctx1: (ctx1, Style::default()),
-            ctx2: (ctx2, Style::default()),
-            ctx3: (ctx3, Style::default()),
+            contexts: contexts
+                .into_iter()
+                .map(|text| (text, Style::default()))
+                .collect(),
             back_s: Style::default(),
         }
     }
@@ -33,20 +39,107 @@ impl StatusBar {
         self.text.1 = nu_style_to_tui(style).add_modifier(Modifier::BOLD);
     }
 
-    pub fn set_ctx1_style(&mut self, style: NuStyle) {
-        self.ctx1.1 = nu_style_to_tui(style);
+    pub fn set_context_style(&mut self, style: NuStyle) {
+        let style = nu_style_to_tui(style);
+        for (_, context) in &mut self.contexts {
+            *context = style;
+        }
     }
 
-    pub fn set_ctx2_style(&mut self, style: NuStyle) {
-        self.ctx2.1 = nu_style_to_tui(style);
+    pub fn set_background_style(&mut self, style: NuStyle) {
+        self.back_s = nu_style_to_tui(style);
     }
+}
 
-    pub fn set_ctx3_style(&mut self, style: NuStyle) {
-        self.ctx3.1 = nu_style_to_tui(style);
+/// What the right of the status bar is about, switched with `s`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatusMode {
+    /// Where the cursor is, as each view reports it
+    #[default]
+    Position,
+    /// The selected cell or range
+    Selection,
+    /// How long frames take to draw
+    Perf,
+    /// Where the data comes from
+    Source,
+}
+
+impl StatusMode {
+    pub const ALL: [Self; 4] = [Self::Position, Self::Selection, Self::Perf, Self::Source];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|mode| *mode == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
     }
 
-    pub fn set_background_style(&mut self, style: NuStyle) {
-        self.back_s = nu_style_to_tui(style);
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Position => "position",
+            Self::Selection => "selection",
+            Self::Perf => "perf",
+            Self::Source => "source",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(name))
+    }
+
+    // the first segment, so it's clear what the others are
+    fn label(self) -> String {
+        match self {
+            Self::Position => tr!("POS"),
+            Self::Selection => tr!("SEL"),
+            Self::Perf => tr!("PERF"),
+            Self::Source => tr!("SRC"),
+        }
+    }
+}
+
+/// The bits of information shown on the right of the status bar in a [`StatusMode`], which
+/// the pager and the view each add to with [`View::status_segments`].
+///
+/// [`View::status_segments`]: super::super::views::View::status_segments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusSegments {
+    mode: StatusMode,
+    segments: Vec<String>,
+}
+
+impl StatusSegments {
+    pub fn new(mode: StatusMode) -> Self {
+        Self {
+            mode,
+            segments: Vec::new(),
+        }
+    }
+
+    pub fn mode(&self) -> StatusMode {
+        self.mode
+    }
+
+    /// Add a segment like `draw 2ms`.
+    pub fn push(&mut self, label: &str, value: impl Display) {
+        self.segments.push(format!("{label} {value}"));
+    }
+
+    /// Add a segment which is a value alone.
+    pub fn push_text(&mut self, text: impl Into<String>) {
+        self.segments.push(text.into());
+    }
+
+    /// The contexts of the status bar: the mode, then the segments in the order they came.
+    pub fn into_contexts(self) -> Vec<String> {
+        let mut contexts = vec![self.mode.label()];
+        if self.segments.is_empty() {
+            contexts.push(String::from("–"));
+        }
+        contexts.extend(self.segments);
+
+        contexts
     }
 }
 
@@ -67,14 +160,13 @@ impl Widget for StatusBar {
         let mut used_width: u16 = 0;
 
         // Collect non-empty context items
-        let contexts: Vec<(&String, Style)> = [
-            (&self.ctx3.0, self.ctx3.1),
-            (&self.ctx2.0, self.ctx2.1),
-            (&self.ctx1.0, self.ctx1.1),
-        ]
-        .into_iter()
-        .filter(|(text, _)| !text.is_empty())
-        .collect();
+        let contexts: Vec<(&String, Style)> = self
+            .contexts
+            .iter()
+            .rev()
+            .filter(|(text, _)| !text.is_empty())
+            .map(|(text, style)| (text, *style))
+            .collect();
 
         // Render context items from right to left
         for (i, (text, style)) in contexts.iter().enumerate() {
@@ -314,6 +406,34 @@ mod tests {
         assert!(content.contains('│'));
     }
 
+    #[test]
+    fn test_status_modes() {
+        let mut mode = StatusMode::default();
+        let mut seen = Vec::new();
+        for _ in 0..StatusMode::ALL.len() {
+            seen.push(mode.name());
+            mode = mode.next();
+        }
+        assert_eq!(seen, ["position", "selection", "perf", "source"]);
+        assert_eq!(mode, StatusMode::Position);
+        assert_eq!(StatusMode::from_name("Perf"), Some(StatusMode::Perf));
+        assert_eq!(StatusMode::from_name("speed"), None);
+    }
+
+    #[test]
+    fn test_status_segments_shown_in_order() {
+        let mut segments = StatusSegments::new(StatusMode::Perf);
+        segments.push("draw", "2ms");
+        segments.push_text("120 fps");
+        let status_bar = StatusBar::with_contexts(String::new(), segments.into_contexts());
+        let content = buffer_to_string(&render_status_bar(status_bar, 60, 1));
+
+        let perf = content.find("PERF").unwrap();
+        let draw = content.find("draw 2ms").unwrap();
+        let fps = content.find("120 fps").unwrap();
+        assert!(perf < draw && draw < fps);
+    }
+
     #[test]
     fn test_status_bar_zero_height() {
         // Zero height buffer - just verify we can create the area
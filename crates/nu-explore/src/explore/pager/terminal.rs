@@ -0,0 +1,203 @@
+use anyhow::Result;
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{
+        Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+        enable_raw_mode,
+    },
+};
+use nu_protocol::{HandlerGuard, engine::EngineState};
+use std::{
+    io,
+    panic::{self, PanicHookInfo},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Send + Sync>;
+
+// set while the terminal is set up for the pager, so that it's restored once
+static IS_SET_UP: AtomicBool = AtomicBool::new(false);
+
+// whether the mouse is captured, set up again after a suspend as it was at first
+static WITH_MOUSE: AtomicBool = AtomicBool::new(false);
+
+// set when the terminal was handed back for a while, as nothing drawn on it is left then
+static WAS_LEFT: AtomicBool = AtomicBool::new(false);
+
+/// Keeps the terminal in raw mode on the alternate screen until dropped.
+///
+/// A panic restores the terminal before its message is printed, and so does an interrupt,
+/// instead of leaving the shell unusable.
+///
+/// With `mouse`, the pager gets the clicks, drags and wheel scrolls, which the terminal
+/// would otherwise use to select text.
+pub struct TerminalGuard {
+    previous_hook: Option<Arc<PanicHook>>,
+    _interrupt: Option<HandlerGuard>,
+}
+
+impl TerminalGuard {
+    pub fn new(engine_state: &EngineState, mouse: bool) -> Result<Self> {
+        let previous_hook = Arc::new(panic::take_hook());
+        let hook = Arc::clone(&previous_hook);
+        panic::set_hook(Box::new(move |info| {
+            restore();
+            hook(info);
+        }));
+
+        let interrupt = match &engine_state.signal_handlers {
+            Some(handlers) => Some(handlers.register(Box::new(|_| restore()))?),
+            None => None,
+        };
+
+        // created before touching the terminal, so a failure below undoes the rest
+        let guard = Self {
+            previous_hook: Some(previous_hook),
+            _interrupt: interrupt,
+        };
+        WITH_MOUSE.store(mouse, Ordering::SeqCst);
+        set_up()?;
+
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore();
+
+        // the hook can't be changed while panicking; ours is harmless once the terminal is back
+        if !std::thread::panicking()
+            && let Some(previous_hook) = self.previous_hook.take()
+        {
+            // dropping ours leaves the previous hook to us alone, to put back as it was
+            drop(panic::take_hook());
+            match Arc::try_unwrap(previous_hook) {
+                Ok(previous_hook) => panic::set_hook(previous_hook),
+                Err(previous_hook) => panic::set_hook(Box::new(move |info| previous_hook(info))),
+            }
+        }
+    }
+}
+
+/// Hand the terminal back and stop the process, as Ctrl-Z does outside of raw mode;
+/// once resumed with `fg`, the terminal is set up again.
+///
+/// Nothing happens when no shell does job control for nushell, as none could resume it.
+///
+/// Whatever was drawn is gone by then, so everything needs to be drawn again.
+#[cfg(unix)]
+pub fn suspend() -> Result<()> {
+    use nix::sys::signal::{SaFlags, SigAction, SigHandler, SigSet, Signal, raise, sigaction};
+
+    if !parent_does_job_control() {
+        return Ok(());
+    }
+
+    handed_back(|| {
+        // an interactive nushell ignores SIGTSTP, being the shell doing job control
+        let default = SigAction::new(SigHandler::SigDfl, SaFlags::empty(), SigSet::empty());
+        // SAFETY: the default action is no handler, and the previous action is put back
+        let previous = unsafe { sigaction(Signal::SIGTSTP, &default) }?;
+        let stopped = raise(Signal::SIGTSTP);
+        // SAFETY: it's the action that was in place before
+        unsafe { sigaction(Signal::SIGTSTP, &previous) }?;
+
+        Ok(stopped?)
+    })?
+}
+
+/// Whether nushell was started as a job of a shell doing job control, which can resume it.
+///
+/// Such a shell gives each job a process group of its own, in its session; a nushell started
+/// by the terminal leads a session instead, and nothing would be left to resume it.
+#[cfg(unix)]
+fn parent_does_job_control() -> bool {
+    use nix::unistd::{getpgid, getpgrp, getppid, getsid};
+
+    let parent = getppid();
+    match (getsid(None), getsid(Some(parent)), getpgid(Some(parent))) {
+        (Ok(session), Ok(parent_session), Ok(parent_group)) => {
+            is_job(session, getpgrp(), parent_session, parent_group)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(unix)]
+fn is_job(
+    session: nix::unistd::Pid,
+    group: nix::unistd::Pid,
+    parent_session: nix::unistd::Pid,
+    parent_group: nix::unistd::Pid,
+) -> bool {
+    session == parent_session && group != parent_group
+}
+
+/// Hand the terminal back while `run` runs a program which may need it, as an opener of
+/// links in the terminal does, and set it up again after.
+pub fn handed_back<T>(run: impl FnOnce() -> T) -> Result<T> {
+    if !IS_SET_UP.load(Ordering::SeqCst) {
+        return Ok(run());
+    }
+
+    restore();
+    let output = run();
+    set_up()?;
+    WAS_LEFT.store(true, Ordering::SeqCst);
+
+    Ok(output)
+}
+
+/// Whether the terminal was handed back since the last time it was asked, so that
+/// everything is drawn again.
+pub fn was_left() -> bool {
+    WAS_LEFT.swap(false, Ordering::SeqCst)
+}
+
+fn set_up() -> Result<()> {
+    enable_raw_mode()?;
+    IS_SET_UP.store(true, Ordering::SeqCst);
+    execute!(io::stdout(), EnterAlternateScreen, Clear(ClearType::All))?;
+    if WITH_MOUSE.load(Ordering::SeqCst) {
+        execute!(io::stdout(), EnableMouseCapture)?;
+    }
+
+    Ok(())
+}
+
+/// Leave raw mode and the alternate screen, if the pager still has the terminal.
+fn restore() {
+    if IS_SET_UP.swap(false, Ordering::SeqCst) {
+        let _ = disable_raw_mode();
+        let _ = execute!(
+            io::stdout(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            Show
+        );
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use nix::unistd::Pid;
+
+    #[test]
+    fn suspend_only_jobs() {
+        let pid = Pid::from_raw;
+
+        // started by a shell doing job control, in a process group of its own
+        assert!(is_job(pid(10), pid(30), pid(10), pid(20)));
+        // started by a shell without job control, in its process group
+        assert!(!is_job(pid(10), pid(20), pid(10), pid(20)));
+        // leading its session, started by the terminal
+        assert!(!is_job(pid(30), pid(30), pid(1), pid(1)));
+    }
+}
@@ -0,0 +1,201 @@
+use super::super::{
+    bindings::{MODIFIERS, key_code, key_name},
+    l10n::tr,
+};
+use anyhow::{Context, Result, anyhow, bail};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde_json::{Value as Json, json};
+use std::{fs, path::Path};
+
+/// What happened in a session, for `explore --record` and `--replay`: the keys pressed, the
+/// terminal sizes and the commands run, but nothing of the data explored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    /// The size of the terminal when the session started
+    pub size: (u16, u16),
+    pub events: Vec<TraceEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceEvent {
+    Key(KeyEvent),
+    Resize(u16, u16),
+    /// A command run from the command bar; replaying the keys that typed it runs it again,
+    /// so it's only there for whoever reads the trace
+    Command(String),
+}
+
+const VERSION: u64 = 1;
+
+impl Trace {
+    pub fn new(size: (u16, u16)) -> Self {
+        Self {
+            size,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| tr!("can't read the trace {path}", path = path.display()))?;
+        let json = serde_json::from_str(&text)
+            .with_context(|| tr!("{path} isn't a JSON trace", path = path.display()))?;
+
+        Self::from_json(&json)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let text = serde_json::to_string_pretty(&self.to_json())?;
+        fs::write(path, text)
+            .with_context(|| tr!("can't write the trace {path}", path = path.display()))
+    }
+
+    fn to_json(&self) -> Json {
+        let events: Vec<_> = self.events.iter().map(event_to_json).collect();
+
+        json!({
+            "version": VERSION,
+            "width": self.size.0,
+            "height": self.size.1,
+            "events": events,
+        })
+    }
+
+    fn from_json(json: &Json) -> Result<Self> {
+        match json.get("version").and_then(Json::as_u64) {
+            Some(VERSION) => {}
+            Some(version) => bail!(tr!(
+                "traces of version {version} aren't supported",
+                version = version
+            )),
+            None => bail!(tr!("a trace needs a version")),
+        }
+
+        let width = size_from_json(json.get("width"))?;
+        let height = size_from_json(json.get("height"))?;
+        let events = json
+            .get("events")
+            .and_then(Json::as_array)
+            .ok_or_else(|| anyhow!(tr!("a trace needs a list of events")))?
+            .iter()
+            .map(event_from_json)
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            size: (width, height),
+            events,
+        })
+    }
+}
+
+fn event_to_json(event: &TraceEvent) -> Json {
+    match event {
+        TraceEvent::Key(key) => {
+            let modifiers: Vec<_> = MODIFIERS
+                .iter()
+                .filter(|(modifier, _)| key.modifiers.contains(*modifier))
+                .map(|(_, name)| *name)
+                .collect();
+
+            if modifiers.is_empty() {
+                json!({ "key": key_name(key.code) })
+            } else {
+                json!({ "key": key_name(key.code), "modifiers": modifiers })
+            }
+        }
+        TraceEvent::Resize(width, height) => json!({ "resize": [width, height] }),
+        TraceEvent::Command(command) => json!({ "command": command }),
+    }
+}
+
+fn event_from_json(json: &Json) -> Result<TraceEvent> {
+    if let Some(name) = json.get("key").and_then(Json::as_str) {
+        let code = key_code(name).ok_or_else(|| {
+            anyhow!(tr!(
+                "unknown key {name} in the trace",
+                name = format!("{name:?}")
+            ))
+        })?;
+        let mut modifiers = KeyModifiers::NONE;
+        for name in json
+            .get("modifiers")
+            .and_then(Json::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let (modifier, _) = MODIFIERS
+                .iter()
+                .find(|(_, known)| Some(*known) == name.as_str())
+                .ok_or_else(|| anyhow!(tr!("unknown modifier {name} in the trace", name = name)))?;
+            modifiers |= *modifier;
+        }
+
+        return Ok(TraceEvent::Key(KeyEvent::new(code, modifiers)));
+    }
+
+    if let Some(size) = json.get("resize").and_then(Json::as_array)
+        && let [width, height] = size.as_slice()
+    {
+        let width = size_from_json(Some(width))?;
+        let height = size_from_json(Some(height))?;
+        return Ok(TraceEvent::Resize(width, height));
+    }
+
+    if let Some(command) = json.get("command").and_then(Json::as_str) {
+        return Ok(TraceEvent::Command(command.to_string()));
+    }
+
+    bail!(tr!("unknown event {json} in the trace", json = json))
+}
+
+fn size_from_json(json: Option<&Json>) -> Result<u16> {
+    json.and_then(Json::as_u64)
+        .and_then(|n| u16::try_from(n).ok())
+        .ok_or_else(|| anyhow!(tr!("a trace needs terminal sizes as numbers")))
+}
+
+/// Whether a key is one the pager reacts to, and so worth recording.
+pub fn is_recorded(code: KeyCode) -> bool {
+    key_name(code).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_round_trip() {
+        let trace = Trace {
+            size: (120, 40),
+            events: vec![
+                TraceEvent::Key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE)),
+                TraceEvent::Key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+                TraceEvent::Key(KeyEvent::new(
+                    KeyCode::Left,
+                    KeyModifiers::ALT | KeyModifiers::SHIFT,
+                )),
+                TraceEvent::Key(KeyEvent::new(KeyCode::F(10), KeyModifiers::NONE)),
+                TraceEvent::Resize(80, 24),
+                TraceEvent::Command("filter status open".to_string()),
+            ],
+        };
+
+        let json = trace.to_json();
+        assert_eq!(
+            json["events"][1],
+            json!({ "key": "d", "modifiers": ["ctrl"] })
+        );
+        assert_eq!(Trace::from_json(&json).unwrap(), trace);
+    }
+
+    #[test]
+    fn bad_traces_are_refused() {
+        let events =
+            |events: Json| json!({ "version": 1, "width": 80, "height": 24, "events": events });
+
+        assert!(Trace::from_json(&events(json!([{ "key": "hyper" }]))).is_err());
+        assert!(Trace::from_json(&events(json!([{ "key": "a", "modifiers": ["meta"] }]))).is_err());
+        assert!(Trace::from_json(&events(json!([{ "resize": [80] }]))).is_err());
+        assert!(Trace::from_json(&json!({ "version": 2, "width": 80, "height": 24 })).is_err());
+    }
+}
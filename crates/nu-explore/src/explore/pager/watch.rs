@@ -0,0 +1,106 @@
+//! Watching the file explored, for `explore --watch-file`.
+
+use anyhow::{Result, anyhow};
+use notify_debouncer_full::{
+    DebounceEventResult, Debouncer, FileIdMap, new_debouncer,
+    notify::{RecommendedWatcher, RecursiveMode, Watcher},
+};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    time::Duration,
+};
+
+// changes closer together than this are taken as one, like the writes of a single save
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Tells when a file changed. The directory of the file is watched rather than the file, as
+/// editors often save by writing another file and renaming it over the first.
+pub struct FileWatcher {
+    path: PathBuf,
+    events: Receiver<DebounceEventResult>,
+    // the watch ends when it's dropped
+    _debouncer: Debouncer<RecommendedWatcher, FileIdMap>,
+}
+
+impl FileWatcher {
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.canonicalize()?;
+        let dir = path
+            .parent()
+            .ok_or_else(|| anyhow!("{} has no directory", path.display()))?
+            .to_path_buf();
+
+        let (sender, events) = mpsc::channel();
+        let mut debouncer = new_debouncer(DEBOUNCE, None, sender)?;
+        debouncer
+            .watcher()
+            .watch(&dir, RecursiveMode::NonRecursive)?;
+        // renames are only told apart with the ids of the files
+        debouncer
+            .cache()
+            .add_root(&dir, RecursiveMode::NonRecursive);
+
+        Ok(Self {
+            path,
+            events,
+            _debouncer: debouncer,
+        })
+    }
+
+    /// Whether the file changed since this was last asked; it doesn't wait.
+    pub fn changed(&self) -> bool {
+        let mut changed = false;
+        while let Ok(events) = self.events.try_recv() {
+            // errors of the watcher are left out, the file is read again on its next change
+            let Ok(events) = events else {
+                continue;
+            };
+
+            changed |= events
+                .iter()
+                .any(|event| !event.kind.is_access() && event.paths.contains(&self.path));
+        }
+
+        changed
+    }
+}
+
+impl fmt::Debug for FileWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileWatcher")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_utils::time::Instant;
+    use std::{fs, thread};
+
+    #[test]
+    fn tells_when_the_file_changed() {
+        let dir = std::env::temp_dir().join(format!("explore-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data.csv");
+        fs::write(&path, "a,b\n1,2\n").unwrap();
+
+        let watcher = FileWatcher::new(&path).unwrap();
+        assert!(!watcher.changed());
+
+        fs::write(&path, "a,b\n1,2\n3,4\n").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut changed = false;
+        while !changed && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(50));
+            changed = watcher.changed();
+        }
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(changed);
+    }
+}
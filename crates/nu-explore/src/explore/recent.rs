@@ -0,0 +1,329 @@
+//! The sources explored lately, for `explore --recent` and `:recent`: the files opened and
+//! the pipelines run, kept in `explore_recent.json` in the nushell config directory.
+
+use super::{l10n::tr, nu_common::run_nu_command};
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, FixedOffset, Local};
+use nu_protocol::{
+    PipelineData, Record, Span, Value,
+    engine::{EngineState, Stack},
+};
+use nu_utils::escape_quote_string;
+use serde_json::{Value as Json, json};
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+const VERSION: u64 = 1;
+
+/// Sources explored, the latest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recent {
+    pub entries: Vec<RecentEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentEntry {
+    pub kind: SourceKind,
+    /// The path of the file, or the pipeline as it was written
+    pub source: String,
+    /// A [`fingerprint`] of the data, to tell when it changed since
+    pub fingerprint: String,
+    /// The preset it was explored with, from `explore --preset`
+    pub preset: Option<String>,
+    pub opened: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    File,
+    Pipeline,
+}
+
+impl SourceKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Pipeline => "pipeline",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "file" => Some(Self::File),
+            "pipeline" => Some(Self::Pipeline),
+            _ => None,
+        }
+    }
+}
+
+impl Recent {
+    /// How many sources are kept.
+    pub const LIMIT: usize = 20;
+
+    /// Where they're kept, if there's a config directory.
+    pub fn path() -> Option<PathBuf> {
+        nu_path::nu_config_dir().map(|dir| dir.join("explore_recent.json").into_std_path_buf())
+    }
+
+    /// Read the sources kept at `path`, none if nothing was explored yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err).with_context(|| tr!("can't read {path}", path = path.display()));
+            }
+        };
+        let json = serde_json::from_str(&text)
+            .with_context(|| tr!("{path} isn't JSON", path = path.display()))?;
+
+        Self::from_json(&json)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let text = serde_json::to_string_pretty(&self.to_json())?;
+        fs::write(path, text).with_context(|| tr!("can't write {path}", path = path.display()))
+    }
+
+    /// Put `entry` first, in place of an earlier one of the same source.
+    pub fn remember(&mut self, entry: RecentEntry) {
+        self.entries
+            .retain(|known| (known.kind, &known.source) != (entry.kind, &entry.source));
+        self.entries.insert(0, entry);
+        self.entries.truncate(Self::LIMIT);
+    }
+
+    /// The one at `index`, the latest being 0.
+    pub fn get(&self, index: usize) -> Result<&RecentEntry> {
+        self.entries
+            .get(index)
+            .ok_or_else(|| match self.entries.len() {
+                0 => anyhow!(tr!("Nothing was explored yet")),
+                count => anyhow!(tr!("There are only {count} recent sources", count = count)),
+            })
+    }
+
+    /// The sources as a table, for `:recent`.
+    pub fn to_value(&self, span: Span) -> Value {
+        let rows = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let mut record = Record::new();
+                record.push("source", Value::string(&entry.source, span));
+                record.push("kind", Value::string(entry.kind.name(), span));
+                record.push(
+                    "preset",
+                    entry
+                        .preset
+                        .as_ref()
+                        .map_or(Value::nothing(span), |name| Value::string(name, span)),
+                );
+                record.push("opened", Value::date(entry.opened, span));
+                Value::record(record, span)
+            })
+            .collect();
+
+        Value::list(rows, span)
+    }
+
+    fn to_json(&self) -> Json {
+        let entries: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                json!({
+                    "kind": entry.kind.name(),
+                    "source": entry.source,
+                    "fingerprint": entry.fingerprint,
+                    "preset": entry.preset,
+                    "opened": entry.opened.to_rfc3339(),
+                })
+            })
+            .collect();
+
+        json!({ "version": VERSION, "entries": entries })
+    }
+
+    fn from_json(json: &Json) -> Result<Self> {
+        if json.get("version").and_then(Json::as_u64) != Some(VERSION) {
+            bail!(tr!("recent sources of another version of explore"));
+        }
+
+        let field = |entry: &Json, name: &str| {
+            entry
+                .get(name)
+                .and_then(Json::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!(tr!("a recent source needs a {name}", name = name)))
+        };
+
+        let entries = json
+            .get("entries")
+            .and_then(Json::as_array)
+            .ok_or_else(|| anyhow!(tr!("there are no recent sources")))?
+            .iter()
+            .map(|entry| {
+                let kind = field(entry, "kind")?;
+                let opened = field(entry, "opened")?;
+
+                Ok(RecentEntry {
+                    kind: SourceKind::from_name(&kind).ok_or_else(|| {
+                        anyhow!(tr!(
+                            "unknown kind of source {kind}",
+                            kind = format!("{kind:?}")
+                        ))
+                    })?,
+                    source: field(entry, "source")?,
+                    fingerprint: field(entry, "fingerprint")?,
+                    preset: field(entry, "preset").ok(),
+                    opened: DateTime::parse_from_rfc3339(&opened)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { entries })
+    }
+}
+
+impl RecentEntry {
+    /// A source explored now, its fingerprint yet to be taken.
+    pub fn new(kind: SourceKind, source: String, preset: Option<String>) -> Self {
+        Self {
+            kind,
+            source,
+            fingerprint: String::new(),
+            preset,
+            opened: Local::now().fixed_offset(),
+        }
+    }
+
+    /// The source as shown in the status bar: the name of the file, or the pipeline.
+    pub fn short_source(&self) -> String {
+        match self.kind {
+            SourceKind::File => Path::new(&self.source)
+                .file_name()
+                .map_or_else(|| self.source.clone(), |name| name.to_string_lossy().into()),
+            SourceKind::Pipeline => self.source.clone(),
+        }
+    }
+
+    /// Read the source again: open the file, or run the pipeline.
+    pub fn reopen(&self, engine_state: &EngineState, stack: &mut Stack) -> Result<PipelineData> {
+        let command = match self.kind {
+            SourceKind::File => format!("open {}", escape_quote_string(&self.source)),
+            SourceKind::Pipeline => self.source.clone(),
+        };
+
+        run_nu_command(engine_state, stack, &command, PipelineData::empty()).map_err(|err| {
+            anyhow!(tr!(
+                "{source} failed: {err}",
+                source = self.source,
+                err = err
+            ))
+        })
+    }
+}
+
+/// Note that `entry` was explored, with data of the given fingerprint.
+///
+/// Returns whether the data changed since it was last explored.
+pub fn remember(mut entry: RecentEntry, fingerprint: String) -> Result<bool> {
+    let Some(path) = Recent::path() else {
+        return Ok(false);
+    };
+
+    let mut recent = Recent::load(&path).unwrap_or_default();
+    let changed = recent
+        .entries
+        .iter()
+        .find(|known| (known.kind, &known.source) == (entry.kind, &entry.source))
+        .is_some_and(|known| known.fingerprint != fingerprint);
+
+    entry.fingerprint = fingerprint;
+    entry.opened = Local::now().fixed_offset();
+    recent.remember(entry);
+    recent.save(&path)?;
+
+    Ok(changed)
+}
+
+/// A short hash of the shape of a table and of its first and last rows: enough to notice
+/// most changes, without going through every cell.
+pub fn fingerprint(columns: &[String], rows: &[Vec<Value>]) -> String {
+    // FNV-1a, the same in every build
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut feed = |bytes: &[u8]| {
+        for byte in bytes {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    };
+
+    for column in columns {
+        feed(column.as_bytes());
+    }
+    feed(&rows.len().to_le_bytes());
+    for row in rows.first().into_iter().chain(rows.last()) {
+        feed(format!("{row:?}").as_bytes());
+    }
+
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(source: &str) -> RecentEntry {
+        RecentEntry::new(SourceKind::Pipeline, source.to_string(), None)
+    }
+
+    #[test]
+    fn recent_round_trip() {
+        let mut recent = Recent::default();
+        recent.remember(RecentEntry::new(
+            SourceKind::File,
+            "data.json".to_string(),
+            Some("triage".to_string()),
+        ));
+        recent.remember(entry("ls | where size > 1mb"));
+
+        assert_eq!(Recent::from_json(&recent.to_json()).unwrap(), recent);
+    }
+
+    #[test]
+    fn latest_first_without_repeats() {
+        let mut recent = Recent::default();
+        for source in ["ls", "ps", "ls"] {
+            recent.remember(entry(source));
+        }
+        let sources: Vec<_> = recent.entries.iter().map(|e| e.source.as_str()).collect();
+        assert_eq!(sources, ["ls", "ps"]);
+
+        for n in 0..Recent::LIMIT {
+            recent.remember(entry(&n.to_string()));
+        }
+        assert_eq!(recent.entries.len(), Recent::LIMIT);
+        assert!(recent.get(Recent::LIMIT).is_err());
+    }
+
+    #[test]
+    fn fingerprint_follows_the_data() {
+        let span = Span::test_data();
+        let columns = vec!["a".to_string()];
+        let rows = |n: i64| vec![vec![Value::int(1, span)], vec![Value::int(n, span)]];
+
+        assert_eq!(
+            fingerprint(&columns, &rows(2)),
+            fingerprint(&columns, &rows(2))
+        );
+        assert_ne!(
+            fingerprint(&columns, &rows(2)),
+            fingerprint(&columns, &rows(3))
+        );
+    }
+}
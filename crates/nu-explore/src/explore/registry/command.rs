@@ -1,5 +1,6 @@
 use super::super::{
     commands::{SimpleCommand, ViewCommand},
+    pager::PagePolicy,
     views::{View, ViewConfig},
 };
 use anyhow::Result;
@@ -9,19 +10,19 @@ pub enum Command {
     Reactive(Box<dyn SCommand>),
     View {
         cmd: Box<dyn VCommand>,
-        stackable: bool,
+        policy: PagePolicy,
     },
 }
 
 impl Command {
-    pub fn view<C>(command: C, stackable: bool) -> Self
+    pub fn view<C>(command: C, policy: PagePolicy) -> Self
     where
         C: ViewCommand + Clone + 'static,
         C::View: View,
     {
         let cmd = Box::new(ViewCmd(command)) as Box<dyn VCommand>;
 
-        Self::View { cmd, stackable }
+        Self::View { cmd, policy }
     }
 
     pub fn reactive<C>(command: C) -> Self
@@ -42,12 +43,33 @@ impl Command {
         }
     }
 
+    pub fn description(&self) -> &str {
+        match self {
+            Command::Reactive(cmd) => cmd.description(),
+            Command::View { cmd, .. } => cmd.description(),
+        }
+    }
+
     pub fn parse(&mut self, args: &str) -> Result<()> {
         match self {
             Command::Reactive(cmd) => cmd.parse(args),
             Command::View { cmd, .. } => cmd.parse(args),
         }
     }
+
+    pub fn flags(&self) -> &'static [&'static str] {
+        match self {
+            Command::Reactive(cmd) => cmd.flags(),
+            Command::View { cmd, .. } => cmd.flags(),
+        }
+    }
+
+    pub fn menu_entries(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Command::Reactive(cmd) => cmd.menu_entries(),
+            Command::View { cmd, .. } => cmd.menu_entries(),
+        }
+    }
 }
 
 // type helper to deal with `Box`es
@@ -73,6 +95,22 @@ where
         self.0.parse(args)
     }
 
+    fn flags(&self) -> &'static [&'static str] {
+        self.0.flags()
+    }
+
+    fn menu_entries(&self) -> &'static [(&'static str, &'static str)] {
+        self.0.menu_entries()
+    }
+
+    fn uses_selection(&self) -> bool {
+        self.0.uses_selection()
+    }
+
+    fn uses_cell(&self) -> bool {
+        self.0.uses_cell()
+    }
+
     fn spawn(
         &mut self,
         engine_state: &nu_protocol::engine::EngineState,
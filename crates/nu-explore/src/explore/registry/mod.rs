@@ -2,11 +2,14 @@ mod command;
 
 use super::{
     commands::{SimpleCommand, ViewCommand},
+    config::MenuEntry,
+    l10n::tr,
+    pager::PagePolicy,
     views::View,
 };
 use anyhow::Result;
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub use command::Command;
 
@@ -14,6 +17,10 @@ pub use command::Command;
 pub struct CommandRegistry {
     commands: HashMap<Cow<'static, str>, Command>,
     aliases: HashMap<Cow<'static, str>, Cow<'static, str>>,
+    // commands taken out by `explore --no-eval`, told apart from unknown ones
+    disabled: HashSet<Cow<'static, str>>,
+    // the commands defined in `explore.aliases`, by name: the command lines they run
+    macros: HashMap<String, Vec<String>>,
 }
 
 impl CommandRegistry {
@@ -26,14 +33,14 @@ impl CommandRegistry {
             .insert(Cow::Owned(command.name().to_owned()), command);
     }
 
-    pub fn register_command_view<C>(&mut self, command: C, stackable: bool)
+    pub fn register_command_view<C>(&mut self, command: C, policy: PagePolicy)
     where
         C: ViewCommand + Clone + 'static,
         C::View: View,
     {
         self.commands.insert(
             Cow::Owned(command.name().to_owned()),
-            Command::view(command, stackable),
+            Command::view(command, policy),
         );
     }
 
@@ -47,6 +54,24 @@ impl CommandRegistry {
         );
     }
 
+    /// Change the policy of the pages opened by the view command `name` (or an alias of it).
+    pub fn set_page_policy(&mut self, name: &str, policy: PagePolicy) {
+        let name = self.aliases.get(name).map_or(name, |name| name.as_ref());
+        if let Some(Command::View {
+            policy: current, ..
+        }) = self.commands.get_mut(name)
+        {
+            *current = policy;
+        }
+    }
+
+    /// Take the command or the macro `name` out, for it runs code that `--no-eval` disables.
+    pub fn disable(&mut self, name: &str) {
+        self.commands.remove(name);
+        self.macros.remove(name);
+        self.disabled.insert(Cow::Owned(name.to_owned()));
+    }
+
     pub fn create_aliases(&mut self, aliases: &str, command: &str) {
         self.aliases.insert(
             Cow::Owned(aliases.to_owned()),
@@ -54,10 +79,49 @@ impl CommandRegistry {
         );
     }
 
+    /// Define the command `name` running `commands` in turn, from `explore.aliases`; a
+    /// command or an alias of the same name is kept in its place.
+    pub fn define_macro(&mut self, name: &str, commands: Vec<String>) {
+        if self.commands.contains_key(name) || self.aliases.contains_key(name) {
+            return;
+        }
+        self.macros.insert(name.to_owned(), commands);
+    }
+
+    /// The command lines `args` runs when it starts with the name of a macro: its commands,
+    /// with what's typed after the name added to the last one.
+    pub fn expand_macro(&self, args: &str) -> Option<Vec<String>> {
+        let (name, rest) = args.split_once(' ').unwrap_or((args, ""));
+        let mut commands = self.macros.get(name)?.clone();
+        if let Some(last) = commands.last_mut()
+            && !rest.trim().is_empty()
+        {
+            last.push(' ');
+            last.push_str(rest.trim());
+        }
+
+        Some(commands)
+    }
+
+    /// The macros, by name, with the command lines they run.
+    pub fn get_macros(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.macros
+            .iter()
+            .map(|(name, commands)| (name.as_str(), commands.as_slice()))
+    }
+
     pub fn find(&self, args: &str) -> Option<Result<Command>> {
         let cmd = args.split_once(' ').map_or(args, |(cmd, _)| cmd);
         let args = &args[cmd.len()..];
 
+        let name = self.aliases.get(cmd).map_or(cmd, |name| name.as_ref());
+        if self.disabled.contains(name) {
+            return Some(Err(anyhow::anyhow!(tr!(
+                ":{command} runs code, which --no-eval disables",
+                command = name
+            ))));
+        }
+
         let mut command = self.find_command(cmd)?;
         if let Err(err) = command.parse(args) {
             return Some(Err(err));
@@ -76,6 +140,29 @@ impl CommandRegistry {
             .map(|(key, value)| (key.as_ref(), value.as_ref()))
     }
 
+    /// The aliases of the command `name`, sorted.
+    pub fn aliases_of(&self, name: &str) -> Vec<&str> {
+        let mut aliases: Vec<&str> = self
+            .get_aliases()
+            .filter(|(_, command)| *command == name)
+            .map(|(alias, _)| alias)
+            .collect();
+        aliases.sort_unstable();
+        aliases
+    }
+
+    /// What the commands offer in the menu of a cell, by label.
+    pub fn menu_entries(&self) -> Vec<MenuEntry> {
+        let mut entries: Vec<MenuEntry> = self
+            .commands
+            .values()
+            .flat_map(Command::menu_entries)
+            .map(|(label, command)| MenuEntry::new(*label, *command))
+            .collect();
+        entries.sort_by(|a, b| a.label.cmp(&b.label));
+        entries
+    }
+
     fn find_command(&self, cmd: &str) -> Option<Command> {
         match self.commands.get(cmd).cloned() {
             None => self
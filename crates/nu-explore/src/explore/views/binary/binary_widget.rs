@@ -6,6 +6,7 @@ use ratatui::{
     text::Span,
     widgets::{Paragraph, Widget},
 };
+use std::collections::BTreeMap;
 
 use super::super::super::nu_common::NuStyle;
 use super::super::util::{nu_style_to_tui, text_style_to_tui_style};
@@ -19,6 +20,32 @@ pub struct BinaryWidget<'a> {
     opts: BinarySettings,
     style: BinaryStyle,
     row_offset: usize,
+    // the bytes as they were before editing, by their offset, and the cursor when editing
+    originals: Option<&'a BTreeMap<usize, u8>>,
+    cursor: Option<EditCursor>,
+    matches: Option<Matches<'a>>,
+}
+
+/// The bytes a search found: where each match starts, in order, all of them as long; and the
+/// one shown, which stands out.
+#[derive(Debug, Clone, Copy)]
+pub struct Matches<'a> {
+    pub offsets: &'a [usize],
+    pub length: usize,
+    pub shown: Option<usize>,
+}
+
+impl Matches<'_> {
+    fn contains(&self, offset: usize) -> bool {
+        // the last match starting before the byte ends the furthest
+        let i = self.offsets.partition_point(|&start| start <= offset);
+        i > 0 && offset < self.offsets[i - 1] + self.length
+    }
+
+    fn is_shown(&self, offset: usize) -> bool {
+        self.shown
+            .is_some_and(|start| (start..start + self.length).contains(&offset))
+    }
 }
 
 impl<'a> BinaryWidget<'a> {
@@ -28,6 +55,9 @@ impl<'a> BinaryWidget<'a> {
             opts,
             style,
             row_offset: 0,
+            originals: None,
+            cursor: None,
+            matches: None,
         }
     }
 
@@ -36,27 +66,70 @@ impl<'a> BinaryWidget<'a> {
     }
 
     pub fn count_elements(&self) -> usize {
-        self.opts.count_segments * self.opts.segment_size
+        self.opts.count_elements()
+    }
+
+    /// The columns it takes to be drawn.
+    pub fn width(&self) -> usize {
+        get_widget_width(self)
     }
 
     pub fn set_row_offset(&mut self, offset: usize) {
         self.row_offset = offset;
     }
+
+    /// Highlight the bytes which differ from `originals`, and show the cursor of editing.
+    pub fn set_edits(&mut self, originals: &'a BTreeMap<usize, u8>, cursor: Option<EditCursor>) {
+        self.originals = Some(originals);
+        self.cursor = cursor;
+    }
+
+    /// Highlight the matches of a search.
+    pub fn set_matches(&mut self, matches: Matches<'a>) {
+        self.matches = Some(matches);
+    }
+}
+
+/// The pane bytes are edited in: as hex digits, or as the characters they are.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    #[default]
+    Hex,
+    Ascii,
+}
+
+/// Where the bytes are being edited: the offset of a byte, and of the hex pane the digit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EditCursor {
+    pub offset: usize,
+    pub pane: Pane,
+    pub low_nibble: bool,
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct BinarySettings {
     segment_size: usize,
     count_segments: usize,
+    show_ascii: bool,
 }
 
 impl BinarySettings {
-    pub fn new(segment_size: usize, count_segments: usize) -> Self {
+    pub fn new(segment_size: usize, count_segments: usize, show_ascii: bool) -> Self {
         Self {
             segment_size,
             count_segments,
+            show_ascii,
         }
     }
+
+    /// The bytes a line holds.
+    pub fn count_elements(&self) -> usize {
+        self.count_segments * self.segment_size
+    }
+
+    pub fn show_ascii(&self) -> bool {
+        self.show_ascii
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -65,6 +138,8 @@ pub struct BinaryStyle {
     column_padding_left: u16,
     column_padding_right: u16,
     hex_styles: HexStyles,
+    color_modified: NuStyle,
+    color_match: NuStyle,
 }
 
 impl BinaryStyle {
@@ -73,12 +148,16 @@ impl BinaryStyle {
         column_padding_left: u16,
         column_padding_right: u16,
         hex_styles: HexStyles,
+        color_modified: NuStyle,
+        color_match: NuStyle,
     ) -> Self {
         Self {
             color_index,
             column_padding_left,
             column_padding_right,
             hex_styles,
+            color_modified,
+            color_match,
         }
     }
 }
@@ -126,14 +205,18 @@ fn render_hexdump(area: Rect, buf: &mut Buffer, w: BinaryWidget) {
 
         // data/hex column
         x += render_space(buf, x, y, 1, w.style.column_padding_left);
-        x += render_data_line(buf, x, y, line, &w);
+        x += render_data_line(buf, x, y, line, address, &w);
         x += render_space(buf, x, y, 1, w.style.column_padding_right);
 
+        if !w.opts.show_ascii {
+            continue;
+        }
+
         x += render_vertical_split(buf, x, y);
 
         // ASCII column
         x += render_space(buf, x, y, 1, w.style.column_padding_left);
-        x += render_ascii_line(buf, x, y, line, &w);
+        x += render_ascii_line(buf, x, y, line, address, &w);
         render_space(buf, x, y, 1, w.style.column_padding_right);
     }
 
@@ -162,6 +245,10 @@ fn render_hexdump(area: Rect, buf: &mut Buffer, w: BinaryWidget) {
             x += render_space(buf, x, y, 1, data_line_size);
             x += render_space(buf, x, y, 1, w.style.column_padding_right);
 
+            if !w.opts.show_ascii {
+                continue;
+            }
+
             x += render_vertical_split(buf, x, y);
 
             // ASCII column
@@ -172,19 +259,28 @@ fn render_hexdump(area: Rect, buf: &mut Buffer, w: BinaryWidget) {
     }
 }
 
-fn render_data_line(buf: &mut Buffer, x: u16, y: u16, line: &[u8], w: &BinaryWidget) -> u16 {
+// `address` is the offset of the first byte of `line`
+fn render_data_line(
+    buf: &mut Buffer,
+    x: u16,
+    y: u16,
+    line: &[u8],
+    address: usize,
+    w: &BinaryWidget,
+) -> u16 {
     let mut size = 0;
     let mut count = 0;
     let count_max = w.opts.count_segments;
     let segment_size = w.opts.segment_size;
 
-    size += render_segment(buf, x, y, line, w);
+    size += render_segment(buf, x, y, line, address, w);
     count += 1;
 
     while count != count_max && count * segment_size < line.len() {
         let data = &line[count * segment_size..];
+        let address = address + count * segment_size;
         size += render_space(buf, x + size, y, 1, SEGMENT_PADDING);
-        size += render_segment(buf, x + size, y, data, w);
+        size += render_segment(buf, x + size, y, data, address, w);
         count += 1;
     }
 
@@ -197,17 +293,43 @@ fn render_data_line(buf: &mut Buffer, x: u16, y: u16, line: &[u8], w: &BinaryWid
     size
 }
 
-fn render_segment(buf: &mut Buffer, x: u16, y: u16, line: &[u8], w: &BinaryWidget) -> u16 {
+fn render_segment(
+    buf: &mut Buffer,
+    x: u16,
+    y: u16,
+    line: &[u8],
+    address: usize,
+    w: &BinaryWidget,
+) -> u16 {
     let mut count = w.opts.segment_size;
     let mut size = 0;
 
-    for &n in line {
+    for (i, &n) in line.iter().enumerate() {
         if count == 0 {
             break;
         }
 
         let (_, style) = get_segment_char(w, n);
-        size += render_hex_u8(buf, x + size, y, n, style);
+        let style = get_edited_style(w, address + i, n, style);
+        size += match w.cursor {
+            // the digit under the cursor is shown apart from the other
+            Some(cursor) if cursor.offset == address + i && cursor.pane == Pane::Hex => {
+                let digits = usize_to_hex(n as usize, 2);
+                let mut digits = digits.chars();
+                let (high, low) = match cursor.low_nibble {
+                    true => (cursor_style(style, false), cursor_style(style, true)),
+                    false => (cursor_style(style, true), cursor_style(style, false)),
+                };
+                let high = render_ascii_char(buf, x + size, y, digits.next().unwrap_or(' '), high);
+                let low =
+                    render_ascii_char(buf, x + size + 1, y, digits.next().unwrap_or(' '), low);
+                high + low
+            }
+            Some(cursor) if cursor.offset == address + i => {
+                render_hex_u8(buf, x + size, y, n, cursor_style(style, false))
+            }
+            _ => render_hex_u8(buf, x + size, y, n, style),
+        };
         count -= 1;
     }
 
@@ -218,17 +340,30 @@ fn render_segment(buf: &mut Buffer, x: u16, y: u16, line: &[u8], w: &BinaryWidge
     size
 }
 
-fn render_ascii_line(buf: &mut Buffer, x: u16, y: u16, line: &[u8], w: &BinaryWidget) -> u16 {
+fn render_ascii_line(
+    buf: &mut Buffer,
+    x: u16,
+    y: u16,
+    line: &[u8],
+    address: usize,
+    w: &BinaryWidget,
+) -> u16 {
     let mut size = 0;
     let mut count = 0;
     let length = w.count_elements();
 
-    for &n in line {
+    for (i, &n) in line.iter().enumerate() {
         if count == length {
             break;
         }
 
         let (c, style) = get_ascii_char(w, n);
+        let mut style = get_edited_style(w, address + i, n, style);
+        if let Some(cursor) = w.cursor
+            && cursor.offset == address + i
+        {
+            style = cursor_style(style, cursor.pane == Pane::Ascii);
+        }
         size += render_ascii_char(buf, x + size, y, c, style);
         count += 1;
     }
@@ -298,6 +433,39 @@ fn get_segment_char(w: &BinaryWidget, n: u8) -> (char, Option<NuStyle>) {
     (c, style)
 }
 
+// the style of the byte at `offset`: of a match of the search if it's in one, else of an
+// edited one if it isn't what it was
+fn get_edited_style(
+    w: &BinaryWidget,
+    offset: usize,
+    n: u8,
+    style: Option<NuStyle>,
+) -> Option<NuStyle> {
+    if let Some(matches) = w.matches
+        && matches.contains(offset)
+    {
+        return match matches.is_shown(offset) {
+            true => Some(w.style.color_match.bold().underline()),
+            false => Some(w.style.color_match),
+        };
+    }
+
+    let original = w.originals.and_then(|originals| originals.get(&offset));
+    match original {
+        Some(&original) if original != n => Some(w.style.color_modified),
+        _ => style,
+    }
+}
+
+// the cursor is reversed in the pane being edited, and underlined in the other
+fn cursor_style(style: Option<NuStyle>, active: bool) -> Option<NuStyle> {
+    let style = style.unwrap_or_default();
+    match active {
+        true => Some(style.reverse()),
+        false => Some(style.underline()),
+    }
+}
+
 fn get_index_style(w: &BinaryWidget) -> Option<NuStyle> {
     w.style.color_index
 }
@@ -353,7 +521,15 @@ fn get_widget_width(w: &BinaryWidget) -> usize {
     let data_split_size = w.opts.count_segments.saturating_sub(1) * (SEGMENT_PADDING as usize);
     let data_size = line_size + data_split_size;
 
-    let ascii_size = w.opts.count_segments * w.opts.segment_size;
+    let ascii_size = match w.opts.show_ascii {
+        true => {
+            1 // split
+            + w.style.column_padding_left as usize
+            + w.opts.count_segments * w.opts.segment_size
+            + w.style.column_padding_right as usize
+        }
+        false => 0,
+    };
 
     #[allow(clippy::identity_op)]
     let min_width = 0
@@ -364,10 +540,7 @@ fn get_widget_width(w: &BinaryWidget) -> usize {
         + w.style.column_padding_left as usize
         + data_size
         + w.style.column_padding_right as usize
-        + 1 //split
-        + w.style.column_padding_left as usize
-        + ascii_size
-        + w.style.column_padding_right as usize;
+        + ascii_size;
 
     min_width
 }
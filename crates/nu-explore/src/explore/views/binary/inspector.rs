@@ -0,0 +1,171 @@
+use chrono::DateTime;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Widget},
+};
+
+use super::super::super::nu_common::NuStyle;
+use super::super::util::nu_style_to_tui;
+
+/// The width of the panel, with its border.
+pub const WIDTH: u16 = 34;
+
+// the width of the names of the values
+const NAME_WIDTH: usize = 9;
+
+/// The bytes from an offset on, as the values they could be the start of: integers and
+/// floats of both endiannesses, a character of UTF-8 or UTF-16, and a time in seconds since
+/// the epoch. Those there aren't enough bytes left for are left out.
+pub fn inspect(bytes: &[u8]) -> Vec<(&'static str, String)> {
+    let mut rows = Vec::new();
+    if let Some(&n) = bytes.first() {
+        rows.push(("u8", n.to_string()));
+        rows.push(("i8", (n as i8).to_string()));
+    }
+
+    macro_rules! numbers {
+        ($($t:ty, $le:literal, $be:literal, $text:expr;)*) => {$(
+            if let Some(bytes) = bytes.first_chunk() {
+                let text = $text;
+                rows.push(($le, text(<$t>::from_le_bytes(*bytes))));
+                rows.push(($be, text(<$t>::from_be_bytes(*bytes))));
+            }
+        )*};
+    }
+    numbers! {
+        u16, "u16 le", "u16 be", |n: u16| n.to_string();
+        i16, "i16 le", "i16 be", |n: i16| n.to_string();
+        u32, "u32 le", "u32 be", |n: u32| n.to_string();
+        i32, "i32 le", "i32 be", |n: i32| n.to_string();
+        u64, "u64 le", "u64 be", |n: u64| n.to_string();
+        i64, "i64 le", "i64 be", |n: i64| n.to_string();
+        f32, "f32 le", "f32 be", |n: f32| float_text(n as f64);
+        f64, "f64 le", "f64 be", float_text;
+    }
+
+    if !bytes.is_empty() {
+        rows.push(("utf-8", utf8_text(bytes)));
+    }
+    if bytes.len() >= 2 {
+        rows.push(("utf-16 le", utf16_text(bytes, u16::from_le_bytes)));
+        rows.push(("utf-16 be", utf16_text(bytes, u16::from_be_bytes)));
+    }
+
+    numbers! {
+        u32, "time le", "time be", time_text;
+    }
+
+    rows
+}
+
+/// Draw the panel of the values of `bytes`, at `offset`, on the right of a binary view.
+pub fn render(
+    area: Rect,
+    buf: &mut Buffer,
+    offset: usize,
+    bytes: &[u8],
+    text_style: NuStyle,
+    border_style: NuStyle,
+) {
+    let block = Block::default()
+        .borders(Borders::LEFT)
+        .border_style(nu_style_to_tui(border_style));
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    if inner.width < 4 || inner.height == 0 {
+        return;
+    }
+
+    let text_style = nu_style_to_tui(text_style);
+    let name_style = Style::default().add_modifier(Modifier::DIM);
+    let x = inner.x + 1;
+    let width = (inner.width - 1) as usize;
+
+    let title = format!("{offset:#010x}");
+    let rows = inspect(bytes);
+    let lines = std::iter::once(("at", title)).chain(rows);
+    for (y, (name, value)) in (inner.y..inner.bottom()).zip(lines) {
+        buf.set_stringn(x, y, format!("{name:NAME_WIDTH$}"), width, name_style);
+        let value_width = width.saturating_sub(NAME_WIDTH + 1);
+        buf.set_stringn(x + NAME_WIDTH as u16 + 1, y, value, value_width, text_style);
+    }
+}
+
+// floats in full, but for the very large or small which take an exponent
+fn float_text(n: f64) -> String {
+    match n.abs() {
+        abs if abs == 0.0 || !abs.is_finite() || (1e-4..1e15).contains(&abs) => n.to_string(),
+        _ => format!("{n:e}"),
+    }
+}
+
+// the character the bytes start with, if they're UTF-8
+fn utf8_text(bytes: &[u8]) -> String {
+    let c = (1..=4.min(bytes.len()))
+        .find_map(|n| std::str::from_utf8(&bytes[..n]).ok()?.chars().next());
+    match c {
+        Some(c) => char_text(c),
+        None => String::from("-"),
+    }
+}
+
+// the character the bytes start with, if they're UTF-16 in the byte order of `unit`
+fn utf16_text(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    let units = bytes
+        .chunks_exact(2)
+        .take(2)
+        .map(|pair| unit([pair[0], pair[1]]));
+    match char::decode_utf16(units).next() {
+        Some(Ok(c)) => char_text(c),
+        _ => String::from("-"),
+    }
+}
+
+fn char_text(c: char) -> String {
+    format!("{c:?} U+{:04X}", c as u32)
+}
+
+// seconds since the epoch, in UTC
+fn time_text(seconds: u32) -> String {
+    DateTime::from_timestamp(seconds as i64, 0)
+        .map(|date| date.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value<'a>(rows: &'a [(&str, String)], name: &str) -> Option<&'a str> {
+        rows.iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn values_of_the_bytes() {
+        let rows = inspect(&[0xfe, 0xff, 0x00, 0x00, 0x00, 0x00, 0xf0, 0x3f]);
+        assert_eq!(value(&rows, "u8"), Some("254"));
+        assert_eq!(value(&rows, "i8"), Some("-2"));
+        assert_eq!(value(&rows, "u16 le"), Some("65534"));
+        assert_eq!(value(&rows, "u16 be"), Some("65279"));
+        assert_eq!(value(&rows, "i32 le"), Some("65534"));
+        assert_eq!(value(&rows, "f64 le"), Some("1.0000000000145515"));
+        assert_eq!(value(&rows, "utf-8"), Some("-"));
+        assert_eq!(value(&rows, "time le"), Some("1970-01-01 18:12:14"));
+        assert_eq!(value(&rows, "time be"), Some("2105-07-27 07:55:44"));
+    }
+
+    #[test]
+    fn short_of_bytes() {
+        let rows = inspect("é".as_bytes());
+        assert_eq!(value(&rows, "utf-8"), Some("'é' U+00E9"));
+        assert_eq!(value(&rows, "u16 be"), Some("50089"));
+        assert_eq!(value(&rows, "utf-16 be"), Some("'쎩' U+C3A9"));
+        assert_eq!(value(&rows, "u32 le"), None);
+        assert!(inspect(&[]).is_empty());
+    }
+}
@@ -1,30 +1,38 @@
 // todo: 3 cursor modes one for section
 
 mod binary_widget;
+mod inspector;
 
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use nu_pretty_hex::HexStyles;
 use nu_protocol::{
     Value,
     engine::{EngineState, Stack},
 };
 use ratatui::layout::Rect;
+use std::collections::BTreeMap;
 
 use super::super::{
-    config::ExploreConfig,
-    nu_common::NuText,
+    config::{ExploreConfig, HexConfig},
+    l10n::tr,
+    nu_common::{NuColor, NuSpan, NuStyle, NuText},
     pager::{
         Frame, Transition, ViewInfo,
         report::{Report, Severity},
+        status_bar::{StatusMode, StatusSegments},
     },
 };
-use super::cursor::Position;
 
-use self::binary_widget::{BinarySettings, BinaryStyle, BinaryWidget};
+use self::binary_widget::{BinarySettings, BinaryStyle, BinaryWidget, EditCursor, Matches, Pane};
 
-use super::{Layout, View, ViewConfig, cursor::CursorMoveHandler, cursor::WindowCursor2D};
+use super::{KeyHint, Layout, View, ViewConfig, cursor::CursorMoveHandler, cursor::WindowCursor2D};
 
-/// An interactive view that displays binary data in a hex dump format.
+/// An interactive view that displays binary data in a hex dump format, and edits it: `i`
+/// overwrites bytes in the hex or ASCII pane, which peeking or `:save` gives back. `/`
+/// searches the bytes for hex digits, `/deadbeef`, or text, `/text:hello`; `d` shows what
+/// the bytes at the cursor are as numbers, text and a time, by the side; `:goto` goes to an
+/// offset and `G` to the last byte. How many bytes a line has, grouped by how many, and
+/// whether they're shown as characters too, is set in `explore.hex` or with `:set hex.*`.
 /// Not finished; many aspects are still WIP.
 #[derive(Debug, Clone)]
 pub struct BinaryView {
@@ -32,8 +40,32 @@ pub struct BinaryView {
     // HACK: we are only using the vertical dimension of the cursor, should we use a plain old WindowCursor?
     cursor: WindowCursor2D,
     settings: Settings,
+    // the layout the settings are made from, the bytes of a line fitting the width if `auto`
+    hex: HexConfig,
+    // the cursor of the bytes while editing them
+    edit: Option<EditCursor>,
+    // the offsets and previous values of the bytes overwritten, the last one last
+    undo: Vec<(usize, u8)>,
+    // the bytes edited, as they were at first
+    originals: BTreeMap<usize, u8>,
+    // the length of the undo stack when the bytes were last saved, none if it can't get back
+    // to the bytes saved
+    saved: Option<usize>,
+    // the rows shown when last drawn, for the cursor to page through them
+    page: usize,
+    // where the matches of the search start, in order, how long they are and the one shown
+    matches: Vec<usize>,
+    match_length: usize,
+    shown_match: Option<usize>,
+    // the byte gone to with `:goto`, `G` or to a match, which is at the cursor until scrolling
+    at: Option<usize>,
+    // whether the data inspector is shown
+    inspector: bool,
 }
 
+// the most bytes on a line fitting the width, for `hex.bytes_per_line: auto`
+const MAX_LINE_LENGTH: usize = 256;
+
 #[derive(Debug, Default, Clone)]
 struct Settings {
     opts: BinarySettings,
@@ -50,12 +82,318 @@ impl BinaryView {
             data,
             cursor: WindowCursor2D::new(count_rows, 1).expect("Failed to create XYCursor"),
             settings,
+            hex: cfg.hex,
+            edit: None,
+            undo: Vec::new(),
+            originals: BTreeMap::new(),
+            saved: Some(0),
+            page: 0,
+            matches: Vec::new(),
+            match_length: 0,
+            shown_match: None,
+            at: None,
+            inspector: false,
+        }
+    }
+
+    /// The bytes with the edits made to them.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Whether there are edits since the bytes were given or last saved.
+    pub fn is_modified(&self) -> bool {
+        self.saved != Some(self.undo.len())
+    }
+
+    /// Mark the bytes as saved, so they're no longer modified.
+    pub fn set_saved(&mut self) {
+        self.saved = Some(self.undo.len());
+    }
+
+    /// Go to the byte at `offset`, showing its row at the top, or move the cursor to it while
+    /// editing; false if there are fewer bytes.
+    pub fn go_to(&mut self, offset: usize) -> bool {
+        if offset >= self.data.len() {
+            return false;
+        }
+
+        match &mut self.edit {
+            Some(edit) => {
+                edit.offset = offset;
+                edit.low_nibble = false;
+            }
+            None => {
+                let row = offset / self.line_length();
+                self.cursor.set_window_start_position(row, 0);
+                self.at = Some(offset);
+            }
+        }
+
+        true
+    }
+
+    /// The offset of the byte at the cursor out of the size of the bytes, in decimal and hex.
+    pub fn position_text(&self) -> String {
+        let (offset, size) = (self.offset(), self.data.len());
+        format!("{offset}/{size} {offset:#x}/{size:#x}")
+    }
+
+    // the last byte, with the last page of rows shown
+    fn go_to_end(&mut self) {
+        if self.data.is_empty() {
+            return;
+        }
+
+        let rows = self.data.len().div_ceil(self.line_length());
+        let origin = rows.saturating_sub(self.page.max(1));
+        self.cursor.set_window_start_position(origin, 0);
+        self.at = Some(self.data.len() - 1);
+    }
+
+    /// How the bytes are laid out.
+    pub fn hex(&self) -> HexConfig {
+        self.hex
+    }
+
+    /// Lay the bytes out anew, the first shown still at the top; with `auto`, as many as fit
+    /// once drawn.
+    pub fn set_hex(&mut self, hex: HexConfig) {
+        let groups = hex.groups(self.settings.opts.count_elements() / hex.group);
+        self.hex = hex;
+        self.set_layout(BinarySettings::new(hex.group, groups, hex.ascii));
+    }
+
+    fn set_layout(&mut self, opts: BinarySettings) {
+        if opts == self.settings.opts {
+            return;
+        }
+
+        let top = self.cursor.window_origin().row * self.line_length();
+        self.settings.opts = opts;
+        let count_rows = BinaryWidget::new(&self.data, opts, Default::default()).count_lines();
+        self.cursor = WindowCursor2D::new(count_rows, 1).expect("Failed to create XYCursor");
+        self.cursor
+            .set_window_start_position(top / self.line_length(), 0);
+
+        if let Some(edit) = &mut self.edit
+            && !opts.show_ascii()
+        {
+            edit.pane = Pane::Hex;
+        }
+    }
+
+    // the groups of bytes fitting `width` on a line
+    fn fitting_groups(&self, width: u16) -> usize {
+        let group = self.hex.group;
+        let fits = |groups: usize| {
+            let opts = BinarySettings::new(group, groups, self.hex.ascii);
+            let widget = BinaryWidget::new(&self.data, opts, self.settings.style.clone());
+            widget.width() <= width as usize
+        };
+        (2..=MAX_LINE_LENGTH / group)
+            .take_while(|&groups| fits(groups))
+            .last()
+            .unwrap_or(1)
+    }
+
+    fn line_length(&self) -> usize {
+        BinaryWidget::new(&[], self.settings.opts, Default::default()).count_elements()
+    }
+
+    fn start_editing(&mut self) {
+        if self.data.is_empty() {
+            return;
+        }
+
+        let offset = self.cursor.window_origin().row * self.line_length();
+        self.edit = Some(EditCursor {
+            offset: offset.min(self.data.len() - 1),
+            ..Default::default()
+        });
+    }
+
+    fn write(&mut self, offset: usize, byte: u8, coalesce: bool) {
+        let previous = self.data[offset];
+        if previous == byte {
+            return;
+        }
+
+        // the two digits of a byte are undone at once
+        if !(coalesce && self.undo.last().is_some_and(|(last, _)| *last == offset)) {
+            self.undo.push((offset, previous));
+        }
+        self.originals.entry(offset).or_insert(previous);
+        self.data[offset] = byte;
+        // the matches were of the bytes as they were
+        self.matches.clear();
+        self.shown_match = None;
+    }
+
+    // undo the last edit, going to its byte if editing
+    fn undo(&mut self) -> bool {
+        let Some((offset, previous)) = self.undo.pop() else {
+            return false;
+        };
+
+        self.data[offset] = previous;
+        if self.saved.is_some_and(|saved| saved > self.undo.len()) {
+            self.saved = None;
+        }
+        if let Some(edit) = &mut self.edit {
+            edit.offset = offset;
+            edit.low_nibble = false;
+        }
+
+        true
+    }
+
+    fn handle_edit_key(&mut self, key: KeyEvent) {
+        let Some(mut edit) = self.edit else {
+            return;
+        };
+
+        let last = self.data.len() - 1;
+        let line = self.line_length();
+        let page = line * self.page.max(1);
+        match key.code {
+            KeyCode::Esc => {
+                self.edit = None;
+                return;
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                edit.pane = match edit.pane {
+                    Pane::Hex if self.settings.opts.show_ascii() => Pane::Ascii,
+                    _ => Pane::Hex,
+                };
+                edit.low_nibble = false;
+            }
+            KeyCode::Backspace => {
+                self.undo();
+                return;
+            }
+            // in the hex pane, digit by digit
+            KeyCode::Left => match (edit.pane, edit.low_nibble) {
+                (Pane::Hex, true) => edit.low_nibble = false,
+                (Pane::Hex, false) if edit.offset > 0 => {
+                    edit.offset -= 1;
+                    edit.low_nibble = true;
+                }
+                _ => edit.offset = edit.offset.saturating_sub(1),
+            },
+            KeyCode::Right => match (edit.pane, edit.low_nibble) {
+                (Pane::Hex, false) => edit.low_nibble = true,
+                (Pane::Hex, true) if edit.offset < last => {
+                    edit.offset += 1;
+                    edit.low_nibble = false;
+                }
+                _ => edit.offset = (edit.offset + 1).min(last),
+            },
+            KeyCode::Up if edit.offset >= line => edit.offset -= line,
+            KeyCode::Down if edit.offset + line <= last => edit.offset += line,
+            KeyCode::PageUp => edit.offset = edit.offset.saturating_sub(page),
+            KeyCode::PageDown => edit.offset = (edit.offset + page).min(last),
+            KeyCode::Home => {
+                edit.offset -= edit.offset % line;
+                edit.low_nibble = false;
+            }
+            KeyCode::End => {
+                edit.offset = (edit.offset - edit.offset % line + line - 1).min(last);
+                edit.low_nibble = false;
+            }
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                match edit.pane {
+                    Pane::Hex => {
+                        let Some(digit) = c.to_digit(16) else {
+                            return;
+                        };
+                        let byte = self.data[edit.offset];
+                        let byte = match edit.low_nibble {
+                            true => (byte & 0xf0) | digit as u8,
+                            false => (byte & 0x0f) | ((digit as u8) << 4),
+                        };
+                        self.write(edit.offset, byte, edit.low_nibble);
+                        match edit.low_nibble {
+                            false => edit.low_nibble = true,
+                            true if edit.offset < last => {
+                                edit.offset += 1;
+                                edit.low_nibble = false;
+                            }
+                            true => {}
+                        }
+                    }
+                    Pane::Ascii => {
+                        if !c.is_ascii() || c.is_ascii_control() {
+                            return;
+                        }
+                        self.write(edit.offset, c as u8, false);
+                        edit.offset = (edit.offset + 1).min(last);
+                    }
+                }
+            }
+            _ => return,
+        }
+
+        self.edit = Some(edit);
+    }
+
+    // the byte at the cursor, which the data inspector starts at: the one edited, or the one
+    // gone to, or the first one shown
+    fn offset(&self) -> usize {
+        let offset = match (self.edit, self.at) {
+            (Some(edit), _) => edit.offset,
+            (None, Some(offset)) => offset,
+            (None, None) => self.cursor.window_origin().row * self.line_length(),
+        };
+        offset.min(self.data.len())
+    }
+
+    // scroll for the byte being edited to be shown
+    fn scroll_to_edit(&mut self) {
+        let Some(edit) = self.edit else {
+            return;
+        };
+
+        let row = edit.offset / self.line_length();
+        let origin = self.cursor.window_origin().row;
+        if row < origin {
+            self.cursor.set_window_start_position(row, 0);
+        } else if self.page > 0 && row >= origin + self.page {
+            self.cursor
+                .set_window_start_position(row + 1 - self.page, 0);
         }
     }
 }
 
 impl View for BinaryView {
-    fn draw(&mut self, f: &mut Frame, area: Rect, _cfg: ViewConfig<'_>, _layout: &mut Layout) {
+    fn draw(&mut self, f: &mut Frame, area: Rect, cfg: ViewConfig<'_>, _layout: &mut Layout) {
+        let mut area = area;
+        if self.inspector && area.width > inspector::WIDTH * 2 {
+            area.width -= inspector::WIDTH;
+            let panel = Rect::new(area.right(), area.y, inspector::WIDTH, area.height);
+            let offset = self.offset();
+            inspector::render(
+                panel,
+                f.buffer_mut(),
+                offset,
+                &self.data[offset..],
+                cfg.explore_config.status_bar_text,
+                cfg.explore_config.table.separator_style,
+            );
+        }
+
+        if self.hex.bytes_per_line.is_none() {
+            let groups = self.fitting_groups(area.width);
+            self.set_layout(BinarySettings::new(self.hex.group, groups, self.hex.ascii));
+        }
+
+        self.page = area.height as usize;
+        self.scroll_to_edit();
+
         let widget = create_binary_widget(self);
         f.render_widget(widget, area);
     }
@@ -68,28 +406,138 @@ impl View for BinaryView {
         info: &mut ViewInfo,
         key: KeyEvent,
     ) -> Transition {
+        // while editing, all the keys are the view's, `:` and `/` being characters to write
+        if self.edit.is_some() {
+            self.handle_edit_key(key);
+            info.status = Some(create_report(self));
+            return Transition::Ok;
+        }
+
+        if key.modifiers.difference(KeyModifiers::SHIFT).is_empty() {
+            match key.code {
+                KeyCode::Char('i') => {
+                    self.start_editing();
+                    info.status = Some(create_report(self));
+                    return Transition::Ok;
+                }
+                KeyCode::Char('u') => {
+                    if self.undo() {
+                        info.status = Some(create_report(self));
+                    }
+                    return Transition::Ok;
+                }
+                KeyCode::Char('d') => {
+                    self.inspector = !self.inspector;
+                    return Transition::Ok;
+                }
+                KeyCode::Char('G') | KeyCode::End => {
+                    self.go_to_end();
+                    info.status = Some(create_report(self));
+                    return Transition::Ok;
+                }
+                _ => {}
+            }
+        }
+
         // currently only handle_enter() in crates/nu-explore/src/views/record/mod.rs raises an Err()
         if let Ok((Transition::Ok, ..)) = self.handle_input_key(&key) {
-            let report = create_report(self.cursor);
+            // scrolled on, the cursor is back at the top row
+            self.at = None;
+            let report = create_report(self);
             info.status = Some(report);
         }
 
         Transition::None
     }
 
+    fn kind(&self) -> &'static str {
+        "binary"
+    }
+
+    fn mode(&self) -> Option<&'static str> {
+        match self.edit {
+            Some(_) => Some("edit"),
+            None => Some("view"),
+        }
+    }
+
+    fn as_binary_view(&mut self) -> Option<&mut BinaryView> {
+        Some(self)
+    }
+
+    fn status_segments(&self, segments: &mut StatusSegments) {
+        if segments.mode() == StatusMode::Perf {
+            segments.push(&tr!("bytes"), self.data.len());
+        }
+        let modified = self
+            .originals
+            .iter()
+            .filter(|(offset, original)| self.data[**offset] != **original)
+            .count();
+        if modified > 0 {
+            segments.push(&tr!("modified"), modified);
+        }
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        if self.edit.is_some() {
+            return vec![
+                KeyHint::new("←→↑↓", "move"),
+                KeyHint::new("Tab", "hex/ascii"),
+                KeyHint::new("Bksp", "undo"),
+                KeyHint::new("Esc", "stop editing"),
+            ];
+        }
+
+        let mut hints = vec![
+            KeyHint::new("↑↓", "scroll"),
+            KeyHint::new("PgUp/PgDn", "page"),
+            KeyHint::new("i", "edit"),
+            KeyHint::new("/", "search"),
+            KeyHint::new("d", "inspect"),
+            KeyHint::new("G", "end"),
+        ];
+        if !self.undo.is_empty() {
+            hints.push(KeyHint::new("u", "undo"));
+        }
+        hints.extend([KeyHint::new(":", "command"), KeyHint::new("q", "quit")]);
+        hints
+    }
+
     fn collect_data(&self) -> Vec<NuText> {
-        // todo: impl to allow search
+        // the bytes are searched by `search`, they aren't lines of text
         vec![]
     }
 
-    fn show_data(&mut self, _pos: usize) -> bool {
-        // todo: impl to allow search
-        false
+    fn search(&mut self, pattern: &str) -> Option<Vec<usize>> {
+        let bytes = search_bytes(pattern);
+        self.match_length = bytes.len();
+        Some(find_bytes(&self.data, &bytes))
+    }
+
+    fn show_matches(&mut self, offsets: &[usize]) {
+        self.matches = offsets.to_vec();
+        self.matches.sort_unstable();
+        self.shown_match = None;
+    }
+
+    // the match starting at `offset`, at the top
+    fn show_data(&mut self, offset: usize) -> bool {
+        if offset >= self.data.len() {
+            return false;
+        }
+
+        let row = offset / self.line_length();
+        self.cursor.set_window_start_position(row, 0);
+        self.shown_match = Some(offset);
+        self.at = Some(offset);
+        true
     }
 
     fn exit(&mut self) -> Option<Value> {
-        // todo: impl Cursor + peek of a value
-        None
+        // todo: impl Cursor + peek of the bytes under it; all of them until then, edits and
+        // all, which `:encoding` decodes
+        Some(Value::binary(self.data.clone(), NuSpan::unknown()))
     }
 }
 
@@ -108,6 +556,14 @@ fn create_binary_widget(v: &BinaryView) -> BinaryWidget<'_> {
 
     let mut w = BinaryWidget::new(data, v.settings.opts, v.settings.style.clone());
     w.set_row_offset(index);
+    w.set_edits(&v.originals, v.edit);
+    if !v.matches.is_empty() {
+        w.set_matches(Matches {
+            offsets: &v.matches,
+            length: v.match_length,
+            shown: v.shown_match,
+        });
+    }
 
     w
 }
@@ -115,32 +571,79 @@ fn create_binary_widget(v: &BinaryView) -> BinaryWidget<'_> {
 fn settings_from_config(config: &ExploreConfig) -> Settings {
     // Most of this is hardcoded for now, add it to the config later if needed
     Settings {
-        opts: BinarySettings::new(2, 8),
+        opts: BinarySettings::new(
+            config.hex.group,
+            config.hex.groups(16 / config.hex.group),
+            config.hex.ascii,
+        ),
         style: BinaryStyle::new(
             None,
             config.table.column_padding_left as u16,
             config.table.column_padding_right as u16,
             HexStyles::default(),
+            NuStyle::new().fg(NuColor::Yellow).bold(),
+            config.highlight,
         ),
     }
 }
 
-fn create_report(cursor: WindowCursor2D) -> Report {
-    let covered_percent = report_row_position(cursor);
-    let cursor = report_cursor_position(cursor);
-    let mode = report_mode_name();
-    let msg = String::new();
+/// The bytes `/` looks for: hex digits, spaces between them or not, as the bytes they are,
+/// the last digit of one still being typed left out; anything else as UTF-8 text, which
+/// `text:` makes of hex digits too.
+fn search_bytes(pattern: &str) -> Vec<u8> {
+    if let Some(text) = pattern.strip_prefix("text:") {
+        return text.as_bytes().to_vec();
+    }
+
+    let digits: Option<Vec<u8>> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect();
+    match digits {
+        Some(digits) if !digits.is_empty() => digits
+            .chunks_exact(2)
+            .map(|pair| (pair[0] << 4) | pair[1])
+            .collect(),
+        _ => pattern.as_bytes().to_vec(),
+    }
+}
+
+// where `bytes` are found in `data`, matches overlapping
+fn find_bytes(data: &[u8], bytes: &[u8]) -> Vec<usize> {
+    if bytes.is_empty() {
+        return Vec::new();
+    }
+
+    data.windows(bytes.len())
+        .enumerate()
+        .filter(|(_, window)| *window == bytes)
+        .map(|(offset, _)| offset)
+        .collect()
+}
+
+fn create_report(v: &BinaryView) -> Report {
+    let covered_percent = report_row_position(v.cursor);
+    let cursor = v.position_text();
+    let mode = report_mode_name(v);
+    let msg = match v.is_modified() {
+        true => tr!("modified"),
+        false => String::new(),
+    };
 
     Report::new(msg, Severity::Info, mode, cursor, covered_percent)
 }
 
-fn report_mode_name() -> String {
-    String::from("VIEW")
+fn report_mode_name(v: &BinaryView) -> String {
+    match v.edit {
+        Some(_) => tr!("EDIT"),
+        None => tr!("VIEW"),
+    }
 }
 
 fn report_row_position(cursor: WindowCursor2D) -> String {
     if cursor.window_origin().row == 0 {
-        return String::from("Top");
+        return tr!("Top");
     }
 
     // todo: there's some bug in XYCursor; when we hit PgDOWN/UP and general move it exceeds the limit
@@ -150,18 +653,157 @@ fn report_row_position(cursor: WindowCursor2D) -> String {
     let count_rows = cursor.row_limit();
     let percent_rows = get_percentage(row, count_rows);
     match percent_rows {
-        100 => String::from("All"),
+        100 => tr!("All"),
         value => format!("{value}%"),
     }
 }
 
-fn report_cursor_position(cursor: WindowCursor2D) -> String {
-    let Position { row, column } = cursor.window_origin();
-    format!("{row},{column}")
-}
-
 fn get_percentage(value: usize, max: usize) -> usize {
     debug_assert!(value <= max, "{value:?} {max:?}");
 
     ((value as f32 / max as f32) * 100.0).floor() as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_keys(view: &mut BinaryView, codes: impl IntoIterator<Item = KeyCode>) {
+        for code in codes {
+            view.handle_edit_key(key(code));
+        }
+    }
+
+    #[test]
+    fn overwrite_in_both_panes() {
+        let mut view = BinaryView::new(vec![0; 20], &ExploreConfig::default());
+        view.start_editing();
+
+        type_keys(&mut view, "4a7".chars().map(KeyCode::Char));
+        assert_eq!(&view.data[..3], [0x4a, 0x70, 0]);
+        assert_eq!(
+            view.edit,
+            Some(EditCursor {
+                offset: 1,
+                pane: Pane::Hex,
+                low_nibble: true
+            })
+        );
+
+        // not hex digits are left out, and the ASCII pane writes characters
+        type_keys(&mut view, [KeyCode::Char('z'), KeyCode::Tab, KeyCode::Down]);
+        type_keys(&mut view, "hi".chars().map(KeyCode::Char));
+        assert_eq!(&view.data[17..19], b"hi");
+
+        // the cursor stays at the last byte
+        type_keys(&mut view, "!!!".chars().map(KeyCode::Char));
+        assert_eq!(&view.data[18..], b"i!");
+        assert_eq!(view.edit.map(|edit| edit.offset), Some(19));
+        assert!(view.is_modified());
+
+        type_keys(&mut view, [KeyCode::Esc]);
+        assert_eq!(view.edit, None);
+        assert_eq!(
+            view.exit().and_then(|value| value.into_binary().ok()),
+            Some(view.data.clone())
+        );
+    }
+
+    #[test]
+    fn undo_the_edits() {
+        let mut view = BinaryView::new(vec![0x11; 4], &ExploreConfig::default());
+        view.start_editing();
+        type_keys(&mut view, "abcd".chars().map(KeyCode::Char));
+        assert_eq!(view.data, [0xab, 0xcd, 0x11, 0x11]);
+        view.set_saved();
+        assert!(!view.is_modified());
+
+        // a byte's two digits are undone at once
+        type_keys(&mut view, [KeyCode::Backspace]);
+        assert_eq!(view.data, [0xab, 0x11, 0x11, 0x11]);
+        assert_eq!(view.edit.map(|edit| edit.offset), Some(1));
+        assert!(view.is_modified());
+
+        // what's saved can't be got back to by editing it again
+        type_keys(&mut view, "cd".chars().map(KeyCode::Char));
+        assert!(view.is_modified());
+
+        while view.undo() {}
+        assert_eq!(view.data, [0x11; 4]);
+        assert_eq!(view.originals.len(), 2);
+    }
+
+    #[test]
+    fn search_for_bytes_or_text() {
+        assert_eq!(search_bytes("deadBEEF"), [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(search_bytes("de ad b"), [0xde, 0xad]);
+        assert_eq!(search_bytes("text:cafe"), b"cafe");
+        assert_eq!(search_bytes("hello"), b"hello");
+
+        let mut data = vec![0; 64];
+        data[20..24].copy_from_slice(b"abab");
+        data[50..52].copy_from_slice(b"ab");
+        let mut view = BinaryView::new(data, &ExploreConfig::default());
+
+        let matches = view.search("text:ab").unwrap();
+        assert_eq!(matches, [20, 22, 50]);
+        assert_eq!(view.search("6162 61").unwrap(), [20]);
+        assert!(view.search("").unwrap().is_empty());
+
+        view.show_matches(&matches);
+        assert!(view.show_data(50));
+        assert_eq!(view.cursor.window_origin().row, 3);
+        assert!(!view.show_data(64));
+    }
+
+    #[test]
+    fn lay_the_bytes_out() {
+        let mut view = BinaryView::new(vec![0; 100], &ExploreConfig::default());
+        assert!(view.go_to(40));
+        assert_eq!(view.cursor.window_origin().row, 2);
+
+        // the first byte shown stays at the top
+        let mut hex = view.hex();
+        hex.set("bytes_per_line", "8").unwrap();
+        view.set_hex(hex);
+        assert_eq!(view.line_length(), 8);
+        assert_eq!(view.cursor.window_origin().row, 4);
+
+        hex.set("group", "3").unwrap();
+        view.set_hex(hex);
+        assert_eq!(view.line_length(), 6);
+
+        // as many groups as fit
+        hex.set("bytes_per_line", "auto").unwrap();
+        view.set_hex(hex);
+        let groups = view.fitting_groups(80);
+        let opts = BinarySettings::new(3, groups, true);
+        let style = view.settings.style.clone();
+        let width = |opts| BinaryWidget::new(&view.data, opts, style.clone()).width();
+        assert!(width(opts) <= 80);
+        assert!(width(BinarySettings::new(3, groups + 1, true)) > 80);
+    }
+
+    #[test]
+    fn go_to_an_offset() {
+        let mut view = BinaryView::new(vec![0; 100], &ExploreConfig::default());
+        view.page = 3;
+        assert!(view.go_to(0x21));
+        assert_eq!(view.cursor.window_origin().row, 2);
+        assert_eq!(view.position_text(), "33/100 0x21/0x64");
+        assert!(!view.go_to(100));
+
+        view.go_to_end();
+        assert_eq!(view.cursor.window_origin().row, 4);
+        assert_eq!(view.offset(), 99);
+
+        // while editing, the cursor goes there
+        view.start_editing();
+        assert!(view.go_to(5));
+        assert_eq!(view.offset(), 5);
+    }
+}
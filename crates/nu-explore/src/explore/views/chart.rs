@@ -0,0 +1,386 @@
+use super::super::{
+    l10n::tr,
+    nu_common::{NuConfig, Unit},
+    pager::{
+        Frame, Transition, ViewInfo,
+        report::Report,
+        status_bar::{StatusMode, StatusSegments},
+    },
+};
+use super::{KeyHint, Layout, View, ViewConfig};
+use chrono::{DateTime, FixedOffset};
+use crossterm::event::{KeyCode, KeyEvent};
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    widgets::{Axis, Chart, Dataset, GraphType, LegendPosition},
+};
+use std::cmp::Ordering;
+
+// the colors of the series, in turn
+const COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Green,
+    Color::Red,
+    Color::Blue,
+];
+
+/// What the numbers along the x axis are: dates, as seconds since the epoch shown in the
+/// offset of the first, or numbers of a unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scale {
+    Date(FixedOffset),
+    Unit(Unit),
+}
+
+impl Scale {
+    /// `value` as a number on the scale, if it's of the same kind.
+    pub fn number(&self, value: &Value) -> Option<f64> {
+        match (self, value) {
+            (Self::Date(_), Value::Date { val, .. }) => {
+                Some(val.timestamp() as f64 + val.timestamp_subsec_nanos() as f64 / 1e9)
+            }
+            (Self::Unit(unit), value) => Unit::of(value)
+                .filter(|(_, of)| of == unit)
+                .map(|(number, _)| number),
+            _ => None,
+        }
+    }
+
+    // `x` as text, precise enough for marks `step` apart
+    fn text(&self, x: f64, step: f64, config: &NuConfig) -> String {
+        match self {
+            Self::Date(offset) => {
+                let format = match step {
+                    step if step >= 86_400.0 => "%Y-%m-%d",
+                    step if step >= 60.0 => "%m-%d %H:%M",
+                    _ => "%H:%M:%S",
+                };
+                let nanos = (x.fract() * 1e9) as u32;
+                DateTime::from_timestamp(x.floor() as i64, nanos)
+                    .map(|date| date.with_timezone(offset).format(format).to_string())
+                    .unwrap_or_default()
+            }
+            Self::Unit(unit) => unit.text(x, step, config),
+        }
+    }
+}
+
+/// A column plotted against the x column: its points in order of x.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    pub name: String,
+    pub points: Vec<(f64, f64)>,
+}
+
+/// The columns of a table plotted against a date or number column by `:chart`, as lines or
+/// bars; the window along x is zoomed and panned with the keys.
+#[derive(Debug)]
+pub struct ChartView {
+    x_name: String,
+    x_scale: Scale,
+    y_unit: Unit,
+    series: Vec<Series>,
+    // the x of the first and the last points, and those shown
+    bounds: [f64; 2],
+    window: [f64; 2],
+    bars: bool,
+    // the table charted, for what comes after the chart
+    source: Option<Value>,
+}
+
+impl ChartView {
+    pub fn new(x_name: String, x_scale: Scale, y_unit: Unit, series: Vec<Series>) -> Self {
+        let xs = series
+            .iter()
+            .flat_map(|series| series.points.iter().map(|p| p.0));
+        let [min, max] = xs.fold([f64::INFINITY, f64::NEG_INFINITY], |[min, max], x| {
+            [min.min(x), max.max(x)]
+        });
+        let bounds = match min.partial_cmp(&max) {
+            Some(Ordering::Less) => [min, max],
+            // a single x is in the middle
+            Some(Ordering::Equal) => [min - 1.0, max + 1.0],
+            _ => [0.0, 1.0],
+        };
+
+        Self {
+            x_name,
+            x_scale,
+            y_unit,
+            series,
+            bounds,
+            window: bounds,
+            bars: false,
+            source: None,
+        }
+    }
+
+    /// Draw bars from 0 rather than lines, which `b` switches between.
+    pub fn with_bars(mut self, bars: bool) -> Self {
+        self.bars = bars;
+        self
+    }
+
+    /// What the chart was made of, which peeking returns.
+    pub fn with_source(mut self, value: Value) -> Self {
+        self.source = Some(value);
+        self
+    }
+
+    fn zoom(&mut self, factor: f64) {
+        let [start, end] = self.window;
+        let center = (start + end) / 2.0;
+        let width = ((end - start) * factor).min(self.bounds[1] - self.bounds[0]);
+        self.set_window(center - width / 2.0, width);
+    }
+
+    // by a share of the window's width, negative to the left
+    fn pan(&mut self, share: f64) {
+        let [start, end] = self.window;
+        let width = end - start;
+        self.set_window(start + width * share, width);
+    }
+
+    // the window `width` wide from `start`, moved back within the bounds
+    fn set_window(&mut self, start: f64, width: f64) {
+        let start = start.min(self.bounds[1] - width).max(self.bounds[0]);
+        self.window = [start, start + width];
+    }
+
+    // the points of a series within the window, with one more on each side for lines to
+    // leave the chart where they would
+    fn visible<'a>(&self, series: &'a Series) -> &'a [(f64, f64)] {
+        let [start, end] = self.window;
+        let points = &series.points;
+        let from = points.partition_point(|p| p.0 < start);
+        let to = points.partition_point(|p| p.0 <= end);
+        match self.bars {
+            true => &points[from..to],
+            false => &points[from.saturating_sub(1)..(to + 1).min(points.len())],
+        }
+    }
+
+    fn y_bounds(&self) -> [f64; 2] {
+        let [start, end] = self.window;
+        let ys = self.series.iter().flat_map(|series| {
+            self.visible(series)
+                .iter()
+                .filter(move |p| p.0 >= start && p.0 <= end)
+                .map(|p| p.1)
+        });
+        let [mut min, mut max] = ys.fold([f64::INFINITY, f64::NEG_INFINITY], |[min, max], y| {
+            [min.min(y), max.max(y)]
+        });
+        if min > max {
+            return [0.0, 1.0];
+        }
+
+        // bars stand on 0
+        if self.bars {
+            min = min.min(0.0);
+            max = max.max(0.0);
+        }
+        if min == max {
+            let pad = match min == 0.0 {
+                true => 1.0,
+                false => min.abs() / 10.0,
+            };
+            return [min - pad, max + pad];
+        }
+
+        [min, max]
+    }
+
+    fn window_text(&self, config: &NuConfig) -> String {
+        let [start, end] = self.window;
+        tr!(
+            "{start} to {end}",
+            start = self.x_scale.text(start, end - start, config),
+            end = self.x_scale.text(end, end - start, config)
+        )
+    }
+}
+
+impl View for ChartView {
+    fn draw(&mut self, f: &mut Frame, area: Rect, cfg: ViewConfig<'_>, _: &mut Layout) {
+        let marker = match cfg.explore_config.compat {
+            true => Marker::Dot,
+            false => Marker::Braille,
+        };
+        let graph_type = match self.bars {
+            true => GraphType::Bar,
+            false => GraphType::Line,
+        };
+
+        let datasets = self
+            .series
+            .iter()
+            .enumerate()
+            .map(|(i, series)| {
+                Dataset::default()
+                    .name(series.name.clone())
+                    .marker(marker)
+                    .graph_type(graph_type)
+                    .style(Style::default().fg(COLORS[i % COLORS.len()]))
+                    .data(self.visible(series))
+            })
+            .collect();
+
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let [start, end] = self.window;
+        let x_labels = [start, (start + end) / 2.0, end]
+            .map(|x| self.x_scale.text(x, (end - start) / 2.0, cfg.nu_config));
+        let [min, max] = self.y_bounds();
+        let y_labels = [min, (min + max) / 2.0, max]
+            .map(|y| self.y_unit.text(y, (max - min) / 2.0, cfg.nu_config));
+
+        // a single series is named on its axis, several in a legend
+        let (y_title, legend) = match self.series.as_slice() {
+            [series] => (series.name.clone(), None),
+            _ => (String::new(), Some(LegendPosition::TopLeft)),
+        };
+
+        let chart = Chart::new(datasets)
+            .x_axis(
+                Axis::default()
+                    .title(self.x_name.clone())
+                    .style(dim)
+                    .bounds(self.window)
+                    .labels(x_labels),
+            )
+            .y_axis(
+                Axis::default()
+                    .title(y_title)
+                    .style(dim)
+                    .bounds([min, max])
+                    .labels(y_labels),
+            )
+            .legend_position(legend);
+
+        f.render_widget(chart, area);
+    }
+
+    fn handle_input(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        _: &Layout,
+        info: &mut ViewInfo,
+        key: KeyEvent,
+    ) -> Transition {
+        match key.code {
+            KeyCode::Char('+' | '=' | 'k') | KeyCode::Up => self.zoom(0.5),
+            KeyCode::Char('-' | 'j') | KeyCode::Down => self.zoom(2.0),
+            KeyCode::Char('h') | KeyCode::Left => self.pan(-0.25),
+            KeyCode::Char('l') | KeyCode::Right => self.pan(0.25),
+            KeyCode::Char('0') | KeyCode::Home => self.window = self.bounds,
+            KeyCode::Char('b') => {
+                self.bars = !self.bars;
+                return Transition::Ok;
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Transition::Exit,
+            _ => return Transition::None,
+        }
+
+        info.status = Some(Report::info(self.window_text(&NuConfig::default())));
+        Transition::Ok
+    }
+
+    fn exit(&mut self) -> Option<Value> {
+        self.source.clone()
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        let bars = match self.bars {
+            true => "lines",
+            false => "bars",
+        };
+        vec![
+            KeyHint::new("+-", "zoom"),
+            KeyHint::new("←→", "pan"),
+            KeyHint::new("0", "all"),
+            KeyHint::new("b", bars),
+            KeyHint::new("q", "back"),
+        ]
+    }
+
+    fn status_segments(&self, segments: &mut StatusSegments) {
+        if segments.mode() == StatusMode::Perf {
+            let points: usize = self.series.iter().map(|series| series.points.len()).sum();
+            segments.push(&tr!("points"), points);
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        "chart"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::Span;
+
+    fn test_chart(points: &[(f64, f64)]) -> ChartView {
+        let series = Series {
+            name: "y".to_string(),
+            points: points.to_vec(),
+        };
+        let scale = Scale::Unit(Unit::Number);
+        ChartView::new("x".to_string(), scale, Unit::Number, vec![series])
+    }
+
+    #[test]
+    fn zoom_and_pan_within_the_bounds() {
+        let points: Vec<(f64, f64)> = (0..=100).map(|x| (x as f64, x as f64)).collect();
+        let mut chart = test_chart(&points);
+        assert_eq!(chart.window, [0.0, 100.0]);
+
+        chart.zoom(0.5);
+        assert_eq!(chart.window, [25.0, 75.0]);
+        chart.pan(0.25);
+        assert_eq!(chart.window, [37.5, 87.5]);
+        chart.pan(1.0);
+        assert_eq!(chart.window, [50.0, 100.0]);
+
+        // zooming out goes no further than all of it
+        chart.zoom(4.0);
+        assert_eq!(chart.window, [0.0, 100.0]);
+    }
+
+    #[test]
+    fn points_in_the_window() {
+        let points = [(0.0, 5.0), (1.0, 2.0), (2.0, 8.0), (3.0, 4.0)];
+        let mut chart = test_chart(&points);
+        chart.window = [1.0, 2.0];
+
+        // lines go on to the points beyond the window
+        assert_eq!(chart.visible(&chart.series[0]).len(), 4);
+        assert_eq!(chart.y_bounds(), [2.0, 8.0]);
+
+        chart.bars = true;
+        assert_eq!(chart.visible(&chart.series[0]), &points[1..3]);
+        assert_eq!(chart.y_bounds(), [0.0, 8.0]);
+    }
+
+    #[test]
+    fn dates_as_text() {
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let date = DateTime::parse_from_rfc3339("2024-03-01T12:30:00+01:00").unwrap();
+        let scale = Scale::Date(offset);
+        let x = scale.number(&Value::date(date, Span::test_data())).unwrap();
+
+        let config = NuConfig::default();
+        assert_eq!(scale.text(x, 7.0 * 86_400.0, &config), "2024-03-01");
+        assert_eq!(scale.text(x, 3600.0, &config), "03-01 12:30");
+        assert_eq!(scale.text(x, 1.0, &config), "12:30:00");
+    }
+}
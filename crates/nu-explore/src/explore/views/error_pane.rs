@@ -0,0 +1,193 @@
+use nu_protocol::{LabeledError, ShellError};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Widget},
+};
+use std::ops::Range;
+use unicode_width::UnicodeWidthStr;
+
+/// The error a pipeline failed with, drawn as nushell prints it: its message, the pipeline
+/// with the parts it's about underlined and labeled, and its help.
+#[derive(Debug, Clone)]
+pub struct ErrorPane {
+    message: String,
+    code: Option<String>,
+    line: String,
+    // the bytes of `line` each label is about, none when it's about some other source
+    labels: Vec<(Option<Range<usize>>, String)>,
+    help: Option<String>,
+}
+
+impl ErrorPane {
+    /// The pane of `error`, raised by running `line` whose spans start at `offset`.
+    ///
+    /// An error wrapping those it comes from, as a failing pipeline does, is shown by the
+    /// first of them that points somewhere.
+    pub fn new(error: &ShellError, line: &str, offset: usize) -> Self {
+        let error = LabeledError::from(error.clone());
+        let shown = find_labeled(&error).unwrap_or(&error);
+
+        let labels = shown
+            .labels
+            .iter()
+            .map(|label| {
+                let start = label.span.start.checked_sub(offset);
+                let end = label.span.end.checked_sub(offset);
+                let range = start
+                    .zip(end)
+                    .map(|(start, end)| start..end)
+                    .filter(|range| {
+                        range.end <= line.len()
+                            && line.is_char_boundary(range.start)
+                            && line.is_char_boundary(range.end)
+                    });
+                (range, label.text.clone())
+            })
+            .collect();
+
+        Self {
+            message: shown.msg.clone(),
+            code: shown.code.clone(),
+            line: line.to_string(),
+            labels,
+            help: shown.help.clone(),
+        }
+    }
+
+    /// The lines drawn, without the border.
+    fn lines(&self) -> Vec<Line<'_>> {
+        let error = Style::default().fg(Color::Red);
+        let dim = Style::default().add_modifier(Modifier::DIM);
+
+        let mut title = vec![Span::styled(
+            format!("× {}", self.message),
+            error.add_modifier(Modifier::BOLD),
+        )];
+        if let Some(code) = &self.code {
+            title.push(Span::styled(format!("  {code}"), dim));
+        }
+        let mut lines = vec![Line::from(title)];
+
+        let mut elsewhere = Vec::new();
+        let mut marks = Vec::new();
+        for (range, text) in &self.labels {
+            match range {
+                Some(range) => {
+                    let column = self.line[..range.start].width();
+                    let marked = self.line[range.clone()].width().max(1);
+                    let mut mark = format!("{}{}", " ".repeat(column), "^".repeat(marked));
+                    if !text.is_empty() {
+                        mark.push(' ');
+                        mark.push_str(text);
+                    }
+                    marks.push(Line::styled(mark, error));
+                }
+                None if !text.is_empty() => {
+                    elsewhere.push(Line::styled(format!("· {text}"), dim));
+                }
+                None => {}
+            }
+        }
+        if !marks.is_empty() {
+            lines.push(Line::raw(self.line.as_str()));
+            lines.extend(marks);
+        }
+        lines.extend(elsewhere);
+
+        if let Some(help) = &self.help {
+            lines.push(Line::from(vec![
+                Span::styled("help: ", Style::default().fg(Color::Cyan)),
+                Span::raw(help.as_str()),
+            ]));
+        }
+
+        lines
+    }
+
+    /// How high the pane is drawn, its border included.
+    pub fn height(&self) -> u16 {
+        self.lines().len() as u16 + 2
+    }
+}
+
+impl Widget for &ErrorPane {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border = Style::default().fg(Color::Red);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(border)
+            .title(Line::from(vec![
+                Span::raw(" "),
+                Span::styled("Error", border.add_modifier(Modifier::BOLD)),
+                Span::raw(" "),
+            ]));
+
+        Paragraph::new(self.lines()).block(block).render(area, buf);
+    }
+}
+
+// the first error, depth first, with a label; those an error wraps come before it, as a
+// generic error is labeled even when it only knows where in the code it was raised
+fn find_labeled(error: &LabeledError) -> Option<&LabeledError> {
+    error
+        .inner
+        .iter()
+        .find_map(|inner| match inner {
+            ShellError::LabeledError(inner) => find_labeled(inner),
+            _ => None,
+        })
+        .or_else(|| (!error.labels.is_empty()).then_some(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::{ErrorLabel, Span, shell_error::generic::GenericError};
+
+    #[test]
+    fn underline_the_spans_in_the_line() {
+        let offset = 100;
+        let line = "ls | get nope";
+        let cause = LabeledError::new("Cannot find column")
+            .with_label("not here", Span::new(offset + 9, offset + 13))
+            .with_help("check the columns");
+        let error = ShellError::Generic(
+            GenericError::new_internal("Error from pipeline", "Cannot find column")
+                .with_inner([cause.into()]),
+        );
+
+        let pane = ErrorPane::new(&error, line, offset);
+        assert_eq!(pane.message, "Cannot find column");
+        assert_eq!(pane.labels, [(Some(9..13), "not here".to_string())]);
+
+        let text: Vec<String> = pane.lines().iter().map(|line| line.to_string()).collect();
+        assert_eq!(
+            text,
+            [
+                "× Cannot find column",
+                "ls | get nope",
+                "         ^^^^ not here",
+                "help: check the columns",
+            ]
+        );
+        assert_eq!(pane.height(), 6);
+    }
+
+    #[test]
+    fn labels_of_other_sources() {
+        let mut error = LabeledError::new("Failed");
+        error.labels.push(ErrorLabel {
+            text: "in the closure".into(),
+            span: Span::new(3, 8),
+        });
+
+        let pane = ErrorPane::new(&error.into(), "do $f", 100);
+        assert_eq!(pane.labels, [(None, "in the closure".to_string())]);
+        let text: Vec<String> = pane.lines().iter().map(|line| line.to_string()).collect();
+        assert_eq!(text, ["× Failed", "· in the closure"]);
+    }
+}
@@ -0,0 +1,256 @@
+use super::super::{
+    l10n::tr,
+    nu_common::{NuSpan, NuText, string_width, truncate_str},
+    pager::{
+        Frame, StatusTopOrEnd, Transition, ViewInfo,
+        report::Report,
+        status_bar::{StatusMode, StatusSegments},
+    },
+};
+use super::{
+    KeyHint, Layout, View, ViewConfig,
+    cursor::{CursorMoveHandler, WindowCursor2D},
+    util::nu_style_to_tui,
+};
+use crossterm::event::KeyEvent;
+use nu_color_config::TextStyle;
+use nu_protocol::{
+    Value,
+    engine::{EngineState, Stack},
+};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Span,
+};
+
+// the eighths of a block, for the end of a bar
+const BLOCKS: [&str; 8] = [" ", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// A bar of a histogram: the values of a range, or equal to one value, and how many there are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bar {
+    pub label: String,
+    pub count: usize,
+}
+
+impl Bar {
+    pub fn new(label: impl Into<String>, count: usize) -> Self {
+        Self {
+            label: label.into(),
+            count,
+        }
+    }
+}
+
+/// The histogram of a column made by `:hist`, a bar a line, the selected one highlighted.
+#[derive(Debug)]
+pub struct HistogramView {
+    // what the bars are of, shown above them
+    title: String,
+    bars: Vec<Bar>,
+    cursor: WindowCursor2D,
+}
+
+impl HistogramView {
+    pub fn new(title: String, bars: Vec<Bar>) -> Self {
+        // TODO: refactor so this is fallible and returns a Result instead of panicking
+        let cursor = WindowCursor2D::new(bars.len(), 1).expect("Failed to create cursor");
+        Self {
+            title,
+            bars,
+            cursor,
+        }
+    }
+}
+
+impl View for HistogramView {
+    fn draw(&mut self, f: &mut Frame, area: Rect, cfg: ViewConfig<'_>, layout: &mut Layout) {
+        if area.height < 2 {
+            return;
+        }
+
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let mut title = self.title.clone();
+        truncate_str(&mut title, area.width as usize);
+        f.render_widget(
+            Span::styled(title, dim),
+            Rect::new(area.x, area.y, area.width, 1),
+        );
+
+        let area = Rect::new(area.x, area.y + 1, area.width, area.height - 1);
+        let _ = self.cursor.set_window_size(area.height as usize, 1);
+
+        // the labels take up to a third of the width, the counts what the largest needs
+        let label_width = self.bars.iter().map(|bar| string_width(&bar.label));
+        let label_width = label_width.max().unwrap_or(0).min(area.width as usize / 3);
+        let max_count = self.bars.iter().map(|bar| bar.count).max().unwrap_or(0);
+        let count_width = max_count.to_string().len();
+        let bar_width = (area.width as usize).saturating_sub(label_width + count_width + 3);
+
+        let selected = nu_style_to_tui(cfg.explore_config.selected_cell);
+        let start = self.cursor.window_origin().row;
+        let rows = self.bars.iter().enumerate().skip(start);
+        for (i, (row, bar)) in rows.enumerate().take(area.height as usize) {
+            let mut label = bar.label.clone();
+            truncate_str(&mut label, label_width);
+            let pad = label_width.saturating_sub(string_width(&label));
+            let line = format!(
+                "{}{label} {} {:>count_width$}",
+                " ".repeat(pad),
+                bar_text(bar.count, max_count, bar_width),
+                bar.count,
+            );
+
+            let line_area = Rect::new(area.x, area.y + i as u16, area.width, 1);
+            let style = match row == self.cursor.row() {
+                true => selected,
+                false => Style::default(),
+            };
+            f.render_widget(Span::styled(line, style), line_area);
+
+            layout.push(&bar.label, area.x, line_area.y, area.width, 1);
+        }
+    }
+
+    fn handle_input(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        _: &Layout,
+        info: &mut ViewInfo,
+        key: KeyEvent,
+    ) -> Transition {
+        match self.handle_input_key(&key) {
+            Ok((transition, status_top_or_end)) => {
+                let status = match status_top_or_end {
+                    StatusTopOrEnd::Top if self.cursor.row() == 0 => Report::info(tr!("TOP")),
+                    StatusTopOrEnd::End if self.cursor.row() + 1 == self.bars.len() => {
+                        Report::info(tr!("END"))
+                    }
+                    _ => Report::default(),
+                };
+                info.status = Some(status);
+                transition
+            }
+            _ => Transition::None,
+        }
+    }
+
+    fn collect_data(&self) -> Vec<NuText> {
+        self.bars
+            .iter()
+            .map(|bar| (bar.label.clone(), TextStyle::default()))
+            .collect()
+    }
+
+    fn show_data(&mut self, row: usize) -> bool {
+        if row >= self.bars.len() {
+            return false;
+        }
+
+        // the window follows the cursor
+        let selected = self.cursor.row();
+        match row > selected {
+            true => self.cursor.y.next_n(row - selected),
+            false => self.cursor.y.prev_n(selected - row),
+        }
+        true
+    }
+
+    fn exit(&mut self) -> Option<Value> {
+        let span = NuSpan::unknown();
+        let rows = self
+            .bars
+            .iter()
+            .map(|bar| {
+                let record = [
+                    ("bin".to_string(), Value::string(&bar.label, span)),
+                    ("count".to_string(), Value::int(bar.count as i64, span)),
+                ];
+                Value::record(record.into_iter().collect(), span)
+            })
+            .collect();
+
+        Some(Value::list(rows, span))
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new("↑↓", "select"),
+            KeyHint::new("/", "search"),
+            KeyHint::new(":", "command"),
+            KeyHint::new("q", "back"),
+        ]
+    }
+
+    fn status_segments(&self, segments: &mut StatusSegments) {
+        if segments.mode() == StatusMode::Perf {
+            segments.push(&tr!("bars"), self.bars.len());
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        "histogram"
+    }
+}
+
+impl CursorMoveHandler for HistogramView {
+    fn get_cursor(&mut self) -> &mut WindowCursor2D {
+        &mut self.cursor
+    }
+
+    // the bars are a list, up and down select them and there's nothing to the sides
+    fn handle_up(&mut self) {
+        self.cursor.prev_row()
+    }
+    fn handle_down(&mut self) {
+        self.cursor.next_row()
+    }
+    fn handle_left(&mut self) {}
+    fn handle_right(&mut self) {}
+}
+
+/// A bar `width` long for the largest count, in eighths of a block.
+fn bar_text(count: usize, max_count: usize, width: usize) -> String {
+    if max_count == 0 {
+        return " ".repeat(width);
+    }
+
+    let eighths = count * width * 8 / max_count;
+    let mut text = "█".repeat(eighths / 8);
+    if !eighths.is_multiple_of(8) {
+        text.push_str(BLOCKS[eighths % 8]);
+    }
+    let pad = width - text.chars().count();
+    text.push_str(&" ".repeat(pad));
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bars_in_eighths() {
+        assert_eq!(bar_text(4, 4, 4), "████");
+        assert_eq!(bar_text(1, 4, 4), "█   ");
+        assert_eq!(bar_text(3, 16, 4), "▊   ");
+        assert_eq!(bar_text(0, 4, 3), "   ");
+        assert_eq!(bar_text(0, 0, 2), "  ");
+    }
+
+    #[test]
+    fn search_selects_the_bar() {
+        let bars = (0..50).map(|i| Bar::new(i.to_string(), i)).collect();
+        let mut view = HistogramView::new(String::new(), bars);
+        let _ = view.cursor.set_window_size(10, 1);
+
+        assert!(view.show_data(30));
+        assert_eq!(view.cursor.row(), 30);
+        assert!(view.show_data(32));
+        assert_eq!(view.cursor.row(), 32);
+        assert!(!view.show_data(50));
+    }
+}
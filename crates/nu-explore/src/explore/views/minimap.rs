@@ -0,0 +1,139 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+use std::ops::Range;
+
+/// Something worth finding in the rows, marked on the minimap; where a line of the minimap
+/// stands for rows of several marks, the greatest one shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Mark {
+    /// A match of the search
+    Match,
+    /// A row marked with `m`
+    Bookmark,
+    /// A cell holding an error
+    Error,
+}
+
+/// A scrollbar one column wide, drawn in ASCII: `|` for the rows, `#` for those in the window,
+/// `*` for search matches, `@` for marked rows and `!` for errors.
+pub struct Minimap<'a> {
+    total: usize,
+    window: Range<usize>,
+    marks: &'a [(usize, Mark)],
+    highlight: Style,
+}
+
+impl<'a> Minimap<'a> {
+    pub const WIDTH: u16 = 1;
+
+    /// A minimap of `total` rows, `window` being the ones shown.
+    pub fn new(total: usize, window: Range<usize>, marks: &'a [(usize, Mark)]) -> Self {
+        Self {
+            total,
+            window,
+            marks,
+            highlight: Style::default(),
+        }
+    }
+
+    /// The style of search matches, the one they're highlighted with.
+    pub fn with_highlight(mut self, style: Style) -> Self {
+        self.highlight = style;
+        self
+    }
+
+    /// The first of the `total` rows the line `y` of a minimap `height` lines high stands for,
+    /// the row a click on it goes to.
+    pub fn row_at(total: usize, y: u16, height: u16) -> usize {
+        y as usize * total / (height as usize).max(1)
+    }
+
+    /// The rows the line `y` of a minimap `height` lines high stands for.
+    fn rows(&self, y: usize, height: usize) -> Range<usize> {
+        let start = y * self.total / height;
+        let end = ((y + 1) * self.total / height).max(start + 1);
+        start..end
+    }
+}
+
+impl Widget for Minimap<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 || self.total == 0 {
+            return;
+        }
+
+        let height = area.height as usize;
+        let mut lines: Vec<Option<Mark>> = vec![None; height];
+        for &(row, mark) in self.marks.iter().filter(|(row, _)| *row < self.total) {
+            let line = &mut lines[row * height / self.total];
+            *line = (*line).max(Some(mark));
+        }
+
+        for (y, mark) in lines.into_iter().enumerate() {
+            let rows = self.rows(y, height);
+            let in_window = rows.start < self.window.end && self.window.start < rows.end;
+            let (symbol, mut style) = match mark {
+                Some(Mark::Error) => ("!", Style::default().fg(Color::Red)),
+                Some(Mark::Bookmark) => ("@", Style::default().fg(Color::Cyan)),
+                Some(Mark::Match) => ("*", self.highlight),
+                None if in_window => ("#", Style::default()),
+                None => ("|", Style::default().add_modifier(Modifier::DIM)),
+            };
+            if in_window && mark.is_some() {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+
+            buf.set_string(area.x, area.y + y as u16, symbol, style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(minimap: Minimap, height: u16) -> String {
+        let area = Rect::new(0, 0, 1, height);
+        let mut buf = Buffer::empty(area);
+        minimap.render(area, &mut buf);
+
+        (0..height)
+            .map(|y| buf[(0, y)].symbol().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn minimap_shows_window_and_marks() {
+        let marks = [
+            (95, Mark::Match),
+            (10, Mark::Match),
+            (12, Mark::Error),
+            (55, Mark::Bookmark),
+        ];
+        let minimap = Minimap::new(100, 40..60, &marks);
+
+        // 10 lines of 10 rows each; the error outweighs the match of the same line
+        assert_eq!(render(minimap, 10), "|!||#@|||*");
+    }
+
+    #[test]
+    fn minimap_of_few_rows() {
+        let minimap = Minimap::new(2, 1..2, &[]);
+        assert_eq!(render(minimap, 4), "||##");
+
+        let minimap = Minimap::new(0, 0..0, &[]);
+        assert_eq!(render(minimap, 4), "    ");
+    }
+
+    #[test]
+    fn rows_of_a_click() {
+        assert_eq!(Minimap::row_at(100, 0, 10), 0);
+        assert_eq!(Minimap::row_at(100, 5, 10), 50);
+        assert_eq!(Minimap::row_at(3, 9, 10), 2);
+        assert_eq!(Minimap::row_at(3, 0, 0), 0);
+    }
+}
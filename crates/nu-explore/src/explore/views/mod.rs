@@ -1,30 +1,43 @@
 mod binary;
+mod chart;
 mod colored_text_widget;
 mod cursor;
+mod error_pane;
+mod histogram;
+mod minimap;
+mod palette;
 mod preview;
 mod record;
 mod r#try;
 pub mod util;
 
 use super::{
+    bindings::{self, KeyBinding, Rebound},
     config::ExploreConfig,
+    l10n,
     nu_common::{NuConfig, NuText},
-    pager::{Frame, Transition, ViewInfo},
+    pager::{Frame, Transition, ViewInfo, status_bar::StatusSegments},
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use lscolors::LsColors;
 use nu_color_config::StyleComputer;
 use nu_protocol::{
-    Value,
+    Record, Value,
     engine::{EngineState, Stack},
 };
 use ratatui::layout::Rect;
 
 pub use binary::BinaryView;
+pub use chart::{ChartView, Scale, Series};
+pub use histogram::{Bar, HistogramView};
+pub use palette::{PaletteEntry, PaletteView};
 pub use preview::Preview;
-pub use record::{Orientation, RecordView};
+pub use record::{Orientation, RecordView, sort_message};
 pub use r#try::TryView;
 
+/// How many rows or lines a turn of the mouse wheel scrolls.
+pub const WHEEL_ROWS: usize = 3;
+
 #[derive(Debug, Default)]
 pub struct Layout {
     pub data: Vec<ElementInfo>,
@@ -52,6 +65,48 @@ impl ElementInfo {
     }
 }
 
+/// A key and what it does, as listed in the hint bar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyHint {
+    pub key: String,
+    pub action: String,
+    // the default key of the keymap the hint is of, with its action there
+    bound: Option<(&'static str, &'static str)>,
+}
+
+impl KeyHint {
+    /// The action is translated, the key isn't.
+    pub fn new(key: impl Into<String>, action: &str) -> Self {
+        Self {
+            key: key.into(),
+            action: l10n::translate(action, &[]),
+            bound: None,
+        }
+    }
+
+    /// The hint is of `key`, a default key doing `action` in the keymap, so it follows the
+    /// key when `explore.keybindings` binds it to something else.
+    pub fn bound(mut self, key: &'static str, action: &'static str) -> Self {
+        self.bound = Some((key, action));
+        self
+    }
+
+    /// The hint as `config_bindings` have it in `view`: of the keys its action was moved to,
+    /// or none when no key does it anymore.
+    pub fn rebind(mut self, config_bindings: &[KeyBinding], view: &str) -> Option<Self> {
+        let Some((key, action)) = self.bound else {
+            return Some(self);
+        };
+        match bindings::rebound(key, action, view, config_bindings) {
+            Rebound::Same => {}
+            Rebound::Moved(keys) => self.key = keys,
+            Rebound::Off => return None,
+        }
+
+        Some(self)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ViewConfig<'a> {
     pub nu_config: &'a NuConfig,
@@ -59,6 +114,10 @@ pub struct ViewConfig<'a> {
     pub style_computer: &'a StyleComputer<'a>,
     pub lscolors: &'a LsColors,
     pub cwd: &'a str,
+    /// The texts copied in the session, the latest first
+    pub registers: &'a [String],
+    /// The pipelines run in `:try`, the oldest first
+    pub try_history: &'a [String],
 }
 
 impl<'a> ViewConfig<'a> {
@@ -75,8 +134,20 @@ impl<'a> ViewConfig<'a> {
             style_computer,
             lscolors,
             cwd,
+            registers: &[],
+            try_history: &[],
         }
     }
+
+    pub fn with_registers(mut self, registers: &'a [String]) -> Self {
+        self.registers = registers;
+        self
+    }
+
+    pub fn with_try_history(mut self, history: &'a [String]) -> Self {
+        self.try_history = history;
+        self
+    }
 }
 
 pub trait View {
@@ -91,6 +162,17 @@ pub trait View {
         key: KeyEvent,
     ) -> Transition;
 
+    /// A click, drag or turn of the wheel at a place of `layout`; views that don't handle the
+    /// wheel are scrolled with the arrow keys instead.
+    fn handle_mouse(
+        &mut self,
+        _layout: &Layout,
+        _info: &mut ViewInfo,
+        _event: MouseEvent,
+    ) -> Transition {
+        Transition::None
+    }
+
     /// Called every frame to allow the view to update its internal state
     /// (e.g., check for streaming data) and update the status bar.
     /// Returns true if the view has pending updates that require continued polling.
@@ -102,6 +184,16 @@ pub trait View {
         false
     }
 
+    /// The rows of `collect_data` matching the search, each time it changes.
+    fn show_matches(&mut self, _rows: &[usize]) {}
+
+    /// The matches of a search the view makes itself, for data which isn't lines of text like
+    /// bytes, `None` to search `collect_data`; what's returned is what `show_data` and
+    /// `show_matches` are given.
+    fn search(&mut self, _pattern: &str) -> Option<Vec<usize>> {
+        None
+    }
+
     fn collect_data(&self) -> Vec<NuText> {
         Vec::new()
     }
@@ -109,6 +201,44 @@ pub trait View {
     fn exit(&mut self) -> Option<Value> {
         None
     }
+
+    /// The handful of keys most useful in the view's current mode, most important first.
+    fn key_hints(&self) -> Vec<KeyHint> {
+        Vec::new()
+    }
+
+    /// Add what the view knows in the status bar's mode to its right, after the pager's own
+    /// segments; the position mode shows the view's report instead.
+    fn status_segments(&self, _segments: &mut StatusSegments) {}
+
+    /// What `$env.EXPLORE_LAST` tells of the view the session ended in.
+    fn exit_details(&self, _details: &mut Record) {}
+
+    /// What the page is called in the title bar, e.g. the name given with `:title`.
+    fn title(&self) -> Option<String> {
+        None
+    }
+
+    /// Gives commands which rework a table in place (e.g. `:filter`) access to it.
+    fn as_record_view(&mut self) -> Option<&mut RecordView> {
+        None
+    }
+
+    /// Gives `:save` the bytes of a binary view, edits and all.
+    fn as_binary_view(&mut self) -> Option<&mut BinaryView> {
+        None
+    }
+
+    /// What the view is called in keybindings: `table`, `preview`, `binary` or `try`.
+    fn kind(&self) -> &'static str {
+        ""
+    }
+
+    /// The mode the view is in, for views with several: keys can be bound in one of them
+    /// alone, as `table:cursor` or `table:view`.
+    fn mode(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 impl View for Box<dyn View> {
@@ -128,6 +258,15 @@ impl View for Box<dyn View> {
             .handle_input(engine_state, stack, layout, info, key)
     }
 
+    fn handle_mouse(
+        &mut self,
+        layout: &Layout,
+        info: &mut ViewInfo,
+        event: MouseEvent,
+    ) -> Transition {
+        self.as_mut().handle_mouse(layout, info, event)
+    }
+
     fn update(&mut self, info: &mut ViewInfo) -> bool {
         self.as_mut().update(info)
     }
@@ -140,7 +279,47 @@ impl View for Box<dyn View> {
         self.as_mut().exit()
     }
 
+    fn key_hints(&self) -> Vec<KeyHint> {
+        self.as_ref().key_hints()
+    }
+
     fn show_data(&mut self, i: usize) -> bool {
         self.as_mut().show_data(i)
     }
+
+    fn search(&mut self, pattern: &str) -> Option<Vec<usize>> {
+        self.as_mut().search(pattern)
+    }
+
+    fn show_matches(&mut self, rows: &[usize]) {
+        self.as_mut().show_matches(rows)
+    }
+
+    fn status_segments(&self, segments: &mut StatusSegments) {
+        self.as_ref().status_segments(segments)
+    }
+
+    fn exit_details(&self, details: &mut Record) {
+        self.as_ref().exit_details(details)
+    }
+
+    fn title(&self) -> Option<String> {
+        self.as_ref().title()
+    }
+
+    fn as_record_view(&mut self) -> Option<&mut RecordView> {
+        self.as_mut().as_record_view()
+    }
+
+    fn as_binary_view(&mut self) -> Option<&mut BinaryView> {
+        self.as_mut().as_binary_view()
+    }
+
+    fn kind(&self) -> &'static str {
+        self.as_ref().kind()
+    }
+
+    fn mode(&self) -> Option<&'static str> {
+        self.as_ref().mode()
+    }
 }
@@ -0,0 +1,276 @@
+use super::super::{
+    nu_common::{NuText, string_width, truncate_str},
+    pager::{Frame, Transition, ViewInfo, fuzzy::fuzzy_match},
+};
+use super::{KeyHint, Layout, View, ViewConfig, util::nu_style_to_tui};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use nu_color_config::TextStyle;
+use nu_protocol::engine::{EngineState, Stack};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Span,
+};
+use std::cmp::Reverse;
+
+// the widest the column of the names gets
+const NAME_WIDTH: usize = 24;
+
+/// A command listed in the palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteEntry {
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub description: String,
+}
+
+impl PaletteEntry {
+    // the name as listed, with the aliases after it
+    fn label(&self) -> String {
+        match self.aliases.is_empty() {
+            true => format!(":{}", self.name),
+            false => format!(":{} (:{})", self.name, self.aliases.join(", :")),
+        }
+    }
+}
+
+/// The commands, found by typing a few characters of their names or of what they do, the
+/// closest first: Enter runs the selected one, Tab types it in the command bar, to add its
+/// arguments.
+#[derive(Debug)]
+pub struct PaletteView {
+    entries: Vec<PaletteEntry>,
+    typed: String,
+    // the entries matching what's typed, the closest first
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+impl PaletteView {
+    pub fn new(entries: Vec<PaletteEntry>) -> Self {
+        let mut view = Self {
+            entries,
+            typed: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+        };
+        view.find_matches();
+        view
+    }
+
+    pub fn selected(&self) -> Option<&PaletteEntry> {
+        let index = self.matches.get(self.selected)?;
+        self.entries.get(*index)
+    }
+
+    // a match of the name or an alias comes before one of the description alone
+    fn find_matches(&mut self) {
+        self.selected = 0;
+        if self.typed.is_empty() {
+            self.matches = (0..self.entries.len()).collect();
+            return;
+        }
+
+        let score = |text: &str| fuzzy_match(&self.typed, text).map(|found| found.score);
+        let mut matches: Vec<_> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| {
+                let names = std::iter::once(&entry.name).chain(&entry.aliases);
+                match names.filter_map(|name| score(name)).max() {
+                    Some(score) => Some((i, 0, score)),
+                    None => score(&entry.description).map(|score| (i, 1, score)),
+                }
+            })
+            .collect();
+        matches.sort_by_key(|&(i, kind, score)| (kind, Reverse(score), i));
+
+        self.matches = matches.into_iter().map(|(i, _, _)| i).collect();
+    }
+}
+
+impl View for PaletteView {
+    fn draw(&mut self, f: &mut Frame, area: Rect, cfg: ViewConfig<'_>, _: &mut Layout) {
+        if area.height < 2 {
+            return;
+        }
+
+        let prompt = format!("> {}", self.typed);
+        f.render_widget(
+            Span::styled(prompt, Style::default().add_modifier(Modifier::BOLD)),
+            Rect::new(area.x, area.y, area.width, 1),
+        );
+
+        let labels: Vec<_> = self
+            .matches
+            .iter()
+            .map(|&i| self.entries[i].label())
+            .collect();
+        let name_width = labels.iter().map(|label| string_width(label)).max();
+        let name_width = name_width.unwrap_or(0).min(NAME_WIDTH);
+
+        // keep the selected command in sight when there are more than lines
+        let height = area.height as usize - 1;
+        let offset = (self.selected + 1).saturating_sub(height);
+
+        let selected = nu_style_to_tui(cfg.explore_config.selected_cell);
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let rows = self.matches.iter().zip(labels).enumerate().skip(offset);
+        for (y, (row, (&i, mut label))) in (area.y + 1..).zip(rows.take(height)) {
+            truncate_str(&mut label, name_width);
+            let pad = name_width.saturating_sub(string_width(&label));
+            let line = Rect::new(area.x, y, area.width, 1);
+            let style = match row == self.selected {
+                true => selected,
+                false => Style::default(),
+            };
+            // the selected line is highlighted across
+            f.render_widget(Span::styled(" ".repeat(area.width as usize), style), line);
+
+            let name = format!(" {label}{}  ", " ".repeat(pad));
+            let name_len = string_width(&name) as u16;
+            f.render_widget(Span::styled(name, style), line);
+
+            let mut description = self.entries[i].description.clone();
+            let width = area.width.saturating_sub(name_len);
+            truncate_str(&mut description, width as usize);
+            f.render_widget(
+                Span::styled(description, style.patch(dim)),
+                Rect::new(area.x + name_len.min(area.width), y, width, 1),
+            );
+        }
+    }
+
+    fn handle_input(
+        &mut self,
+        _: &EngineState,
+        _: &mut Stack,
+        _: &Layout,
+        _: &mut ViewInfo,
+        key: KeyEvent,
+    ) -> Transition {
+        let control = key.modifiers == KeyModifiers::CONTROL;
+        match key.code {
+            KeyCode::Esc => return Transition::Exit,
+            KeyCode::Enter => {
+                if let Some(entry) = self.selected() {
+                    return Transition::Cmd(entry.name.clone());
+                }
+            }
+            KeyCode::Tab => {
+                if let Some(entry) = self.selected() {
+                    return Transition::Prompt(format!("{} ", entry.name));
+                }
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('p') if control => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.matches.len().saturating_sub(1))
+            }
+            KeyCode::Char('n') if control => {
+                self.selected = (self.selected + 1).min(self.matches.len().saturating_sub(1))
+            }
+            KeyCode::Backspace => {
+                self.typed.pop();
+                self.find_matches();
+            }
+            KeyCode::Char(c) if !control && !key.modifiers.contains(KeyModifiers::ALT) => {
+                self.typed.push(c);
+                self.find_matches();
+            }
+            _ => {}
+        }
+
+        // the keys are all typed into the palette
+        Transition::Ok
+    }
+
+    fn collect_data(&self) -> Vec<NuText> {
+        self.matches
+            .iter()
+            .map(|&i| (self.entries[i].label(), TextStyle::default()))
+            .collect()
+    }
+
+    fn show_data(&mut self, row: usize) -> bool {
+        if row < self.matches.len() {
+            self.selected = row;
+            return true;
+        }
+        false
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        vec![
+            KeyHint::new("↑↓", "select"),
+            KeyHint::new("Enter", "run"),
+            KeyHint::new("Tab", "add arguments"),
+            KeyHint::new("Esc", "close"),
+        ]
+    }
+
+    fn kind(&self) -> &'static str {
+        "palette"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, aliases: &[&str], description: &str) -> PaletteEntry {
+        PaletteEntry {
+            name: name.to_string(),
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+            description: description.to_string(),
+        }
+    }
+
+    fn names(view: &PaletteView) -> Vec<&str> {
+        view.matches
+            .iter()
+            .map(|&i| view.entries[i].name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn closest_commands_first() {
+        let mut view = PaletteView::new(vec![
+            entry(
+                "filter",
+                &[],
+                "keep only the rows where a column has a value",
+            ),
+            entry("quit", &["q"], "exit explore"),
+            entry("sort", &[], "sort the rows by a column"),
+            entry("stats", &[], "sum up each column"),
+        ]);
+        assert_eq!(names(&view), ["filter", "quit", "sort", "stats"]);
+
+        view.typed = String::from("srt");
+        view.find_matches();
+        assert_eq!(names(&view), ["sort"]);
+
+        // names before descriptions
+        view.typed = String::from("rows");
+        view.find_matches();
+        let mut found = names(&view);
+        found.sort_unstable();
+        assert_eq!(found, ["filter", "sort"]);
+        view.typed = String::from("s");
+        view.find_matches();
+        assert_eq!(names(&view)[..2], ["sort", "stats"]);
+
+        view.typed = String::from("q");
+        view.find_matches();
+        assert_eq!(
+            view.selected().map(|entry| entry.name.as_str()),
+            Some("quit")
+        );
+
+        view.typed = String::from("zzz");
+        view.find_matches();
+        assert!(view.selected().is_none());
+    }
+}
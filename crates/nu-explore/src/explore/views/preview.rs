@@ -1,18 +1,31 @@
 use super::super::{
+    l10n::tr,
     nu_common::{NuSpan, NuText},
-    pager::{Frame, StatusTopOrEnd, Transition, ViewInfo, report::Report},
+    pager::{
+        Frame, StatusTopOrEnd, Transition, ViewInfo,
+        report::Report,
+        status_bar::{StatusMode, StatusSegments},
+    },
 };
 use super::{
-    Layout, View, ViewConfig, colored_text_widget::ColoredTextWidget, cursor::CursorMoveHandler,
+    KeyHint, Layout, View, ViewConfig,
+    colored_text_widget::ColoredTextWidget,
+    cursor::CursorMoveHandler,
     cursor::WindowCursor2D,
+    minimap::{Mark, Minimap},
+    util::nu_style_to_tui,
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent};
 use nu_color_config::TextStyle;
 use nu_protocol::{
     Value,
     engine::{EngineState, Stack},
 };
-use ratatui::layout::Rect;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Span,
+};
 use std::cmp::max;
 
 // todo: Add wrap option
@@ -21,6 +34,15 @@ pub struct Preview {
     underlying_value: Option<Value>,
     lines: Vec<String>,
     cursor: WindowCursor2D,
+    // (levels expanded, levels there are) of the nested tables of an `:expand`
+    depth: Option<(usize, usize)>,
+    // the lines matching the search, in order
+    matches: Vec<usize>,
+    // the lines of context around each match, from `explore.search_context`
+    context: Option<usize>,
+    // the lines shown while there are matches and a context: the matches with their context,
+    // `None` for the gap between two of them which don't touch
+    excerpt: Option<Vec<Option<usize>>>,
 }
 
 impl Preview {
@@ -36,27 +58,118 @@ impl Preview {
             lines,
             cursor,
             underlying_value: None,
+            depth: None,
+            matches: Vec::new(),
+            context: None,
+            excerpt: None,
+        }
+    }
+
+    /// Make this the preview of `value` with `depth` of its `max_depth` levels of nested
+    /// tables expanded; `+` and `-` expand a level more or less.
+    pub fn with_depth(mut self, value: Value, depth: usize, max_depth: usize) -> Self {
+        self.underlying_value = Some(value);
+        self.depth = Some((depth, max_depth));
+        self
+    }
+
+    /// Make this the preview of text decoded from `bytes`, which `:encoding` decodes again
+    /// and peeking returns.
+    pub fn with_source(mut self, bytes: Vec<u8>) -> Self {
+        self.underlying_value = Some(Value::binary(bytes, NuSpan::unknown()));
+        self
+    }
+
+    // the matches of the search, by line as shown
+    fn minimap_marks(&self) -> Vec<(usize, Mark)> {
+        match &self.excerpt {
+            Some(excerpt) => excerpt
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| row.is_some_and(|row| self.matches.binary_search(&row).is_ok()))
+                .map(|(shown, _)| (shown, Mark::Match))
+                .collect(),
+            None => self.matches.iter().map(|row| (*row, Mark::Match)).collect(),
         }
     }
+
+    // show the excerpt of the matches, or all the lines again
+    fn update_excerpt(&mut self) {
+        let excerpt = match self.context {
+            Some(context) if !self.matches.is_empty() => {
+                Some(excerpt(&self.matches, context, self.lines.len()))
+            }
+            _ => None,
+        };
+        if excerpt == self.excerpt {
+            return;
+        }
+        self.excerpt = excerpt;
+
+        let count = self.excerpt.as_ref().map_or(self.lines.len(), Vec::len);
+        let (height, width) = (self.cursor.y.window_size(), self.cursor.x.window_size());
+        let column = self.cursor.window_origin().column;
+        // TODO: refactor so this is fallible and returns a Result instead of panicking
+        self.cursor = WindowCursor2D::new(count, usize::MAX).expect("Failed to create cursor");
+        let _ = self.cursor.set_window_size(height, width);
+        self.cursor.set_window_start_position(0, column);
+    }
 }
 
 impl View for Preview {
-    fn draw(&mut self, f: &mut Frame, area: Rect, _: ViewConfig<'_>, layout: &mut Layout) {
+    fn draw(&mut self, f: &mut Frame, area: Rect, cfg: ViewConfig<'_>, layout: &mut Layout) {
+        if self.context != cfg.explore_config.search_context {
+            self.context = cfg.explore_config.search_context;
+            self.update_excerpt();
+        }
+
+        let mut area = area;
+        let minimap = cfg.explore_config.minimap && area.width > Minimap::WIDTH * 10;
+        if minimap {
+            area.width -= Minimap::WIDTH;
+        }
+
         let _ = self
             .cursor
             .set_window_size(area.height as usize, area.width as usize);
 
-        let lines = &self.lines[self.cursor.window_origin().row..];
-        for (i, line) in lines.iter().enumerate().take(area.height as usize) {
-            let text_widget = ColoredTextWidget::new(line, self.cursor.column());
-            let plain_text = text_widget.get_plain_text(area.width as usize);
-
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let count = self.excerpt.as_ref().map_or(self.lines.len(), Vec::len);
+        let rows = self.cursor.window_origin().row..count;
+        for (i, row) in rows.enumerate().take(area.height as usize) {
             let area = Rect::new(area.x, area.y + i as u16, area.width, 1);
+            let row = match &self.excerpt {
+                Some(excerpt) => match excerpt[row] {
+                    Some(row) => row,
+                    None => {
+                        f.render_widget(Span::styled("--", dim), area);
+                        continue;
+                    }
+                },
+                None => row,
+            };
+
+            let text_widget = ColoredTextWidget::new(&self.lines[row], self.cursor.column());
+            let plain_text = text_widget.get_plain_text(area.width as usize);
             f.render_widget(text_widget, area);
 
+            // the context is dimmed, so the matches stand out
+            if self.excerpt.is_some() && self.matches.binary_search(&row).is_err() {
+                f.buffer_mut().set_style(area, dim);
+            }
+
             // push the plain text to layout so it can be searched
             layout.push(&plain_text, area.x, area.y, area.width, area.height);
         }
+
+        if minimap {
+            let marks = self.minimap_marks();
+            let start = self.cursor.window_origin().row;
+            let minimap = Minimap::new(count, start..start + area.height as usize, &marks)
+                .with_highlight(nu_style_to_tui(cfg.explore_config.highlight));
+            let bar = Rect::new(area.right(), area.y, Minimap::WIDTH, area.height);
+            f.render_widget(minimap, bar);
+        }
     }
 
     fn handle_input(
@@ -67,6 +180,18 @@ impl View for Preview {
         info: &mut ViewInfo, // add this arg to draw too?
         key: KeyEvent,
     ) -> Transition {
+        if let Some((depth, max_depth)) = self.depth {
+            let depth = match key.code {
+                KeyCode::Char('+') if depth < max_depth => Some(depth + 1),
+                KeyCode::Char('-') if depth > 0 => Some(depth - 1),
+                _ => None,
+            };
+            if let Some(depth) = depth {
+                // the expand command replaces this view with the value expanded to `depth`
+                return Transition::Cmd(format!("expand {depth}"));
+            }
+        }
+
         match self.handle_input_key(&key) {
             Ok((transition, status_top_or_end)) => {
                 match status_top_or_end {
@@ -92,10 +217,51 @@ impl View for Preview {
         //
         // todo: improve somehow?
 
-        self.cursor.set_window_start_position(row, 0);
+        match &self.excerpt {
+            Some(excerpt) => {
+                // in the middle, its context above and below it
+                let Some(shown) = excerpt.iter().position(|line| *line == Some(row)) else {
+                    return false;
+                };
+                let half = self.cursor.y.window_size() / 2;
+                self.cursor
+                    .set_window_start_position(shown.saturating_sub(half), 0);
+            }
+            None => self.cursor.set_window_start_position(row, 0),
+        }
         true
     }
 
+    fn show_matches(&mut self, rows: &[usize]) {
+        self.matches = rows.to_vec();
+        self.matches.sort_unstable();
+        self.update_excerpt();
+    }
+
+    fn kind(&self) -> &'static str {
+        "preview"
+    }
+
+    fn status_segments(&self, segments: &mut StatusSegments) {
+        if segments.mode() == StatusMode::Perf {
+            segments.push(&tr!("lines"), self.lines.len());
+        }
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        let mut hints = vec![KeyHint::new("↑↓", "scroll"), KeyHint::new("←→", "pan")];
+        if let Some((depth, max_depth)) = self.depth {
+            hints.push(KeyHint::new(format!("+- {depth}/{max_depth}"), "depth"));
+        }
+        hints.extend([
+            KeyHint::new("/", "search"),
+            KeyHint::new(":", "command"),
+            KeyHint::new("q", "back"),
+        ]);
+
+        hints
+    }
+
     fn exit(&mut self) -> Option<Value> {
         match &self.underlying_value {
             Some(value) => Some(value.clone()),
@@ -123,7 +289,7 @@ impl CursorMoveHandler for Preview {
 
 fn set_status_end(view: &Preview, info: &mut ViewInfo) {
     if view.cursor.row() + 1 == view.cursor.row_limit() {
-        info.status = Some(Report::info("END"));
+        info.status = Some(Report::info(tr!("END")));
     } else {
         info.status = Some(Report::default());
     }
@@ -131,8 +297,76 @@ fn set_status_end(view: &Preview, info: &mut ViewInfo) {
 
 fn set_status_top(view: &Preview, info: &mut ViewInfo) {
     if view.cursor.window_origin().row == 0 {
-        info.status = Some(Report::info("TOP"));
+        info.status = Some(Report::info(tr!("TOP")));
     } else {
         info.status = Some(Report::default());
     }
 }
+
+/// The lines shown around `matches` with `context` lines before and after each, like
+/// `grep -C`: indexes of lines, with `None` between groups which don't touch.
+fn excerpt(matches: &[usize], context: usize, count: usize) -> Vec<Option<usize>> {
+    let mut rows = Vec::new();
+    // the first line after those shown
+    let mut next = 0;
+    for &row in matches {
+        let start = row.saturating_sub(context).max(next);
+        let end = row.saturating_add(context + 1).min(count);
+        if start >= end {
+            continue;
+        }
+
+        if start > next && !rows.is_empty() {
+            rows.push(None);
+        }
+        rows.extend((start..end).map(Some));
+        next = end;
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excerpt_like_grep() {
+        let lines = |rows: &[Option<usize>]| {
+            rows.iter()
+                .map(|row| row.map_or("--".to_string(), |row| row.to_string()))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        assert_eq!(lines(&excerpt(&[5], 1, 10)), "4 5 6");
+        assert_eq!(lines(&excerpt(&[0, 9], 2, 10)), "0 1 2 -- 7 8 9");
+        // groups which touch or overlap are one
+        assert_eq!(lines(&excerpt(&[2, 5], 1, 10)), "1 2 3 4 5 6");
+        assert_eq!(lines(&excerpt(&[2, 3], 1, 10)), "1 2 3 4");
+        assert_eq!(lines(&excerpt(&[4], 0, 10)), "4");
+    }
+
+    #[test]
+    fn matches_are_centered() {
+        let text = (0..100)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut preview = Preview::new(&text);
+        let _ = preview.cursor.set_window_size(9, 80);
+        preview.context = Some(2);
+
+        preview.show_matches(&[50, 20]);
+        assert_eq!(preview.excerpt.as_ref().map(Vec::len), Some(11));
+
+        // 48 49 [50] 51 52 are the last lines, with the gap and the first group above
+        assert!(preview.show_data(50));
+        assert_eq!(preview.cursor.window_origin().row, 4);
+
+        preview.show_matches(&[]);
+        assert_eq!(preview.excerpt, None);
+        assert!(preview.show_data(50));
+        assert_eq!(preview.cursor.window_origin().row, 50);
+    }
+}
@@ -0,0 +1,240 @@
+use super::super::super::nu_common::{NuStyle, string_width};
+use super::super::util::{nu_style_to_tui, set_span};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Modifier,
+    widgets::{Block, Borders, Widget},
+};
+
+/// The most common values of a column, with how many rows have each and whether
+/// the table is filtered by it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Facet {
+    pub column: String,
+    pub values: Vec<(String, usize, bool)>,
+}
+
+/// A sidebar listing the facets of a table, one of whose values is selected.
+#[derive(Debug, Clone)]
+pub struct FacetPanel {
+    lines: Vec<FacetLine>,
+    // index into `lines`, always of a `FacetLine::Value`
+    selected: Option<usize>,
+    // the first line shown
+    offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FacetLine {
+    Column(String),
+    Value {
+        column: String,
+        value: String,
+        count: usize,
+        active: bool,
+    },
+}
+
+impl FacetPanel {
+    pub const WIDTH: u16 = 32;
+
+    /// How many of the most common values of each column are listed.
+    pub const VALUES_PER_COLUMN: usize = 5;
+
+    pub fn new(facets: Vec<Facet>) -> Self {
+        let mut panel = Self {
+            lines: Vec::new(),
+            selected: None,
+            offset: 0,
+        };
+        panel.update(facets);
+
+        panel
+    }
+
+    /// Replace the listed facets, keeping the selection on the same line if it's still there.
+    pub fn update(&mut self, facets: Vec<Facet>) {
+        let selected = self.selected.unwrap_or(0);
+
+        self.lines.clear();
+        for facet in facets {
+            if facet.values.is_empty() {
+                continue;
+            }
+
+            self.lines.push(FacetLine::Column(facet.column.clone()));
+            for (value, count, active) in facet.values {
+                self.lines.push(FacetLine::Value {
+                    column: facet.column.clone(),
+                    value,
+                    count,
+                    active,
+                });
+            }
+        }
+
+        self.selected = self.next_value_line(selected.min(self.lines.len()), true);
+        if self.selected.is_none() {
+            self.selected = self.next_value_line(selected.min(self.lines.len()), false);
+        }
+    }
+
+    /// The `(column, value, active)` of the selected line.
+    pub fn selected(&self) -> Option<(&str, &str, bool)> {
+        match self.lines.get(self.selected?)? {
+            FacetLine::Value {
+                column,
+                value,
+                active,
+                ..
+            } => Some((column, value, *active)),
+            FacetLine::Column(_) => None,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if let Some(selected) = self.selected
+            && let Some(next) = self.next_value_line(selected + 1, true)
+        {
+            self.selected = Some(next);
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if let Some(selected) = self.selected
+            && selected > 0
+            && let Some(prev) = self.next_value_line(selected - 1, false)
+        {
+            self.selected = Some(prev);
+        }
+    }
+
+    // the first value line at or after `from` (or at or before it, going backwards)
+    fn next_value_line(&self, from: usize, forward: bool) -> Option<usize> {
+        let is_value = |i: &usize| matches!(self.lines.get(*i), Some(FacetLine::Value { .. }));
+        if forward {
+            (from..self.lines.len()).find(is_value)
+        } else {
+            (0..=from).rev().find(is_value)
+        }
+    }
+
+    pub fn render(
+        &mut self,
+        area: Rect,
+        buf: &mut Buffer,
+        text_style: NuStyle,
+        border_style: NuStyle,
+        selected_style: NuStyle,
+    ) {
+        let block = Block::default()
+            .borders(Borders::LEFT)
+            .border_style(nu_style_to_tui(border_style));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.width < 4 || inner.height == 0 {
+            return;
+        }
+
+        let height = inner.height as usize;
+        if let Some(selected) = self.selected {
+            if selected < self.offset {
+                // show the column a value belongs to when scrolling back up to it
+                self.offset = selected.saturating_sub(1);
+            } else if selected >= self.offset + height {
+                self.offset = selected + 1 - height;
+            }
+        }
+
+        let text_style = nu_style_to_tui(text_style);
+        let column_style = text_style.add_modifier(Modifier::BOLD);
+        let selected_style = nu_style_to_tui(selected_style);
+
+        let lines = self.lines.iter().enumerate().skip(self.offset).take(height);
+        for (y, (i, line)) in (inner.y..).zip(lines) {
+            let x = inner.x + 1;
+            let width = inner.width - 1;
+
+            match line {
+                FacetLine::Column(column) => {
+                    set_span(buf, (x, y), column, column_style, width);
+                }
+                FacetLine::Value {
+                    value,
+                    count,
+                    active,
+                    ..
+                } => {
+                    let style = if self.selected == Some(i) {
+                        selected_style
+                    } else {
+                        text_style
+                    };
+                    Block::default()
+                        .style(style)
+                        .render(Rect::new(x, y, width, 1), buf);
+
+                    let count = count.to_string();
+                    let count_width = string_width(&count) as u16;
+                    let mark = if *active { "✓ " } else { "  " };
+                    let value_width = width.saturating_sub(count_width + 1);
+
+                    let w = set_span(buf, (x, y), mark, style, value_width);
+                    set_span(buf, (x + w, y), value, style, value_width - w);
+                    if count_width < width {
+                        let x = x + width - count_width;
+                        set_span(buf, (x, y), &count, style, count_width);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facets() -> Vec<Facet> {
+        vec![
+            Facet {
+                column: "status".to_string(),
+                values: vec![
+                    ("open".to_string(), 3, false),
+                    ("done".to_string(), 1, true),
+                ],
+            },
+            Facet {
+                column: "empty".to_string(),
+                values: Vec::new(),
+            },
+            Facet {
+                column: "owner".to_string(),
+                values: vec![("me".to_string(), 4, false)],
+            },
+        ]
+    }
+
+    #[test]
+    fn selection_skips_column_lines() {
+        let mut panel = FacetPanel::new(facets());
+        assert_eq!(panel.selected(), Some(("status", "open", false)));
+
+        panel.select_prev();
+        assert_eq!(panel.selected(), Some(("status", "open", false)));
+
+        panel.select_next();
+        assert_eq!(panel.selected(), Some(("status", "done", true)));
+
+        panel.select_next();
+        assert_eq!(panel.selected(), Some(("owner", "me", false)));
+
+        panel.select_next();
+        assert_eq!(panel.selected(), Some(("owner", "me", false)));
+
+        panel.update(facets()[..1].to_vec());
+        assert_eq!(panel.selected(), Some(("status", "done", true)));
+    }
+}
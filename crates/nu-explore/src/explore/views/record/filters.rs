@@ -0,0 +1,127 @@
+use super::super::super::nu_common::{NuStyle, string_width};
+use super::super::util::{nu_style_to_tui, set_span};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+/// An overlay listing the filters of a table in the order they were added, one of which is
+/// selected to be turned on or off, or removed.
+#[derive(Debug, Clone)]
+pub struct FilterList {
+    // (column, value, whether it's on)
+    filters: Vec<(String, String, bool)>,
+    selected: usize,
+}
+
+impl FilterList {
+    pub fn new(filters: Vec<(String, String, bool)>) -> Self {
+        Self {
+            filters,
+            selected: 0,
+        }
+    }
+
+    /// The index of the selected filter, if there are any.
+    pub fn selected(&self) -> Option<usize> {
+        (self.selected < self.filters.len()).then_some(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.filters.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Show the filters as they are now, keeping the selection on the same line.
+    pub fn update(&mut self, filters: Vec<(String, String, bool)>) {
+        self.filters = filters;
+        self.selected = self.selected.min(self.filters.len().saturating_sub(1));
+    }
+
+    pub fn render(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        text_style: NuStyle,
+        border_style: NuStyle,
+        selected_style: NuStyle,
+    ) {
+        let lines: Vec<String> = self
+            .filters
+            .iter()
+            .map(|(column, value, on)| {
+                let check = if *on { "[x]" } else { "[ ]" };
+                format!("{check} {column} = {value}")
+            })
+            .collect();
+
+        let text_width = lines.iter().map(|line| string_width(line)).max();
+        let width = (text_width.unwrap_or(0) as u16 + 4).clamp(24, area.width);
+        let height = (lines.len().max(1) as u16 + 2).min(area.height);
+        if width < 4 || height < 3 {
+            return;
+        }
+
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + (area.height - height) / 2;
+        let popup = Rect::new(x, y, width, height);
+
+        Clear.render(popup, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(nu_style_to_tui(border_style))
+            .title(" filters ");
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let text_style = nu_style_to_tui(text_style);
+        let selected_style = nu_style_to_tui(selected_style);
+
+        // keep the selected filter in sight when there are more filters than lines
+        let height = inner.height as usize;
+        let offset = (self.selected + 1).saturating_sub(height);
+
+        let lines = lines.iter().enumerate().skip(offset).take(height);
+        for (y, (i, line)) in (inner.y..).zip(lines) {
+            let style = if i == self.selected {
+                selected_style
+            } else {
+                text_style
+            };
+            let row = Rect::new(inner.x, y, inner.width, 1);
+            Block::default().style(style).render(row, buf);
+            set_span(buf, (inner.x + 1, y), line, style, inner.width - 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(column: &str, on: bool) -> (String, String, bool) {
+        (column.to_string(), "x".to_string(), on)
+    }
+
+    #[test]
+    fn selection_follows_the_filters() {
+        let mut filters = FilterList::new(vec![filter("a", true), filter("b", false)]);
+        assert_eq!(filters.selected(), Some(0));
+
+        filters.select_next();
+        filters.select_next();
+        assert_eq!(filters.selected(), Some(1));
+
+        filters.update(vec![filter("a", true)]);
+        assert_eq!(filters.selected(), Some(0));
+
+        filters.update(Vec::new());
+        assert_eq!(filters.selected(), None);
+    }
+}
@@ -0,0 +1,125 @@
+use super::super::super::nu_common::{NuStyle, string_width};
+use super::super::util::{nu_style_to_tui, set_span};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+/// An overlay listing what can be done with the selected cell, one of which is selected
+/// to be done.
+#[derive(Debug, Clone)]
+pub struct CellMenu {
+    items: Vec<MenuItem>,
+    selected: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    pub label: String,
+    pub action: MenuAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuAction {
+    /// Drill into the cell, as with Enter
+    Open,
+    /// Run a `:` command line
+    Run(String),
+}
+
+impl CellMenu {
+    pub fn new(items: Vec<MenuItem>) -> Self {
+        Self { items, selected: 0 }
+    }
+
+    pub fn selected(&self) -> Option<&MenuItem> {
+        self.items.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.items.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn render(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        text_style: NuStyle,
+        border_style: NuStyle,
+        selected_style: NuStyle,
+    ) {
+        let text_width = self
+            .items
+            .iter()
+            .map(|item| string_width(&item.label))
+            .max();
+        let width = (text_width.unwrap_or(0) as u16 + 4).clamp(24, area.width);
+        let height = (self.items.len().max(1) as u16 + 2).min(area.height);
+        if width < 4 || height < 3 {
+            return;
+        }
+
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + (area.height - height) / 2;
+        let popup = Rect::new(x, y, width, height);
+
+        Clear.render(popup, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(nu_style_to_tui(border_style))
+            .title(" cell ");
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let text_style = nu_style_to_tui(text_style);
+        let selected_style = nu_style_to_tui(selected_style);
+
+        // keep the selected item in sight when there are more items than lines
+        let height = inner.height as usize;
+        let offset = (self.selected + 1).saturating_sub(height);
+
+        let items = self.items.iter().enumerate().skip(offset).take(height);
+        for (y, (i, item)) in (inner.y..).zip(items) {
+            let style = if i == self.selected {
+                selected_style
+            } else {
+                text_style
+            };
+            let row = Rect::new(inner.x, y, inner.width, 1);
+            Block::default().style(style).render(row, buf);
+            set_span(buf, (inner.x + 1, y), &item.label, style, inner.width - 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(label: &str) -> MenuItem {
+        MenuItem {
+            label: label.to_string(),
+            action: MenuAction::Run(label.to_string()),
+        }
+    }
+
+    #[test]
+    fn selection_stays_in_the_menu() {
+        let mut menu = CellMenu::new(vec![item("a"), item("b")]);
+        menu.select_prev();
+        assert_eq!(menu.selected(), Some(&item("a")));
+
+        menu.select_next();
+        menu.select_next();
+        assert_eq!(menu.selected(), Some(&item("b")));
+
+        assert_eq!(CellMenu::new(Vec::new()).selected(), None);
+    }
+}
@@ -1,27 +1,50 @@
+mod facets;
+mod filters;
+mod menu;
+mod pages;
 mod table_widget;
 
-use self::table_widget::{TableWidget, TableWidgetState};
+use self::{
+    facets::{Facet, FacetPanel},
+    filters::FilterList,
+    menu::{CellMenu, MenuAction, MenuItem},
+    pages::PageList,
+    table_widget::{ColumnLayout, TableWidget, TableWidgetState},
+};
 use super::super::{
-    config::ExploreConfig,
-    nu_common::{NuSpan, NuText, collect_input, lscolorize},
+    commands::{ColCmd, CopyCmd, FilterCmd, MenuCmd, OpenCmd},
+    config::{ColumnOverride, ExploreConfig, MenuEntry, Nulls, Preset},
+    l10n::tr,
+    nu_common::{
+        NuSpan, NuText, base_value, closure_source, collect_input, format_size, guess_kind,
+        is_missing_cell, left_out, lscolorize, string_width, truncate_bytes,
+    },
     pager::{
         Frame, Transition, ViewInfo,
         report::{Report, Severity},
+        status_bar::{StatusMode, StatusSegments},
     },
 };
 use super::{
-    ElementInfo, Layout, View, ViewConfig,
+    ElementInfo, KeyHint, Layout, View, ViewConfig, WHEEL_ROWS,
     cursor::{CursorMoveHandler, Position, WindowCursor2D},
+    minimap::{Mark, Minimap},
     util::{make_styled_string, nu_style_to_tui},
 };
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use fancy_regex::Regex;
 use nu_color_config::{StyleComputer, TextStyle};
 use nu_protocol::{
     Config, Record, Value,
     engine::{EngineState, Stack},
 };
 use ratatui::{layout::Rect, widgets::Block};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
 
 pub use self::table_widget::Orientation;
 
@@ -34,19 +57,52 @@ pub struct RecordView {
     auto_tail: bool, // Track if tail mode is active for auto-scroll
     previous_row_count: usize,
     page_size: usize,
+    // The facet sidebar, while it's open
+    facets: Option<FacetPanel>,
+    // The `:pages` overlay, while it's open
+    pages: Option<PageList>,
+    // The `:filters` overlay, while it's open
+    filter_list: Option<FilterList>,
+    // The menu of the selected cell, while it's open
+    menu: Option<CellMenu>,
+    // The cells matching the search, as indexes of `collect_data`, marked on the minimap
+    search_matches: Vec<usize>,
+    // How far the text of a cell is scrolled with Shift ← →: (row, column, characters), the
+    // cell being where the cursor was
+    cell_scroll: Option<(usize, usize, usize)>,
+    // The rows of the table as last drawn, to tell which cell a click is on
+    drawn_rows: usize,
+    // What the mouse is dragging, from the click to the release
+    dragging: Option<Dragging>,
+    // Where the minimap was last drawn, if it was
+    minimap: Option<Rect>,
 }
 
 impl RecordView {
     pub fn new(columns: Vec<String>, records: Vec<Vec<Value>>, cfg: ExploreConfig) -> Self {
         let row_count = records.len();
+        let mut layer = RecordLayer::new(columns, records);
+        layer.hide_columns(&cfg.columns);
+        if cfg.auto_order {
+            layer.order_columns();
+        }
         Self {
-            layer_stack: vec![RecordLayer::new(columns, records)],
+            layer_stack: vec![layer],
             mode: UIMode::View,
             orientation: Orientation::Top,
             cfg,
             auto_tail: true, // Enable auto-tail by default
             previous_row_count: row_count,
             page_size: 0,
+            facets: None,
+            pages: None,
+            filter_list: None,
+            menu: None,
+            search_matches: Vec::new(),
+            cell_scroll: None,
+            drawn_rows: 0,
+            dragging: None,
+            minimap: None,
         }
     }
 
@@ -57,811 +113,5703 @@ impl RecordView {
         self.auto_tail = true; // Enable auto-tail mode
     }
 
-    pub fn transpose(&mut self) {
-        let layer = self.get_top_layer_mut();
-        transpose_table(layer);
-
-        layer.reset_cursor();
+    /// Show only the first and last `count` rows of the top layer, hiding the rest
+    /// behind a `… N more rows …` separator until [`RecordView::expand_abbreviated`].
+    ///
+    /// The hidden rows are kept as values and only get formatted once expanded.
+    pub fn abbreviate(&mut self, count: usize) {
+        self.get_top_layer_mut().abbreviate(count);
     }
 
-    pub fn get_top_layer(&self) -> &RecordLayer {
-        self.layer_stack
-            .last()
-            .expect("we guarantee that 1 entry is always in a list")
+    /// Put back the rows hidden by [`RecordView::abbreviate`]; returns false if there were none.
+    pub fn expand_abbreviated(&mut self) -> bool {
+        self.get_top_layer_mut().expand_abbreviated()
     }
 
-    pub fn get_top_layer_mut(&mut self) -> &mut RecordLayer {
-        self.layer_stack
-            .last_mut()
-            .expect("we guarantee that 1 entry is always in a list")
+    /// Show `records` read again from the source of the table in place of its rows, keeping
+    /// its filters, sort, the order of its columns, those left out and its cursor as far as
+    /// they still apply, for `explore --watch-file`.
+    ///
+    /// Layers opened from the table keep the cells they were opened with.
+    pub fn reload(&mut self, columns: Vec<String>, records: Vec<Vec<Value>>) {
+        let mut fresh = RecordLayer::new(columns, records);
+        fresh.hide_columns(&self.cfg.columns);
+        if self.cfg.auto_order {
+            fresh.order_columns();
+        }
+
+        self.layer_stack[0].reload(fresh);
+        self.search_matches.clear();
+        self.cell_scroll = None;
     }
 
-    pub fn set_top_layer_orientation(&mut self, orientation: Orientation) {
-        let layer = self.get_top_layer_mut();
-        layer.orientation = orientation;
-        layer.reset_cursor();
+    /// Add `records` read from the same source after the others, in `columns`; false if the
+    /// table doesn't show the rows as they were read, filtered, sorted or with other columns,
+    /// and has to be reloaded with all of them.
+    pub fn append(&mut self, columns: &[String], records: Vec<Vec<Value>>) -> bool {
+        self.layer_stack[0].append(columns, records)
     }
 
-    /// Get the current position of the cursor in the table as a whole
-    pub fn get_cursor_position(&self) -> Position {
-        let layer = self.get_top_layer();
-        layer.cursor.position()
+    /// Keep only the rows whose `column` reads exactly as `value`, on top of earlier filters.
+    ///
+    /// Returns the number of rows left; a filter matching nothing is rejected.
+    pub fn add_filter(&mut self, column: &str, value: String) -> Result<usize> {
+        self.get_top_layer_mut().add_filter(column, value)
     }
 
-    /// Get the current position of the cursor in the window being shown
-    pub fn get_cursor_position_in_window(&self) -> Position {
-        let layer = self.get_top_layer();
-        layer.cursor.window_relative_position()
+    /// Keep only the rows `eval` returns true for, on top of the filters by value and in
+    /// place of an earlier closure filter. `eval` is given the columns and every row, the
+    /// filtered out ones too, and returns whether each row is kept.
+    ///
+    /// Returns the number of rows left; a filter matching nothing is rejected.
+    pub fn set_closure_filter(
+        &mut self,
+        source: String,
+        eval: impl FnOnce(&[String], &[Vec<Value>]) -> Result<Vec<bool>>,
+    ) -> Result<usize> {
+        if self.cfg.no_eval {
+            return Err(anyhow::anyhow!(tr!(
+                "Closures run code, which --no-eval disables"
+            )));
+        }
+
+        self.get_top_layer_mut().set_closure_filter(source, eval)
     }
 
-    /// Get the origin of the window being shown. (0,0), top left corner.
-    pub fn get_window_origin(&self) -> Position {
-        let layer = self.get_top_layer();
-        layer.cursor.window_origin()
+    /// The closure of the closure filter, as it was typed.
+    pub fn closure_filter(&self) -> Option<&str> {
+        let filter = self.get_top_layer().closure_filter.as_ref()?;
+        Some(&filter.source)
     }
 
-    pub fn set_cursor_mode(&mut self) {
-        self.mode = UIMode::Cursor;
+    /// Drop all filters and show every row again; returns false if nothing was filtered.
+    pub fn clear_filters(&mut self) -> bool {
+        self.get_top_layer_mut().clear_filters()
     }
 
-    pub fn set_view_mode(&mut self) {
-        self.mode = UIMode::View;
+    /// Replace the filter at `index`, counted from 0 in the order they were added.
+    ///
+    /// Returns the number of rows left; a filter matching nothing is rejected.
+    pub fn replace_filter(&mut self, index: usize, column: &str, value: String) -> Result<usize> {
+        self.get_top_layer_mut()
+            .replace_filter(index, column, value)
     }
 
-    pub fn get_current_value(&self) -> &Value {
-        let Position { row, column } = self.get_cursor_position();
-        let layer = self.get_top_layer();
+    /// Sort the rows by `column`, descending if `descending` is true; when it's `None`, the
+    /// other way round from how it's sorted by the column already, ascending at first.
+    /// The empty cells go where `nulls` says, or as they did, or as `table.sort_nulls` says.
+    ///
+    /// Returns whether the rows are now in descending order.
+    pub fn sort_by(
+        &mut self,
+        column: &str,
+        descending: Option<bool>,
+        nulls: Option<Nulls>,
+    ) -> Result<bool> {
+        let default_nulls = self.cfg.sort_nulls;
+        let layer = self.get_top_layer_mut();
+        let sorted = layer.sort.filter(|sort| {
+            layer
+                .column_names
+                .get(sort.column)
+                .is_some_and(|name| name == column)
+        });
 
-        let (row, column) = match layer.orientation {
-            Orientation::Top => (row, column),
-            Orientation::Left => (column, row),
-        };
+        let descending = descending.unwrap_or(sorted.is_some_and(|sort| !sort.descending));
+        let nulls = nulls
+            .or(sorted.map(|sort| sort.nulls))
+            .unwrap_or(default_nulls);
 
-        // These should never happen as long as the cursor is working correctly
-        assert!(row < layer.record_values.len(), "row out of bounds");
-        assert!(column < layer.column_names.len(), "column out of bounds");
+        layer.sort_by(column, descending, nulls)?;
 
-        &layer.record_values[row][column]
+        Ok(descending)
     }
 
-    fn create_table_widget<'a>(&'a mut self, cfg: ViewConfig<'a>) -> TableWidget<'a> {
-        let style = self.cfg.table;
-        let style_computer = cfg.style_computer;
-        let Position { row, column } = self.get_window_origin();
-
+    /// Sort the fields of a record shown a field a line by name, from A to Z, or from Z to A
+    /// when they already are; the cursor stays on its field. Returns whether it's from Z to A.
+    pub fn sort_fields(&mut self) -> bool {
+        let mode = self.mode;
         let layer = self.get_top_layer_mut();
-        if layer.record_text.is_none() {
-            let mut data =
-                convert_records_to_string(&layer.record_values, cfg.nu_config, cfg.style_computer);
-            lscolorize(&layer.column_names, &mut data, cfg.cwd, cfg.lscolors);
+        let field = layer.column_names.get(layer.cursor.row()).cloned();
+        let descending = layer
+            .column_names
+            .is_sorted_by_key(|name| name.to_lowercase());
+        layer.sort_columns(descending);
 
-            layer.record_text = Some(data);
+        if let Some(field) = field {
+            self.go_to_column(&field);
+            self.mode = mode;
         }
 
-        let headers = &layer.column_names;
-        let data = layer.record_text.as_ref().expect("always ok");
+        descending
+    }
 
-        TableWidget::new(
-            headers,
-            data,
-            style_computer,
-            row,
-            column,
-            style,
-            layer.orientation,
-        )
+    /// The column under the cursor, which is a row when the layer is transposed.
+    pub fn current_column(&self) -> Option<String> {
+        self.get_top_layer().get_column_header()
     }
 
-    fn update_cursors(&mut self, rows: usize, columns: usize) {
-        match self.get_top_layer().orientation {
-            Orientation::Top => {
-                let _ = self
-                    .get_top_layer_mut()
-                    .cursor
-                    .set_window_size(rows, columns);
-            }
+    /// Where the selected cell links to, as set for its column in `explore.table.columns`.
+    pub fn current_link(&self) -> Option<String> {
+        let column = self.cfg.columns.get(&self.current_column()?)?;
+        column.link(self.get_current_value())
+    }
 
-            Orientation::Left => {
-                let _ = self
-                    .get_top_layer_mut()
-                    .cursor
-                    .set_window_size(rows, columns);
-            }
-        }
+    /// The filters of the table in the order they were added: (column, value, whether it's on).
+    pub fn filters(&self) -> Vec<(String, String, bool)> {
+        self.get_top_layer().filter_list()
     }
 
-    fn create_records_report(&self) -> Report {
-        let layer = self.get_top_layer();
-        let covered_percent = report_row_position(layer.cursor);
-        let cursor = report_cursor_position(self.mode, layer.cursor);
-        let message = layer.name.clone().unwrap_or_default();
-        // note: maybe came up with a better short names? E/V/N?
-        let mode = match self.mode {
-            UIMode::Cursor => String::from("EDIT"),
-            UIMode::View => String::from("VIEW"),
-        };
+    /// Open an overlay listing the filters, to turn them on and off or remove them.
+    pub fn show_filters(&mut self) -> Result<()> {
+        let filters = self.filters();
+        if filters.is_empty() {
+            return Err(anyhow::anyhow!(tr!(
+                "There are no filters, add one with :filter"
+            )));
+        }
 
-        Report::new(message, Severity::Info, mode, cursor, covered_percent)
-    }
-}
+        self.facets = None;
+        self.pages = None;
+        self.filter_list = Some(FilterList::new(filters));
 
-impl View for RecordView {
-    fn draw(&mut self, f: &mut Frame, area: Rect, cfg: ViewConfig<'_>, layout: &mut Layout) {
-        let mut table_layout = TableWidgetState::default();
-        let table = self.create_table_widget(cfg);
-        f.render_stateful_widget(table, area, &mut table_layout);
+        Ok(())
+    }
 
-        *layout = table_layout.layout;
+    fn handle_filters_key(&mut self, key: KeyEvent, info: &mut ViewInfo) -> Transition {
+        let Some(list) = &mut self.filter_list else {
+            return Transition::None;
+        };
 
-        self.update_cursors(table_layout.count_rows, table_layout.count_columns);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.filter_list = None,
+            KeyCode::Up | KeyCode::Char('k') => list.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') => list.select_next(),
+            KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Delete | KeyCode::Char('d') => {
+                let Some(selected) = list.selected() else {
+                    return Transition::Ok;
+                };
 
-        // Update page_size
-        self.page_size = estimate_page_size(area, self.cfg.table.show_header) as usize;
+                let layer = self.get_top_layer_mut();
+                if matches!(key.code, KeyCode::Delete | KeyCode::Char('d')) {
+                    let filter = layer.filters.remove(selected);
+                    let column = layer.column_names[filter.column].clone();
+                    layer.apply_filters();
+                    info.status = Some(Report::message(
+                        tr!(
+                            "Filter {column} = {value} removed",
+                            column = column,
+                            value = filter.value
+                        ),
+                        Severity::Info,
+                    ));
+                } else {
+                    layer.toggle_filter(selected);
+                    info.status = Some(self.create_records_report());
+                }
 
-        // Check for new rows and handle auto-tail
-        let current_row_count = self.get_top_layer().record_values.len();
-        if current_row_count > self.previous_row_count {
-            // Invalidate record_text to force redraw
-            self.get_top_layer_mut().record_text = None;
-            // If auto_tail, scroll to bottom
-            if self.auto_tail {
-                let page_size = self.page_size;
-                if current_row_count > page_size {
-                    self.get_top_layer_mut()
-                        .cursor
-                        .set_window_start_position(current_row_count - page_size, 0);
+                let filters = self.filters();
+                match &mut self.filter_list {
+                    Some(_) if filters.is_empty() => self.filter_list = None,
+                    Some(list) => list.update(filters),
+                    None => {}
                 }
             }
+            _ => return Transition::None,
         }
-        self.previous_row_count = current_row_count;
 
-        if self.mode == UIMode::Cursor {
-            let Position { row, column } = self.get_cursor_position_in_window();
-            let info = get_element_info(
-                layout,
-                row,
-                column,
-                table_layout.count_rows,
-                self.get_top_layer().orientation,
-                self.cfg.table.show_header,
-            );
+        Transition::Ok
+    }
 
-            if let Some(info) = info {
-                highlight_selected_cell(f, info.clone(), &self.cfg);
-            }
+    /// Open the menu of the selected cell: opening it, then `entries` for the cell. Entries
+    /// needing its column are left out of records and transposed tables.
+    pub fn show_menu(&mut self, entries: &[MenuEntry]) {
+        let layer = self.get_top_layer();
+        let column = layer
+            .get_column_header()
+            .filter(|_| layer.is_filterable())
+            .map(|column| FilterCmd::quote_column(&column));
+        let value = filter_text(self.get_current_value());
+        let path = layer.cell_path();
+
+        let mut items = vec![MenuItem {
+            label: tr!("Open"),
+            action: MenuAction::Open,
+        }];
+        for entry in entries {
+            let command = match &column {
+                Some(column) => entry.command_for(column, &value, &path),
+                None if entry.needs_column() => continue,
+                None => entry.command_for("", &value, &path),
+            };
+            items.push(MenuItem {
+                label: entry.label.clone(),
+                action: MenuAction::Run(command),
+            });
         }
+
+        self.facets = None;
+        self.pages = None;
+        self.filter_list = None;
+        self.menu = Some(CellMenu::new(items));
     }
 
-    fn handle_input(
-        &mut self,
-        _engine_state: &EngineState,
-        _stack: &mut Stack,
-        _layout: &Layout,
-        info: &mut ViewInfo,
-        key: KeyEvent,
-    ) -> Transition {
-        if key.code == KeyCode::PageUp {
-            let page_size = self.page_size;
-            let current_row = self.get_top_layer().cursor.window_origin().row;
-            let new_row = current_row.saturating_sub(page_size);
-            let layer = self.get_top_layer_mut();
-            layer
-                .cursor
-                .set_window_start_position(new_row, layer.cursor.window_origin().column);
-            let report = self.create_records_report();
-            info.status = Some(report);
-            return Transition::Ok;
-        }
-        if key.code == KeyCode::PageDown {
-            let page_size = self.page_size;
-            let current_row = self.get_top_layer().cursor.window_origin().row;
-            let row_count = self.get_top_layer().record_values.len();
-            let max_row = row_count.saturating_sub(page_size);
-            let new_row = (current_row + page_size).min(max_row);
-            let layer = self.get_top_layer_mut();
-            layer
-                .cursor
-                .set_window_start_position(new_row, layer.cursor.window_origin().column);
-            let report = self.create_records_report();
-            info.status = Some(report);
-            return Transition::Ok;
-        }
-        match self.handle_input_key(&key) {
-            Ok((transition, ..)) => {
-                if matches!(&transition, Transition::Ok | Transition::Cmd { .. }) {
-                    let report = self.create_records_report();
-                    info.status = Some(report);
-                }
+    fn handle_menu_key(&mut self, key: KeyEvent, info: &mut ViewInfo) -> Transition {
+        let Some(menu) = &mut self.menu else {
+            return Transition::None;
+        };
 
-                transition
-            }
-            Err(e) => {
-                log::error!("Error handling input in RecordView: {e}");
-                let report = Report::message(e.to_string(), Severity::Err);
-                info.status = Some(report);
-                Transition::None
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('x') => self.menu = None,
+            KeyCode::Up | KeyCode::Char('k') => menu.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') => menu.select_next(),
+            KeyCode::Enter => {
+                let action = menu.selected().map(|item| item.action.clone());
+                self.menu = None;
+                match action {
+                    Some(MenuAction::Open) => match self.handle_enter() {
+                        Ok(_) => info.status = Some(self.create_records_report()),
+                        Err(err) => info.status = Some(Report::error(err.to_string())),
+                    },
+                    Some(MenuAction::Run(command)) => return Transition::Cmd(command),
+                    None => {}
+                }
             }
+            _ => return Transition::None,
         }
+
+        Transition::Ok
     }
 
-    fn collect_data(&self) -> Vec<NuText> {
-        let layer = self.get_top_layer();
-        let mut texts = Vec::new();
+    /// The path of the selected cell, like `3.name`.
+    pub fn cell_path(&self) -> String {
+        self.get_top_layer().cell_path()
+    }
 
-        // Add headers
-        for name in &layer.column_names {
-            texts.push((name.clone(), TextStyle::default()));
-        }
+    /// The table as it's shown, filters and sorts applied and abbreviated rows put back: a
+    /// record if it's shown as one, a list of records otherwise; with the number of rows.
+    pub fn table_value(&mut self) -> (Value, usize) {
+        self.expand_abbreviated();
 
-        // Add data
-        for row in &layer.record_values {
-            for value in row {
-                let text = value.to_abbreviated_string(&Config::default());
-                let text = strip_string(&text);
-                texts.push((text, TextStyle::default()));
-            }
+        let layer = self.get_top_layer();
+        let rows = layer.record_values.len();
+        match layer.orientation {
+            Orientation::Left if rows <= 1 => (build_table_as_record(self), rows),
+            _ => (build_table_as_list(self), rows),
         }
-
-        texts
     }
 
-    fn show_data(&mut self, pos: usize) -> bool {
+    /// The rows and columns of the table, all of them whether they're shown or not, and
+    /// roughly how many bytes their values take.
+    pub fn data_size(&self) -> (usize, usize, usize) {
         let layer = self.get_top_layer();
-        let num_headers = layer.column_names.len();
+        let rows = layer.unfiltered.as_ref().unwrap_or(&layer.record_values);
+        let hidden = layer.abbreviated.iter().flat_map(|hidden| &hidden.rows);
+        let all = rows.iter().chain(hidden);
 
-        if pos < num_headers {
-            // Header
-            let column = pos;
-            let row = 0;
-            self.get_top_layer_mut()
-                .cursor
-                .set_window_start_position(row, column);
-            return true;
-        } else {
-            let data_pos = pos - num_headers;
-            let mut i = 0;
-            for (data_row, cells) in layer.record_values.iter().enumerate() {
-                if data_pos >= i && data_pos < i + cells.len() {
-                    let column = data_pos - i;
-                    self.get_top_layer_mut()
-                        .cursor
-                        .set_window_start_position(data_row, column);
-                    return true;
-                }
-                i += cells.len();
-            }
-        }
+        let (count, bytes) = all.fold((0, 0), |(count, bytes), row| {
+            let size: usize = row.iter().map(Value::memory_size).sum();
+            (count + 1, bytes + size)
+        });
 
-        false
+        (count, layer.column_names.len(), bytes)
     }
 
-    fn update(&mut self, _info: &mut ViewInfo) -> bool {
-        false
+    /// Capture the filters, the sort and the columns of the top layer, so they can be saved
+    /// by name.
+    pub fn preset(&self) -> Preset {
+        self.get_top_layer().preset()
     }
 
-    fn exit(&mut self) -> Option<Value> {
-        Some(build_last_value(self))
+    /// Set the top layer up as `preset` says: its columns left out and put in order, its
+    /// sort and its filters, in place of the filters there are.
+    pub fn apply_preset(&mut self, preset: &Preset) -> Result<()> {
+        let nulls = self.cfg.sort_nulls;
+        let layer = self.get_top_layer_mut();
+        layer.clear_filters();
+        layer.leave_out_columns(&preset.hidden);
+        layer.arrange_columns(&preset.order);
+        if let Some((column, descending)) = &preset.sort {
+            layer.sort_by(column, *descending, nulls)?;
+        }
+        for (column, value) in &preset.filters {
+            layer.add_filter(column, value.clone())?;
+        }
+
+        Ok(())
     }
-}
 
-fn build_last_value(v: &RecordView) -> Value {
-    if v.mode == UIMode::Cursor {
-        v.get_current_value().clone()
-    } else if v.get_top_layer().count_rows() < 2 {
-        build_table_as_record(v)
-    } else {
-        build_table_as_list(v)
+    /// The distinct values of `column`, the most common first, as they are matched by
+    /// [`RecordView::add_filter`].
+    ///
+    /// At most `limit` values are collected, so this stays cheap on huge tables.
+    pub fn column_values(&self, column: &str, limit: usize) -> Vec<String> {
+        self.get_top_layer().column_values(column, limit)
     }
-}
 
-fn build_table_as_list(v: &RecordView) -> Value {
-    let layer = v.get_top_layer();
+    /// The selected part of the table as a table: the range of cells selected with `v`,
+    /// or else the marked rows. `None` when nothing is selected.
+    pub fn selection_value(&self) -> Option<Value> {
+        let layer = self.get_top_layer();
+        let (names, rows): (&[String], Vec<&[Value]>) = match layer.selection_anchor {
+            Some((anchor_row, anchor_column)) => {
+                let Position { row, column } = layer.cursor.position();
+                let mut rows = row.min(anchor_row)..=row.max(anchor_row);
+                let mut columns = column.min(anchor_column)..=column.max(anchor_column);
+                if layer.orientation == Orientation::Left {
+                    std::mem::swap(&mut rows, &mut columns);
+                }
 
-    let vals = layer
-        .record_values
-        .iter()
-        .map(|vals| {
-            let record = layer
-                .column_names
-                .iter()
-                .cloned()
-                .zip(vals.iter().cloned())
-                .collect();
-            Value::record(record, NuSpan::unknown())
-        })
-        .collect();
+                let names = layer.column_names.get(columns.clone())?;
+                let rows = rows
+                    .filter_map(|row| layer.record_values.get(row)?.get(columns.clone()))
+                    .collect();
+                (names, rows)
+            }
+            None if !layer.marked.is_empty() => {
+                let rows = layer.marked.iter().map(Vec::as_slice).collect();
+                (&layer.column_names, rows)
+            }
+            None => return None,
+        };
 
-    Value::list(vals, NuSpan::unknown())
-}
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                let record = names.iter().cloned().zip(row.iter().cloned()).collect();
+                Value::record(record, NuSpan::unknown())
+            })
+            .collect();
 
-fn build_table_as_record(v: &RecordView) -> Value {
-    let layer = v.get_top_layer();
+        Some(Value::list(rows, NuSpan::unknown()))
+    }
 
-    let mut record = Record::new();
-    if let Some(row) = layer.record_values.first() {
-        record = layer
-            .column_names
-            .iter()
-            .cloned()
-            .zip(row.iter().cloned())
-            .collect();
+    pub fn column_names(&self) -> &[String] {
+        &self.get_top_layer().column_names
     }
 
-    Value::record(record, NuSpan::unknown())
-}
+    /// Move the cursor to `column`, which is a row when the layer is transposed.
+    pub fn go_to_column(&mut self, column: &str) {
+        let layer = self.get_top_layer_mut();
+        let Some(index) = layer.column_names.iter().position(|name| name == column) else {
+            return;
+        };
 
-fn get_element_info(
-    layout: &mut Layout,
-    row: usize,
-    column: usize,
-    count_rows: usize,
-    orientation: Orientation,
-    with_head: bool,
-) -> Option<&ElementInfo> {
-    let with_head = with_head as usize;
-    let index = match orientation {
-        Orientation::Top => column * (count_rows + with_head) + row + 1,
-        Orientation::Left => (column + with_head) * count_rows + row,
-    };
+        match layer.orientation {
+            Orientation::Top => {
+                let current = layer.cursor.column();
+                if index > current {
+                    layer.cursor.next_column_by(index - current);
+                } else {
+                    layer.cursor.prev_column_by(current - index);
+                }
+            }
+            Orientation::Left => {
+                let current = layer.cursor.row();
+                for _ in index..current {
+                    layer.cursor.prev_row();
+                }
+                for _ in current..index {
+                    layer.cursor.next_row();
+                }
+            }
+        }
 
-    layout.data.get(index)
-}
+        self.mode = UIMode::Cursor;
+    }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum UIMode {
-    Cursor,
-    View,
-}
+    /// Swap the column under the cursor with its neighbour, keeping the cursor on it.
+    ///
+    /// The new order is what the table is returned with on exit.
+    pub fn move_column(&mut self, right: bool) -> bool {
+        let column = self.get_cursor_position().column;
+        let layer = self.get_top_layer_mut();
+        if !layer.move_column(column, right) {
+            return false;
+        }
 
-#[derive(Debug, Clone)]
-pub struct RecordLayer {
-    pub column_names: Vec<String>,
-    // These are the raw records in the current layer. The sole reason we keep this around is so we can return the original value
-    // if it's being peeked. Otherwise we could accept an iterator over it.
-    // or if it could be Cloneable we could do that anyway;
-    // cause it would keep memory footprint lower while keep everything working
-    // (yee would make return O(n); we would need to traverse iterator once again; but maybe worth it)
-    pub record_values: Vec<Vec<Value>>,
-    // This is the text representation of the record values (the actual text that will be displayed to users).
-    // It's an Option because we need configuration to set it and we (currently) don't have access to configuration when things are created.
-    pub record_text: Option<Vec<Vec<NuText>>>,
-    orientation: Orientation,
-    name: Option<String>,
-    was_transposed: bool,
-    pub cursor: WindowCursor2D,
-}
+        if right {
+            layer.cursor.next_column();
+        } else {
+            layer.cursor.prev_column();
+        }
 
-impl RecordLayer {
-    fn new(columns: Vec<String>, records: Vec<Vec<Value>>) -> Self {
-        // TODO: refactor so this is fallible and returns a Result instead of panicking
-        let cursor =
-            WindowCursor2D::new(records.len(), columns.len()).expect("Failed to create cursor");
+        true
+    }
 
-        let column_names = columns.iter().map(|s| strip_string(s)).collect();
+    /// Swap the row under the cursor with its neighbour, keeping the cursor on it.
+    ///
+    /// Rows can only be moved while none are filtered out or abbreviated.
+    pub fn move_row(&mut self, down: bool) -> bool {
+        let row = self.get_cursor_position().row;
+        let layer = self.get_top_layer_mut();
+        if !layer.move_row(row, down) {
+            return false;
+        }
 
-        Self {
-            column_names,
-            record_values: records,
-            record_text: None,
-            cursor,
-            orientation: Orientation::Top,
-            name: None,
-            was_transposed: false,
+        if down {
+            layer.cursor.next_row();
+        } else {
+            layer.cursor.prev_row();
         }
+
+        true
     }
 
-    fn set_name(&mut self, name: impl Into<String>) {
-        self.name = Some(name.into());
+    /// Pin the row under the cursor on top of the table, or unpin it if it already is.
+    pub fn toggle_pin(&mut self) -> bool {
+        let row = self.get_cursor_position().row;
+        self.get_top_layer_mut().toggle_pin(row)
     }
 
-    fn count_rows(&self) -> usize {
-        match self.orientation {
-            Orientation::Top => self.record_values.len(),
-            Orientation::Left => self.column_names.len(),
-        }
+    /// Freeze the row under the cursor on top of the table, the cells of the row under the
+    /// cursor differing from it being highlighted, or let it go if it already is frozen.
+    pub fn toggle_reference(&mut self) -> bool {
+        let row = self.get_cursor_position().row;
+        self.get_top_layer_mut().toggle_reference(row)
     }
 
-    fn count_columns(&self) -> usize {
-        match self.orientation {
-            Orientation::Top => self.column_names.len(),
-            Orientation::Left => self.record_values.len(),
+    /// The columns and all rows of the top layer, if it's a table.
+    ///
+    /// Abbreviated rows are expanded first, so that none are left out.
+    pub fn top_table(&mut self) -> Option<(&[String], &[Vec<Value>])> {
+        let layer = self.get_top_layer_mut();
+        if !layer.is_filterable() {
+            return None;
         }
-    }
 
-    fn get_column_header(&self) -> Option<String> {
-        let col = self.cursor.column();
-        self.column_names.get(col).map(|header| header.to_string())
+        layer.expand_abbreviated();
+
+        Some((&layer.column_names, &layer.record_values))
     }
 
-    fn reset_cursor(&mut self) {
-        // TODO: refactor so this is fallible and returns a Result instead of panicking
-        self.cursor = WindowCursor2D::new(self.count_rows(), self.count_columns())
-            .expect("Failed to create cursor");
+    /// Show a table derived from the top layer on top of it, until going back with Esc.
+    pub fn push_table(&mut self, name: &str, columns: Vec<String>, rows: Vec<Vec<Value>>) {
+        let layer = RecordLayer::new(columns, rows);
+        push_layer(self, layer);
+
+        let layer = self.get_top_layer_mut();
+        layer.set_name(name);
+        layer.opened_by = format!(":{name}");
     }
-}
 
-impl CursorMoveHandler for RecordView {
-    fn get_cursor(&mut self) -> &mut WindowCursor2D {
-        &mut self.get_top_layer_mut().cursor
+    /// Name the page shown, for the title bar and `:pages`; `None` takes the name away.
+    pub fn set_title(&mut self, title: Option<String>) {
+        self.get_top_layer_mut().title = title;
     }
-    fn handle_enter(&mut self) -> Result<Transition> {
-        match self.mode {
-            UIMode::View => self.set_cursor_mode(),
-            UIMode::Cursor => {
-                let value = self.get_current_value();
 
-                // ...but it only makes sense to drill down into a few types of values
-                if !matches!(
-                    value,
-                    Value::Record { .. } | Value::List { .. } | Value::Custom { .. }
-                ) {
-                    return Ok(Transition::None);
+    /// Open an overlay listing the layers drilled down into, with how each was opened,
+    /// to go back to one of them directly.
+    pub fn show_pages(&mut self) {
+        let pages = self
+            .layer_stack
+            .iter()
+            .map(|layer| {
+                let opened_by = match layer.opened_by.as_str() {
+                    "" => tr!("input"),
+                    opened_by => opened_by.to_string(),
+                };
+                match &layer.title {
+                    Some(title) => format!("{title} ({opened_by})"),
+                    None => opened_by,
                 }
+            })
+            .collect();
 
-                let is_record = matches!(value, Value::Record { .. });
-                let next_layer = create_layer(value.clone())?;
-                push_layer(self, next_layer);
+        self.facets = None;
+        self.filter_list = None;
+        self.pages = Some(PageList::new(pages));
+    }
 
-                if is_record {
-                    self.set_top_layer_orientation(Orientation::Left);
-                } else {
-                    self.set_top_layer_orientation(self.orientation);
-                }
-            }
-        }
+    fn handle_pages_key(&mut self, key: KeyEvent, info: &mut ViewInfo) -> Transition {
+        let Some(pages) = &mut self.pages else {
+            return Transition::None;
+        };
 
-        Ok(Transition::Ok)
-    }
-    fn handle_esc(&mut self) -> Transition {
-        match self.mode {
-            UIMode::View => {
-                if self.layer_stack.len() > 1 {
-                    self.layer_stack.pop();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.pages = None,
+            KeyCode::Up | KeyCode::Char('k') => pages.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') => pages.select_next(),
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let selected = pages.selected();
+                self.pages = None;
+
+                if selected + 1 < self.layer_stack.len() {
+                    self.layer_stack.truncate(selected + 1);
                     self.mode = UIMode::Cursor;
-                } else {
-                    return Transition::Exit;
+                    info.status = Some(self.create_records_report());
                 }
             }
-            UIMode::Cursor => self.set_view_mode(),
+            _ => return Transition::None,
         }
+
         Transition::Ok
     }
-    fn handle_expand(&mut self) -> Transition {
-        Transition::Cmd(String::from("expand"))
+
+    /// Tag the row under the cursor with `tag`, or remove its tag with `None`.
+    ///
+    /// Tags are kept in a column of their own, added on first use, so they can be
+    /// filtered by and are part of the value returned on exit.
+    pub fn set_tag(&mut self, tag: Option<String>) -> Result<()> {
+        let row = self.get_cursor_position().row;
+        self.get_top_layer_mut().set_tag(row, tag)
     }
-    fn handle_transpose(&mut self) -> Transition {
-        match self.mode {
-            UIMode::View => {
-                self.transpose();
 
-                Transition::Ok
+    /// Open the facet sidebar, or close it if it's open.
+    pub fn toggle_facets(&mut self) {
+        self.facets = match self.facets {
+            Some(_) => None,
+            None => {
+                let facets = self.get_top_layer().facets(FacetPanel::VALUES_PER_COLUMN);
+                Some(FacetPanel::new(facets))
             }
-            _ => Transition::None,
-        }
+        };
     }
-    // for these, copy standard CursorMoveHandler for UIMode::View, but use special handling for UIMode::Cursor
-    // NOTE: https://stackoverflow.com/a/31462293/2016290 says there's plans for Rust to allow calling super functions,
-    // but not yet, and since they're all one line, it seems simpler to copy than make a lot of helper functions
-    fn handle_left(&mut self) {
-        match self.mode {
-            UIMode::View => self.get_top_layer_mut().cursor.prev_column_i(),
-            _ => self.get_top_layer_mut().cursor.prev_column(),
+
+    // filter by the selected facet value, or stop filtering by it
+    fn toggle_selected_facet(&mut self) -> Result<String> {
+        let Some((column, value, active)) = self
+            .facets
+            .as_ref()
+            .and_then(|facets| facets.selected())
+            .map(|(column, value, active)| (column.to_string(), value.to_string(), active))
+        else {
+            return Ok(String::new());
+        };
+
+        let layer = self.get_top_layer_mut();
+        let message = if active {
+            layer.remove_filter(&column, &value);
+            tr!("{count} rows", count = layer.record_values.len())
+        } else {
+            let count = layer.add_filter(&column, value)?;
+            tr!("{count} rows match", count = count)
+        };
+
+        let facets = layer.facets(FacetPanel::VALUES_PER_COLUMN);
+        if let Some(panel) = &mut self.facets {
+            panel.update(facets);
         }
+
+        Ok(message)
     }
-    fn handle_right(&mut self) {
-        match self.mode {
-            UIMode::View => self.get_top_layer_mut().cursor.next_column_i(),
-            _ => self.get_top_layer_mut().cursor.next_column(),
+
+    fn handle_facets_key(&mut self, key: KeyEvent, info: &mut ViewInfo) -> Transition {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('F') => self.facets = None,
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Some(facets) = &mut self.facets {
+                    facets.select_prev();
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let Some(facets) = &mut self.facets {
+                    facets.select_next();
+                }
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                info.status = Some(match self.toggle_selected_facet() {
+                    Ok(message) => Report::message(message, Severity::Info),
+                    Err(err) => Report::message(err.to_string(), Severity::Err),
+                });
+            }
+            _ => return Transition::None,
         }
+
+        Transition::Ok
     }
-    fn handle_up(&mut self) {
-        match self.mode {
-            UIMode::View => self.get_top_layer_mut().cursor.prev_row_i(),
-            _ => self.get_top_layer_mut().cursor.prev_row(),
-        }
+
+    /// Show the small tables of `column`, or of the column under the cursor, inline in their
+    /// cells rather than as `[table N rows]`, or stop showing them so.
+    ///
+    /// Returns the column and whether its tables are now shown inline.
+    pub fn toggle_inline(&mut self, column: Option<&str>) -> Result<(String, bool)> {
+        let column = match column {
+            Some(column) => column.to_string(),
+            None => {
+                let Position { row, column } = self.get_cursor_position();
+                let layer = self.get_top_layer();
+                let index = match layer.orientation {
+                    Orientation::Top => column,
+                    Orientation::Left => row,
+                };
+                layer.column_names.get(index).cloned().unwrap_or_default()
+            }
+        };
+
+        let inline = self.get_top_layer_mut().toggle_inline(&column)?;
+        Ok((column, inline))
     }
-    fn handle_down(&mut self) {
-        match self.mode {
-            UIMode::View => self.get_top_layer_mut().cursor.next_row_i(),
-            _ => self.get_top_layer_mut().cursor.next_row(),
+
+    /// Show the table with only the columns every row has, when its rows are of different
+    /// shapes, on top of it; or, on such a table, go back to all the columns.
+    ///
+    /// Returns how many columns are left out, `None` going back.
+    pub fn toggle_common_columns(&mut self) -> Result<Option<usize>> {
+        if self.layer_stack.len() > 1
+            && self.get_top_layer().opened_by == format!(":{COMMON_COLUMNS}")
+        {
+            self.layer_stack.pop();
+            self.mode = UIMode::Cursor;
+            return Ok(None);
+        }
+
+        let layer = self.get_top_layer_mut();
+        if !layer.is_filterable() {
+            return Err(anyhow::anyhow!(tr!(
+                "Only the columns of tables can be left out"
+            )));
+        }
+        layer.expand_abbreviated();
+
+        let common = layer.common_columns();
+        let left_out = layer.column_names.len() - common.len();
+        if left_out == 0 {
+            return Err(anyhow::anyhow!(tr!("Every row has every column")));
+        }
+        if common.is_empty() {
+            return Err(anyhow::anyhow!(tr!("No column is in every row")));
         }
+
+        let columns = common
+            .iter()
+            .map(|&i| layer.column_names[i].clone())
+            .collect();
+        let rows = layer
+            .record_values
+            .iter()
+            .map(|row| common.iter().map(|&i| row[i].clone()).collect())
+            .collect();
+        self.push_table(COMMON_COLUMNS, columns, rows);
+
+        Ok(Some(left_out))
     }
-}
 
-fn create_layer(value: Value) -> Result<RecordLayer> {
-    let (columns, values) = collect_input(value)?;
-    if columns.is_empty() {
-        return Err(anyhow::anyhow!("Nothing to explore in empty collections!"));
+    /// Add a column for each named capture group of `regex`, filled from the text of
+    /// `column`; rows the regex doesn't match are highlighted.
+    ///
+    /// Returns the number of rows matched.
+    pub fn extract(&mut self, column: &str, regex: &Regex) -> Result<usize> {
+        self.get_top_layer_mut().extract(column, regex)
     }
-    Ok(RecordLayer::new(columns, values))
-}
 
-fn push_layer(view: &mut RecordView, mut next_layer: RecordLayer) {
-    let layer = view.get_top_layer();
-    let header = layer.get_column_header();
+    /// Highlight the string cells where `regex` would be replaced by `replacement`, in
+    /// `column` or in any column; Enter then replaces it in them and Esc cancels.
+    ///
+    /// Returns the number of cells that would change.
+    pub fn preview_replace(
+        &mut self,
+        regex: Regex,
+        replacement: String,
+        column: Option<String>,
+    ) -> Result<usize> {
+        self.get_top_layer_mut().preview_replace(Replacement {
+            regex,
+            replacement,
+            column,
+        })
+    }
 
-    if let Some(header) = header {
-        next_layer.set_name(header);
+    // apply or cancel the `:replace` being previewed
+    fn handle_replace_key(&mut self, key: KeyEvent, info: &mut ViewInfo) -> Transition {
+        let layer = self.get_top_layer_mut();
+        let message = match key.code {
+            KeyCode::Enter => tr!("Replaced {count} cells", count = layer.apply_replace()),
+            KeyCode::Esc => {
+                layer.cancel_replace();
+                tr!("Replace cancelled")
+            }
+            _ => return Transition::None,
+        };
+
+        info.status = Some(Report::message(message, Severity::Info));
+        Transition::Ok
     }
 
-    view.layer_stack.push(next_layer);
-    view.auto_tail = false;
-    view.previous_row_count = view.get_top_layer().record_values.len();
-}
+    // show text, like the source of a closure, a line per row on top of the table
+    fn open_text(&mut self, text: &str) {
+        let lines = text
+            .lines()
+            .map(|line| vec![Value::string(line, NuSpan::unknown())])
+            .collect();
+        push_layer(self, RecordLayer::new(vec![String::new()], lines));
+        self.set_top_layer_orientation(Orientation::Top);
+
+        let layer = self.get_top_layer_mut();
+        layer.opened_by.push_str(" source");
+    }
+
+    /// Add the parts of `column` split by `delimiter` as new columns, named after it.
+    ///
+    /// Returns the number of columns added.
+    pub fn split_into_columns(&mut self, column: &str, delimiter: &str) -> Result<usize> {
+        self.get_top_layer_mut()
+            .split_into_columns(column, delimiter)
+    }
+
+    /// Call `column` `name` from now on, in what's shown and in the table returned, saved and
+    /// copied; the name is kept when the table is read again.
+    pub fn rename_column(&mut self, column: &str, name: &str) -> Result<()> {
+        self.get_top_layer_mut().rename_column(column, name)
+    }
+
+    /// Mark the row under the cursor for [`RecordView::compare_marked`], or unmark it.
+    pub fn toggle_mark(&mut self) -> bool {
+        let row = self.get_cursor_position().row;
+        self.get_top_layer_mut().toggle_mark(row)
+    }
+
+    /// Open a layer showing the marked rows side by side, with the fields that differ
+    /// between them highlighted. The marks are cleared.
+    pub fn compare_marked(&mut self) -> Result<()> {
+        let layer = self.get_top_layer_mut().compare_marked()?;
+        push_layer(self, layer);
+
+        let layer = self.get_top_layer_mut();
+        layer.set_name("compare");
+        layer.opened_by = String::from(":compare");
+
+        Ok(())
+    }
+
+    /// Unpin all rows, the frozen one too; returns false if none were pinned.
+    pub fn unpin_all(&mut self) -> bool {
+        self.get_top_layer_mut().unpin_all()
+    }
+
+    pub fn transpose(&mut self) {
+        let layer = self.get_top_layer_mut();
+        layer.expand_abbreviated();
+        layer.unpin_all();
+        transpose_table(layer);
+
+        layer.reset_cursor();
+    }
+
+    pub fn get_top_layer(&self) -> &RecordLayer {
+        self.layer_stack
+            .last()
+            .expect("we guarantee that 1 entry is always in a list")
+    }
+
+    pub fn get_top_layer_mut(&mut self) -> &mut RecordLayer {
+        self.layer_stack
+            .last_mut()
+            .expect("we guarantee that 1 entry is always in a list")
+    }
+
+    pub fn set_top_layer_orientation(&mut self, orientation: Orientation) {
+        let layer = self.get_top_layer_mut();
+        layer.orientation = orientation;
+        layer.auto_orientation = false;
+        layer.reset_cursor();
+    }
+
+    /// Show the top layer, a record, a field a line; or across as a row when its fields
+    /// don't fit the height of the terminal but do its width. It's turned again as the
+    /// terminal is resized, until it's rotated by hand.
+    pub fn set_top_layer_record(&mut self) {
+        self.set_top_layer_orientation(Orientation::Left);
+        self.get_top_layer_mut().auto_orientation = true;
+    }
+
+    /// Show the top layer the other way round, a row a column rather than a line, for good.
+    pub fn rotate(&mut self) {
+        let orientation = match self.get_top_layer().orientation {
+            Orientation::Top => Orientation::Left,
+            Orientation::Left => Orientation::Top,
+        };
+        self.set_top_layer_orientation(orientation);
+    }
+
+    // turn the top layer, a record turned by itself, to fit `area`
+    fn orient_record(&mut self, area: Rect, config: &Config) {
+        let show_header = self.cfg.table.show_header;
+        let padding = self.cfg.table.column_padding_left + self.cfg.table.column_padding_right;
+        let layer = self.get_top_layer_mut();
+        let [values] = layer.record_values.as_slice() else {
+            return;
+        };
+        if !layer.auto_orientation {
+            return;
+        }
+
+        // a field across is as wide as its name or its value, with a line between fields
+        let text = layer.record_text.as_ref().and_then(|text| text.first());
+        let width: usize = layer
+            .column_names
+            .iter()
+            .zip(values)
+            .enumerate()
+            .map(|(i, (name, value))| {
+                let value = match text.and_then(|text| text.get(i)) {
+                    Some((text, _)) => string_width(text),
+                    None => string_width(&value.to_abbreviated_string(config)),
+                };
+                string_width(name).max(value) + padding + 1
+            })
+            .sum();
+
+        let orientation = record_orientation(
+            layer.column_names.len(),
+            width,
+            area.width as usize,
+            estimate_page_size(area, show_header) as usize,
+            layer.orientation,
+        );
+        if orientation != layer.orientation {
+            layer.orientation = orientation;
+            layer.reset_cursor();
+        }
+    }
+
+    /// Get the current position of the cursor in the table as a whole
+    pub fn get_cursor_position(&self) -> Position {
+        let layer = self.get_top_layer();
+        layer.cursor.position()
+    }
+
+    /// Get the current position of the cursor in the window being shown
+    pub fn get_cursor_position_in_window(&self) -> Position {
+        let layer = self.get_top_layer();
+        layer.cursor.window_relative_position()
+    }
+
+    /// Get the origin of the window being shown. (0,0), top left corner.
+    pub fn get_window_origin(&self) -> Position {
+        let layer = self.get_top_layer();
+        layer.cursor.window_origin()
+    }
+
+    pub fn set_cursor_mode(&mut self) {
+        self.mode = UIMode::Cursor;
+    }
+
+    pub fn set_view_mode(&mut self) {
+        self.mode = UIMode::View;
+    }
+
+    /// Switch between moving a cursor over the cells and scrolling the table.
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            UIMode::Cursor => UIMode::View,
+            UIMode::View => UIMode::Cursor,
+        };
+    }
+
+    pub fn get_current_value(&self) -> &Value {
+        let (row, column) = self.current_cell();
+        &self.get_top_layer().record_values[row][column]
+    }
+
+    /// The row of the selected cell as a record; a record shown as one is its only row.
+    pub fn current_row_value(&self) -> Value {
+        let (row, _) = self.current_cell();
+        let layer = self.get_top_layer();
+        let values = layer.record_values[row].iter().cloned();
+        let record = layer.column_names.iter().cloned().zip(values).collect();
+
+        Value::record(record, NuSpan::unknown())
+    }
+
+    /// The values of the column of the selected cell as a list, in the order they're shown,
+    /// abbreviated rows put back.
+    pub fn current_column_value(&mut self) -> Value {
+        self.expand_abbreviated();
+        let (_, column) = self.current_cell();
+        let values = self
+            .get_top_layer()
+            .record_values
+            .iter()
+            .filter_map(|row| row.get(column).cloned())
+            .collect();
+
+        Value::list(values, NuSpan::unknown())
+    }
+
+    // the row and column in `record_values` of the selected cell
+    fn current_cell(&self) -> (usize, usize) {
+        let Position { row, column } = self.get_cursor_position();
+        let layer = self.get_top_layer();
+
+        let (row, column) = match layer.orientation {
+            Orientation::Top => (row, column),
+            Orientation::Left => (column, row),
+        };
+
+        // These should never happen as long as the cursor is working correctly
+        assert!(row < layer.record_values.len(), "row out of bounds");
+        assert!(column < layer.column_names.len(), "column out of bounds");
+
+        (row, column)
+    }
+
+    // `height` is the most rows in sight
+    fn create_table_widget<'a>(
+        &'a mut self,
+        cfg: ViewConfig<'a>,
+        height: usize,
+    ) -> TableWidget<'a> {
+        let style = self.cfg.table;
+        let compat = self.cfg.compat;
+        let highlight = self.cfg.highlight;
+        let budget = self.cfg.cell_budget;
+        let inline_rows = self.cfg.inline_rows;
+        let style_computer = cfg.style_computer;
+        let Position { row, column } = self.get_window_origin();
+        let overrides: Vec<_> = self
+            .get_top_layer()
+            .column_names
+            .iter()
+            .map(|name| self.cfg.columns.get(name).cloned())
+            .collect();
+        let layouts = overrides
+            .iter()
+            .map(|column| ColumnLayout {
+                width: column.as_ref().and_then(|c| c.width).map(|w| w as u16),
+                align: column.as_ref().and_then(|c| c.align),
+            })
+            .collect();
+
+        let cell_scroll = self.cell_scroll_offset();
+
+        let layer = self.get_top_layer_mut();
+        let format = CellFormat {
+            budget,
+            inline_rows,
+            overrides,
+            inline: layer
+                .column_names
+                .iter()
+                .map(|name| layer.inline_columns.contains(name))
+                .collect(),
+        };
+        if layer.record_text.is_none() {
+            let mut data = convert_records_to_string(
+                &layer.record_values,
+                cfg.nu_config,
+                cfg.style_computer,
+                &format,
+            );
+            lscolorize(&layer.column_names, &mut data, cfg.cwd, cfg.lscolors);
+
+            if layer.is_filterable() && !layer.extractions.is_empty() {
+                for (row, values) in data.iter_mut().zip(&layer.record_values) {
+                    if layer.is_extract_miss(values) {
+                        for (_, text_style) in row {
+                            text_style.color_style = Some(highlight);
+                        }
+                    }
+                }
+            }
+
+            if let Some(replace) = &layer.pending_replace
+                && let Ok(column) = replace.column_index(&layer.column_names)
+            {
+                for (row, values) in data.iter_mut().zip(&layer.record_values) {
+                    for i in replace.changed_cells(values, column) {
+                        if let Some((_, text_style)) = row.get_mut(i) {
+                            text_style.color_style = Some(highlight);
+                        }
+                    }
+                }
+            }
+
+            if layer.highlight_differences {
+                for column in layer.differing_columns() {
+                    for row in &mut data {
+                        if let Some((_, text_style)) = row.get_mut(column) {
+                            text_style.color_style = Some(highlight);
+                        }
+                    }
+                }
+            }
+
+            layer.record_text = Some(data);
+            layer.find_first_values();
+            layer.find_fill();
+        }
+
+        if layer.pinned_text.is_none() {
+            // the frozen row comes first
+            let rows: Vec<_> = layer
+                .reference
+                .iter()
+                .chain(&layer.pinned)
+                .cloned()
+                .collect();
+            let data = convert_records_to_string(&rows, cfg.nu_config, cfg.style_computer, &format);
+            layer.pinned_text = Some(data);
+        }
+
+        let samples = layer.column_samples();
+        let fill = match layer.orientation {
+            Orientation::Top => layer.fill.clone(),
+            Orientation::Left => Vec::new(),
+        };
+        // the terminals without ANSI support would show the sequences of the links
+        let links = match layer.orientation {
+            Orientation::Top if !compat => layer.links(row..row + height, &format.overrides),
+            _ => Vec::new(),
+        };
+        let headers = &layer.column_names;
+        let data = layer.record_text.as_ref().expect("always ok");
+        let pinned = layer.pinned_text.as_ref().expect("always ok");
+
+        let table = TableWidget::new(
+            headers,
+            data,
+            style_computer,
+            row,
+            column,
+            style,
+            layer.orientation,
+        )
+        .with_pinned_rows(pinned)
+        .with_column_layouts(layouts)
+        .with_samples(samples)
+        .with_fill(fill)
+        .with_links(links);
+        let table = match cell_scroll {
+            Some((row, column, offset)) => table.with_cell_scroll(row, column, offset),
+            None => table,
+        };
+
+        match &layer.abbreviated {
+            Some(hidden) => table.with_row_separator(hidden.at, hidden.rows.len()),
+            None => table,
+        }
+    }
+
+    /// The cell whose text is scrolled, as indexes of the data, with how far it is; only
+    /// while the cursor is on it.
+    fn cell_scroll_offset(&self) -> Option<(usize, usize, usize)> {
+        let layer = self.get_top_layer();
+        let Position { row, column } = layer.cursor.position();
+        let (scrolled_row, scrolled_column, offset) = self.cell_scroll?;
+        if self.mode != UIMode::Cursor || (row, column) != (scrolled_row, scrolled_column) {
+            return None;
+        }
+
+        match layer.orientation {
+            Orientation::Top => Some((row, column, offset)),
+            Orientation::Left => Some((column, row, offset)),
+        }
+    }
+
+    /// Scroll the text of the selected cell a character to the right or back to the left,
+    /// for cells too wide for their column. Returns false if it can't go further.
+    fn scroll_cell(&mut self, right: bool) -> bool {
+        let offset = self.cell_scroll_offset().map_or(0, |(.., offset)| offset);
+        let length = self.cell_text().chars().count();
+        let offset = match right {
+            true if offset + 1 < length => offset + 1,
+            false if offset > 0 => offset - 1,
+            _ => return false,
+        };
+
+        let Position { row, column } = self.get_top_layer().cursor.position();
+        self.cell_scroll = Some((row, column, offset));
+        true
+    }
+
+    // The text of the selected cell, as it's shown in the table
+    fn cell_text(&self) -> String {
+        let layer = self.get_top_layer();
+        let Position { row, column } = layer.cursor.position();
+        let (row, column) = match layer.orientation {
+            Orientation::Top => (row, column),
+            Orientation::Left => (column, row),
+        };
+
+        layer
+            .record_text
+            .as_ref()
+            .and_then(|text| text.get(row)?.get(column))
+            .map(|(text, _)| text.replace('\n', " "))
+            .unwrap_or_default()
+    }
+
+    fn highlight_selection(
+        &self,
+        f: &mut Frame,
+        layout: &mut Layout,
+        count_rows: usize,
+        count_columns: usize,
+    ) {
+        let layer = self.get_top_layer();
+        let Some((anchor_row, anchor_column)) = layer.selection_anchor else {
+            return;
+        };
+
+        let Position { row, column } = layer.cursor.position();
+        let origin = layer.cursor.window_origin();
+
+        // only the part of the selection inside the window
+        let rows = row.min(anchor_row).max(origin.row)
+            ..(row.max(anchor_row) + 1).min(origin.row + count_rows);
+        let columns = column.min(anchor_column).max(origin.column)
+            ..(column.max(anchor_column) + 1).min(origin.column + count_columns);
+
+        for row in rows {
+            for column in columns.clone() {
+                let info = get_element_info(
+                    layout,
+                    row - origin.row,
+                    column - origin.column,
+                    count_rows,
+                    layer.orientation,
+                    self.cfg.table.show_header,
+                );
+
+                if let Some(info) = info {
+                    highlight_selected_cell(f, info.clone(), &self.cfg);
+                }
+            }
+        }
+    }
+
+    // the cells of the row under the cursor whose values aren't those of the frozen row
+    fn highlight_reference_differences(
+        &self,
+        f: &mut Frame,
+        layout: &mut Layout,
+        count_rows: usize,
+        count_columns: usize,
+    ) {
+        let layer = self.get_top_layer();
+        if layer.orientation != Orientation::Top {
+            return;
+        }
+
+        let Position { row, .. } = layer.cursor.position();
+        let origin = layer.cursor.window_origin();
+        let highlight = Block::default().style(nu_style_to_tui(self.cfg.highlight));
+        for column in layer.reference_differences(row) {
+            if !(origin.column..origin.column + count_columns).contains(&column) {
+                continue;
+            }
+
+            let info = get_element_info(
+                layout,
+                row - origin.row,
+                column - origin.column,
+                count_rows,
+                layer.orientation,
+                self.cfg.table.show_header,
+            );
+            if let Some(info) = info {
+                let area = Rect::new(info.area.x, info.area.y, info.area.width, 1);
+                f.render_widget(highlight.clone(), area);
+            }
+        }
+    }
+
+    fn update_cursors(&mut self, rows: usize, columns: usize) {
+        match self.get_top_layer().orientation {
+            Orientation::Top => {
+                let _ = self
+                    .get_top_layer_mut()
+                    .cursor
+                    .set_window_size(rows, columns);
+            }
+
+            Orientation::Left => {
+                let _ = self
+                    .get_top_layer_mut()
+                    .cursor
+                    .set_window_size(rows, columns);
+            }
+        }
+    }
+
+    /// The cells between the selection anchor and the cursor, both included.
+    fn selected_values(&self) -> Vec<&Value> {
+        let layer = self.get_top_layer();
+        let Some((anchor_row, anchor_column)) = layer.selection_anchor else {
+            return Vec::new();
+        };
+
+        let Position { row, column } = layer.cursor.position();
+        let rows = row.min(anchor_row)..=row.max(anchor_row);
+        let columns = column.min(anchor_column)..=column.max(anchor_column);
+
+        rows.flat_map(|row| columns.clone().map(move |column| (row, column)))
+            .filter_map(|(row, column)| match layer.orientation {
+                Orientation::Top => layer.record_values.get(row)?.get(column),
+                Orientation::Left => layer.record_values.get(column)?.get(row),
+            })
+            .collect()
+    }
+
+    /// Move the cursor to the cell clicked, or start dragging: the edge of a header resizes
+    /// its column, a header moves its column (or sorts by it, released where it was pressed)
+    /// and the minimap scrolls the table.
+    fn click(&mut self, layout: &Layout, x: u16, y: u16, info: &mut ViewInfo) {
+        if let Some(bar) = self.minimap
+            && (bar.x..bar.right()).contains(&x)
+            && (bar.y..bar.bottom()).contains(&y)
+        {
+            self.dragging = Some(Dragging::Minimap);
+            self.scroll_to_minimap(y);
+            return;
+        }
+
+        let orientation = self.get_top_layer().orientation;
+        let with_head = self.cfg.table.show_header;
+        let padding = self.cfg.table.column_padding_right as u16;
+        let origin = self.get_window_origin();
+
+        // the edge of a header is its right padding and the line after it
+        let edge = layout
+            .data
+            .iter()
+            .position(|e| e.area.y == y && x >= e.area.right() && x <= e.area.right() + padding);
+        if let Some(index) = edge
+            && orientation == Orientation::Top
+            && let Some(Clicked::Header(column)) =
+                clicked_element(index, self.drawn_rows, orientation, with_head)
+            && let Some(name) = self
+                .get_top_layer()
+                .column_names
+                .get(origin.column + column)
+        {
+            self.dragging = Some(Dragging::Edge(ColumnDrag {
+                column: name.clone(),
+                x,
+                width: layout.data[index].area.width,
+            }));
+            return;
+        }
+
+        match self.clicked(layout, x, y) {
+            Some(Clicked::Header(column))
+                if orientation == Orientation::Top && self.get_top_layer().is_filterable() =>
+            {
+                self.dragging = Some(Dragging::Header {
+                    column: origin.column + column,
+                    moved: false,
+                });
+            }
+            Some(Clicked::Cell(row, column)) => self.select_clicked(row, column, info),
+            _ => {}
+        }
+    }
+
+    /// Select the cell right-clicked and open its menu, as `x` does.
+    fn right_click(&mut self, layout: &Layout, x: u16, y: u16, info: &mut ViewInfo) -> Transition {
+        match self.clicked(layout, x, y) {
+            Some(Clicked::Cell(row, column)) => {
+                self.select_clicked(row, column, info);
+                Transition::Cmd(MenuCmd::NAME.to_string())
+            }
+            _ => Transition::Ok,
+        }
+    }
+
+    /// What's at `x`, `y` in the table as last laid out.
+    fn clicked(&self, layout: &Layout, x: u16, y: u16) -> Option<Clicked> {
+        let index = layout.data.iter().position(|e| {
+            (e.area.x..e.area.right()).contains(&x) && (e.area.y..e.area.bottom()).contains(&y)
+        })?;
+
+        let orientation = self.get_top_layer().orientation;
+        clicked_element(
+            index,
+            self.drawn_rows,
+            orientation,
+            self.cfg.table.show_header,
+        )
+    }
+
+    // put the cursor on a cell clicked, by its position in the window
+    fn select_clicked(&mut self, row: usize, column: usize, info: &mut ViewInfo) {
+        let cursor = &mut self.get_top_layer_mut().cursor;
+        cursor.y.window.set_position(row);
+        cursor.x.window.set_position(column);
+        self.mode = UIMode::Cursor;
+        info.status = Some(self.create_records_report());
+    }
+
+    /// Carry on the drag started by the last click to `x`, `y`.
+    fn drag(&mut self, layout: &Layout, x: u16, y: u16, info: &mut ViewInfo) {
+        match &self.dragging {
+            Some(Dragging::Edge(drag)) => {
+                let width = (drag.width + x).saturating_sub(drag.x).max(1);
+                let column = drag.column.clone();
+                self.cfg.columns.entry(column.clone()).or_default().width = Some(width as usize);
+                info.status = Some(Report::info(tr!(
+                    "{column} is {width} wide",
+                    column = column,
+                    width = width
+                )));
+            }
+            Some(Dragging::Header { column: from, .. }) => {
+                let from = *from;
+                // the header in the column of the pointer, wherever it is up and down
+                let orientation = self.get_top_layer().orientation;
+                let with_head = self.cfg.table.show_header;
+                let header = layout
+                    .data
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, e)| (e.area.x..e.area.right()).contains(&x))
+                    .find_map(|(index, _)| {
+                        match clicked_element(index, self.drawn_rows, orientation, with_head) {
+                            Some(Clicked::Header(column)) => Some(column),
+                            _ => None,
+                        }
+                    });
+                let to = match header {
+                    Some(header) => self.get_window_origin().column + header,
+                    None => return,
+                };
+                let layer = self.get_top_layer_mut();
+                if to == from || to >= layer.column_names.len() {
+                    return;
+                }
+
+                let mut order: Vec<usize> = (0..layer.column_names.len()).collect();
+                order.remove(from);
+                order.insert(to, from);
+                layer.reorder_columns(&order);
+                let name = layer.column_names[to].clone();
+                self.dragging = Some(Dragging::Header {
+                    column: to,
+                    moved: true,
+                });
+                info.status = Some(Report::info(tr!(
+                    "{column} moved to column {n}",
+                    column = name,
+                    n = to + 1
+                )));
+            }
+            Some(Dragging::Minimap) => self.scroll_to_minimap(y),
+            None => {}
+        }
+    }
+
+    /// End the drag started by the last click: a header released without being moved sorts
+    /// by its column, the other way round the second time.
+    fn release(&mut self, info: &mut ViewInfo) {
+        if let Some(Dragging::Header {
+            column,
+            moved: false,
+        }) = self.dragging.take()
+            && let Some(name) = self.get_top_layer().column_names.get(column)
+        {
+            let name = name.clone();
+            info.status = Some(match self.sort_by(&name, None, None) {
+                Ok(descending) => Report::info(sort_message(&name, descending)),
+                Err(err) => Report::error(err.to_string()),
+            });
+        }
+    }
+
+    /// Scroll the table to the rows the line `y` of the minimap stands for, in the middle of
+    /// the window.
+    fn scroll_to_minimap(&mut self, y: u16) {
+        let Some(bar) = self.minimap else {
+            return;
+        };
+
+        let page_size = self.page_size;
+        let layer = self.get_top_layer_mut();
+        let total = layer.count_rows();
+        let line = y.clamp(bar.top(), bar.bottom().saturating_sub(1)) - bar.top();
+        let row = Minimap::row_at(total, line, bar.height);
+        let start = row
+            .saturating_sub(page_size / 2)
+            .min(total.saturating_sub(page_size));
+        let column = layer.cursor.window_origin().column;
+        layer.cursor.set_window_start_position(start, column);
+    }
+
+    /// Scroll the table `rows` rows down, or up if negative, and `columns` columns to the right
+    /// or left, as the wheel does; the cursor keeps its place in the window.
+    fn scroll(&mut self, rows: isize, columns: isize) {
+        let page_size = self.page_size;
+        let layer = self.get_top_layer_mut();
+        let origin = layer.cursor.window_origin();
+        let max_row = layer.count_rows().saturating_sub(page_size);
+        let row = origin
+            .row
+            .saturating_add_signed(rows)
+            .min(max_row.max(origin.row));
+        let column = origin
+            .column
+            .saturating_add_signed(columns)
+            .min(layer.count_columns().saturating_sub(1));
+
+        layer.cursor.set_window_start_position(row, column);
+    }
+
+    fn create_records_report(&self) -> Report {
+        let layer = self.get_top_layer();
+        let covered_percent = match layer.field_page(self.page_size) {
+            Some((page, pages)) => tr!("Page {page}/{pages}", page = page, pages = pages),
+            None => report_row_position(layer.cursor),
+        };
+        let cursor = report_cursor_position(self.mode, layer.cursor);
+        let stats = layer
+            .selection_anchor
+            .and_then(|_| selection_stats(&self.selected_values()));
+        let message = match &layer.abbreviated {
+            _ if layer.selection_anchor.is_some() => {
+                stats.unwrap_or_else(|| tr!("No numbers selected"))
+            }
+            Some(hidden) => tr!(
+                "{count} rows hidden, press a to show all",
+                count = hidden.rows.len()
+            ),
+            None if !layer.marked.is_empty() => tr!(
+                "{count} rows marked, :compare to compare",
+                count = layer.marked.len()
+            ),
+            None if layer.unfiltered.is_some() => tr!(
+                "{count} of {total} rows",
+                count = layer.record_values.len(),
+                total = layer.unfiltered.as_ref().map_or(0, Vec::len)
+            ),
+            None => layer.name.clone().unwrap_or_default(),
+        };
+        let mut mode = self.mode.label();
+        if layer.edited {
+            mode.push('*');
+        }
+
+        Report::new(message, Severity::Info, mode, cursor, covered_percent)
+    }
+}
+
+impl View for RecordView {
+    fn draw(&mut self, f: &mut Frame, area: Rect, cfg: ViewConfig<'_>, layout: &mut Layout) {
+        let full_area = area;
+        let mut area = area;
+        if let Some(facets) = &mut self.facets
+            && area.width > FacetPanel::WIDTH * 2
+        {
+            area.width -= FacetPanel::WIDTH;
+            let sidebar = Rect::new(area.right(), area.y, FacetPanel::WIDTH, area.height);
+            facets.render(
+                sidebar,
+                f.buffer_mut(),
+                self.cfg.status_bar_text,
+                self.cfg.table.separator_style,
+                self.cfg.selected_cell,
+            );
+        }
+
+        let minimap = self.cfg.minimap && area.width > Minimap::WIDTH * 10;
+        if minimap {
+            area.width -= Minimap::WIDTH;
+        }
+
+        self.orient_record(area, cfg.nu_config);
+
+        let mut table_layout = TableWidgetState::default();
+        let table = self.create_table_widget(cfg, area.height as usize);
+        f.render_stateful_widget(table, area, &mut table_layout);
+
+        if minimap {
+            let layer = self.get_top_layer();
+            let start = layer.cursor.window_origin().row;
+            let marks = layer.minimap_marks(&self.search_matches);
+            let minimap = Minimap::new(
+                layer.count_rows(),
+                start..start + table_layout.count_rows,
+                &marks,
+            )
+            .with_highlight(nu_style_to_tui(self.cfg.highlight));
+            let bar = Rect::new(area.right(), area.y, Minimap::WIDTH, area.height);
+            f.render_widget(minimap, bar);
+            self.minimap = Some(bar);
+        } else {
+            self.minimap = None;
+        }
+
+        *layout = table_layout.layout;
+        self.drawn_rows = table_layout.count_rows;
+
+        self.update_cursors(table_layout.count_rows, table_layout.count_columns);
+
+        // Update page_size
+        self.page_size = estimate_page_size(area, self.cfg.table.show_header) as usize;
+
+        // Check for new rows and handle auto-tail
+        let current_row_count = self.get_top_layer().record_values.len();
+        if current_row_count > self.previous_row_count {
+            // Invalidate record_text to force redraw
+            self.get_top_layer_mut().record_text = None;
+            // If auto_tail, scroll to bottom
+            if self.auto_tail {
+                let page_size = self.page_size;
+                if current_row_count > page_size {
+                    self.get_top_layer_mut()
+                        .cursor
+                        .set_window_start_position(current_row_count - page_size, 0);
+                }
+            }
+        }
+        self.previous_row_count = current_row_count;
+
+        if self.mode == UIMode::Cursor {
+            self.highlight_reference_differences(
+                f,
+                layout,
+                table_layout.count_rows,
+                table_layout.count_columns,
+            );
+
+            let Position { row, column } = self.get_cursor_position_in_window();
+            let info = get_element_info(
+                layout,
+                row,
+                column,
+                table_layout.count_rows,
+                self.get_top_layer().orientation,
+                self.cfg.table.show_header,
+            );
+
+            if let Some(info) = info {
+                highlight_selected_cell(f, info.clone(), &self.cfg);
+            }
+
+            self.highlight_selection(
+                f,
+                layout,
+                table_layout.count_rows,
+                table_layout.count_columns,
+            );
+        }
+
+        if let Some(pages) = &self.pages {
+            pages.render(
+                full_area,
+                f.buffer_mut(),
+                self.cfg.status_bar_text,
+                self.cfg.table.separator_style,
+                self.cfg.selected_cell,
+            );
+        }
+
+        if let Some(filters) = &self.filter_list {
+            filters.render(
+                full_area,
+                f.buffer_mut(),
+                self.cfg.status_bar_text,
+                self.cfg.table.separator_style,
+                self.cfg.selected_cell,
+            );
+        }
+
+        if let Some(menu) = &self.menu {
+            menu.render(
+                full_area,
+                f.buffer_mut(),
+                self.cfg.status_bar_text,
+                self.cfg.table.separator_style,
+                self.cfg.selected_cell,
+            );
+        }
+    }
+
+    fn handle_input(
+        &mut self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        _layout: &Layout,
+        info: &mut ViewInfo,
+        key: KeyEvent,
+    ) -> Transition {
+        if self.menu.is_some() {
+            return self.handle_menu_key(key, info);
+        }
+        if self.pages.is_some() {
+            return self.handle_pages_key(key, info);
+        }
+        if self.filter_list.is_some() {
+            return self.handle_filters_key(key, info);
+        }
+        if self.facets.is_some() {
+            return self.handle_facets_key(key, info);
+        }
+        if key.code == KeyCode::Enter
+            && self.mode == UIMode::Cursor
+            && let Value::Closure { val, .. } = self.get_current_value()
+        {
+            let source = closure_source(engine_state, val);
+            self.open_text(&source);
+            return Transition::Ok;
+        }
+        if self.get_top_layer().pending_replace.is_some()
+            && let Transition::Ok = self.handle_replace_key(key, info)
+        {
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Char('c')
+            && key.modifiers == KeyModifiers::NONE
+            && !self.get_top_layer().column_names.is_empty()
+        {
+            return Transition::Prompt(format!("{} ", ColCmd::NAME));
+        }
+        if key.code == KeyCode::Char('F') && self.get_top_layer().is_filterable() {
+            self.toggle_facets();
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Char('r') && key.modifiers == KeyModifiers::NONE {
+            self.rotate();
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::PageUp {
+            let page_size = self.page_size;
+            let current_row = self.get_top_layer().cursor.window_origin().row;
+            let new_row = current_row.saturating_sub(page_size);
+            let layer = self.get_top_layer_mut();
+            layer
+                .cursor
+                .set_window_start_position(new_row, layer.cursor.window_origin().column);
+            let report = self.create_records_report();
+            info.status = Some(report);
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::PageDown {
+            let page_size = self.page_size;
+            let current_row = self.get_top_layer().cursor.window_origin().row;
+            // the lines shown, which are the fields of a record shown a field a line
+            let row_count = self.get_top_layer().count_rows();
+            let max_row = row_count.saturating_sub(page_size);
+            let new_row = (current_row + page_size).min(max_row);
+            let layer = self.get_top_layer_mut();
+            layer
+                .cursor
+                .set_window_start_position(new_row, layer.cursor.window_origin().column);
+            let report = self.create_records_report();
+            info.status = Some(report);
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Char('a') && self.expand_abbreviated() {
+            let report = self.create_records_report();
+            info.status = Some(report);
+            return Transition::Ok;
+        }
+        if key.modifiers == KeyModifiers::SHIFT
+            && matches!(key.code, KeyCode::Left | KeyCode::Right)
+            && self.mode == UIMode::Cursor
+        {
+            self.scroll_cell(key.code == KeyCode::Right);
+            return Transition::Ok;
+        }
+        if key.modifiers == KeyModifiers::ALT
+            && matches!(key.code, KeyCode::Left | KeyCode::Right)
+            && self.mode == UIMode::Cursor
+        {
+            if self.move_column(key.code == KeyCode::Right) {
+                let report = self.create_records_report();
+                info.status = Some(report);
+            }
+            return Transition::Ok;
+        }
+        if key.modifiers == KeyModifiers::ALT
+            && matches!(key.code, KeyCode::Up | KeyCode::Down)
+            && self.mode == UIMode::Cursor
+        {
+            if self.move_row(key.code == KeyCode::Down) {
+                let report = self.create_records_report();
+                info.status = Some(report);
+            }
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Char('v')
+            && key.modifiers == KeyModifiers::NONE
+            && self.mode == UIMode::Cursor
+        {
+            let layer = self.get_top_layer_mut();
+            layer.selection_anchor = match layer.selection_anchor {
+                Some(_) => None,
+                None => Some((layer.cursor.row(), layer.cursor.column())),
+            };
+            let report = self.create_records_report();
+            info.status = Some(report);
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Esc && self.get_top_layer().selection_anchor.is_some() {
+            self.get_top_layer_mut().selection_anchor = None;
+            let report = self.create_records_report();
+            info.status = Some(report);
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Char('p')
+            && key.modifiers == KeyModifiers::NONE
+            && self.mode == UIMode::Cursor
+            && self.get_top_layer().is_filterable()
+        {
+            self.toggle_pin();
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Char('P')
+            && key.modifiers.difference(KeyModifiers::SHIFT) == KeyModifiers::NONE
+            && self.mode == UIMode::Cursor
+            && self.get_top_layer().is_filterable()
+        {
+            if self.toggle_reference() {
+                info.status = Some(match self.get_top_layer().reference {
+                    Some(_) => Report::info(tr!(
+                        "Row frozen, the cells differing from it are highlighted"
+                    )),
+                    None => Report::info(tr!("Row unfrozen")),
+                });
+            }
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Char('m')
+            && key.modifiers == KeyModifiers::NONE
+            && self.mode == UIMode::Cursor
+            && self.get_top_layer().is_filterable()
+        {
+            self.toggle_mark();
+            let report = self.create_records_report();
+            info.status = Some(report);
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Char('f')
+            && key.modifiers == KeyModifiers::NONE
+            && self.mode == UIMode::Cursor
+            && self.get_top_layer().is_filterable()
+            && let Some(column) = self.get_top_layer().get_column_header()
+        {
+            let command = format!("{} {} ", FilterCmd::NAME, FilterCmd::quote_column(&column));
+            return Transition::Prompt(command);
+        }
+        if key.code == KeyCode::Char('x')
+            && key.modifiers == KeyModifiers::NONE
+            && self.mode == UIMode::Cursor
+        {
+            return Transition::Cmd(MenuCmd::NAME.to_string());
+        }
+        // a shifted character says so by its case already
+        let copy = match (key.code, key.modifiers.difference(KeyModifiers::SHIFT)) {
+            (KeyCode::Char('y'), KeyModifiers::NONE) => Some(""),
+            (KeyCode::Char('Y'), KeyModifiers::NONE) => Some(" row"),
+            (KeyCode::Char('y'), KeyModifiers::ALT) => Some(" column"),
+            _ => None,
+        };
+        if let Some(what) = copy
+            && self.mode == UIMode::Cursor
+        {
+            return Transition::Cmd(format!("{}{what}", CopyCmd::NAME));
+        }
+        if key.code == KeyCode::Char('O')
+            && key.modifiers.difference(KeyModifiers::SHIFT) == KeyModifiers::NONE
+            && self.mode == UIMode::Cursor
+        {
+            return Transition::Cmd(OpenCmd::NAME.to_string());
+        }
+        if key.code == KeyCode::Char('o')
+            && key.modifiers == KeyModifiers::NONE
+            && self.get_top_layer().shows_fields()
+        {
+            let message = match self.sort_fields() {
+                true => tr!("Fields sorted by name, Z to A"),
+                false => tr!("Fields sorted by name, A to Z"),
+            };
+            info.status = Some(Report::info(message));
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Char('o')
+            && key.modifiers == KeyModifiers::NONE
+            && self.mode == UIMode::Cursor
+            && self.get_top_layer().is_filterable()
+            && let Some(column) = self.current_column()
+        {
+            info.status = Some(match self.sort_by(&column, None, None) {
+                Ok(descending) => Report::info(sort_message(&column, descending)),
+                Err(err) => Report::error(err.to_string()),
+            });
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Tab && key.modifiers == KeyModifiers::NONE {
+            self.toggle_mode();
+            let report = self.create_records_report();
+            info.status = Some(report);
+            return Transition::Ok;
+        }
+        match self.handle_input_key(&key) {
+            Ok((transition, ..)) => {
+                if matches!(&transition, Transition::Ok | Transition::Cmd { .. }) {
+                    let report = self.create_records_report();
+                    info.status = Some(report);
+                }
+
+                transition
+            }
+            Err(e) => {
+                log::error!("Error handling input in RecordView: {e}");
+                let report = Report::message(e.to_string(), Severity::Err);
+                info.status = Some(report);
+                Transition::None
+            }
+        }
+    }
+
+    fn handle_mouse(
+        &mut self,
+        layout: &Layout,
+        info: &mut ViewInfo,
+        event: MouseEvent,
+    ) -> Transition {
+        // the overlays are used with the keys
+        if self.menu.is_some()
+            || self.pages.is_some()
+            || self.filter_list.is_some()
+            || self.facets.is_some()
+        {
+            return Transition::Ok;
+        }
+
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.click(layout, event.column, event.row, info)
+            }
+            MouseEventKind::Down(MouseButton::Right) => {
+                return self.right_click(layout, event.column, event.row, info);
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                self.drag(layout, event.column, event.row, info)
+            }
+            MouseEventKind::Up(MouseButton::Left) => self.release(info),
+            MouseEventKind::ScrollDown => self.scroll(WHEEL_ROWS as isize, 0),
+            MouseEventKind::ScrollUp => self.scroll(-(WHEEL_ROWS as isize), 0),
+            MouseEventKind::ScrollRight => self.scroll(0, 1),
+            MouseEventKind::ScrollLeft => self.scroll(0, -1),
+            _ => return Transition::None,
+        }
+
+        Transition::Ok
+    }
+
+    fn collect_data(&self) -> Vec<NuText> {
+        let layer = self.get_top_layer();
+        let mut texts = Vec::new();
+
+        // Add headers
+        for name in &layer.column_names {
+            texts.push((name.clone(), TextStyle::default()));
+        }
+
+        // Add data
+        for row in &layer.record_values {
+            for value in row {
+                let text = value.to_abbreviated_string(&Config::default());
+                let text = strip_string(&text);
+                texts.push((text, TextStyle::default()));
+            }
+        }
+
+        texts
+    }
+
+    fn show_data(&mut self, pos: usize) -> bool {
+        let layer = self.get_top_layer();
+        let num_headers = layer.column_names.len();
+
+        // the columns are lines when the layer is turned, the fields of a record with them
+        let shown = |row: usize, column: usize| match layer.orientation {
+            Orientation::Top => (row, column),
+            Orientation::Left => (column, row),
+        };
+
+        if pos < num_headers {
+            // Header
+            let (row, column) = shown(0, pos);
+            self.get_top_layer_mut()
+                .cursor
+                .set_window_start_position(row, column);
+            return true;
+        } else {
+            let data_pos = pos - num_headers;
+            let mut i = 0;
+            for (data_row, cells) in layer.record_values.iter().enumerate() {
+                if data_pos >= i && data_pos < i + cells.len() {
+                    let (row, column) = shown(data_row, data_pos - i);
+                    self.get_top_layer_mut()
+                        .cursor
+                        .set_window_start_position(row, column);
+                    return true;
+                }
+                i += cells.len();
+            }
+        }
+
+        false
+    }
+
+    fn show_matches(&mut self, rows: &[usize]) {
+        self.search_matches = rows.to_vec();
+    }
+
+    fn update(&mut self, _info: &mut ViewInfo) -> bool {
+        false
+    }
+
+    fn exit(&mut self) -> Option<Value> {
+        // whatever consumes the value (peek or another command) wants the whole table
+        if self.mode == UIMode::View {
+            self.expand_abbreviated();
+        }
+
+        Some(build_last_value(self))
+    }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        if self.pages.is_some() {
+            return vec![
+                KeyHint::new("↑↓", "select"),
+                KeyHint::new("Enter", "go to page"),
+                KeyHint::new("Esc", "close"),
+            ];
+        }
+        if self.menu.is_some() {
+            return vec![
+                KeyHint::new("↑↓", "select"),
+                KeyHint::new("Enter", "do"),
+                KeyHint::new("Esc", "close"),
+            ];
+        }
+        if self.filter_list.is_some() {
+            return vec![
+                KeyHint::new("↑↓", "select"),
+                KeyHint::new("Space", "on/off"),
+                KeyHint::new("d", "remove"),
+                KeyHint::new("Esc", "close"),
+            ];
+        }
+        if self.facets.is_some() {
+            return vec![
+                KeyHint::new("↑↓", "select"),
+                KeyHint::new("Enter", "filter/unfilter"),
+                KeyHint::new("Esc", "close facets"),
+            ];
+        }
+
+        let layer = self.get_top_layer();
+        if layer.pending_replace.is_some() {
+            return vec![
+                KeyHint::new("Enter", "replace"),
+                KeyHint::new("Esc", "cancel"),
+            ];
+        }
+
+        let mut hints = match self.mode {
+            UIMode::View => vec![
+                KeyHint::new("↑↓←→", "scroll"),
+                KeyHint::new("Enter", "select").bound("enter", SELECT),
+                KeyHint::new("Tab", "cursor").bound("tab", SWITCH_MODE),
+                KeyHint::new("t", "transpose").bound("t", "transpose"),
+                KeyHint::new("e", "expand").bound("e", "expand nested data"),
+            ],
+            UIMode::Cursor => {
+                let mut hints = vec![
+                    KeyHint::new("↑↓←→", "move"),
+                    KeyHint::new("Enter", "open").bound("enter", SELECT),
+                    KeyHint::new("Esc", "done").bound("esc", "go back"),
+                    KeyHint::new("Tab", "scroll").bound("tab", SWITCH_MODE),
+                ];
+                hints.push(KeyHint::new("Shift-←→", "scroll cell"));
+                hints.push(KeyHint::new("x", "menu").bound("x", ":menu of the selected cell"));
+                let range = match layer.selection_anchor {
+                    Some(_) => "clear selection",
+                    None => "select range",
+                };
+                hints.push(KeyHint::new("v", range).bound("v", "select a range"));
+                if layer.is_filterable() {
+                    hints.extend([
+                        KeyHint::new("f", "filter")
+                            .bound("f", ":filter by the selected cell's column"),
+                        KeyHint::new("o", "sort")
+                            .bound("o", "sort by the column, again to reverse"),
+                        KeyHint::new("p", "pin").bound("p", "pin the selected row"),
+                        KeyHint::new("P", "freeze")
+                            .bound("P", "freeze the selected row to compare the others to"),
+                        KeyHint::new("m", "mark").bound("m", "mark the selected row"),
+                        KeyHint::new("Alt-←→", "move column"),
+                        KeyHint::new("Alt-↑↓", "move row"),
+                    ]);
+                }
+                hints
+            }
+        };
+
+        if layer.abbreviated.is_some() {
+            hints.push(
+                KeyHint::new("a", "all rows").bound("a", "show all rows of an abbreviated table"),
+            );
+        }
+        if layer.is_filterable() {
+            hints.push(KeyHint::new("F", "facets").bound("F", "facets"));
+        }
+        if !layer.column_names.is_empty() {
+            hints.push(KeyHint::new("c", "column").bound("c", ":col"));
+        }
+
+        hints.push(KeyHint::new("/", "search").bound("/", "search"));
+        hints.push(KeyHint::new(":", "command").bound(":", "type a command"));
+        if self.mode == UIMode::View {
+            let back = if self.layer_stack.len() > 1 {
+                "back"
+            } else {
+                "quit"
+            };
+            hints.push(KeyHint::new("q", back).bound("q", "go back"));
+        }
+
+        hints
+    }
+
+    fn status_segments(&self, segments: &mut StatusSegments) {
+        let layer = self.get_top_layer();
+        match segments.mode() {
+            StatusMode::Selection if layer.selection_anchor.is_some() => {
+                segments.push(&tr!("cells"), self.selected_values().len());
+            }
+            StatusMode::Selection
+                if self.mode == UIMode::Cursor
+                    && layer.count_rows() * layer.count_columns() > 0 =>
+            {
+                segments.push_text(layer.cell_path());
+                segments.push_text(self.get_current_value().get_type().to_string());
+            }
+            StatusMode::Perf => {
+                segments.push(&tr!("rows"), layer.count_rows());
+                segments.push(&tr!("cols"), layer.count_columns());
+            }
+            StatusMode::Source => {
+                segments.push(&tr!("depth"), self.layer_stack.len());
+                if let Some(name) = &layer.name {
+                    segments.push_text(name.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn exit_details(&self, details: &mut Record) {
+        let layer = self.get_top_layer();
+        let span = NuSpan::unknown();
+        let selected = match layer.selection_anchor {
+            Some(_) => self.selected_values().len(),
+            None => layer.marked.len(),
+        };
+
+        details.push("cursor", Value::string(layer.cell_path(), span));
+        details.push("depth", Value::int(self.layer_stack.len() as i64, span));
+        details.push("rows", Value::int(layer.count_rows() as i64, span));
+        let preset = layer.preset();
+        details.push("filters", preset.filters_value(span));
+        let closure_filter = layer.closure_filter.as_ref();
+        details.push(
+            "closure_filter",
+            closure_filter.map_or(Value::nothing(span), |filter| {
+                Value::string(&filter.source, span)
+            }),
+        );
+        details.push("sort", preset.sort_value(span));
+        details.push("selected", Value::int(selected as i64, span));
+    }
+
+    fn title(&self) -> Option<String> {
+        let layer = self.get_top_layer();
+        match (&layer.title, layer.opened_by.as_str()) {
+            (Some(title), _) => Some(title.clone()),
+            (None, "") => None,
+            (None, opened_by) => Some(opened_by.to_string()),
+        }
+    }
+
+    fn as_record_view(&mut self) -> Option<&mut RecordView> {
+        Some(self)
+    }
+
+    fn kind(&self) -> &'static str {
+        "table"
+    }
+
+    fn mode(&self) -> Option<&'static str> {
+        Some(self.mode.name())
+    }
+}
+
+fn build_last_value(v: &RecordView) -> Value {
+    if v.mode == UIMode::Cursor {
+        v.get_current_value().clone()
+    } else if v.get_top_layer().count_rows() < 2 {
+        build_table_as_record(v)
+    } else {
+        build_table_as_list(v)
+    }
+}
+
+fn build_table_as_list(v: &RecordView) -> Value {
+    let layer = v.get_top_layer();
+
+    let vals = layer
+        .record_values
+        .iter()
+        .map(|vals| {
+            let record = layer
+                .column_names
+                .iter()
+                .cloned()
+                .zip(vals.iter().cloned())
+                .collect();
+            Value::record(record, NuSpan::unknown())
+        })
+        .collect();
+
+    Value::list(vals, NuSpan::unknown())
+}
+
+fn build_table_as_record(v: &RecordView) -> Value {
+    let layer = v.get_top_layer();
+
+    let mut record = Record::new();
+    if let Some(row) = layer.record_values.first() {
+        record = layer
+            .column_names
+            .iter()
+            .cloned()
+            .zip(row.iter().cloned())
+            .collect();
+    }
+
+    Value::record(record, NuSpan::unknown())
+}
+
+fn get_element_info(
+    layout: &mut Layout,
+    row: usize,
+    column: usize,
+    count_rows: usize,
+    orientation: Orientation,
+    with_head: bool,
+) -> Option<&ElementInfo> {
+    let with_head = with_head as usize;
+    let index = match orientation {
+        Orientation::Top => column * (count_rows + with_head) + row + 1,
+        Orientation::Left => (column + with_head) * count_rows + row,
+    };
+
+    layout.data.get(index)
+}
+
+/// What a click on the table is on, by its position in the window: the header of a column,
+/// or a cell (row, column).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Clicked {
+    Header(usize),
+    Cell(usize, usize),
+}
+
+// the reverse of get_element_info: the layout has each column in turn, header first, or
+// the headers first for records
+fn clicked_element(
+    index: usize,
+    count_rows: usize,
+    orientation: Orientation,
+    with_head: bool,
+) -> Option<Clicked> {
+    let with_head = with_head as usize;
+    match orientation {
+        Orientation::Top => {
+            let (column, line) = (
+                index / (count_rows + with_head),
+                index % (count_rows + with_head),
+            );
+            Some(match line.checked_sub(with_head) {
+                Some(row) => Clicked::Cell(row, column),
+                None => Clicked::Header(column),
+            })
+        }
+        Orientation::Left if count_rows > 0 => {
+            let (column, row) = (index / count_rows, index % count_rows);
+            Some(match column.checked_sub(with_head) {
+                Some(column) => Clicked::Cell(row, column),
+                None => Clicked::Header(row),
+            })
+        }
+        Orientation::Left => None,
+    }
+}
+
+/// What the mouse drags, pressed on the table.
+#[derive(Debug, Clone)]
+enum Dragging {
+    /// The edge of a header, resizing its column
+    Edge(ColumnDrag),
+    /// A header, moving its column, by its index; released unmoved, it sorts by the column
+    Header { column: usize, moved: bool },
+    /// The minimap, scrolling the table
+    Minimap,
+}
+
+/// A column being resized by dragging the edge of its header.
+#[derive(Debug, Clone)]
+struct ColumnDrag {
+    column: String,
+    // where the drag started, and how wide the column was then
+    x: u16,
+    width: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum UIMode {
+    /// A cell is selected, the keys move it
+    Cursor,
+    /// The keys scroll the table
+    View,
+}
+
+impl UIMode {
+    /// What the mode is called in keybindings.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Cursor => "cursor",
+            Self::View => "view",
+        }
+    }
+
+    /// The mode as shown in the status bar.
+    fn label(self) -> String {
+        match self {
+            Self::Cursor => tr!("CURSOR"),
+            Self::View => tr!("VIEW"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordLayer {
+    pub column_names: Vec<String>,
+    // These are the raw records in the current layer. The sole reason we keep this around is so we can return the original value
+    // if it's being peeked. Otherwise we could accept an iterator over it.
+    // or if it could be Cloneable we could do that anyway;
+    // cause it would keep memory footprint lower while keep everything working
+    // (yee would make return O(n); we would need to traverse iterator once again; but maybe worth it)
+    pub record_values: Vec<Vec<Value>>,
+    // This is the text representation of the record values (the actual text that will be displayed to users).
+    // It's an Option because we need configuration to set it and we (currently) don't have access to configuration when things are created.
+    pub record_text: Option<Vec<Vec<NuText>>>,
+    // The row of the first value of each column which isn't empty, found along with the text
+    first_values: Vec<Option<usize>>,
+    // The percentage of the rows having each column, for the columns some rows don't have,
+    // found along with the text
+    fill: Vec<Option<u8>>,
+    orientation: Orientation,
+    name: Option<String>,
+    was_transposed: bool,
+    pub cursor: WindowCursor2D,
+    abbreviated: Option<HiddenRows>,
+    filters: Vec<RowFilter>,
+    // The rows kept by `:filter {closure}`, on top of `filters`
+    closure_filter: Option<ClosureFilter>,
+    // All the rows as they were before filtering; `None` while there are no filters
+    unfiltered: Option<Vec<Vec<Value>>>,
+    // The place in `unfiltered` of each row shown, while there are filters
+    kept: Vec<usize>,
+    // Copies of rows kept on top of the table while it scrolls, filters or changes order
+    pinned: Vec<Vec<Value>>,
+    pinned_text: Option<Vec<Vec<NuText>>>,
+    // Copy of the row frozen with `P`, shown before the pinned ones; the cells of the row
+    // under the cursor differing from it are highlighted
+    reference: Option<Vec<Value>>,
+    // Copies of the rows marked for `:compare`
+    marked: Vec<Vec<Value>>,
+    // Highlight the fields whose values aren't the same in every row, as `:compare` does
+    highlight_differences: bool,
+    // The names of the columns added by each `:extract`
+    extractions: Vec<Vec<String>>,
+    // The column added for `:tag`, once a row is tagged
+    tag_column: Option<String>,
+    // (row, column) of the corner a range selection was started at with `v`; the cursor is
+    // at the opposite corner
+    selection_anchor: Option<(usize, usize)>,
+    // The `:replace` being previewed, waiting for Enter
+    pending_replace: Option<Replacement>,
+    // Whether cells were changed, with `:replace`
+    edited: bool,
+    // How the layer was opened: the path of the cell drilled into, or the command
+    opened_by: String,
+    // The name given to the page with `:title`
+    title: Option<String>,
+    // The columns whose small tables are shown inline, with `:inline`
+    inline_columns: HashSet<String>,
+    // How the rows are sorted with `:sort`
+    sort: Option<SortOrder>,
+    // The columns left out, by `explore.table.columns` or a preset
+    hidden_columns: Vec<String>,
+    // Whether the columns were put in an order of their own: moved, sorted by name or by a
+    // preset
+    arranged: bool,
+    // Whether the layer is a record turned to fit the terminal, until it's rotated
+    auto_orientation: bool,
+    // The columns renamed with `:rename`, from the names they were read with to the new ones
+    renames: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SortOrder {
+    column: usize,
+    descending: bool,
+    nulls: Nulls,
+}
+
+/// A regex replacement in string cells, see [`RecordView::preview_replace`].
+#[derive(Debug, Clone)]
+struct Replacement {
+    regex: Regex,
+    replacement: String,
+    // `None` for every column
+    column: Option<String>,
+}
+
+impl Replacement {
+    fn column_index(&self, columns: &[String]) -> Result<Option<usize>> {
+        let Some(column) = &self.column else {
+            return Ok(None);
+        };
+
+        match columns.iter().position(|name| name == column) {
+            Some(i) => Ok(Some(i)),
+            None => Err(anyhow::anyhow!(tr!(
+                "There is no column {column}",
+                column = format!("{column:?}")
+            ))),
+        }
+    }
+
+    /// The new text of `value`, if it's a string the replacement changes.
+    fn apply(&self, value: &Value) -> Option<String> {
+        let Value::String { val, .. } = value else {
+            return None;
+        };
+
+        let text = self.regex.replace_all(val, self.replacement.as_str());
+        (text != val.as_str()).then(|| text.into_owned())
+    }
+
+    /// The indexes of the cells of `row` the replacement changes.
+    fn changed_cells(&self, row: &[Value], column: Option<usize>) -> Vec<usize> {
+        row.iter()
+            .enumerate()
+            .filter(|(i, _)| column.is_none_or(|column| column == *i))
+            .filter(|(_, value)| self.apply(value).is_some())
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// Rows cut out of the middle of [`RecordLayer::record_values`] while the layer is abbreviated.
+#[derive(Debug, Clone)]
+struct HiddenRows {
+    /// Index in `record_values` the hidden rows were taken from
+    at: usize,
+    rows: Vec<Vec<Value>>,
+}
+
+#[derive(Debug, Clone)]
+struct RowFilter {
+    column: usize,
+    value: String,
+    // turned off in `:filters`, it's kept but doesn't hide rows
+    enabled: bool,
+}
+
+impl RowFilter {
+    fn matches(&self, row: &[Value]) -> bool {
+        row.get(self.column)
+            .is_some_and(|value| filter_text(value) == self.value)
+    }
+}
+
+/// The rows a closure returned true for; it's run once, so the rows are kept by their places
+/// among all the rows, which move with them when they're sorted, and a row edited since is
+/// still kept.
+#[derive(Debug, Clone)]
+struct ClosureFilter {
+    // the closure as it was typed
+    source: String,
+    // the places of the rows kept, in `unfiltered` or in the rows when there's none
+    rows: HashSet<usize>,
+}
+
+impl RecordLayer {
+    fn new(columns: Vec<String>, records: Vec<Vec<Value>>) -> Self {
+        // TODO: refactor so this is fallible and returns a Result instead of panicking
+        let cursor =
+            WindowCursor2D::new(records.len(), columns.len()).expect("Failed to create cursor");
+
+        let column_names = columns.iter().map(|s| strip_string(s)).collect();
+
+        Self {
+            column_names,
+            record_values: records,
+            record_text: None,
+            first_values: Vec::new(),
+            fill: Vec::new(),
+            cursor,
+            orientation: Orientation::Top,
+            name: None,
+            was_transposed: false,
+            abbreviated: None,
+            filters: Vec::new(),
+            closure_filter: None,
+            unfiltered: None,
+            kept: Vec::new(),
+            pinned: Vec::new(),
+            pinned_text: None,
+            reference: None,
+            marked: Vec::new(),
+            highlight_differences: false,
+            extractions: Vec::new(),
+            tag_column: None,
+            selection_anchor: None,
+            pending_replace: None,
+            edited: false,
+            opened_by: String::new(),
+            title: None,
+            inline_columns: HashSet::new(),
+            sort: None,
+            hidden_columns: Vec::new(),
+            arranged: false,
+            auto_orientation: false,
+            renames: Vec::new(),
+        }
+    }
+
+    fn find_first_values(&mut self) {
+        self.first_values = (0..self.column_names.len())
+            .map(|column| {
+                self.record_values
+                    .iter()
+                    .position(|row| row.get(column).is_some_and(|value| !is_empty_cell(value)))
+            })
+            .collect();
+    }
+
+    fn find_fill(&mut self) {
+        let rows = self.record_values.len();
+        self.fill = (0..self.column_names.len())
+            .map(|column| {
+                let missing = self
+                    .record_values
+                    .iter()
+                    .filter(|row| row.get(column).is_none_or(is_missing_cell))
+                    .count();
+                (missing > 0).then(|| ((rows - missing) * 100 / rows) as u8)
+            })
+            .collect();
+    }
+
+    /// The indexes of the columns every row has, in a table of rows of different shapes.
+    fn common_columns(&self) -> Vec<usize> {
+        (0..self.column_names.len())
+            .filter(|&column| {
+                self.record_values
+                    .iter()
+                    .all(|row| row.get(column).is_some_and(|value| !is_missing_cell(value)))
+            })
+            .collect()
+    }
+
+    /// The text of the first value of each column whose rows in the window are all empty, by
+    /// index, hinting at what it holds without scrolling to it.
+    fn column_samples(&self) -> Vec<Option<String>> {
+        let Some(text) = &self.record_text else {
+            return Vec::new();
+        };
+        let start = self
+            .cursor
+            .window_origin()
+            .row
+            .min(self.record_values.len());
+        let end = (start + self.cursor.y.window_size()).min(self.record_values.len());
+        let window = &self.record_values[start..end];
+        if self.orientation != Orientation::Top || window.is_empty() {
+            return Vec::new();
+        }
+
+        self.first_values
+            .iter()
+            .enumerate()
+            .map(|(column, first)| {
+                let empty = window
+                    .iter()
+                    .all(|row| row.get(column).is_none_or(is_empty_cell));
+                let (sample, _) = text.get((*first)?)?.get(column)?;
+                empty.then(|| sample.replace('\n', " "))
+            })
+            .collect()
+    }
+
+    fn abbreviate(&mut self, count: usize) {
+        let total = self.record_values.len();
+        let is_table = self.orientation == Orientation::Top;
+        let is_filtered = self.unfiltered.is_some();
+        if !is_table
+            || is_filtered
+            || self.abbreviated.is_some()
+            || count == 0
+            || total <= count * 2
+        {
+            return;
+        }
+
+        let rows = self.record_values.drain(count..total - count).collect();
+        self.abbreviated = Some(HiddenRows { at: count, rows });
+        self.record_text = None;
+        self.reset_cursor();
+    }
+
+    fn expand_abbreviated(&mut self) -> bool {
+        let Some(hidden) = self.abbreviated.take() else {
+            return false;
+        };
+
+        let count_hidden = hidden.rows.len();
+        let origin = self.cursor.window_origin();
+        self.record_values.splice(hidden.at..hidden.at, hidden.rows);
+        self.record_text = None;
+
+        let _ = self.cursor.y.view.set_size(self.record_values.len());
+        let row = if origin.row >= hidden.at {
+            origin.row + count_hidden
+        } else {
+            origin.row
+        };
+        self.cursor.set_window_start_position(row, origin.column);
+
+        true
+    }
+
+    // take the rows of `fresh`, read again from the same source; the rows pinned, marked,
+    // tagged or edited were copies of the old ones, so they're let go
+    fn reload(&mut self, mut fresh: RecordLayer) {
+        let origin = self.cursor.window_origin();
+        let relative = self.cursor.window_relative_position();
+
+        for (from, to) in &self.renames {
+            if let Some(name) = fresh.column_names.iter_mut().find(|name| **name == *from) {
+                name.clone_from(to);
+            }
+        }
+        fresh.renames = std::mem::take(&mut self.renames);
+
+        // the columns left out and the order they were put in go by name as well
+        fresh.leave_out_columns(&self.hidden_columns);
+        if self.arranged && !self.was_transposed {
+            fresh.arrange_columns(&self.column_names);
+        }
+        fresh.arranged = self.arranged;
+
+        // filters and sort go by the names of their columns, which may have moved
+        let column = |i: usize| {
+            let name = self.column_names.get(i)?;
+            fresh.column_names.iter().position(|fresh| fresh == name)
+        };
+        let filters: Vec<RowFilter> = self
+            .filters
+            .iter()
+            .filter_map(|filter| {
+                Some(RowFilter {
+                    column: column(filter.column)?,
+                    ..filter.clone()
+                })
+            })
+            .collect();
+        let sort = self
+            .sort
+            .and_then(|sort| Some((self.column_names.get(sort.column)?.clone(), sort)));
+
+        fresh.filters = filters;
+        // the closure isn't run again: it keeps the rows at the places it kept, once they're
+        // sorted the same
+        fresh.closure_filter = self.closure_filter.take();
+        if let Some((column, sort)) = sort {
+            // a column that's gone leaves the rows as they were read
+            let _ = fresh.sort_by(&column, sort.descending, sort.nulls);
+        }
+        fresh.apply_filters();
+
+        fresh.orientation = self.orientation;
+        if self.was_transposed {
+            transpose_table(&mut fresh);
+        }
+        fresh.reset_cursor();
+        fresh
+            .cursor
+            .set_window_start_position(origin.row, origin.column);
+        fresh.cursor.y.next_n(relative.row);
+        fresh.cursor.x.next_n(relative.column);
+
+        fresh.name = self.name.take();
+        fresh.opened_by = std::mem::take(&mut self.opened_by);
+        fresh.title = self.title.take();
+        fresh.inline_columns = std::mem::take(&mut self.inline_columns);
+        fresh.highlight_differences = self.highlight_differences;
+
+        *self = fresh;
+    }
+
+    // add `rows` after the others, if the rows are shown as they were read
+    fn append(&mut self, columns: &[String], rows: Vec<Vec<Value>>) -> bool {
+        let as_read = self.orientation == Orientation::Top
+            && !self.was_transposed
+            && self.column_names == columns
+            && self.unfiltered.is_none()
+            && self.sort.is_none()
+            && self.abbreviated.is_none();
+        if !as_read {
+            return false;
+        }
+
+        let origin = self.cursor.window_origin();
+        let relative = self.cursor.window_relative_position();
+        self.record_values.extend(rows);
+        self.record_text = None;
+        self.reset_cursor();
+        self.cursor
+            .set_window_start_position(origin.row, origin.column);
+        self.cursor.y.next_n(relative.row);
+        self.cursor.x.next_n(relative.column);
+
+        true
+    }
+
+    fn is_filterable(&self) -> bool {
+        self.orientation == Orientation::Top && !self.was_transposed
+    }
+
+    fn add_filter(&mut self, column: &str, value: String) -> Result<usize> {
+        if !self.is_filterable() {
+            return Err(anyhow::anyhow!(tr!("Only tables can be filtered")));
+        }
+
+        let column = self
+            .column_names
+            .iter()
+            .position(|name| name == column)
+            .ok_or_else(|| {
+                anyhow::anyhow!(tr!(
+                    "There is no column {column}",
+                    column = format!("{column:?}")
+                ))
+            })?;
+
+        self.expand_abbreviated();
+
+        let filter = RowFilter {
+            column,
+            value,
+            enabled: true,
+        };
+        let count = self
+            .record_values
+            .iter()
+            .filter(|row| filter.matches(row))
+            .count();
+        if count == 0 {
+            return Err(anyhow::anyhow!(tr!("No rows match the filter")));
+        }
+
+        self.filters.push(filter);
+        self.apply_filters();
+
+        Ok(count)
+    }
+
+    fn remove_filter(&mut self, column: &str, value: &str) -> bool {
+        let Some(column) = self.column_names.iter().position(|name| name == column) else {
+            return false;
+        };
+
+        let count = self.filters.len();
+        self.filters
+            .retain(|filter| filter.column != column || filter.value != value);
+        if self.filters.len() == count {
+            return false;
+        }
+
+        self.apply_filters();
+
+        true
+    }
+
+    fn clear_filters(&mut self) -> bool {
+        if self.filters.is_empty() && self.closure_filter.is_none() {
+            return false;
+        }
+
+        self.filters.clear();
+        self.closure_filter = None;
+        self.apply_filters();
+
+        true
+    }
+
+    fn set_closure_filter(
+        &mut self,
+        source: String,
+        eval: impl FnOnce(&[String], &[Vec<Value>]) -> Result<Vec<bool>>,
+    ) -> Result<usize> {
+        if !self.is_filterable() {
+            return Err(anyhow::anyhow!(tr!("Only tables can be filtered")));
+        }
+
+        self.expand_abbreviated();
+
+        let rows = self.unfiltered.as_ref().unwrap_or(&self.record_values);
+        let kept = eval(&self.column_names, rows)?;
+        let rows: HashSet<usize> = kept
+            .into_iter()
+            .enumerate()
+            .filter(|(_, kept)| *kept)
+            .map(|(row, _)| row)
+            .collect();
+        if rows.is_empty() {
+            return Err(anyhow::anyhow!(tr!("No rows match the filter")));
+        }
+
+        self.closure_filter = Some(ClosureFilter { source, rows });
+        self.apply_filters();
+
+        Ok(self.record_values.len())
+    }
+
+    /// Put a filter of `column` by `value` in place of the one at `index`, keeping its
+    /// place and whether it's on. A filter leaving no rows is rejected.
+    fn replace_filter(&mut self, index: usize, column: &str, value: String) -> Result<usize> {
+        if index >= self.filters.len() {
+            return Err(anyhow::anyhow!(tr!(
+                "There are only {count} filters",
+                count = self.filters.len()
+            )));
+        }
+        let column = self
+            .column_names
+            .iter()
+            .position(|name| name == column)
+            .ok_or_else(|| {
+                anyhow::anyhow!(tr!(
+                    "There is no column {column}",
+                    column = format!("{column:?}")
+                ))
+            })?;
+
+        let filter = RowFilter {
+            column,
+            value,
+            enabled: self.filters[index].enabled,
+        };
+        let previous = std::mem::replace(&mut self.filters[index], filter);
+        self.apply_filters();
+        if self.record_values.is_empty() {
+            self.filters[index] = previous;
+            self.apply_filters();
+            return Err(anyhow::anyhow!(tr!("No rows match the filter")));
+        }
+
+        Ok(self.record_values.len())
+    }
+
+    /// Turn the filter at `index` off, or back on; returns false if there's none there.
+    fn toggle_filter(&mut self, index: usize) -> bool {
+        let Some(filter) = self.filters.get_mut(index) else {
+            return false;
+        };
+
+        filter.enabled = !filter.enabled;
+        self.apply_filters();
+
+        true
+    }
+
+    /// The filters in the order they were added: (column, value, whether it's on).
+    fn filter_list(&self) -> Vec<(String, String, bool)> {
+        self.filters
+            .iter()
+            .map(|filter| {
+                let column = self.column_names[filter.column].clone();
+                (column, filter.value.clone(), filter.enabled)
+            })
+            .collect()
+    }
+
+    fn preset(&self) -> Preset {
+        let filters = self
+            .filters
+            .iter()
+            .filter(|filter| filter.enabled)
+            .map(|filter| {
+                let column = self.column_names[filter.column].clone();
+                (column, filter.value.clone())
+            })
+            .collect();
+        let sort = self.sort.map(|sort| {
+            let column = self.column_names[sort.column].clone();
+            (column, sort.descending)
+        });
+
+        Preset {
+            filters,
+            sort,
+            hidden: self.hidden_columns.clone(),
+            order: self.column_names.clone(),
+        }
+    }
+
+    fn toggle_pin(&mut self, row: usize) -> bool {
+        let Some(values) = self.record_values.get(row) else {
+            return false;
+        };
+
+        match self.pinned.iter().position(|pinned| pinned == values) {
+            Some(i) => {
+                self.pinned.remove(i);
+            }
+            None => self.pinned.push(values.clone()),
+        }
+        self.pinned_text = None;
+
+        true
+    }
+
+    fn toggle_reference(&mut self, row: usize) -> bool {
+        let Some(values) = self.record_values.get(row) else {
+            return false;
+        };
+
+        self.reference = match &self.reference {
+            Some(reference) if reference == values => None,
+            _ => Some(values.clone()),
+        };
+        self.pinned_text = None;
+
+        true
+    }
+
+    /// The columns of `row` whose values aren't those of the frozen row.
+    fn reference_differences(&self, row: usize) -> Vec<usize> {
+        let (Some(reference), Some(values)) = (&self.reference, self.record_values.get(row)) else {
+            return Vec::new();
+        };
+
+        (0..self.column_names.len())
+            .filter(|&i| reference.get(i) != values.get(i))
+            .collect()
+    }
+
+    /// Leave out the columns hidden in `explore.table.columns`, unless none would be left.
+    fn hide_columns(&mut self, columns: &HashMap<String, ColumnOverride>) {
+        let hidden: Vec<String> = self
+            .column_names
+            .iter()
+            .filter(|name| columns.get(*name).is_some_and(|column| column.hidden))
+            .cloned()
+            .collect();
+        self.leave_out_columns(&hidden);
+    }
+
+    /// Leave out the columns of `names` there are, unless none would be left; the rows are
+    /// expected not to be filtered.
+    fn leave_out_columns(&mut self, names: &[String]) {
+        let hidden: Vec<usize> = self
+            .column_names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| names.contains(name))
+            .map(|(i, _)| i)
+            .collect();
+        if hidden.is_empty() || hidden.len() == self.column_names.len() {
+            return;
+        }
+
+        for &i in hidden.iter().rev() {
+            for row in self.all_rows_mut() {
+                if i < row.len() {
+                    row.remove(i);
+                }
+            }
+            if let Some(sort) = self.sort {
+                self.sort = match sort.column.cmp(&i) {
+                    Ordering::Less => Some(sort),
+                    Ordering::Equal => None,
+                    Ordering::Greater => Some(SortOrder {
+                        column: sort.column - 1,
+                        ..sort
+                    }),
+                };
+            }
+        }
+        for &i in &hidden {
+            self.hidden_columns.push(self.column_names[i].clone());
+        }
+        for &i in hidden.iter().rev() {
+            self.column_names.remove(i);
+        }
+        self.record_text = None;
+        self.pinned_text = None;
+        self.reset_cursor();
+    }
+
+    /// Put the columns that look like identifiers first and those of long text last, the
+    /// others keeping their order, for `explore.table.auto_order`.
+    fn order_columns(&mut self) {
+        let ranks: Vec<ColumnRank> = self
+            .column_names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let values = self.record_values.iter().filter_map(|row| row.get(i));
+                ColumnRank::of(name, values.take(ORDER_SAMPLE))
+            })
+            .collect();
+
+        let mut order: Vec<usize> = (0..ranks.len()).collect();
+        order.sort_by_key(|&i| ranks[i]);
+        if order.iter().enumerate().all(|(to, &from)| to == from) {
+            return;
+        }
+
+        self.column_names = order
+            .iter()
+            .map(|&i| self.column_names[i].clone())
+            .collect();
+        for row in &mut self.record_values {
+            if row.len() == order.len() {
+                *row = order.iter().map(|&i| row[i].clone()).collect();
+            }
+        }
+        self.reset_cursor();
+    }
+
+    /// What the minimap marks, by row as shown: the cells holding errors, the marked rows and
+    /// the `matches` of a search, as indexes of `collect_data`.
+    fn minimap_marks(&self, matches: &[usize]) -> Vec<(usize, Mark)> {
+        let columns = self.column_names.len();
+        let shown = |row: usize, column: usize| match self.orientation {
+            Orientation::Top => row,
+            Orientation::Left => column,
+        };
+
+        let mut marks = Vec::new();
+        if columns > 0 {
+            // the headers come first
+            let cells = matches
+                .iter()
+                .filter_map(|index| index.checked_sub(columns));
+            marks.extend(cells.map(|cell| (shown(cell / columns, cell % columns), Mark::Match)));
+        }
+        for (row, values) in self.record_values.iter().enumerate() {
+            if self.orientation == Orientation::Top && self.marked.contains(values) {
+                marks.push((row, Mark::Bookmark));
+            }
+            for (column, value) in values.iter().enumerate() {
+                if let Value::Error { .. } = value {
+                    marks.push((shown(row, column), Mark::Error));
+                }
+            }
+        }
+
+        marks
+    }
+
+    fn toggle_mark(&mut self, row: usize) -> bool {
+        let Some(values) = self.record_values.get(row) else {
+            return false;
+        };
+
+        match self.marked.iter().position(|marked| marked == values) {
+            Some(i) => {
+                self.marked.remove(i);
+            }
+            None => self.marked.push(values.clone()),
+        }
+
+        true
+    }
+
+    /// A layer listing the marked rows side by side, one column each.
+    fn compare_marked(&mut self) -> Result<RecordLayer> {
+        if self.marked.len() < 2 {
+            return Err(anyhow::anyhow!(tr!("Mark at least 2 rows to compare (m)")));
+        }
+
+        let rows = std::mem::take(&mut self.marked);
+        let mut layer = RecordLayer::new(self.column_names.clone(), rows);
+        layer.orientation = Orientation::Left;
+        layer.highlight_differences = true;
+        layer.reset_cursor();
+
+        Ok(layer)
+    }
+
+    /// Indexes of the columns whose values aren't all the same.
+    fn differing_columns(&self) -> Vec<usize> {
+        (0..self.column_names.len())
+            .filter(|&column| {
+                let mut values = self.record_values.iter().map(|row| row.get(column));
+                let first = values.next().flatten();
+                values.any(|value| value != first)
+            })
+            .collect()
+    }
+
+    fn unpin_all(&mut self) -> bool {
+        if self.pinned.is_empty() && self.reference.is_none() {
+            return false;
+        }
+
+        self.pinned.clear();
+        self.reference = None;
+        self.pinned_text = None;
+
+        true
+    }
+
+    fn move_row(&mut self, row: usize, down: bool) -> bool {
+        let target = match down {
+            true => row.checked_add(1),
+            false => row.checked_sub(1),
+        };
+        let Some(target) = target else {
+            return false;
+        };
+
+        // with rows filtered out or hidden, there's no telling where a row would go in the full table
+        let is_whole = self.unfiltered.is_none() && self.abbreviated.is_none();
+        if !self.is_filterable() || !is_whole || target >= self.record_values.len() {
+            return false;
+        }
+
+        self.record_values.swap(row, target);
+        if let Some(text) = &mut self.record_text {
+            text.swap(row, target);
+        }
+
+        true
+    }
+
+    fn move_column(&mut self, column: usize, right: bool) -> bool {
+        let target = match right {
+            true => column.checked_add(1),
+            false => column.checked_sub(1),
+        };
+        let Some(target) = target else {
+            return false;
+        };
+        if !self.is_filterable() || target >= self.column_names.len() {
+            return false;
+        }
+
+        self.column_names.swap(column, target);
+
+        for row in self.all_rows_mut() {
+            if target < row.len() && column < row.len() {
+                row.swap(column, target);
+            }
+        }
+
+        // swapping the cached text is cheaper than formatting everything again
+        let pinned_text = self.pinned_text.iter_mut().flatten();
+        for row in self.record_text.iter_mut().flatten().chain(pinned_text) {
+            if target < row.len() && column < row.len() {
+                row.swap(column, target);
+            }
+        }
+
+        for filter in &mut self.filters {
+            if filter.column == column {
+                filter.column = target;
+            } else if filter.column == target {
+                filter.column = column;
+            }
+        }
+
+        if let Some(sort) = &mut self.sort {
+            if sort.column == column {
+                sort.column = target;
+            } else if sort.column == target {
+                sort.column = column;
+            }
+        }
+        self.arranged = true;
+
+        true
+    }
+
+    /// Put the columns in the order of their names, case ignored, from Z to A if `descending`.
+    fn sort_columns(&mut self, descending: bool) {
+        let mut order: Vec<usize> = (0..self.column_names.len()).collect();
+        order.sort_by_cached_key(|&i| self.column_names[i].to_lowercase());
+        if descending {
+            order.reverse();
+        }
+
+        self.reorder_columns(&order);
+    }
+
+    /// Put the columns of `names` there are first, in that order, the others after them
+    /// keeping theirs.
+    fn arrange_columns(&mut self, names: &[String]) {
+        let mut order: Vec<usize> = names
+            .iter()
+            .filter_map(|name| self.column_names.iter().position(|column| column == name))
+            .collect();
+        let rest: Vec<usize> = (0..self.column_names.len())
+            .filter(|i| !order.contains(i))
+            .collect();
+        order.extend(rest);
+        if order.iter().enumerate().all(|(to, &from)| to == from) {
+            return;
+        }
+
+        self.reorder_columns(&order);
+        self.reset_cursor();
+    }
+
+    /// Put the columns in `order`, the indexes of the columns as they are.
+    fn reorder_columns(&mut self, order: &[usize]) {
+        self.arranged = true;
+        self.column_names = order
+            .iter()
+            .map(|&i| self.column_names[i].clone())
+            .collect();
+        for row in self.all_rows_mut() {
+            if row.len() == order.len() {
+                *row = order.iter().map(|&i| row[i].clone()).collect();
+            }
+        }
+
+        let moved = |column: usize| order.iter().position(|&i| i == column).unwrap_or(column);
+        for filter in &mut self.filters {
+            filter.column = moved(filter.column);
+        }
+        if let Some(sort) = &mut self.sort {
+            sort.column = moved(sort.column);
+        }
+        self.record_text = None;
+        self.pinned_text = None;
+    }
+
+    /// Whether the layer is a record shown a field a line.
+    fn shows_fields(&self) -> bool {
+        self.orientation == Orientation::Left && self.record_values.len() == 1
+    }
+
+    /// The page of `size` lines the window shows and how many there are, for records with
+    /// more fields than fit a page.
+    fn field_page(&self, size: usize) -> Option<(usize, usize)> {
+        let fields = self.column_names.len();
+        if !self.shows_fields() || size == 0 || fields <= size {
+            return None;
+        }
+
+        // a window past the start of a page shows the end of the next one
+        let page = self.cursor.window_origin().row.div_ceil(size) + 1;
+        Some((page, fields.div_ceil(size)))
+    }
+
+    /// Sort the rows by `column`, the rows of equal values keeping their order; the rows
+    /// hidden by filters are sorted along, so they're in order once shown again.
+    fn sort_by(&mut self, column: &str, descending: bool, nulls: Nulls) -> Result<()> {
+        if !self.is_filterable() {
+            return Err(anyhow::anyhow!(tr!("Only tables can be sorted")));
+        }
+
+        let index = self
+            .column_names
+            .iter()
+            .position(|name| name == column)
+            .ok_or_else(|| {
+                anyhow::anyhow!(tr!(
+                    "There is no column {column}",
+                    column = format!("{column:?}")
+                ))
+            })?;
+
+        self.expand_abbreviated();
+
+        let sort = SortOrder {
+            column: index,
+            descending,
+            nulls,
+        };
+        match self.unfiltered.take() {
+            // the rows shown are those kept of all the rows sorted, which the closure filter
+            // follows by their places
+            Some(mut rows) => {
+                let mut order: Vec<usize> = (0..rows.len()).collect();
+                order.sort_by(|&a, &b| sort.compare(&rows[a], &rows[b]));
+                let mut places = vec![0; order.len()];
+                for (to, &from) in order.iter().enumerate() {
+                    places[from] = to;
+                }
+                if let Some(closure) = &mut self.closure_filter {
+                    closure.rows = closure.rows.iter().map(|&row| places[row]).collect();
+                }
+
+                let mut taken: Vec<_> = rows.drain(..).map(Some).collect();
+                rows = order
+                    .iter()
+                    .map(|&from| taken[from].take().unwrap_or_default())
+                    .collect();
+                self.filter_rows(rows);
+            }
+            None => self.record_values.sort_by(|a, b| sort.compare(a, b)),
+        }
+
+        self.sort = Some(sort);
+        self.record_text = None;
+
+        Ok(())
+    }
+
+    fn extract(&mut self, column: &str, regex: &Regex) -> Result<usize> {
+        if !self.is_filterable() {
+            return Err(anyhow::anyhow!(tr!("Only tables can be extracted from")));
+        }
+
+        let column = self
+            .column_names
+            .iter()
+            .position(|name| name == column)
+            .ok_or_else(|| {
+                anyhow::anyhow!(tr!(
+                    "There is no column {column}",
+                    column = format!("{column:?}")
+                ))
+            })?;
+
+        let names: Vec<String> = regex.capture_names().flatten().map(String::from).collect();
+        if names.is_empty() {
+            return Err(anyhow::anyhow!(tr!(
+                "The regex has no named capture groups, like (?<name>...)"
+            )));
+        }
+        if let Some(name) = names.iter().find(|name| self.column_names.contains(name)) {
+            return Err(anyhow::anyhow!(tr!(
+                "There is already a column {name}",
+                name = format!("{name:?}")
+            )));
+        }
+
+        self.expand_abbreviated();
+
+        for row in self.all_rows_mut() {
+            let text = row.get(column).map(filter_text).unwrap_or_default();
+            let captures = regex.captures(&text).ok().flatten();
+            for name in &names {
+                let value = captures
+                    .as_ref()
+                    .and_then(|captures| captures.name(name))
+                    .map_or_else(
+                        || Value::nothing(NuSpan::unknown()),
+                        |capture| Value::string(capture.as_str(), NuSpan::unknown()),
+                    );
+                row.push(value);
+            }
+        }
+
+        self.column_names.extend(names.iter().cloned());
+        self.extractions.push(names);
+        self.record_text = None;
+        self.pinned_text = None;
+        self.reset_cursor();
+
+        let matched = self
+            .record_values
+            .iter()
+            .filter(|row| !self.is_extract_miss(row))
+            .count();
+
+        Ok(matched)
+    }
+
+    fn preview_replace(&mut self, replacement: Replacement) -> Result<usize> {
+        if !self.is_filterable() {
+            return Err(anyhow::anyhow!(tr!(
+                "Only tables can be searched and replaced"
+            )));
+        }
+
+        let column = replacement.column_index(&self.column_names)?;
+        self.expand_abbreviated();
+
+        let count = self.count_replaced(&replacement, column);
+        if count == 0 {
+            return Err(anyhow::anyhow!(tr!(
+                "No cell matches {pattern}",
+                pattern = format!("{:?}", replacement.regex.as_str())
+            )));
+        }
+
+        self.pending_replace = Some(replacement);
+        self.record_text = None;
+
+        Ok(count)
+    }
+
+    // the cells a replacement changes, in the filtered out rows too
+    fn count_replaced(&self, replacement: &Replacement, column: Option<usize>) -> usize {
+        self.unfiltered
+            .as_ref()
+            .unwrap_or(&self.record_values)
+            .iter()
+            .map(|row| replacement.changed_cells(row, column).len())
+            .sum()
+    }
+
+    fn cancel_replace(&mut self) {
+        if self.pending_replace.take().is_some() {
+            self.record_text = None;
+        }
+    }
+
+    /// Replace the cells of the previewed `:replace`; returns how many changed.
+    fn apply_replace(&mut self) -> usize {
+        let Some(replacement) = self.pending_replace.take() else {
+            return 0;
+        };
+        self.record_text = None;
+
+        let Ok(column) = replacement.column_index(&self.column_names) else {
+            return 0;
+        };
+
+        let count = self.count_replaced(&replacement, column);
+        for row in self.all_rows_mut() {
+            for i in replacement.changed_cells(row, column) {
+                if let Some(text) = replacement.apply(&row[i]) {
+                    row[i] = Value::string(text, row[i].span());
+                }
+            }
+        }
+
+        self.pinned_text = None;
+        self.edited |= count > 0;
+
+        count
+    }
+
+    fn split_into_columns(&mut self, column: &str, delimiter: &str) -> Result<usize> {
+        if !self.is_filterable() {
+            return Err(anyhow::anyhow!(tr!("Only tables can be split")));
+        }
+
+        let name = column;
+        let column = self
+            .column_names
+            .iter()
+            .position(|name| name == column)
+            .ok_or_else(|| {
+                anyhow::anyhow!(tr!(
+                    "There is no column {column}",
+                    column = format!("{column:?}")
+                ))
+            })?;
+
+        self.expand_abbreviated();
+
+        let split = |row: &[Value]| {
+            let text = row.get(column).map(filter_text).unwrap_or_default();
+            text.split(delimiter).map(String::from).collect::<Vec<_>>()
+        };
+
+        let count = self.all_rows_mut().map(|row| split(row).len()).max();
+        let count = count.unwrap_or_default();
+        if count < 2 {
+            return Err(anyhow::anyhow!(tr!(
+                "No value of {name} contains {delimiter}",
+                name = format!("{name:?}"),
+                delimiter = format!("{delimiter:?}")
+            )));
+        }
+
+        for row in self.all_rows_mut() {
+            let mut parts = split(row).into_iter();
+            for _ in 0..count {
+                let value = parts.next().map_or_else(
+                    || Value::nothing(NuSpan::unknown()),
+                    |part| Value::string(part, NuSpan::unknown()),
+                );
+                row.push(value);
+            }
+        }
+
+        // `name_1`, `name_2`, ... skipping the names already taken
+        let mut names = Vec::with_capacity(count);
+        let mut i = 0;
+        while names.len() < count {
+            i += 1;
+            let new = format!("{name}_{i}");
+            if !self.column_names.contains(&new) {
+                names.push(new);
+            }
+        }
+
+        self.column_names.extend(names);
+        self.record_text = None;
+        self.pinned_text = None;
+        self.reset_cursor();
+
+        Ok(count)
+    }
+
+    fn rename_column(&mut self, column: &str, name: &str) -> Result<()> {
+        if self.was_transposed {
+            return Err(anyhow::anyhow!(tr!("Transposed columns can't be renamed")));
+        }
+
+        let i = self
+            .column_names
+            .iter()
+            .position(|name| name == column)
+            .ok_or_else(|| {
+                anyhow::anyhow!(tr!(
+                    "There is no column {column}",
+                    column = format!("{column:?}")
+                ))
+            })?;
+        if self.column_names.iter().any(|taken| taken == name) {
+            return Err(anyhow::anyhow!(tr!(
+                "There is already a column {name}",
+                name = format!("{name:?}")
+            )));
+        }
+
+        self.column_names[i] = name.to_string();
+
+        // what goes by the name of the column follows it
+        if self.inline_columns.remove(column) {
+            self.inline_columns.insert(name.to_string());
+        }
+        if self.tag_column.as_deref() == Some(column) {
+            self.tag_column = Some(name.to_string());
+        }
+        for extracted in self.extractions.iter_mut().flatten() {
+            if extracted == column {
+                *extracted = name.to_string();
+            }
+        }
+
+        // renaming a column twice keeps the name it was read with
+        match self.renames.iter_mut().find(|(_, to)| to == column) {
+            Some((_, to)) => *to = name.to_string(),
+            None => self.renames.push((column.to_string(), name.to_string())),
+        }
+
+        Ok(())
+    }
+
+    fn set_tag(&mut self, row: usize, tag: Option<String>) -> Result<()> {
+        if !self.is_filterable() {
+            return Err(anyhow::anyhow!(tr!("Only table rows can be tagged")));
+        }
+        if row >= self.record_values.len() {
+            return Err(anyhow::anyhow!(tr!("There is no row to tag")));
+        }
+
+        let column = self
+            .column_names
+            .iter()
+            .position(|name| Some(name) == self.tag_column.as_ref());
+        let column = match column {
+            Some(column) => column,
+            None => self.add_tag_column(),
+        };
+
+        let value = match tag {
+            Some(tag) => Value::string(tag, NuSpan::unknown()),
+            None => Value::nothing(NuSpan::unknown()),
+        };
+
+        // filtered rows are copies; the tag has to make it to the original too
+        if let Some(unfiltered) = &mut self.unfiltered
+            && let Some(original) = self.kept.get(row).and_then(|&i| unfiltered.get_mut(i))
+        {
+            original[column] = value.clone();
+        }
+
+        self.record_values[row][column] = value;
+        self.record_text = None;
+
+        Ok(())
+    }
+
+    fn toggle_inline(&mut self, column: &str) -> Result<bool> {
+        if !self.column_names.iter().any(|name| name == column) {
+            return Err(anyhow::anyhow!(tr!(
+                "There is no column {column}",
+                column = format!("{column:?}")
+            )));
+        }
+
+        let inline = !self.inline_columns.remove(column);
+        if inline {
+            self.inline_columns.insert(column.to_string());
+        }
+        self.record_text = None;
+        self.pinned_text = None;
+
+        Ok(inline)
+    }
+
+    fn add_tag_column(&mut self) -> usize {
+        let name = (0..)
+            .map(|i| match i {
+                0 => String::from("tag"),
+                i => format!("tag_{i}"),
+            })
+            .find(|name| !self.column_names.contains(name))
+            .expect("there is always a free name");
+
+        for row in self.all_rows_mut() {
+            row.push(Value::nothing(NuSpan::unknown()));
+        }
+
+        self.column_names.push(name.clone());
+        self.tag_column = Some(name);
+        self.pinned_text = None;
+        self.resize_cursor();
+
+        self.column_names.len() - 1
+    }
+
+    // the number of rows or columns changed, but the cursor should stay where it is
+    fn resize_cursor(&mut self) {
+        let origin = self.cursor.window_origin();
+        let relative = self.cursor.window_relative_position();
+        let rows = self.cursor.y.window_size();
+        let columns = self.cursor.x.window_size();
+
+        self.reset_cursor();
+        let _ = self.cursor.set_window_size(rows, columns);
+        self.cursor
+            .set_window_start_position(origin.row, origin.column);
+        self.cursor.y.next_n(relative.row);
+        self.cursor.x.next_n(relative.column);
+    }
+
+    /// Whether the regex of an `:extract` captured nothing from `row`.
+    fn is_extract_miss(&self, row: &[Value]) -> bool {
+        self.extractions.iter().any(|names| {
+            names.iter().all(|name| {
+                self.column_names
+                    .iter()
+                    .position(|column| column == name)
+                    .and_then(|column| row.get(column))
+                    .is_none_or(|value| value.is_nothing())
+            })
+        })
+    }
+
+    /// Every row the layer holds: shown, filtered out, abbreviated, pinned and marked.
+    fn all_rows_mut(&mut self) -> impl Iterator<Item = &mut Vec<Value>> {
+        let hidden = self
+            .abbreviated
+            .iter_mut()
+            .flat_map(|hidden| &mut hidden.rows);
+        let unfiltered = self.unfiltered.iter_mut().flatten();
+
+        self.record_values
+            .iter_mut()
+            .chain(hidden)
+            .chain(unfiltered)
+            .chain(&mut self.pinned)
+            .chain(&mut self.marked)
+            .chain(&mut self.reference)
+    }
+
+    fn apply_filters(&mut self) {
+        let rows = self
+            .unfiltered
+            .take()
+            .unwrap_or_else(|| std::mem::take(&mut self.record_values));
+
+        self.filter_rows(rows);
+        self.reset_cursor();
+    }
+
+    // show those of all the `rows` the filters keep
+    fn filter_rows(&mut self, rows: Vec<Vec<Value>>) {
+        let mut filters = self
+            .filters
+            .iter()
+            .filter(|filter| filter.enabled)
+            .peekable();
+        let closure = self.closure_filter.as_ref();
+        if filters.peek().is_none() && closure.is_none() {
+            self.record_values = rows;
+            self.kept.clear();
+        } else {
+            let filters: Vec<_> = filters.collect();
+            self.kept = (0..rows.len())
+                .filter(|&row| filters.iter().all(|filter| filter.matches(&rows[row])))
+                .filter(|row| closure.is_none_or(|closure| closure.rows.contains(row)))
+                .collect();
+            self.record_values = self.kept.iter().map(|&row| rows[row].clone()).collect();
+            self.unfiltered = Some(rows);
+        }
+
+        self.record_text = None;
+    }
+
+    fn column_values(&self, column: &str, limit: usize) -> Vec<String> {
+        self.column_value_counts(column, limit)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect()
+    }
+
+    // distinct values of `column` and how many rows have them, the most common first
+    fn column_value_counts(&self, column: &str, limit: usize) -> Vec<(String, usize)> {
+        let Some(column) = self.column_names.iter().position(|name| name == column) else {
+            return Vec::new();
+        };
+
+        let hidden = self.abbreviated.iter().flat_map(|hidden| &hidden.rows);
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for row in self.record_values.iter().chain(hidden) {
+            let Some(value) = row.get(column) else {
+                continue;
+            };
+
+            let text = filter_text(value);
+            if let Some(count) = counts.get_mut(&text) {
+                *count += 1;
+            } else if counts.len() < limit {
+                counts.insert(text, 1);
+            }
+        }
+
+        let mut values: Vec<_> = counts.into_iter().collect();
+        values.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+        values
+    }
+
+    fn facets(&self, per_column: usize) -> Vec<Facet> {
+        self.column_names
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let mut values = self.column_value_counts(column, FilterCmd::COMPLETION_LIMIT);
+                values.truncate(per_column);
+
+                let values = values
+                    .into_iter()
+                    .map(|(value, count)| {
+                        let active = self.filters.iter().any(|filter| {
+                            filter.enabled && filter.column == i && filter.value == value
+                        });
+                        (value, count, active)
+                    })
+                    .collect();
+
+                Facet {
+                    column: column.clone(),
+                    values,
+                }
+            })
+            .collect()
+    }
+
+    /// The links of the cells of `rows`, by column, from the templates of `overrides`; none
+    /// when no column has links.
+    fn links(
+        &self,
+        rows: Range<usize>,
+        overrides: &[Option<ColumnOverride>],
+    ) -> Vec<Vec<Option<String>>> {
+        if !overrides
+            .iter()
+            .flatten()
+            .any(|column| column.link.is_some())
+        {
+            return Vec::new();
+        }
+
+        let end = rows.end.min(self.record_values.len());
+        let rows = self.record_values.get(rows.start..end).unwrap_or_default();
+        rows.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(overrides)
+                    .map(|(value, column)| column.as_ref()?.link(value))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn set_name(&mut self, name: impl Into<String>) {
+        self.name = Some(name.into());
+    }
+
+    fn count_rows(&self) -> usize {
+        match self.orientation {
+            Orientation::Top => self.record_values.len(),
+            Orientation::Left => self.column_names.len(),
+        }
+    }
+
+    fn count_columns(&self) -> usize {
+        match self.orientation {
+            Orientation::Top => self.column_names.len(),
+            Orientation::Left => self.record_values.len(),
+        }
+    }
+
+    /// The path of the cell under the cursor, like `3.name`; just the field for a record.
+    fn cell_path(&self) -> String {
+        let Position { row, column } = self.cursor.position();
+        let (row, column) = match self.orientation {
+            Orientation::Top => (row, column),
+            Orientation::Left => (column, row),
+        };
+
+        let name = self.column_names.get(column).map_or("", String::as_str);
+        match (self.orientation, name) {
+            (Orientation::Left, name) if self.record_values.len() == 1 => name.to_string(),
+            (_, "") => row.to_string(),
+            (_, name) => format!("{row}.{name}"),
+        }
+    }
+
+    fn get_column_header(&self) -> Option<String> {
+        let col = self.cursor.column();
+        self.column_names.get(col).map(|header| header.to_string())
+    }
+
+    fn reset_cursor(&mut self) {
+        // TODO: refactor so this is fallible and returns a Result instead of panicking
+        self.cursor = WindowCursor2D::new(self.count_rows(), self.count_columns())
+            .expect("Failed to create cursor");
+    }
+}
+
+impl CursorMoveHandler for RecordView {
+    fn get_cursor(&mut self) -> &mut WindowCursor2D {
+        &mut self.get_top_layer_mut().cursor
+    }
+    fn handle_enter(&mut self) -> Result<Transition> {
+        match self.mode {
+            UIMode::View => self.set_cursor_mode(),
+            UIMode::Cursor => {
+                // custom values are drilled into as what they stand for
+                let value = base_value(self.get_current_value());
+
+                // bytes are read in a binary view, as they're only summed up in their cell
+                if matches!(value.as_ref(), Value::Binary { .. }) {
+                    return Ok(Transition::Cmd(String::from("bytes")));
+                }
+
+                // errors are browsed on a page of their own
+                if matches!(value.as_ref(), Value::Error { .. }) {
+                    return Ok(Transition::Cmd(String::from("error")));
+                }
+
+                // cells cut to the budget, or to their first line, are read in full in a
+                // preview
+                let (size, multiline) = match value.as_ref() {
+                    Value::String { val, .. } => (val.len(), val.contains('\n')),
+                    Value::Binary { val, .. } => (val.len(), false),
+                    _ => (0, false),
+                };
+                if size > self.cfg.cell_budget || multiline {
+                    return Ok(self.handle_expand());
+                }
+
+                // ...but it only makes sense to drill down into a few types of values
+                if !matches!(
+                    value.as_ref(),
+                    Value::Record { .. } | Value::List { .. } | Value::Range { .. }
+                ) {
+                    return Ok(Transition::None);
+                }
+
+                let is_record = matches!(value.as_ref(), Value::Record { .. });
+                let next_layer = create_layer(value.into_owned())?;
+                push_layer(self, next_layer);
+
+                if is_record {
+                    self.set_top_layer_record();
+                } else {
+                    self.set_top_layer_orientation(self.orientation);
+                }
+            }
+        }
+
+        Ok(Transition::Ok)
+    }
+    fn handle_esc(&mut self) -> Transition {
+        match self.mode {
+            UIMode::View => {
+                if self.layer_stack.len() > 1 {
+                    self.layer_stack.pop();
+                    self.mode = UIMode::Cursor;
+                } else {
+                    return Transition::Exit;
+                }
+            }
+            UIMode::Cursor => self.set_view_mode(),
+        }
+        Transition::Ok
+    }
+    fn handle_expand(&mut self) -> Transition {
+        Transition::Cmd(String::from("expand"))
+    }
+    fn handle_transpose(&mut self) -> Transition {
+        match self.mode {
+            UIMode::View => {
+                self.transpose();
+
+                Transition::Ok
+            }
+            _ => Transition::None,
+        }
+    }
+    // for these, copy standard CursorMoveHandler for UIMode::View, but use special handling for UIMode::Cursor
+    // NOTE: https://stackoverflow.com/a/31462293/2016290 says there's plans for Rust to allow calling super functions,
+    // but not yet, and since they're all one line, it seems simpler to copy than make a lot of helper functions
+    fn handle_left(&mut self) {
+        match self.mode {
+            UIMode::View => self.get_top_layer_mut().cursor.prev_column_i(),
+            _ => self.get_top_layer_mut().cursor.prev_column(),
+        }
+    }
+    fn handle_right(&mut self) {
+        match self.mode {
+            UIMode::View => self.get_top_layer_mut().cursor.next_column_i(),
+            _ => self.get_top_layer_mut().cursor.next_column(),
+        }
+    }
+    fn handle_up(&mut self) {
+        match self.mode {
+            UIMode::View => self.get_top_layer_mut().cursor.prev_row_i(),
+            _ => self.get_top_layer_mut().cursor.prev_row(),
+        }
+    }
+    fn handle_down(&mut self) {
+        match self.mode {
+            UIMode::View => self.get_top_layer_mut().cursor.next_row_i(),
+            _ => self.get_top_layer_mut().cursor.next_row(),
+        }
+    }
+}
+
+// the actions of keys hinted in both modes, as the keymap calls them
+const SELECT: &str = "select the cell, then drill into it";
+const SWITCH_MODE: &str = "switch between the cursor and view modes";
+
+// the name of the table of the columns every row has
+const COMMON_COLUMNS: &str = "common-columns";
+
+// rows looked at to rank a column
+const ORDER_SAMPLE: usize = 100;
+
+// strings longer than this on average make a column of long text
+const LONG_TEXT: usize = 40;
+
+/// Where `explore.table.auto_order` puts a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ColumnRank {
+    Key,
+    Other,
+    LongText,
+}
+
+impl ColumnRank {
+    fn of<'a>(name: &str, values: impl Iterator<Item = &'a Value>) -> Self {
+        let name = name.to_lowercase();
+        let is_key = matches!(name.as_str(), "id" | "key" | "name" | "uuid" | "guid")
+            || ["_id", "-id", "_key", "_uuid"]
+                .iter()
+                .any(|suffix| name.ends_with(suffix));
+        if is_key {
+            return Self::Key;
+        }
+
+        let (mut strings, mut length) = (0, 0);
+        for value in values {
+            if let Value::String { val, .. } = value {
+                strings += 1;
+                length += val.chars().count();
+            }
+        }
+        if strings > 0 && length / strings > LONG_TEXT {
+            Self::LongText
+        } else {
+            Self::Other
+        }
+    }
+}
+
+fn create_layer(value: Value) -> Result<RecordLayer> {
+    let (columns, values) = collect_input(value)?;
+    if columns.is_empty() {
+        return Err(anyhow::anyhow!(tr!(
+            "Nothing to explore in empty collections!"
+        )));
+    }
+    Ok(RecordLayer::new(columns, values))
+}
+
+fn push_layer(view: &mut RecordView, mut next_layer: RecordLayer) {
+    next_layer.hide_columns(&view.cfg.columns);
+    if view.cfg.auto_order {
+        next_layer.order_columns();
+    }
+
+    let layer = view.get_top_layer();
+    let header = layer.get_column_header();
+
+    if let Some(header) = header {
+        next_layer.set_name(header);
+    }
+    next_layer.opened_by = layer.cell_path();
+
+    view.layer_stack.push(next_layer);
+    view.auto_tail = false;
+    view.previous_row_count = view.get_top_layer().record_values.len();
+}
+
+/// How a record of `fields`, `width` wide across, is best shown in a table `columns` wide
+/// with `rows` lines: a field a line, unless there are more fields than lines and they fit
+/// across. It's turned from how it's shown only with some room to spare, for resizing not
+/// to flip it back and forth.
+fn record_orientation(
+    fields: usize,
+    width: usize,
+    columns: usize,
+    rows: usize,
+    shown: Orientation,
+) -> Orientation {
+    // the room to spare, in lines and columns
+    const MARGIN: usize = 2;
+
+    let across = match shown {
+        Orientation::Left => fields > rows && width + MARGIN <= columns,
+        Orientation::Top => fields + MARGIN > rows && width <= columns,
+    };
+    match across {
+        true => Orientation::Top,
+        false => Orientation::Left,
+    }
+}
+
+fn estimate_page_size(area: Rect, show_head: bool) -> u16 {
+    let mut available_height = area.height;
+    available_height -= 3; // status_bar
+
+    if show_head {
+        available_height -= 3; // head
+    }
+
+    available_height
+}
+
+/// scroll to the end of the data
+fn tail_data(state: &mut RecordView, page_size: usize) {
+    let layer = state.get_top_layer_mut();
+    let count_rows = layer.record_values.len();
+    if count_rows > page_size {
+        layer
+            .cursor
+            .set_window_start_position(count_rows - page_size, 0);
+    }
+}
+
+/// How the cells of a layer are written.
+struct CellFormat {
+    // the bytes of a string or binary shown
+    budget: usize,
+    // the rows of a small table shown inline
+    inline_rows: usize,
+    // whether the small tables of each column are shown inline
+    inline: Vec<bool>,
+    // how each column is shown, as set in `explore.table.columns`
+    overrides: Vec<Option<ColumnOverride>>,
+}
+
+fn convert_records_to_string(
+    records: &[Vec<Value>],
+    cfg: &Config,
+    style_computer: &StyleComputer,
+    format: &CellFormat,
+) -> Vec<Vec<NuText>> {
+    records
+        .iter()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(column, value)| {
+                    let value = base_value(value);
+                    let value = value.as_ref();
+                    let text = match value {
+                        Value::List { vals, .. } if format.inline.get(column) == Some(&true) => {
+                            inline_table(vals, cfg, format.inline_rows)
+                                .unwrap_or_else(|| cell_text(value, cfg, format.budget))
+                        }
+                        value => cell_text(value, cfg, format.budget),
+                    };
+                    let mut text = strip_string(&text);
+                    let float_precision = cfg.float_precision as usize;
+
+                    let column = format.overrides.get(column).and_then(Option::as_ref);
+                    if let Some(column) = column {
+                        text = column.format(text);
+                    }
+                    let mut cell =
+                        make_styled_string(style_computer, text, Some(value), float_precision);
+                    if let Some(style) = column.and_then(|column| column.style) {
+                        cell.1.color_style = Some(style);
+                    }
+
+                    cell
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+}
+
+// the most bytes of a binary shown in a cell, longer ones are summed up
+const BINARY_CELL: usize = 32;
+
+/// The text of a cell, of at most `budget` bytes of a string or binary: megabytes of them
+/// would be formatted at each layout otherwise, to show a few columns.
+fn cell_text(value: &Value, cfg: &Config, budget: usize) -> String {
+    match value {
+        Value::String { val, .. } => truncate_bytes(val, budget),
+        Value::Binary { val, .. } if val.len() > BINARY_CELL => binary_summary(val),
+        Value::Binary { val, .. } if val.len() > budget => {
+            let start = Value::binary(&val[..budget], value.span());
+            let text = start.to_abbreviated_string(cfg);
+            format!("{text}…{}", left_out(val.len() - budget))
+        }
+        value => value.to_abbreviated_string(cfg),
+    }
+}
+
+/// A binary too long for a cell, as the kind of file it looks like and its size:
+/// `[png 12.3 kB]`; Enter opens it in a binary view.
+fn binary_summary(bytes: &[u8]) -> String {
+    let size = format_size(bytes.len());
+    match guess_kind(bytes) {
+        Some(kind) => format!("[{kind} {size}]"),
+        None => tr!("[binary {size}]", size = size),
+    }
+}
+
+/// A small table on one line, a row after the other, up to `limit` rows:
+/// `name=a size=1 · name=b size=2 · +3 more`.
+///
+/// Lists of anything else than records aren't tables.
+fn inline_table(rows: &[Value], cfg: &Config, limit: usize) -> Option<String> {
+    let records = rows
+        .iter()
+        .map(|row| row.as_record().ok())
+        .collect::<Option<Vec<_>>>()?;
+    if records.is_empty() {
+        return None;
+    }
+
+    let mut parts: Vec<String> = records
+        .iter()
+        .take(limit)
+        .map(|record| {
+            record
+                .iter()
+                .map(|(name, value)| format!("{name}={}", value.to_abbreviated_string(cfg)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    if records.len() > limit {
+        parts.push(tr!("+{count} more", count = records.len() - limit));
+    }
+
+    Some(parts.join(" · "))
+}
+
+fn highlight_selected_cell(f: &mut Frame, info: ElementInfo, cfg: &ExploreConfig) {
+    let cell_style = cfg.selected_cell;
+    let highlight_block = Block::default().style(nu_style_to_tui(cell_style));
+    let area = Rect::new(info.area.x, info.area.y, info.area.width, 1);
+    f.render_widget(highlight_block.clone(), area)
+}
+
+fn report_cursor_position(mode: UIMode, cursor: WindowCursor2D) -> String {
+    if mode == UIMode::Cursor {
+        let Position { row, column } = cursor.position();
+        format!("{row},{column}")
+    } else {
+        let Position { row, column } = cursor.window_origin();
+        format!("{row},{column}")
+    }
+}
+
+fn report_row_position(cursor: WindowCursor2D) -> String {
+    if cursor.window_origin().row == 0 {
+        tr!("Top")
+    } else {
+        let percent_rows = get_percentage(cursor.row(), cursor.row_limit());
+
+        match percent_rows {
+            100 => tr!("All"),
+            value => format!("{value}%"),
+        }
+    }
+}
+
+fn get_percentage(value: usize, max: usize) -> usize {
+    debug_assert!(value <= max, "{value:?} {max:?}");
+
+    ((value as f32 / max as f32) * 100.0).floor() as usize
+}
+
+fn transpose_table(layer: &mut RecordLayer) {
+    if layer.was_transposed {
+        transpose_from(layer);
+    } else {
+        transpose_to(layer);
+    }
+
+    layer.was_transposed = !layer.was_transposed;
+}
+
+fn transpose_from(layer: &mut RecordLayer) {
+    let count_rows = layer.record_values.len();
+    let count_columns = layer.column_names.len();
+
+    // Extract the original column names from the first column
+    let headers = pop_first_column(&mut layer.record_values);
+    let headers = headers
+        .into_iter()
+        .map(|value| match value {
+            Value::String { val, .. } => val,
+            _ => unreachable!("must never happen"),
+        })
+        .collect();
+
+    let data = _transpose_table(&layer.record_values, count_rows, count_columns - 1);
+
+    layer.record_values = data;
+    layer.column_names = headers;
+    // Invalidate the text cache so it gets regenerated with the new structure
+    layer.record_text = None;
+}
+
+fn pop_first_column<T>(values: &mut [Vec<T>]) -> Vec<T>
+where
+    T: Default + Clone,
+{
+    let mut data = vec![T::default(); values.len()];
+    for (row, values) in values.iter_mut().enumerate() {
+        data[row] = values.remove(0);
+    }
+
+    data
+}
+
+fn transpose_to(layer: &mut RecordLayer) {
+    let count_rows = layer.record_values.len();
+    let count_columns = layer.column_names.len();
+
+    let mut data = _transpose_table(&layer.record_values, count_rows, count_columns);
+    for (column, column_name) in layer.column_names.iter().enumerate() {
+        let value = Value::string(column_name, NuSpan::unknown());
+        data[column].insert(0, value);
+    }
+
+    layer.record_values = data;
+    layer.column_names = (1..=count_rows + 1).map(|i| i.to_string()).collect();
+    // Invalidate the text cache so it gets regenerated with the new structure
+    layer.record_text = None;
+}
+
+fn _transpose_table<T>(values: &[Vec<T>], count_rows: usize, count_columns: usize) -> Vec<Vec<T>>
+where
+    T: Clone + Default,
+{
+    let mut data = vec![vec![T::default(); count_rows]; count_columns];
+    for (row, values) in values.iter().enumerate() {
+        for (column, value) in values.iter().enumerate() {
+            data[column][row].clone_from(value);
+        }
+    }
+
+    data
+}
+
+/// Spreadsheet like `sum · avg · min · max · count` of the numbers among `values`.
+fn selection_stats(values: &[&Value]) -> Option<String> {
+    let mut all_ints = true;
+    let numbers: Vec<f64> = values
+        .iter()
+        .filter_map(|value| match value {
+            Value::Int { val, .. } => Some(*val as f64),
+            Value::Float { val, .. } => {
+                all_ints = false;
+                Some(*val)
+            }
+            _ => None,
+        })
+        .collect();
+    if numbers.is_empty() {
+        return None;
+    }
+
+    let count = numbers.len();
+    let sum: f64 = numbers.iter().sum();
+    let min = numbers.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let avg = sum / count as f64;
+
+    let number = |n: f64| match all_ints {
+        true => format!("{}", n as i64),
+        false => format!("{n:.2}"),
+    };
+
+    Some(tr!(
+        "sum {sum} · avg {avg} · min {min} · max {max} · count {count}",
+        sum = number(sum),
+        avg = format!("{avg:.2}"),
+        min = number(min),
+        max = number(max),
+        count = count,
+    ))
+}
+
+/// Whether a cell shows nothing: it's null, an empty string, or missing from its row.
+fn is_empty_cell(value: &Value) -> bool {
+    match value {
+        Value::Nothing { .. } => true,
+        Value::String { val, .. } => val.is_empty() || is_missing_cell(value),
+        _ => false,
+    }
+}
+
+/// What's said once rows are sorted.
+pub fn sort_message(column: &str, descending: bool) -> String {
+    let column = format!("{column:?}");
+    match descending {
+        true => tr!("Sorted by {column}, descending", column = column),
+        false => tr!("Sorted by {column}, ascending", column = column),
+    }
+}
+
+impl SortOrder {
+    /// The order of two rows; their empty cells, nulls or missing from rows of other shapes,
+    /// are all alike and go where `nulls` says.
+    fn compare(&self, a: &[Value], b: &[Value]) -> Ordering {
+        match (self.cell(a), self.cell(b)) {
+            (Some(a), Some(b)) => {
+                let order = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+                if self.descending {
+                    order.reverse()
+                } else {
+                    order
+                }
+            }
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) if self.nulls == Nulls::First => Ordering::Less,
+            (Some(_), None) if self.nulls == Nulls::First => Ordering::Greater,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+        }
+    }
+
+    // the cell sorted by in `row`, unless it's null or missing
+    fn cell<'a>(&self, row: &'a [Value]) -> Option<&'a Value> {
+        row.get(self.column)
+            .filter(|value| !matches!(value, Value::Nothing { .. }) && !is_missing_cell(value))
+    }
+}
+
+/// The text a cell is compared by when filtering on it
+fn filter_text(value: &Value) -> String {
+    strip_string(&value.to_abbreviated_string(&Config::default()))
+}
+
+fn strip_string(text: &str) -> String {
+    String::from_utf8(strip_ansi_escapes::strip(text))
+        .map_err(|_| ())
+        .unwrap_or_else(|_| text.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::{Value, span::Span};
+
+    // Helper to create a simple test Value::Record
+    fn create_test_record() -> Value {
+        let mut record = nu_protocol::Record::new();
+        record.insert(
+            "name".to_string(),
+            Value::string("sample", Span::test_data()),
+        );
+        record.insert("value".to_string(), Value::int(42, Span::test_data()));
+        Value::record(record, Span::test_data())
+    }
+
+    // Helper to create a simple test Value::List
+    fn create_test_list() -> Value {
+        let items = vec![
+            Value::string("item1", Span::test_data()),
+            Value::string("item2", Span::test_data()),
+        ];
+        Value::list(items, Span::test_data())
+    }
+
+    #[test]
+    fn test_create_layer_empty_collection() {
+        // Test with empty record
+        let empty_record = Value::record(nu_protocol::Record::new(), Span::test_data());
+        let result = create_layer(empty_record);
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Nothing to explore in empty collections!"
+        );
+
+        // Test with empty list
+        let empty_list = Value::list(vec![], Span::test_data());
+        let result = create_layer(empty_list);
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Nothing to explore in empty collections!"
+        );
+    }
+
+    #[test]
+    fn test_create_layer_valid_record() {
+        let record = create_test_record();
+        let result = create_layer(record);
+        assert!(result.is_ok());
+
+        let layer = result.unwrap();
+        assert_eq!(layer.column_names, vec!["name", "value"]);
+        assert_eq!(layer.record_values.len(), 1);
+        assert_eq!(layer.record_values[0].len(), 2);
+    }
+
+    #[test]
+    fn test_create_layer_valid_list() {
+        let list = create_test_list();
+        let result = create_layer(list);
+        assert!(result.is_ok());
+
+        let layer = result.unwrap();
+        assert_eq!(layer.column_names, vec![""]);
+        assert_eq!(layer.record_values.len(), 2);
+        assert_eq!(layer.record_values[0].len(), 1);
+    }
+
+    #[test]
+    fn test_transpose_table() {
+        // Create a simple 2x3 table
+        let mut layer = RecordLayer::new(
+            vec!["col1".to_string(), "col2".to_string(), "col3".to_string()],
+            vec![
+                vec![
+                    Value::string("r1c1", Span::test_data()),
+                    Value::string("r1c2", Span::test_data()),
+                    Value::string("r1c3", Span::test_data()),
+                ],
+                vec![
+                    Value::string("r2c1", Span::test_data()),
+                    Value::string("r2c2", Span::test_data()),
+                    Value::string("r2c3", Span::test_data()),
+                ],
+            ],
+        );
+
+        // Before transpose
+        assert_eq!(layer.count_rows(), 2);
+        assert_eq!(layer.count_columns(), 3);
+        assert!(!layer.was_transposed);
+
+        // After transpose
+        transpose_table(&mut layer);
+        assert_eq!(layer.count_rows(), 3);
+        assert_eq!(layer.count_columns(), 3); // Now includes header row
+        assert!(layer.was_transposed);
+
+        // After transpose back
+        transpose_table(&mut layer);
+        assert_eq!(layer.count_rows(), 2);
+        assert_eq!(layer.count_columns(), 3);
+        assert!(!layer.was_transposed);
+    }
+
+    #[test]
+    fn test_abbreviate_and_expand() {
+        let rows = (0..10)
+            .map(|i| vec![Value::int(i, Span::test_data())])
+            .collect();
+        let mut layer = RecordLayer::new(vec!["n".to_string()], rows);
+
+        layer.abbreviate(2);
+        let shown: Vec<_> = layer
+            .record_values
+            .iter()
+            .map(|row| row[0].as_int().unwrap())
+            .collect();
+        assert_eq!(shown, vec![0, 1, 8, 9]);
+        assert_eq!(layer.abbreviated.as_ref().map(|h| h.rows.len()), Some(6));
+
+        assert!(layer.expand_abbreviated());
+        assert_eq!(layer.record_values.len(), 10);
+        assert_eq!(layer.record_values[2][0].as_int().unwrap(), 2);
+        assert!(!layer.expand_abbreviated());
+    }
+
+    #[test]
+    fn test_abbreviate_small_table_is_noop() {
+        let rows = (0..4)
+            .map(|i| vec![Value::int(i, Span::test_data())])
+            .collect();
+        let mut layer = RecordLayer::new(vec!["n".to_string()], rows);
+
+        layer.abbreviate(2);
+        assert!(layer.abbreviated.is_none());
+        assert_eq!(layer.record_values.len(), 4);
+    }
+
+    #[test]
+    fn test_filter_and_clear() {
+        let rows = ["a", "b", "a", "c"]
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                vec![
+                    Value::int(i as i64, Span::test_data()),
+                    Value::string(*s, Span::test_data()),
+                ]
+            })
+            .collect();
+        let mut layer = RecordLayer::new(vec!["n".to_string(), "s".to_string()], rows);
+
+        assert_eq!(layer.column_values("s", 10), vec!["a", "b", "c"]);
+        assert_eq!(layer.column_values("s", 2), vec!["a", "b"]);
+
+        assert_eq!(layer.add_filter("s", "a".to_string()).unwrap(), 2);
+        assert_eq!(layer.record_values.len(), 2);
+        assert!(layer.add_filter("s", "b".to_string()).is_err());
+        assert!(layer.add_filter("missing", "a".to_string()).is_err());
+
+        assert!(layer.clear_filters());
+        assert_eq!(layer.record_values.len(), 4);
+        assert!(!layer.clear_filters());
+    }
+
+    #[test]
+    fn test_move_column() {
+        let rows = vec![
+            vec![
+                Value::int(1, Span::test_data()),
+                Value::string("a", Span::test_data()),
+            ],
+            vec![
+                Value::int(2, Span::test_data()),
+                Value::string("b", Span::test_data()),
+            ],
+        ];
+        let mut layer = RecordLayer::new(vec!["n".to_string(), "s".to_string()], rows);
+        layer.add_filter("s", "b".to_string()).unwrap();
+
+        assert!(!layer.move_column(0, false));
+        assert!(!layer.move_column(1, true));
+        assert!(layer.move_column(0, true));
+
+        assert_eq!(layer.column_names, vec!["s", "n"]);
+        assert_eq!(layer.record_values[0][0].as_str().unwrap(), "b");
+        assert_eq!(
+            layer.preset().filters,
+            vec![("s".to_string(), "b".to_string())]
+        );
+
+        assert!(layer.clear_filters());
+        assert_eq!(layer.record_values[0][1].as_int().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_pin_and_move_rows() {
+        let rows = (0..3)
+            .map(|i| vec![Value::int(i, Span::test_data())])
+            .collect();
+        let mut layer = RecordLayer::new(vec!["n".to_string()], rows);
+
+        assert!(layer.toggle_pin(2));
+        assert!(layer.toggle_pin(0));
+        assert_eq!(layer.pinned.len(), 2);
+        assert!(layer.toggle_pin(2));
+        assert_eq!(layer.pinned.len(), 1);
+        assert_eq!(layer.pinned[0][0].as_int().unwrap(), 0);
+
+        assert!(!layer.move_row(0, false));
+        assert!(layer.move_row(0, true));
+        assert_eq!(layer.record_values[1][0].as_int().unwrap(), 0);
+        assert_eq!(layer.pinned[0][0].as_int().unwrap(), 0);
+
+        layer.add_filter("n", "1".to_string()).unwrap();
+        assert!(!layer.move_row(0, true));
+
+        assert!(layer.unpin_all());
+        assert!(!layer.unpin_all());
+    }
+
+    #[test]
+    fn test_differences_from_the_frozen_row() {
+        let row = |n, s| {
+            vec![
+                Value::int(n, Span::test_data()),
+                Value::string(s, Span::test_data()),
+            ]
+        };
+        let rows = vec![row(1, "a"), row(1, "b"), row(2, "b")];
+        let mut layer = RecordLayer::new(vec!["n".to_string(), "s".to_string()], rows);
+        assert!(layer.reference_differences(1).is_empty());
+
+        assert!(layer.toggle_reference(0));
+        assert!(layer.reference_differences(0).is_empty());
+        assert_eq!(layer.reference_differences(1), vec![1]);
+        assert_eq!(layer.reference_differences(2), vec![0, 1]);
+
+        // the frozen row stays as it was when the table changes order
+        assert!(layer.move_row(0, true));
+        assert_eq!(layer.reference_differences(1), Vec::<usize>::new());
+
+        assert!(layer.toggle_reference(1));
+        assert!(layer.reference.is_none());
+        assert!(layer.toggle_reference(2));
+        assert!(layer.unpin_all());
+        assert!(layer.reference.is_none());
+    }
+
+    #[test]
+    fn test_compare_marked() {
+        let row = |n, s| {
+            vec![
+                Value::int(n, Span::test_data()),
+                Value::string(s, Span::test_data()),
+            ]
+        };
+        let rows = vec![row(1, "a"), row(2, "a"), row(3, "b")];
+        let mut layer = RecordLayer::new(vec!["n".to_string(), "s".to_string()], rows);
+
+        assert!(layer.toggle_mark(0));
+        assert!(layer.compare_marked().is_err());
+        assert!(layer.toggle_mark(1));
+
+        let compare = layer.compare_marked().unwrap();
+        assert!(layer.marked.is_empty());
+        assert_eq!(compare.orientation, Orientation::Left);
+        assert_eq!(compare.count_rows(), 2);
+        assert_eq!(compare.count_columns(), 2);
+        assert_eq!(compare.differing_columns(), vec![0]);
+    }
+
+    #[test]
+    fn test_extract() {
+        let rows = ["v1.2", "nightly", "v3.0"]
+            .iter()
+            .map(|s| vec![Value::string(*s, Span::test_data())])
+            .collect();
+        let mut layer = RecordLayer::new(vec!["tag".to_string()], rows);
+        let regex = Regex::new(r"v(?<major>\d+)\.(?<minor>\d+)").unwrap();
+
+        assert_eq!(layer.extract("tag", &regex).unwrap(), 2);
+        assert_eq!(layer.column_names, vec!["tag", "major", "minor"]);
+        assert_eq!(layer.record_values[0][1].as_str().unwrap(), "1");
+        assert!(layer.record_values[1][2].is_nothing());
+        assert!(layer.is_extract_miss(&layer.record_values[1]));
+        assert!(!layer.is_extract_miss(&layer.record_values[2]));
+
+        assert!(layer.extract("tag", &regex).is_err());
+        assert!(layer.extract("tag", &Regex::new("v").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_split_into_columns() {
+        let rows = ["a,b", "c", "d,e,f"]
+            .iter()
+            .map(|s| {
+                vec![
+                    Value::string(*s, Span::test_data()),
+                    Value::nothing(Span::test_data()),
+                ]
+            })
+            .collect();
+        let mut layer = RecordLayer::new(vec!["x".to_string(), "x_1".to_string()], rows);
+
+        assert!(layer.split_into_columns("x", ";").is_err());
+        assert_eq!(layer.split_into_columns("x", ",").unwrap(), 3);
+        assert_eq!(layer.column_names, vec!["x", "x_1", "x_2", "x_3", "x_4"]);
+        assert_eq!(layer.record_values[0][3].as_str().unwrap(), "b");
+        assert!(layer.record_values[1][3].is_nothing());
+        assert_eq!(layer.record_values[2][4].as_str().unwrap(), "f");
+    }
+
+    #[test]
+    fn test_inline_tables() {
+        let span = Span::test_data();
+        let file = |name: &str, size: i64| {
+            let mut record = nu_protocol::Record::new();
+            record.insert("name", Value::string(name, span));
+            record.insert("size", Value::int(size, span));
+            Value::record(record, span)
+        };
+        let files = Value::list(vec![file("a", 1), file("b", 2), file("c", 3)], span);
+
+        let cfg = Config::default();
+        assert_eq!(
+            inline_table(files.as_list().unwrap(), &cfg, 2).unwrap(),
+            "name=a size=1 · name=b size=2 · +1 more"
+        );
+        assert_eq!(inline_table(&[Value::int(1, span)], &cfg, 2), None);
+
+        let mut layer = RecordLayer::new(vec!["files".to_string()], vec![vec![files]]);
+        assert!(layer.toggle_inline("files").unwrap());
+        assert!(layer.inline_columns.contains("files"));
+        assert!(!layer.toggle_inline("files").unwrap());
+        assert!(layer.toggle_inline("folders").is_err());
+    }
+
+    #[test]
+    fn test_links_of_a_column() {
+        let span = Span::test_data();
+        let issue = ColumnOverride {
+            link: Some(String::from("https://tracker/issue/{value}")),
+            ..Default::default()
+        };
+        let row = |n: i64| vec![Value::string("x", span), Value::int(n, span)];
+        let columns = vec!["title".to_string(), "issue".to_string()];
+        let mut config = ExploreConfig::default();
+        config.columns.insert("issue".to_string(), issue.clone());
+        let mut view = RecordView::new(columns, vec![row(1), row(2), row(3)], config);
+
+        let layer = view.get_top_layer();
+        let links = layer.links(1..5, &[None, Some(issue)]);
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0][0], None);
+        assert_eq!(links[1][1].as_deref(), Some("https://tracker/issue/3"));
+        assert!(layer.links(0..3, &[None, None]).is_empty());
+
+        view.set_cursor_mode();
+        assert_eq!(view.current_link(), None);
+        view.go_to_column("issue");
+        assert_eq!(
+            view.current_link().as_deref(),
+            Some("https://tracker/issue/1")
+        );
+    }
+
+    #[test]
+    fn test_hidden_columns() {
+        let span = Span::test_data();
+        let hidden = ColumnOverride {
+            hidden: true,
+            ..Default::default()
+        };
+        let columns = HashMap::from([("secret".to_string(), hidden)]);
+        let row = |n: i64| vec![Value::int(n, span), Value::string("x", span)];
+
+        let mut layer = RecordLayer::new(
+            vec!["n".to_string(), "secret".to_string()],
+            vec![row(1), row(2)],
+        );
+        layer.hide_columns(&columns);
+        assert_eq!(layer.column_names, ["n"]);
+        assert_eq!(layer.record_values[1], [Value::int(2, span)]);
+
+        // a table of hidden columns alone is shown as it is
+        let mut layer =
+            RecordLayer::new(vec!["secret".to_string()], vec![vec![Value::int(1, span)]]);
+        layer.hide_columns(&columns);
+        assert_eq!(layer.column_names, ["secret"]);
+    }
+
+    #[test]
+    fn test_preset_of_the_table() {
+        let span = Span::test_data();
+        let hidden = ColumnOverride {
+            hidden: true,
+            ..Default::default()
+        };
+        let config = ExploreConfig {
+            columns: HashMap::from([("secret".to_string(), hidden)]),
+            ..ExploreConfig::default()
+        };
+        let columns = ["n", "s", "secret", "t"].map(String::from).to_vec();
+        let row = |n: i64, s: &str| {
+            vec![
+                Value::int(n, span),
+                Value::string(s, span),
+                Value::string("x", span),
+                Value::bool(n > 1, span),
+            ]
+        };
+        let rows = || vec![row(1, "a"), row(3, "b"), row(2, "a")];
+
+        let mut view = RecordView::new(columns.clone(), rows(), config);
+        assert!(view.move_column(true));
+        view.sort_by("n", Some(true), None).unwrap();
+        view.add_filter("s", "a".to_string()).unwrap();
+        let preset = view.preset();
+        assert_eq!(preset.order, ["s", "n", "t"]);
+        assert_eq!(preset.hidden, ["secret"]);
+        assert_eq!(preset.sort, Some(("n".to_string(), true)));
+        assert_eq!(preset.filters, [("s".to_string(), "a".to_string())]);
+
+        // on a table of the same columns, none hidden by the config
+        let mut view = RecordView::new(columns, rows(), ExploreConfig::default());
+        view.apply_preset(&preset).unwrap();
+        let layer = view.get_top_layer();
+        assert_eq!(layer.column_names, preset.order);
+        let n: Vec<_> = layer
+            .record_values
+            .iter()
+            .map(|row| row[1].clone())
+            .collect();
+        assert_eq!(n, [Value::int(2, span), Value::int(1, span)]);
+    }
+
+    #[test]
+    fn test_order_columns() {
+        let span = Span::test_data();
+        let long = "a description far too long to be worth reading in a table".repeat(2);
+        let row = |n: i64| {
+            vec![
+                Value::string(&long, span),
+                Value::int(n, span),
+                Value::string("x", span),
+                Value::int(n, span),
+            ]
+        };
+        let columns = ["description", "size", "kind", "user_id"].map(String::from);
+
+        let mut layer = RecordLayer::new(columns.to_vec(), vec![row(1), row(2)]);
+        layer.order_columns();
+        assert_eq!(
+            layer.column_names,
+            ["user_id", "size", "kind", "description"]
+        );
+        assert_eq!(layer.record_values[1][0], Value::int(2, span));
+        assert_eq!(layer.record_values[1][3], Value::string(&long, span));
+    }
+
+    #[test]
+    fn test_toggle_and_replace_filters() {
+        let span = Span::test_data();
+        let rows = [("a", 1), ("b", 1), ("a", 2), ("a", 1)]
+            .iter()
+            .map(|(s, n)| vec![Value::string(*s, span), Value::int(*n, span)])
+            .collect();
+        let mut layer = RecordLayer::new(vec!["s".to_string(), "n".to_string()], rows);
+
+        layer.add_filter("s", "a".to_string()).unwrap();
+        layer.add_filter("n", "1".to_string()).unwrap();
+        assert_eq!(layer.record_values.len(), 2);
+
+        // off, the filter is kept but the rows it hid are back, in their order
+        assert!(layer.toggle_filter(0));
+        assert_eq!(layer.record_values.len(), 3);
+        assert_eq!(layer.record_values[1][0], Value::string("b", span));
+        assert_eq!(layer.preset().filters.len(), 1);
+
+        assert!(layer.toggle_filter(1));
+        assert!(layer.unfiltered.is_none());
+        assert_eq!(layer.filter_list()[1], ("n".into(), "1".into(), false));
+
+        assert!(layer.toggle_filter(0));
+        assert_eq!(layer.replace_filter(0, "s", "b".to_string()).unwrap(), 1);
+        assert!(layer.replace_filter(0, "s", "c".to_string()).is_err());
+        assert_eq!(layer.filter_list()[0], ("s".into(), "b".into(), true));
+        assert!(!layer.toggle_filter(2));
+    }
+
+    #[test]
+    fn test_closure_filter() {
+        let span = Span::test_data();
+        let rows = [1, 2, 3, 4].map(|n| vec![Value::int(n, span)]);
+        let mut view =
+            RecordView::new(vec!["n".to_string()], rows.into(), ExploreConfig::default());
+        let even = |columns: &[String], rows: &[Vec<Value>]| {
+            assert_eq!(columns, ["n"]);
+            Ok(rows
+                .iter()
+                .map(|row| row[0].as_int().unwrap() % 2 == 0)
+                .collect())
+        };
+
+        assert_eq!(
+            view.set_closure_filter("{ even }".to_string(), even)
+                .unwrap(),
+            2
+        );
+        assert_eq!(view.closure_filter(), Some("{ even }"));
+
+        // it's run on the rows filtered out by value too, and kept once they're cleared
+        view.add_filter("n", "4".to_string()).unwrap();
+        let count = view.set_closure_filter("{ all }".to_string(), |_, rows| {
+            assert_eq!(rows.len(), 4);
+            Ok(vec![true; rows.len()])
+        });
+        assert_eq!(count.unwrap(), 1);
+
+        assert!(
+            view.set_closure_filter("{ none }".to_string(), |_, rows| Ok(vec![
+                false;
+                rows.len()
+            ]))
+            .is_err()
+        );
+        assert_eq!(view.closure_filter(), Some("{ all }"));
+
+        assert!(view.clear_filters());
+        assert_eq!(view.closure_filter(), None);
+        assert_eq!(view.get_top_layer().record_values.len(), 4);
+    }
+
+    #[test]
+    fn test_closure_filter_keeps_edited_rows() {
+        let span = Span::test_data();
+        let rows = [3, 1, 2, 4].map(|n| vec![Value::int(n, span)]);
+        let mut view =
+            RecordView::new(vec!["n".to_string()], rows.into(), ExploreConfig::default());
+        let even = |_: &[String], rows: &[Vec<Value>]| {
+            Ok(rows
+                .iter()
+                .map(|row| row[0].as_int().unwrap() % 2 == 0)
+                .collect())
+        };
+        view.set_closure_filter("{ even }".to_string(), even)
+            .unwrap();
+
+        // tagged, the row is still the one the closure kept, sorted or filtered again
+        let layer = view.get_top_layer_mut();
+        layer.set_tag(0, Some("two".to_string())).unwrap();
+        view.sort_by("n", Some(true), None).unwrap();
+        let shown = |view: &RecordView| -> Vec<(i64, Option<String>)> {
+            let rows = &view.get_top_layer().record_values;
+            rows.iter()
+                .map(|row| {
+                    (
+                        row[0].as_int().unwrap(),
+                        row[1].as_str().ok().map(String::from),
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(shown(&view), [(4, None), (2, Some("two".to_string()))]);
+
+        view.add_filter("tag", "two".to_string()).unwrap();
+        assert_eq!(shown(&view), [(2, Some("two".to_string()))]);
+    }
+
+    #[test]
+    fn test_closure_filter_without_eval() {
+        let span = Span::test_data();
+        let config = ExploreConfig {
+            no_eval: true,
+            ..ExploreConfig::default()
+        };
+        let rows = [1, 2].map(|n| vec![Value::int(n, span)]);
+        let mut view = RecordView::new(vec!["n".to_string()], rows.into(), config);
+
+        let result = view.set_closure_filter("{ true }".to_string(), |_, _| {
+            panic!("the closure must not run")
+        });
+        assert!(result.is_err());
+        assert_eq!(view.get_top_layer().record_values.len(), 2);
+    }
+
+    #[test]
+    fn test_exit_details_tell_the_sort_and_filters() {
+        let span = Span::test_data();
+        let rows = [3, 1, 2].map(|n| vec![Value::int(n, span)]);
+        let mut view =
+            RecordView::new(vec!["n".to_string()], rows.into(), ExploreConfig::default());
+        view.sort_by("n", None, None).unwrap();
+        view.set_closure_filter("{ $in.n > 1 }".to_string(), |_, rows| {
+            Ok(rows
+                .iter()
+                .map(|row| row[0] != Value::int(1, span))
+                .collect())
+        })
+        .unwrap();
+
+        let mut details = Record::new();
+        view.exit_details(&mut details);
+        let span = NuSpan::unknown();
+        assert_eq!(details.get("rows"), Some(&Value::int(2, span)));
+        assert_eq!(
+            details.get("closure_filter"),
+            Some(&Value::string("{ $in.n > 1 }", span))
+        );
+        let sort = details.get("sort").unwrap().as_record().unwrap();
+        assert_eq!(sort.get("column"), Some(&Value::string("n", span)));
+        assert_eq!(sort.get("descending"), Some(&Value::bool(false, span)));
+    }
+
+    #[test]
+    fn test_sort() {
+        let span = Span::test_data();
+        let row = |n: i64, s: &str| vec![Value::int(n, span), Value::string(s, span)];
+        let rows = vec![row(2, "a"), row(1, "b"), row(2, "c"), row(3, "a")];
+        let mut view = RecordView::new(
+            vec!["n".to_string(), "s".to_string()],
+            rows,
+            ExploreConfig::default(),
+        );
+        let column = |view: &RecordView, i: usize| -> Vec<Value> {
+            let layer = view.get_top_layer();
+            layer
+                .record_values
+                .iter()
+                .map(|row| row[i].clone())
+                .collect()
+        };
+
+        // toggled: ascending first, equal values keeping their order
+        assert!(!view.sort_by("n", None, None).unwrap());
+        assert_eq!(
+            column(&view, 1),
+            ["b", "a", "c", "a"].map(|s| Value::string(s, span))
+        );
+        assert!(view.sort_by("n", None, None).unwrap());
+        assert_eq!(column(&view, 0)[0], Value::int(3, span));
+
+        // sorted rows hidden by a filter are in order once it's cleared
+        view.add_filter("s", "a".to_string()).unwrap();
+        assert!(!view.sort_by("n", Some(false), None).unwrap());
+        view.clear_filters();
+        assert_eq!(column(&view, 0), [1, 2, 2, 3].map(|n| Value::int(n, span)));
+
+        assert!(view.sort_by("size", None, None).is_err());
+    }
+
+    #[test]
+    fn test_sort_nulls() {
+        let span = Span::test_data();
+        let missing = Value::string("❎", NuSpan::unknown());
+        let rows = [
+            Value::int(2, span),
+            Value::nothing(span),
+            Value::int(1, span),
+            missing.clone(),
+        ]
+        .map(|value| vec![value]);
+        let mut view =
+            RecordView::new(vec!["n".to_string()], rows.into(), ExploreConfig::default());
+        let column = |view: &RecordView| -> Vec<Value> {
+            let layer = view.get_top_layer();
+            layer
+                .record_values
+                .iter()
+                .map(|row| row[0].clone())
+                .collect()
+        };
+
+        // last by default, whichever the order, nulls and missing cells alike
+        view.sort_by("n", Some(false), None).unwrap();
+        assert_eq!(
+            column(&view),
+            [
+                Value::int(1, span),
+                Value::int(2, span),
+                Value::nothing(span),
+                missing.clone()
+            ]
+        );
+        view.sort_by("n", Some(true), None).unwrap();
+        assert_eq!(
+            column(&view),
+            [
+                Value::int(2, span),
+                Value::int(1, span),
+                Value::nothing(span),
+                missing.clone()
+            ]
+        );
+
+        // first once asked, which reversing the order keeps
+        view.sort_by("n", Some(false), Some(Nulls::First)).unwrap();
+        view.sort_by("n", None, None).unwrap();
+        assert_eq!(
+            column(&view),
+            [
+                Value::nothing(span),
+                missing,
+                Value::int(2, span),
+                Value::int(1, span)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_facets() {
+        let rows = ["a", "b", "a"]
+            .iter()
+            .map(|s| vec![Value::string(*s, Span::test_data())])
+            .collect();
+        let mut layer = RecordLayer::new(vec!["s".to_string()], rows);
+
+        layer.add_filter("s", "a".to_string()).unwrap();
+        let facets = layer.facets(5);
+        assert_eq!(facets[0].values, vec![("a".to_string(), 2, true)]);
+
+        assert!(!layer.remove_filter("s", "b"));
+        assert!(layer.remove_filter("s", "a"));
+        assert_eq!(layer.record_values.len(), 3);
+
+        let facets = layer.facets(1);
+        assert_eq!(facets[0].values, vec![("a".to_string(), 2, false)]);
+    }
+
+    #[test]
+    fn test_tags_survive_filters() {
+        let rows = ["a", "b", "a"]
+            .iter()
+            .map(|s| {
+                vec![
+                    Value::string(*s, Span::test_data()),
+                    Value::nothing(Span::test_data()),
+                ]
+            })
+            .collect();
+        let mut layer = RecordLayer::new(vec!["s".to_string(), "tag".to_string()], rows);
+
+        layer.add_filter("s", "b".to_string()).unwrap();
+        layer.set_tag(0, Some("keep".to_string())).unwrap();
+        assert_eq!(layer.column_names, vec!["s", "tag", "tag_1"]);
+        assert_eq!(layer.record_values[0][2].as_str().unwrap(), "keep");
+
+        layer.clear_filters();
+        assert_eq!(layer.record_values[1][2].as_str().unwrap(), "keep");
+        assert!(layer.record_values[0][2].is_nothing());
+        assert_eq!(layer.add_filter("tag_1", "keep".to_string()).unwrap(), 1);
+
+        layer.set_tag(0, None).unwrap();
+        assert!(layer.record_values[0][2].is_nothing());
+        assert!(layer.set_tag(1, None).is_err());
+
+        // of rows alike, the one tagged is the one shown there
+        layer.clear_filters();
+        layer.add_filter("s", "a".to_string()).unwrap();
+        layer.set_tag(1, Some("second".to_string())).unwrap();
+        layer.clear_filters();
+        assert!(layer.record_values[0][2].is_nothing());
+        assert_eq!(layer.record_values[2][2].as_str().unwrap(), "second");
+    }
+
+    #[test]
+    fn test_replace_after_preview() {
+        let span = Span::test_data();
+        let rows = vec![
+            vec![Value::string("a-1", span), Value::string("x-1", span)],
+            vec![Value::string("b-2", span), Value::int(1, span)],
+        ];
+        let mut layer = RecordLayer::new(vec!["s".to_string(), "t".to_string()], rows);
+        let replacement = |column: Option<&str>| Replacement {
+            regex: Regex::new(r"-(\d)").unwrap(),
+            replacement: String::from("#$1"),
+            column: column.map(String::from),
+        };
+
+        assert!(layer.preview_replace(replacement(Some("u"))).is_err());
+        assert_eq!(layer.preview_replace(replacement(None)).unwrap(), 3);
+        layer.cancel_replace();
+        assert_eq!(layer.apply_replace(), 0);
+        assert!(!layer.edited);
+
+        layer.add_filter("s", "b-2".to_string()).unwrap();
+        assert_eq!(layer.preview_replace(replacement(Some("s"))).unwrap(), 2);
+        assert_eq!(layer.apply_replace(), 2);
+        assert!(layer.edited);
+
+        layer.clear_filters();
+        assert_eq!(layer.record_values[0][0].as_str().unwrap(), "a#1");
+        assert_eq!(layer.record_values[0][1].as_str().unwrap(), "x-1");
+        assert_eq!(layer.record_values[1][0].as_str().unwrap(), "b#2");
+        assert!(layer.preview_replace(replacement(Some("s"))).is_err());
+    }
+
+    #[test]
+    fn test_ranges_are_expanded() {
+        let span = Span::test_data();
+        let range = nu_protocol::Range::new_int(1, None, std::ops::Bound::Included(3));
+        let layer = create_layer(Value::range(range, span)).unwrap();
+        let values: Vec<_> = layer
+            .record_values
+            .iter()
+            .map(|row| row[0].as_int().unwrap())
+            .collect();
+        assert_eq!(values, vec![1, 2, 3]);
+
+        let unbounded = nu_protocol::Range::new_int(1, None, std::ops::Bound::Unbounded);
+        let layer = create_layer(Value::range(unbounded, span)).unwrap();
+        assert!(!layer.record_values.is_empty());
+    }
+
+    #[test]
+    fn test_pages_record_the_drilled_cells() {
+        let span = Span::test_data();
+        let mut file = nu_protocol::Record::new();
+        file.insert("tags", create_test_list());
+        let files = Value::list(vec![Value::record(file, span)], span);
+        let rows = vec![vec![files]];
+        let mut view = RecordView::new(vec!["files".to_string()], rows, ExploreConfig::default());
+
+        view.set_cursor_mode();
+        view.handle_enter().unwrap();
+        view.handle_enter().unwrap();
+        assert_eq!(view.layer_stack.len(), 3);
+        assert_eq!(view.layer_stack[1].opened_by, "0.files");
+        assert_eq!(view.layer_stack[2].opened_by, "0.tags");
+
+        assert_eq!(view.title().as_deref(), Some("0.tags"));
+        view.set_title(Some(String::from("tags")));
+        assert_eq!(view.title().as_deref(), Some("tags"));
+
+        view.show_pages();
+        let pages = view.pages.as_ref().unwrap();
+        assert_eq!(pages.pages(), ["input", "0.files", "tags (0.tags)"]);
+        let mut info = ViewInfo::default();
+        view.handle_pages_key(KeyEvent::from(KeyCode::Up), &mut info);
+        view.handle_pages_key(KeyEvent::from(KeyCode::Up), &mut info);
+        view.handle_pages_key(KeyEvent::from(KeyCode::Enter), &mut info);
+        assert_eq!(view.layer_stack.len(), 1);
+        assert!(view.pages.is_none());
+    }
+
+    #[test]
+    fn test_table_value() {
+        let span = Span::test_data();
+        let rows = [3, 1, 2].map(|n| vec![Value::int(n, span)]);
+        let mut view =
+            RecordView::new(vec!["n".to_string()], rows.into(), ExploreConfig::default());
+        view.sort_by("n", Some(false), None).unwrap();
+        view.set_closure_filter("{ odd }".to_string(), |_, rows| {
+            Ok(rows
+                .iter()
+                .map(|row| row[0].as_int().unwrap() % 2 == 1)
+                .collect())
+        })
+        .unwrap();
+
+        let (value, rows) = view.table_value();
+        assert_eq!(rows, 2);
+        let n = |value: &Value| {
+            value
+                .as_record()
+                .unwrap()
+                .get("n")
+                .unwrap()
+                .as_int()
+                .unwrap()
+        };
+        let shown: Vec<i64> = value.as_list().unwrap().iter().map(n).collect();
+        assert_eq!(shown, [1, 3]);
+
+        // a record is saved as one
+        let rows = vec![vec![Value::int(1, span)]];
+        let mut view = RecordView::new(vec!["a".to_string()], rows, ExploreConfig::default());
+        view.set_top_layer_orientation(Orientation::Left);
+        assert!(matches!(view.table_value(), (Value::Record { .. }, 1)));
+    }
+
+    #[test]
+    fn test_column_samples() {
+        let span = Span::test_data();
+        let columns = vec!["name".to_string(), "note".to_string()];
+        let rows = ["a", "b", "c", "d"]
+            .into_iter()
+            .map(|name| {
+                let note = match name {
+                    "d" => Value::string("late", span),
+                    _ => Value::nothing(span),
+                };
+                vec![Value::string(name, span), note]
+            })
+            .collect();
+        let mut layer = RecordLayer::new(columns, rows);
+        let text = layer
+            .record_values
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|value| {
+                        (
+                            value.to_expanded_string("", &Config::default()),
+                            TextStyle::default(),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        layer.record_text = Some(text);
+        layer.find_first_values();
+        layer.cursor.set_window_size(2, 2).unwrap();
+
+        assert_eq!(layer.column_samples(), [None, Some("late".to_string())]);
+
+        // once a value is in sight, it's there to see
+        layer.cursor.set_window_start_position(2, 0);
+        assert_eq!(layer.column_samples(), [None::<String>, None]);
+    }
+
+    #[test]
+    fn test_common_columns() {
+        let span = Span::test_data();
+        let missing = Value::string("❎", NuSpan::unknown());
+        let columns = vec!["name".to_string(), "size".to_string(), "note".to_string()];
+        let rows = vec![
+            vec![
+                Value::string("a", span),
+                Value::int(1, span),
+                missing.clone(),
+            ],
+            vec![Value::string("b", span), missing.clone(), missing.clone()],
+            vec![
+                Value::string("c", span),
+                Value::int(3, span),
+                missing.clone(),
+            ],
+            vec![
+                Value::string("d", span),
+                Value::int(4, span),
+                Value::nothing(span),
+            ],
+        ];
+        let mut view = RecordView::new(columns, rows, ExploreConfig::default());
+
+        let layer = view.get_top_layer_mut();
+        layer.find_fill();
+        assert_eq!(layer.fill, [None, Some(75), Some(25)]);
+
+        assert_eq!(view.toggle_common_columns().unwrap(), Some(2));
+        assert_eq!(view.column_names(), ["name"]);
+        assert!(view.toggle_common_columns().unwrap().is_none());
+        assert_eq!(view.column_names().len(), 3);
+    }
+
+    #[test]
+    fn test_binary_cells() {
+        let config = Config::default();
+        let short = Value::test_binary(b"\x89PNG".to_vec());
+        assert_eq!(
+            cell_text(&short, &config, 4096),
+            short.to_abbreviated_string(&config)
+        );
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.resize(2000, 0);
+        let text = cell_text(&Value::test_binary(png), &config, 4096);
+        assert_eq!(text, format!("[png {}]", format_size(2000)));
+
+        let text = cell_text(
+            &Value::test_binary((0..40).collect::<Vec<u8>>()),
+            &config,
+            4096,
+        );
+        assert!(text.starts_with("[binary "));
+    }
+
+    #[test]
+    fn test_reload() {
+        let span = Span::test_data();
+        let row = |n: i64, s: &str| vec![Value::int(n, span), Value::string(s, span)];
+        let columns = vec!["n".to_string(), "s".to_string()];
+        let rows = vec![row(3, "a"), row(1, "b"), row(2, "a")];
+        let mut view = RecordView::new(columns, rows, ExploreConfig::default());
+        view.sort_by("n", Some(false), None).unwrap();
+        view.add_filter("s", "a".to_string()).unwrap();
+        view.get_top_layer_mut().cursor.next_row();
 
-fn estimate_page_size(area: Rect, show_head: bool) -> u16 {
-    let mut available_height = area.height;
-    available_height -= 3; // status_bar
+        // the columns moved and a row was added
+        let columns = vec!["s".to_string(), "n".to_string()];
+        let row = |n: i64, s: &str| vec![Value::string(s, span), Value::int(n, span)];
+        let rows = vec![row(3, "a"), row(1, "b"), row(2, "a"), row(0, "a")];
+        view.reload(columns, rows);
 
-    if show_head {
-        available_height -= 3; // head
+        let layer = view.get_top_layer();
+        let shown: Vec<i64> = layer
+            .record_values
+            .iter()
+            .map(|row| row[1].as_int().unwrap())
+            .collect();
+        assert_eq!(shown, [0, 2, 3]);
+        assert_eq!(view.filters(), [("s".to_string(), "a".to_string(), true)]);
+        assert_eq!(layer.cursor.row(), 1);
     }
 
-    available_height
-}
+    #[test]
+    fn test_reload_keeps_the_layout() {
+        let span = Span::test_data();
+        let columns = || ["n", "s", "x"].map(String::from).to_vec();
+        let rows = || {
+            vec![vec![
+                Value::int(1, span),
+                Value::string("a", span),
+                Value::bool(true, span),
+            ]]
+        };
+        let mut view = RecordView::new(columns(), rows(), ExploreConfig::default());
+        let layer = view.get_top_layer_mut();
+        layer.move_column(0, true);
+        layer.leave_out_columns(&["x".to_string()]);
+        assert_eq!(layer.column_names, ["s", "n"]);
 
-/// scroll to the end of the data
-fn tail_data(state: &mut RecordView, page_size: usize) {
-    let layer = state.get_top_layer_mut();
-    let count_rows = layer.record_values.len();
-    if count_rows > page_size {
-        layer
-            .cursor
-            .set_window_start_position(count_rows - page_size, 0);
+        view.reload(columns(), rows());
+        let layer = view.get_top_layer();
+        assert_eq!(layer.column_names, ["s", "n"]);
+        assert_eq!(
+            layer.record_values[0],
+            [Value::string("a", span), Value::int(1, span)]
+        );
     }
-}
-
-fn convert_records_to_string(
-    records: &[Vec<Value>],
-    cfg: &Config,
-    style_computer: &StyleComputer,
-) -> Vec<Vec<NuText>> {
-    records
-        .iter()
-        .map(|row| {
-            row.iter()
-                .map(|value| {
-                    let text = value.clone().to_abbreviated_string(cfg);
-                    let text = strip_string(&text);
-                    let float_precision = cfg.float_precision as usize;
 
-                    make_styled_string(style_computer, text, Some(value), float_precision)
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>()
-}
+    #[test]
+    fn test_append_rows_as_read() {
+        let span = Span::test_data();
+        let row = |n: i64, s: &str| vec![Value::int(n, span), Value::string(s, span)];
+        let columns = vec!["n".to_string(), "s".to_string()];
+        let mut view =
+            RecordView::new(columns.clone(), vec![row(1, "a")], ExploreConfig::default());
 
-fn highlight_selected_cell(f: &mut Frame, info: ElementInfo, cfg: &ExploreConfig) {
-    let cell_style = cfg.selected_cell;
-    let highlight_block = Block::default().style(nu_style_to_tui(cell_style));
-    let area = Rect::new(info.area.x, info.area.y, info.area.width, 1);
-    f.render_widget(highlight_block.clone(), area)
-}
+        assert!(view.append(&columns, vec![row(2, "b")]));
+        assert_eq!(view.get_top_layer().record_values.len(), 2);
+        assert!(!view.append(&["n".to_string()], vec![vec![Value::int(3, span)]]));
 
-fn report_cursor_position(mode: UIMode, cursor: WindowCursor2D) -> String {
-    if mode == UIMode::Cursor {
-        let Position { row, column } = cursor.position();
-        format!("{row},{column}")
-    } else {
-        let Position { row, column } = cursor.window_origin();
-        format!("{row},{column}")
+        // the rows of a filtered table are read again
+        view.add_filter("s", "a".to_string()).unwrap();
+        assert!(!view.append(&columns, vec![row(3, "a")]));
+        assert_eq!(view.get_top_layer().record_values.len(), 1);
     }
-}
 
-fn report_row_position(cursor: WindowCursor2D) -> String {
-    if cursor.window_origin().row == 0 {
-        String::from("Top")
-    } else {
-        let percent_rows = get_percentage(cursor.row(), cursor.row_limit());
+    #[test]
+    fn test_rename_column() {
+        let span = Span::test_data();
+        let row = |n: i64, s: &str| vec![Value::int(n, span), Value::string(s, span)];
+        let columns = vec!["n".to_string(), "s".to_string()];
+        let mut view =
+            RecordView::new(columns.clone(), vec![row(1, "a")], ExploreConfig::default());
+        view.add_filter("s", "a".to_string()).unwrap();
 
-        match percent_rows {
-            100 => String::from("All"),
-            value => format!("{value}%"),
-        }
+        assert!(view.rename_column("x", "y").is_err());
+        assert!(view.rename_column("s", "n").is_err());
+        view.rename_column("s", "text").unwrap();
+        view.rename_column("text", "label").unwrap();
+
+        let (value, _) = view.table_value();
+        let record = value.as_list().unwrap()[0].as_record().unwrap();
+        assert_eq!(record.get("label"), Some(&Value::string("a", span)));
+
+        // read again with the names it had, the filter on it still applies
+        view.reload(columns, vec![row(1, "a"), row(2, "b")]);
+        assert_eq!(view.column_names(), ["n", "label"]);
+        assert_eq!(view.get_top_layer().record_values.len(), 1);
     }
-}
 
-fn get_percentage(value: usize, max: usize) -> usize {
-    debug_assert!(value <= max, "{value:?} {max:?}");
+    #[test]
+    fn test_current_row_and_column() {
+        let span = Span::test_data();
+        let columns = vec!["n".to_string(), "s".to_string()];
+        let rows = (0..6)
+            .map(|n| vec![Value::int(n, span), Value::string("x", span)])
+            .collect();
+        let mut view = RecordView::new(columns, rows, ExploreConfig::default());
+        view.abbreviate(2);
+        view.get_top_layer_mut().cursor.next_row();
 
-    ((value as f32 / max as f32) * 100.0).floor() as usize
-}
+        let row = view.current_row_value();
+        assert_eq!(
+            row.as_record().unwrap().get("n"),
+            Some(&Value::int(1, span))
+        );
 
-fn transpose_table(layer: &mut RecordLayer) {
-    if layer.was_transposed {
-        transpose_from(layer);
-    } else {
-        transpose_to(layer);
+        // the abbreviated rows are copied too
+        let column = view.current_column_value();
+        let n: Vec<i64> = column
+            .as_list()
+            .unwrap()
+            .iter()
+            .map(|value| value.as_int().unwrap())
+            .collect();
+        assert_eq!(n, [0, 1, 2, 3, 4, 5]);
     }
 
-    layer.was_transposed = !layer.was_transposed;
-}
+    #[test]
+    fn test_mouse() {
+        let span = Span::test_data();
+        let columns = vec!["n".to_string(), "s".to_string()];
+        let rows = (0..3)
+            .map(|n| vec![Value::int(n, span), Value::string("x", span)])
+            .collect();
+        let mut cfg = ExploreConfig::default();
+        cfg.table.show_header = true;
+        cfg.table.column_padding_right = 1;
+        let mut view = RecordView::new(columns, rows, cfg);
+        let mut info = ViewInfo::default();
 
-fn transpose_from(layer: &mut RecordLayer) {
-    let count_rows = layer.record_values.len();
-    let count_columns = layer.column_names.len();
+        // as drawn: a column after the other, with its header above a border line
+        let mut layout = Layout::default();
+        for (x, width, head) in [(1, 3, "n"), (7, 5, "s")] {
+            layout.push(head, x, 1, width, 1);
+            for row in 0..3 {
+                layout.push("", x, 3 + row, width, 1);
+            }
+        }
+        view.drawn_rows = 3;
 
-    // Extract the original column names from the first column
-    let headers = pop_first_column(&mut layer.record_values);
-    let headers = headers
-        .into_iter()
-        .map(|value| match value {
-            Value::String { val, .. } => val,
-            _ => unreachable!("must never happen"),
-        })
-        .collect();
+        let mouse = |kind, column, row| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        let click = |column, row| mouse(MouseEventKind::Down(MouseButton::Left), column, row);
 
-    let data = _transpose_table(&layer.record_values, count_rows, count_columns - 1);
+        view.handle_mouse(&layout, &mut info, click(8, 4));
+        assert_eq!(view.mode, UIMode::Cursor);
+        assert_eq!(view.get_top_layer().cursor.row(), 1);
+        assert_eq!(view.get_top_layer().cursor.column(), 1);
 
-    layer.record_values = data;
-    layer.column_names = headers;
-    // Invalidate the text cache so it gets regenerated with the new structure
-    layer.record_text = None;
-}
+        let drag = |column, row| mouse(MouseEventKind::Drag(MouseButton::Left), column, row);
+        let release = |column, row| mouse(MouseEventKind::Up(MouseButton::Left), column, row);
 
-fn pop_first_column<T>(values: &mut [Vec<T>]) -> Vec<T>
-where
-    T: Default + Clone,
-{
-    let mut data = vec![T::default(); values.len()];
-    for (row, values) in values.iter_mut().enumerate() {
-        data[row] = values.remove(0);
-    }
+        // a header sorts by its column, the other way round the second time
+        for _ in 0..2 {
+            view.handle_mouse(&layout, &mut info, click(1, 1));
+            view.handle_mouse(&layout, &mut info, release(1, 1));
+        }
+        assert_eq!(
+            view.get_top_layer().record_values[0][0],
+            Value::int(2, span)
+        );
 
-    data
-}
+        // the edge of a header resizes its column
+        view.handle_mouse(&layout, &mut info, click(4, 1));
+        view.handle_mouse(&layout, &mut info, drag(10, 1));
+        view.handle_mouse(&layout, &mut info, release(10, 1));
+        assert_eq!(view.cfg.columns["n"].width, Some(9));
+        assert!(view.dragging.is_none());
 
-fn transpose_to(layer: &mut RecordLayer) {
-    let count_rows = layer.record_values.len();
-    let count_columns = layer.column_names.len();
+        // a header dragged onto another moves its column there, sorting nothing
+        view.handle_mouse(&layout, &mut info, click(1, 1));
+        view.handle_mouse(&layout, &mut info, drag(8, 4));
+        view.handle_mouse(&layout, &mut info, release(8, 4));
+        assert_eq!(view.get_top_layer().column_names, ["s", "n"]);
+        assert_eq!(
+            view.get_top_layer().record_values[0][1],
+            Value::int(2, span)
+        );
 
-    let mut data = _transpose_table(&layer.record_values, count_rows, count_columns);
-    for (column, column_name) in layer.column_names.iter().enumerate() {
-        let value = Value::string(column_name, NuSpan::unknown());
-        data[column].insert(0, value);
+        // a right click selects the cell and opens its menu
+        let right = mouse(MouseEventKind::Down(MouseButton::Right), 2, 3);
+        let transition = view.handle_mouse(&layout, &mut info, right);
+        assert!(matches!(transition, Transition::Cmd(command) if command == MenuCmd::NAME));
+        assert_eq!(view.get_top_layer().cursor.row(), 0);
+        assert_eq!(view.get_top_layer().cursor.column(), 0);
     }
 
-    layer.record_values = data;
-    layer.column_names = (1..=count_rows + 1).map(|i| i.to_string()).collect();
-    // Invalidate the text cache so it gets regenerated with the new structure
-    layer.record_text = None;
-}
+    #[test]
+    fn test_minimap_click() {
+        let span = Span::test_data();
+        let rows = (0..100).map(|n| vec![Value::int(n, span)]).collect();
+        let mut view = RecordView::new(vec!["n".to_string()], rows, ExploreConfig::default());
+        let mut info = ViewInfo::default();
+        view.page_size = 10;
+        view.minimap = Some(Rect::new(20, 0, 1, 10));
 
-fn _transpose_table<T>(values: &[Vec<T>], count_rows: usize, count_columns: usize) -> Vec<Vec<T>>
-where
-    T: Clone + Default,
-{
-    let mut data = vec![vec![T::default(); count_rows]; count_columns];
-    for (row, values) in values.iter().enumerate() {
-        for (column, value) in values.iter().enumerate() {
-            data[column][row].clone_from(value);
-        }
+        let mouse = |kind, row| MouseEvent {
+            kind,
+            column: 20,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        let layout = Layout::default();
+        let click = mouse(MouseEventKind::Down(MouseButton::Left), 5);
+        let drag = mouse(MouseEventKind::Drag(MouseButton::Left), 30);
+        let release = mouse(MouseEventKind::Up(MouseButton::Left), 30);
+
+        // the line of rows 50 to 59, put in the middle of the window
+        view.handle_mouse(&layout, &mut info, click);
+        assert_eq!(view.get_top_layer().cursor.window_origin().row, 45);
+
+        // dragged past the end, as far as the last line
+        view.handle_mouse(&layout, &mut info, drag);
+        assert_eq!(view.get_top_layer().cursor.window_origin().row, 85);
+        view.handle_mouse(&layout, &mut info, release);
+        assert!(view.dragging.is_none());
     }
 
-    data
-}
+    #[test]
+    fn test_cell_menu() {
+        let span = Span::test_data();
+        let columns = vec!["name".to_string(), "size".to_string()];
+        let rows = vec![vec![Value::string("a b", span), Value::int(3, span)]];
+        let mut view = RecordView::new(columns, rows, ExploreConfig::default());
+        view.set_cursor_mode();
+        let entries = [
+            MenuEntry::new("Filter", "filter {column} {value}"),
+            MenuEntry::new("Copy path", "copy --path"),
+        ];
 
-fn strip_string(text: &str) -> String {
-    String::from_utf8(strip_ansi_escapes::strip(text))
-        .map_err(|_| ())
-        .unwrap_or_else(|_| text.to_owned())
-}
+        view.show_menu(&entries);
+        let labels = |view: &RecordView| -> Vec<String> {
+            let mut menu = view.menu.clone().unwrap();
+            let mut labels = vec![menu.selected().unwrap().label.clone()];
+            loop {
+                menu.select_next();
+                let label = &menu.selected().unwrap().label;
+                if labels.last() == Some(label) {
+                    return labels;
+                }
+                labels.push(label.clone());
+            }
+        };
+        assert_eq!(labels(&view), ["Open", "Filter", "Copy path"]);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use nu_protocol::{Value, span::Span};
+        let mut info = ViewInfo::default();
+        view.handle_menu_key(KeyEvent::from(KeyCode::Down), &mut info);
+        let transition = view.handle_menu_key(KeyEvent::from(KeyCode::Enter), &mut info);
+        assert!(matches!(transition, Transition::Cmd(command) if command == "filter name a b"));
+        assert!(view.menu.is_none());
 
-    // Helper to create a simple test Value::Record
-    fn create_test_record() -> Value {
-        let mut record = nu_protocol::Record::new();
-        record.insert(
-            "name".to_string(),
-            Value::string("sample", Span::test_data()),
-        );
-        record.insert("value".to_string(), Value::int(42, Span::test_data()));
-        Value::record(record, Span::test_data())
+        // a record has no columns to filter by
+        view.transpose();
+        view.show_menu(&entries);
+        assert_eq!(labels(&view), ["Open", "Copy path"]);
     }
 
-    // Helper to create a simple test Value::List
-    fn create_test_list() -> Value {
-        let items = vec![
-            Value::string("item1", Span::test_data()),
-            Value::string("item2", Span::test_data()),
+    #[test]
+    fn test_selection_value() {
+        let span = Span::test_data();
+        let columns = vec!["a".to_string(), "b".to_string()];
+        let rows = vec![
+            vec![Value::int(1, span), Value::int(2, span)],
+            vec![Value::int(3, span), Value::int(4, span)],
         ];
-        Value::list(items, Span::test_data())
+        let mut view = RecordView::new(columns, rows.clone(), ExploreConfig::default());
+        assert_eq!(view.selection_value(), None);
+
+        view.get_top_layer_mut().marked.push(rows[1].clone());
+        let marked = view.selection_value().unwrap();
+        assert_eq!(marked.as_list().unwrap().len(), 1);
+        let row = marked.as_list().unwrap()[0].as_record().unwrap();
+        assert_eq!(row.get("a"), Some(&Value::int(3, span)));
+
+        view.get_top_layer_mut().selection_anchor = Some((0, 0));
+        let cells = view.selection_value().unwrap();
+        let row = cells.as_list().unwrap()[0].as_record().unwrap();
+        assert_eq!(row.columns().collect::<Vec<_>>(), vec!["a"]);
+        assert_eq!(row.get("a"), Some(&Value::int(1, span)));
     }
 
     #[test]
-    fn test_create_layer_empty_collection() {
-        // Test with empty record
-        let empty_record = Value::record(nu_protocol::Record::new(), Span::test_data());
-        let result = create_layer(empty_record);
-        assert!(result.is_err());
+    fn test_selection_stats() {
+        let span = Span::test_data();
+        let ints = [
+            Value::int(1, span),
+            Value::int(4, span),
+            Value::string("x", span),
+        ];
+        let ints: Vec<_> = ints.iter().collect();
         assert_eq!(
-            result.unwrap_err().to_string(),
-            "Nothing to explore in empty collections!"
+            selection_stats(&ints).unwrap(),
+            "sum 5 · avg 2.50 · min 1 · max 4 · count 2"
         );
 
-        // Test with empty list
-        let empty_list = Value::list(vec![], Span::test_data());
-        let result = create_layer(empty_list);
-        assert!(result.is_err());
+        let floats = [Value::int(1, span), Value::float(0.5, span)];
+        let floats: Vec<_> = floats.iter().collect();
         assert_eq!(
-            result.unwrap_err().to_string(),
-            "Nothing to explore in empty collections!"
+            selection_stats(&floats).unwrap(),
+            "sum 1.50 · avg 0.75 · min 0.50 · max 1.00 · count 2"
         );
+
+        assert_eq!(selection_stats(&[]), None);
     }
 
     #[test]
-    fn test_create_layer_valid_record() {
-        let record = create_test_record();
-        let result = create_layer(record);
-        assert!(result.is_ok());
+    fn test_estimate_page_size() {
+        // Test with header
+        let area = Rect::new(0, 0, 80, 24);
+        assert_eq!(estimate_page_size(area, true), 18); // 24 - 3 (status bar) - 3 (header) = 18
 
-        let layer = result.unwrap();
-        assert_eq!(layer.column_names, vec!["name", "value"]);
-        assert_eq!(layer.record_values.len(), 1);
-        assert_eq!(layer.record_values[0].len(), 2);
+        // Test without header
+        assert_eq!(estimate_page_size(area, false), 21); // 24 - 3 (status bar) = 21
     }
 
     #[test]
-    fn test_create_layer_valid_list() {
-        let list = create_test_list();
-        let result = create_layer(list);
-        assert!(result.is_ok());
+    fn test_record_orientation() {
+        use Orientation::{Left, Top};
 
-        let layer = result.unwrap();
-        assert_eq!(layer.column_names, vec![""]);
-        assert_eq!(layer.record_values.len(), 2);
-        assert_eq!(layer.record_values[0].len(), 1);
+        // few fields are a field a line, many fitting across a row
+        assert_eq!(record_orientation(5, 60, 120, 20, Left), Left);
+        assert_eq!(record_orientation(30, 100, 120, 20, Left), Top);
+        assert_eq!(record_orientation(30, 200, 120, 20, Left), Left);
+
+        // a resize by a little doesn't turn it back
+        assert_eq!(record_orientation(30, 100, 101, 20, Left), Left);
+        assert_eq!(record_orientation(30, 100, 101, 29, Top), Top);
+        assert_eq!(record_orientation(30, 100, 120, 32, Top), Left);
     }
 
     #[test]
-    fn test_transpose_table() {
-        // Create a simple 2x3 table
-        let mut layer = RecordLayer::new(
-            vec!["col1".to_string(), "col2".to_string(), "col3".to_string()],
-            vec![
-                vec![
-                    Value::string("r1c1", Span::test_data()),
-                    Value::string("r1c2", Span::test_data()),
-                    Value::string("r1c3", Span::test_data()),
-                ],
-                vec![
-                    Value::string("r2c1", Span::test_data()),
-                    Value::string("r2c2", Span::test_data()),
-                    Value::string("r2c3", Span::test_data()),
-                ],
-            ],
-        );
+    fn test_fields_of_a_long_record() {
+        let span = Span::test_data();
+        let columns = (0..25).rev().map(|i| format!("f{i:02}")).collect();
+        let values = (0..25).rev().map(|i| Value::int(i, span)).collect();
+        let mut view = RecordView::new(columns, vec![values], ExploreConfig::default());
+        view.set_top_layer_record();
+        view.update_cursors(10, 1);
+        assert_eq!(view.get_top_layer().field_page(10), Some((1, 3)));
 
-        // Before transpose
-        assert_eq!(layer.count_rows(), 2);
-        assert_eq!(layer.count_columns(), 3);
-        assert!(!layer.was_transposed);
+        // a field found by a search is brought to the top
+        assert!(view.show_data(20));
+        assert_eq!(view.get_top_layer().cursor.window_origin().row, 20);
+        assert_eq!(view.get_top_layer().field_page(10), Some((3, 3)));
 
-        // After transpose
-        transpose_table(&mut layer);
-        assert_eq!(layer.count_rows(), 3);
-        assert_eq!(layer.count_columns(), 3); // Now includes header row
-        assert!(layer.was_transposed);
+        view.go_to_column("f04");
+        assert!(!view.sort_fields());
+        let layer = view.get_top_layer();
+        assert_eq!(layer.column_names[0], "f00");
+        assert_eq!(layer.record_values[0][0].as_int().unwrap(), 0);
+        assert_eq!(view.get_cursor_position().row, 4);
 
-        // After transpose back
-        transpose_table(&mut layer);
-        assert_eq!(layer.count_rows(), 2);
-        assert_eq!(layer.count_columns(), 3);
-        assert!(!layer.was_transposed);
+        assert!(view.sort_fields());
+        assert_eq!(view.get_top_layer().column_names[0], "f24");
+        assert_eq!(view.get_cursor_position().row, 20);
     }
 
     #[test]
-    fn test_estimate_page_size() {
-        // Test with header
-        let area = Rect::new(0, 0, 80, 24);
-        assert_eq!(estimate_page_size(area, true), 18); // 24 - 3 (status bar) - 3 (header) = 18
+    fn test_rotate_a_record() {
+        let span = Span::test_data();
+        let rows = vec![vec![Value::int(1, span), Value::int(2, span)]];
+        let columns = vec!["a".to_string(), "b".to_string()];
+        let mut view = RecordView::new(columns, rows, ExploreConfig::default());
+        view.set_top_layer_record();
+        assert!(view.get_top_layer().auto_orientation);
 
-        // Test without header
-        assert_eq!(estimate_page_size(area, false), 21); // 24 - 3 (status bar) = 21
+        // rotated by hand, it stays the way it is
+        view.rotate();
+        assert_eq!(view.get_top_layer().orientation, Orientation::Top);
+        assert!(!view.get_top_layer().auto_orientation);
+        view.orient_record(Rect::new(0, 0, 10, 40), &Config::default());
+        assert_eq!(view.get_top_layer().orientation, Orientation::Top);
     }
 }
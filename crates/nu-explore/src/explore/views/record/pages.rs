@@ -0,0 +1,118 @@
+use super::super::super::nu_common::{NuStyle, string_width};
+use super::super::util::{nu_style_to_tui, set_span};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+/// An overlay listing the pages of a drill-down, the first one being the input,
+/// one of which is selected to go back to.
+#[derive(Debug, Clone)]
+pub struct PageList {
+    // how each page was opened, after its title if it has one
+    pages: Vec<String>,
+    selected: usize,
+}
+
+impl PageList {
+    /// The last page is selected, as it's the one shown.
+    pub fn new(pages: Vec<String>) -> Self {
+        let selected = pages.len().saturating_sub(1);
+        Self { pages, selected }
+    }
+
+    /// What each page is called, the input first.
+    #[cfg(test)]
+    pub fn pages(&self) -> &[String] {
+        &self.pages
+    }
+
+    /// The index of the selected page.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.pages.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn render(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        text_style: NuStyle,
+        border_style: NuStyle,
+        selected_style: NuStyle,
+    ) {
+        let lines: Vec<String> = self
+            .pages
+            .iter()
+            .enumerate()
+            .map(|(depth, page)| format!("{}{page}", "  ".repeat(depth)))
+            .collect();
+
+        let text_width = lines.iter().map(|line| string_width(line)).max();
+        let width = (text_width.unwrap_or(0) as u16 + 4).clamp(20, area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        if width < 4 || height < 3 {
+            return;
+        }
+
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + (area.height - height) / 2;
+        let popup = Rect::new(x, y, width, height);
+
+        Clear.render(popup, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(nu_style_to_tui(border_style))
+            .title(" pages ");
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let text_style = nu_style_to_tui(text_style);
+        let selected_style = nu_style_to_tui(selected_style);
+
+        // keep the selected page in sight when there are more pages than lines
+        let height = inner.height as usize;
+        let offset = (self.selected + 1).saturating_sub(height);
+
+        let lines = lines.iter().enumerate().skip(offset).take(height);
+        for (y, (i, line)) in (inner.y..).zip(lines) {
+            let style = if i == self.selected {
+                selected_style
+            } else {
+                text_style
+            };
+            let row = Rect::new(inner.x, y, inner.width, 1);
+            Block::default().style(style).render(row, buf);
+            set_span(buf, (inner.x + 1, y), line, style, inner.width - 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_stays_in_the_list() {
+        let mut pages = PageList::new(vec!["input".into(), "files".into(), "0.name".into()]);
+        assert_eq!(pages.selected(), 2);
+
+        pages.select_next();
+        assert_eq!(pages.selected(), 2);
+
+        pages.select_prev();
+        pages.select_prev();
+        pages.select_prev();
+        assert_eq!(pages.selected(), 0);
+    }
+}
@@ -1,6 +1,7 @@
 use super::super::super::{
     config::TableConfig,
-    nu_common::{NuStyle, NuText, truncate_str},
+    l10n::tr,
+    nu_common::{NuStyle, NuText, scroll_str, truncate_str},
 };
 use super::super::util::{nu_style_to_tui, text_style_to_tui_style};
 use super::Layout;
@@ -10,6 +11,7 @@ use nu_table::string_width;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
+    style::{Modifier, Style},
     text::Span,
     widgets::{Block, Borders, Paragraph, StatefulWidget, Widget},
 };
@@ -24,6 +26,32 @@ pub struct TableWidget<'a> {
     config: TableConfig,
     header_position: Orientation,
     style_computer: &'a StyleComputer<'a>,
+    /// A `… N more rows …` line drawn before the row at the given index: (index, N)
+    row_separator: Option<(usize, usize)>,
+    /// Rows drawn above the scrolled ones, whatever the scroll position
+    pinned: &'a [Vec<NuText>],
+    /// The width and alignment set for each column, in tables with the header at the top
+    layouts: Vec<ColumnLayout>,
+    /// A cell of `data` whose text is scrolled to the left: (row, column, characters)
+    cell_scroll: Option<(usize, usize, usize)>,
+    /// A value of each column shown dimmed after its header, by index, for the columns empty
+    /// in every row in sight
+    samples: Vec<Option<String>>,
+    /// The percentage of the rows having each column, by index, for the columns some rows
+    /// don't have
+    fill: Vec<Option<u8>>,
+    /// Where the cells link to, by row from the first one shown and by column
+    links: Vec<Vec<Option<String>>>,
+}
+
+// the most a sample takes after a header
+const SAMPLE_WIDTH: usize = 20;
+
+/// The width and alignment of a column, as set in `explore.table.columns`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ColumnLayout {
+    pub width: Option<u16>,
+    pub align: Option<Alignment>,
 }
 
 // Basically: where's the header of the value being displayed? Usually at the top for tables, on the left for records
@@ -52,8 +80,66 @@ impl<'a> TableWidget<'a> {
             index_column,
             config,
             header_position,
+            row_separator: None,
+            pinned: &[],
+            layouts: Vec::new(),
+            cell_scroll: None,
+            samples: Vec::new(),
+            fill: Vec::new(),
+            links: Vec::new(),
         }
     }
+
+    /// Show a sample of the columns, by index, after their header: what the column holds
+    /// further on when the rows in sight have nothing in it.
+    pub fn with_samples(mut self, samples: Vec<Option<String>>) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Show how full the columns some rows don't have are, by index, after their header.
+    pub fn with_fill(mut self, fill: Vec<Option<u8>>) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    /// Make cells links, by row from the first one shown and by column.
+    ///
+    /// Only tables with the header at the top have links.
+    pub fn with_links(mut self, links: Vec<Vec<Option<String>>>) -> Self {
+        self.links = links;
+        self
+    }
+
+    /// Give columns, by index, the width and alignment of their layout.
+    pub fn with_column_layouts(mut self, layouts: Vec<ColumnLayout>) -> Self {
+        self.layouts = layouts;
+        self
+    }
+
+    /// Scroll the text of the cell at `row` and `column` of the data `offset` characters to
+    /// the left, within the width of its column.
+    pub fn with_cell_scroll(mut self, row: usize, column: usize, offset: usize) -> Self {
+        self.cell_scroll = (offset > 0).then_some((row, column, offset));
+        self
+    }
+
+    /// Draw a `… {hidden} more rows …` line right before the row at index `at`.
+    ///
+    /// Only tables with the header at the top have a separator line.
+    pub fn with_row_separator(mut self, at: usize, hidden: usize) -> Self {
+        self.row_separator = Some((at, hidden));
+        self
+    }
+
+    /// Keep `rows` on top of the table, above a line, while the rest scrolls below them.
+    ///
+    /// Only tables with the header at the top have pinned rows; they take at most
+    /// half of the lines available for data.
+    pub fn with_pinned_rows(mut self, rows: &'a [Vec<NuText>]) -> Self {
+        self.pinned = rows;
+        self
+    }
 }
 
 #[derive(Debug, Default)]
@@ -120,12 +206,28 @@ impl TableWidget<'_> {
             return;
         }
 
+        let pinned = &self.pinned[..self.pinned.len().min(data_height as usize / 2)];
+        let pinned_y = data_y;
+        if !pinned.is_empty() {
+            data_y += pinned.len() as u16 + 1;
+            data_height -= pinned.len() as u16 + 1;
+        }
+
         let mut width = area.x;
         let mut data = &self.data[self.index_row..];
         if data.len() > data_height as usize {
             data = &data[..data_height as usize];
         }
 
+        // the separator takes up a line of its own, so one less row fits when it's visible
+        let separator = self
+            .row_separator
+            .filter(|(at, _)| *at >= self.index_row && *at - self.index_row < data.len())
+            .map(|(at, hidden)| (at - self.index_row, hidden));
+        if separator.is_some() && data.len() == data_height as usize {
+            data = &data[..data.len() - 1];
+        }
+
         if show_head {
             render_header_borders(buf, area, 1, separator_s);
         }
@@ -136,6 +238,7 @@ impl TableWidget<'_> {
                 Rect::new(width, data_y, area.width, data_height),
                 self.style_computer,
                 self.index_row,
+                separator,
                 padding_l,
                 padding_r,
             );
@@ -160,14 +263,53 @@ impl TableWidget<'_> {
             }
 
             let mut column = create_column(data, col);
-            let column_width = calculate_column_width(&column);
+            if let Some((line, _)) = separator {
+                column.insert(line, NuText::default());
+            }
+            let mut pinned_column = create_column(pinned, col);
+            let column_width = max(
+                calculate_column_width(&column),
+                calculate_column_width(&pinned_column),
+            );
+
+            if let Some((row, column_index, offset)) = self.cell_scroll
+                && column_index == col
+                && let Some(line) = row.checked_sub(self.index_row)
+                && line < data.len()
+            {
+                // after a separator line, the rows are a line further down
+                let line = match separator {
+                    Some((at, _)) if at <= line => line + 1,
+                    _ => line,
+                };
+                column[line].0 = scroll_str(&column[line].0, offset);
+            }
 
             let mut head = String::from(&self.columns[col]);
             let head_width = string_width(&head);
 
+            let mut sample = self.samples.get(col).cloned().flatten();
+            if let Some(sample) = &mut sample {
+                truncate_str(sample, SAMPLE_WIDTH);
+            }
+            if let Some(Some(percent)) = self.fill.get(col) {
+                sample = Some(match sample {
+                    Some(sample) => format!("{percent}% {sample}"),
+                    None => format!("{percent}%"),
+                });
+            }
+            let sample_width = sample.as_ref().map_or(0, |s| string_width(s) + 1);
+
             let mut use_space = column_width as u16;
             if show_head {
-                use_space = max(head_width as u16, use_space);
+                use_space = max((head_width + sample_width) as u16, use_space);
+            }
+
+            let layout = self.layouts.get(col).copied().unwrap_or_default();
+            if let Some(width) = layout.width {
+                use_space = width;
+                truncate_list(&mut column, width as usize);
+                truncate_list(&mut pinned_column, width as usize);
             }
 
             if use_space > 0 {
@@ -190,6 +332,11 @@ impl TableWidget<'_> {
                 use_space = w;
             }
 
+            if let Some(align) = layout.align {
+                align_list(&mut column, use_space as usize, align);
+                align_list(&mut pinned_column, use_space as usize, align);
+            }
+
             if show_head {
                 let head_style = head_style(&head, self.style_computer);
                 if head_width > use_space as usize {
@@ -205,6 +352,33 @@ impl TableWidget<'_> {
 
                 let x = w - padding_r - use_space;
                 state.layout.push(&head, x, head_y, use_space, 1);
+
+                // in what's left of the header after a space
+                let shown = string_width(&head) as u16 + 1;
+                if let Some(mut sample) = sample
+                    && use_space > shown
+                {
+                    truncate_str(&mut sample, (use_space - shown) as usize);
+                    let style = Style::default().add_modifier(Modifier::DIM);
+                    buf.set_stringn(
+                        x + shown,
+                        head_y,
+                        &sample,
+                        (use_space - shown) as usize,
+                        style,
+                    );
+                }
+            }
+
+            if !pinned.is_empty() {
+                truncate_list(&mut pinned_column, use_space as usize);
+                let pinned_rows = pinned_column.iter().map(|(t, s)| (t, *s));
+                let height = pinned.len() as u16;
+
+                let mut w = width;
+                w += render_space(buf, w, pinned_y, height, padding_l);
+                w += render_column(buf, w, pinned_y, use_space, pinned_rows);
+                render_space(buf, w, pinned_y, height, padding_r);
             }
 
             let column_rows = column.iter().map(|(t, s)| (t, *s));
@@ -214,9 +388,23 @@ impl TableWidget<'_> {
             width += render_space(buf, width, data_y, data_height, padding_r);
 
             for (row, (text, _)) in column.iter().enumerate() {
+                if separator.is_some_and(|(line, _)| line == row) {
+                    continue;
+                }
+
                 let x = width - padding_r - use_space;
                 let y = data_y + row as u16;
                 state.layout.push(text, x, y, use_space, 1);
+
+                // after a separator line, the rows are a line further down
+                let line = match separator {
+                    Some((at, _)) if at < row => row - 1,
+                    _ => row,
+                };
+                let link = self.links.get(line).and_then(|links| links.get(col));
+                if let Some(Some(link)) = link {
+                    render_link(buf, Rect::new(x, y, use_space, 1), link);
+                }
             }
 
             state.count_columns += 1;
@@ -242,6 +430,34 @@ impl TableWidget<'_> {
             if show_head {
                 render_space(buf, width, head_y, 1, rest);
             }
+            if !pinned.is_empty() {
+                render_space(buf, width, pinned_y, pinned.len() as u16, rest);
+            }
+        }
+
+        if let Some(zebra) = self.config.zebra {
+            // the separator line isn't a row, the ones under it are a row higher
+            let mut row = self.index_row;
+            for line in 0..data.len() + usize::from(separator.is_some()) {
+                if separator.is_some_and(|(at, _)| at == line) {
+                    continue;
+                }
+                if row % 2 == 1 {
+                    let y = data_y + line as u16;
+                    render_stripe(buf, Rect::new(area.x, y, area.width, 1), zebra);
+                }
+                row += 1;
+            }
+        }
+
+        if let Some((line, hidden)) = separator {
+            let y = data_y + line as u16;
+            render_row_separator(buf, Rect::new(area.x, y, area.width, 1), hidden);
+        }
+
+        if !pinned.is_empty() {
+            let y = data_y - 1;
+            render_pinned_separator(buf, Rect::new(area.x, y, area.width, 1), separator_s);
         }
     }
 
@@ -267,6 +483,7 @@ impl TableWidget<'_> {
                 area,
                 self.style_computer,
                 self.index_row,
+                None,
                 padding_l,
                 padding_r,
             );
@@ -362,6 +579,14 @@ impl TableWidget<'_> {
                 left_w += 1;
             }
 
+            if let Some((row, column_index, offset)) = self.cell_scroll
+                && row == col
+                && let Some(line) = column_index.checked_sub(self.index_row)
+                && let Some((text, _)) = column.get_mut(line)
+            {
+                *text = scroll_str(text, offset);
+            }
+
             let column_width = column_width as u16;
             let available = area.width - left_w;
             let is_last = col + 1 == self.data.len();
@@ -401,6 +626,16 @@ impl TableWidget<'_> {
             }
         }
 
+        // a record's rows are its columns
+        if let Some(zebra) = self.config.zebra {
+            for line in 0..columns.len() {
+                if (self.index_row + line) % 2 == 1 {
+                    let y = area.y + line as u16;
+                    render_stripe(buf, Rect::new(area.x, y, area.width, 1), zebra);
+                }
+            }
+        }
+
         if show_overflow_indicator {
             let x = area.x + left_w;
             left_w += render_space(buf, x, area.y, area.height, padding_l);
@@ -483,26 +718,44 @@ fn check_column_width(
 struct IndexColumn<'a> {
     style_computer: &'a StyleComputer<'a>,
     start: usize,
+    // (line, count) a separator line after which indexes jump over `count` hidden rows
+    separator: Option<(usize, usize)>,
 }
 
 impl<'a> IndexColumn<'a> {
-    fn new(style_computer: &'a StyleComputer, start: usize) -> Self {
+    fn new(
+        style_computer: &'a StyleComputer,
+        start: usize,
+        separator: Option<(usize, usize)>,
+    ) -> Self {
         Self {
             style_computer,
             start,
+            separator,
         }
     }
 
     fn estimate_width(&self, height: u16) -> usize {
-        let last_row = self.start + height as usize;
+        let hidden = self.separator.map_or(0, |(_, hidden)| hidden);
+        let last_row = self.start + height as usize + hidden;
         last_row.to_string().len()
     }
+
+    fn row_index(&self, line: usize) -> Option<usize> {
+        match self.separator {
+            Some((at, _)) if line == at => None,
+            Some((at, hidden)) if line > at => Some(self.start + line - 1 + hidden),
+            _ => Some(self.start + line),
+        }
+    }
 }
 
 impl Widget for IndexColumn<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         for row in 0..area.height {
-            let i = row as usize + self.start;
+            let Some(i) = self.row_index(row as usize) else {
+                continue;
+            };
             let text = i.to_string();
             let style = nu_style_to_tui(self.style_computer.compute(
                 "row_index",
@@ -539,12 +792,13 @@ fn render_index(
 
     style_computer: &StyleComputer,
     start_index: usize,
+    separator: Option<(usize, usize)>,
     padding_left: u16,
     padding_right: u16,
 ) -> u16 {
     let mut width = render_space(buf, area.x, area.y, area.height, padding_left);
 
-    let index = IndexColumn::new(style_computer, start_index);
+    let index = IndexColumn::new(style_computer, start_index, separator);
     let w = index.estimate_width(area.height) as u16;
     let area = Rect::new(area.x + width, area.y, w, area.height);
 
@@ -690,6 +944,19 @@ fn truncate_list(list: &mut [NuText], width: usize) {
     }
 }
 
+/// Pad texts on the left so they're aligned in a column `width` wide.
+fn align_list(list: &mut [NuText], width: usize, align: Alignment) {
+    for (text, _) in list {
+        let space = width.saturating_sub(string_width(text));
+        let left = match align {
+            Alignment::Left => 0,
+            Alignment::Center => space / 2,
+            Alignment::Right => space,
+        };
+        text.insert_str(0, &" ".repeat(left));
+    }
+}
+
 /// Render a column with an ellipsis in the header to indicate that there is more data than can be displayed
 fn render_overflow_column(buf: &mut Buffer, x: u16, y: u16, height: u16) -> u16 {
     let style = TextStyle {
@@ -702,6 +969,82 @@ fn render_overflow_column(buf: &mut Buffer, x: u16, y: u16, height: u16) -> u16
     1
 }
 
+fn render_row_separator(buf: &mut Buffer, area: Rect, hidden: usize) {
+    let text = tr!("… {hidden} more rows …", hidden = hidden);
+    let style = ratatui::style::Style::default().add_modifier(ratatui::style::Modifier::DIM);
+
+    repeat_vertical(
+        buf,
+        area.x,
+        area.y,
+        area.width,
+        1,
+        ' ',
+        TextStyle::default(),
+    );
+    Paragraph::new(text)
+        .style(style)
+        .alignment(ratatui::layout::Alignment::Center)
+        .render(area, buf);
+}
+
+/// Make the text of `area` a link to `url`, underlined, for the terminals knowing OSC 8.
+///
+/// ratatui counts the characters of the sequences as taking room, and so skips the cell after
+/// one holding them: the sequences go in the first of each pair of cells, with the text of
+/// both. A wide character could be split between pairs, such text is only underlined.
+fn render_link(buf: &mut Buffer, area: Rect, url: &str) {
+    let symbols: Vec<String> = (area.left()..area.right())
+        .map(|x| buf[(x, area.y)].symbol().to_string())
+        .collect();
+    let is_text = |symbol: &String| symbol.as_str() != " ";
+    let Some(last) = symbols.iter().rposition(is_text) else {
+        return;
+    };
+
+    let start = symbols.iter().position(is_text).unwrap_or(0);
+    for x in area.x + start as u16..=area.x + last as u16 {
+        buf[(x, area.y)].modifier.insert(Modifier::UNDERLINED);
+    }
+
+    if symbols.iter().any(|symbol| string_width(symbol) != 1) {
+        return;
+    }
+    // a cell left alone at the end would have the one after the area skipped
+    let pairs = symbols.chunks_exact(2);
+    for (x, pair) in (area.x..).step_by(2).zip(pairs) {
+        let link = format!("\x1b]8;;{url}\x1b\\{}{}\x1b]8;;\x1b\\", pair[0], pair[1]);
+        buf[(x, area.y)].set_symbol(&link);
+    }
+}
+
+/// The background of a striped row, under the cells drawn without one of their own
+fn render_stripe(buf: &mut Buffer, area: Rect, style: NuStyle) {
+    let Some(background) = nu_style_to_tui(style).bg else {
+        return;
+    };
+
+    for x in area.left()..area.right() {
+        if let Some(cell) = buf.cell_mut((x, area.y))
+            && cell.bg == ratatui::style::Color::Reset
+        {
+            cell.set_bg(background);
+        }
+    }
+}
+
+/// A horizontal line under the pinned rows, crossing the column split lines
+fn render_pinned_separator(buf: &mut Buffer, area: Rect, style: NuStyle) {
+    let style = nu_style_to_tui(style);
+    for x in area.left()..area.right() {
+        let crosses_split = buf
+            .cell((x, area.y))
+            .is_some_and(|cell| cell.symbol() == "│");
+        let line = if crosses_split { "┼" } else { "─" };
+        buf.set_span(x, area.y, &Span::styled(line, style), 1);
+    }
+}
+
 fn render_top_connector(buf: &mut Buffer, x: u16, y: u16, style: NuStyle) {
     let style = nu_style_to_tui(style);
     let span = Span::styled("┬", style);
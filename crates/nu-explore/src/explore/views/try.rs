@@ -1,14 +1,23 @@
 use super::super::{
-    config::ExploreConfig,
-    nu_common::{collect_pipeline, run_command_with_value},
+    config::{ExploreConfig, TryLayout},
+    l10n::tr,
+    nu_common::{
+        NuSpan, NuStyle, collect_pipeline, complete_pipeline, highlight_pipeline,
+        pipeline_word_start, run_command_with_value,
+    },
     pager::{Frame, Transition, ViewInfo, report::Report},
 };
-use super::{Layout, Orientation, View, ViewConfig, record::RecordView, util::nu_style_to_tui};
+use super::{
+    KeyHint, Layout, View, ViewConfig, error_pane::ErrorPane, record::RecordView,
+    util::nu_style_to_tui,
+};
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use nu_protocol::{
-    PipelineData, Value,
+    ByteStreamSource, PipelineData, ShellError, Signals, Value,
     engine::{EngineState, Stack},
+    process::ChildPipe,
+    shell_error::generic::GenericError,
 };
 use ratatui::{
     layout::Rect,
@@ -16,73 +25,529 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Paragraph},
 };
-use std::cmp::min;
+use std::{
+    cmp::min,
+    io::{self, Read},
+    mem,
+    ops::Range,
+    sync::{
+        Arc,
+        atomic::AtomicBool,
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+};
 use unicode_width::UnicodeWidthStr;
 
+// the lines of the box of the command
+const CMD_HEIGHT: u16 = 3;
+
+// how much the split moves by, in percent
+const SPLIT_STEP: u16 = 5;
+
 pub struct TryView {
-    input: Value,
+    input: TryInput,
     command: String,
     immediate: bool,
     table: Option<RecordView>,
     view_mode: bool,
     border_color: Style,
     config: ExploreConfig,
+    // the texts copied in the session, pasted with Ctrl-R and their number
+    registers: Vec<String>,
+    // set by Ctrl-R, until the number of the register is typed
+    pasting: bool,
+    // the pipelines run so far, the oldest first, gone through with Up and Down
+    history: Vec<String>,
+    // the pipeline of `history` shown, and what was typed before going through it
+    recalled: Option<(usize, String)>,
+    // the parts of the command and their style, by byte range
+    highlights: Vec<(Range<usize>, NuStyle)>,
+    // the words completing the last one of the command and which is in, while Tab is pressed
+    completion: Option<(Vec<String>, usize)>,
+    // the error the pipeline run last failed with, shown until one runs
+    error: Option<ErrorPane>,
+    layout: TryLayout,
+    // the percent of the area the pipeline takes at most
+    split: u16,
+    // whether the results take the whole area, until they're left
+    maximized: bool,
+    // what the pipeline submitted while the input is collected runs with, once it is
+    waiting: Option<(EngineState, Stack)>,
+}
+
+/// The input pipelines are tried on. A stream is collected on a thread, so that the view
+/// shows up and takes keys without waiting for all of it; a pipeline run meanwhile waits for
+/// the rest. The error collecting it failed with is kept, each pipeline run failing with it
+/// rather than running on nothing.
+enum TryInput {
+    Collecting {
+        receiver: Receiver<Collected>,
+        // the rows of a list stream received so far
+        rows: Vec<Value>,
+        span: NuSpan,
+        // stops the thread once the view is gone, as an endless stream is never collected
+        stop: Signals,
+    },
+    Collected(Value),
+    Failed(ShellError),
+}
+
+// what the collecting thread sends: the rows of a list stream one by one and then its end, or
+// all of any other stream at once
+enum Collected {
+    Row(Value),
+    End,
+    Whole(Result<Value, ShellError>),
+}
+
+// the rows taken at most on each poll, so that a fast stream doesn't hold up the view
+const ROWS_PER_POLL: usize = 10_000;
+
+impl TryInput {
+    /// Collect `input`, until it ends or `signals` of the engine are set.
+    fn collect(input: PipelineData, signals: &Signals) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let stop = Signals::new(Arc::new(AtomicBool::new(false)));
+        let stopped = Stopped {
+            view: stop.clone(),
+            engine: signals.clone(),
+        };
+        let span = match input {
+            PipelineData::Empty => return Self::Collected(Value::nothing(NuSpan::unknown())),
+            PipelineData::Value(value, _) => return Self::Collected(value),
+            PipelineData::ListStream(stream, _) => {
+                let span = stream.span();
+                thread::spawn(move || {
+                    for value in stream {
+                        if stopped.is_set() {
+                            return;
+                        }
+                        let row = match value {
+                            Value::Error { error, .. } => Collected::Whole(Err(*error)),
+                            value => Collected::Row(value),
+                        };
+                        let failed = matches!(row, Collected::Whole(_));
+                        if sender.send(row).is_err() || failed {
+                            return;
+                        }
+                    }
+                    let _ = sender.send(Collected::End);
+                });
+                span
+            }
+            PipelineData::ByteStream(mut stream, _) => {
+                let span = stream.span();
+                read_until_stopped(stream.source_mut(), stopped);
+                thread::spawn(move || {
+                    let _ = sender.send(Collected::Whole(stream.into_value()));
+                });
+                span
+            }
+        };
+
+        Self::Collecting {
+            receiver,
+            rows: Vec::new(),
+            span,
+            stop,
+        }
+    }
+
+    /// Take what was collected by now, telling whether all of the input just was.
+    fn poll(&mut self) -> bool {
+        for _ in 0..ROWS_PER_POLL {
+            let TryInput::Collecting { receiver, .. } = self else {
+                return false;
+            };
+            match receiver.try_recv() {
+                Ok(collected) => {
+                    if self.receive(collected) {
+                        return true;
+                    }
+                }
+                Err(TryRecvError::Empty) => return false,
+                Err(TryRecvError::Disconnected) => {
+                    *self = Self::gone();
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// The input, waiting for the rest of it to be collected.
+    fn wait(&mut self) -> Result<&Value> {
+        while let TryInput::Collecting { receiver, .. } = self {
+            match receiver.recv() {
+                Ok(collected) => {
+                    self.receive(collected);
+                }
+                Err(_) => *self = Self::gone(),
+            }
+        }
+
+        self.value()
+    }
+
+    // true once all of the input is received
+    fn receive(&mut self, collected: Collected) -> bool {
+        let TryInput::Collecting { rows, span, .. } = self else {
+            return true;
+        };
+        *self = match collected {
+            Collected::Row(row) => {
+                rows.push(row);
+                return false;
+            }
+            Collected::End => Self::Collected(Value::list(mem::take(rows), *span)),
+            Collected::Whole(Ok(value)) => Self::Collected(value),
+            Collected::Whole(Err(err)) => Self::Failed(err),
+        };
+        true
+    }
+
+    // the thread went without sending all of the input
+    fn gone() -> Self {
+        Self::Failed(ShellError::Generic(GenericError::new_internal(
+            tr!("The input could not be collected"),
+            "",
+        )))
+    }
+
+    /// The rows received so far, while the input is being collected.
+    fn collecting(&self) -> Option<usize> {
+        match self {
+            TryInput::Collecting { rows, .. } => Some(rows.len()),
+            _ => None,
+        }
+    }
+
+    /// The input, if it's collected by now.
+    fn value(&mut self) -> Result<&Value> {
+        self.poll();
+        match self {
+            TryInput::Collected(value) => Ok(value),
+            TryInput::Failed(err) => Err(err.clone().into()),
+            TryInput::Collecting { .. } => {
+                Err(anyhow::anyhow!(tr!("The input is still being collected")))
+            }
+        }
+    }
+}
+
+impl Drop for TryInput {
+    fn drop(&mut self) {
+        if let TryInput::Collecting { stop, .. } = self {
+            stop.trigger();
+        }
+    }
+}
+
+// whether collecting is to stop, the view being gone or the engine interrupted
+#[derive(Clone)]
+struct Stopped {
+    view: Signals,
+    engine: Signals,
+}
+
+impl Stopped {
+    fn is_set(&self) -> bool {
+        self.view.interrupted() || self.engine.interrupted()
+    }
+}
+
+// a reader ending once collecting is stopped
+struct StoppedReader<R> {
+    reader: R,
+    stopped: Stopped,
+}
+
+impl<R: Read> Read for StoppedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.stopped.is_set() {
+            return Ok(0);
+        }
+        self.reader.read(buf)
+    }
+}
+
+// make the bytes of `source` end once collecting is stopped; the output of a process is
+// wrapped in place, for its exit status to still fail the input
+fn read_until_stopped(source: &mut ByteStreamSource, stopped: Stopped) {
+    let taken = mem::replace(source, ByteStreamSource::Read(Box::new(io::empty())));
+    *source = match taken {
+        ByteStreamSource::Read(reader) => {
+            ByteStreamSource::Read(Box::new(StoppedReader { reader, stopped }))
+        }
+        ByteStreamSource::File(reader) => {
+            ByteStreamSource::Read(Box::new(StoppedReader { reader, stopped }))
+        }
+        ByteStreamSource::Child(mut child) => {
+            child.stdout = child
+                .stdout
+                .take()
+                .map(|reader| ChildPipe::Tee(Box::new(StoppedReader { reader, stopped })));
+            ByteStreamSource::Child(child)
+        }
+    };
 }
 
 impl TryView {
     pub fn new(input: Value, config: ExploreConfig) -> Self {
+        Self::with_input(TryInput::Collected(input), config)
+    }
+
+    /// A view trying pipelines on `input`, collected in the background while it's shown
+    /// until it ends or the engine is interrupted through `signals`.
+    pub fn from_pipeline(input: PipelineData, signals: &Signals, config: ExploreConfig) -> Self {
+        Self::with_input(TryInput::collect(input, signals), config)
+    }
+
+    fn with_input(input: TryInput, config: ExploreConfig) -> Self {
         Self {
             input,
             table: None,
             immediate: config.try_reactive,
             border_color: nu_style_to_tui(config.table.separator_style),
+            layout: config.try_layout,
+            split: config.try_split,
             view_mode: false,
             command: String::new(),
             config,
+            registers: Vec::new(),
+            pasting: false,
+            history: Vec::new(),
+            recalled: None,
+            highlights: Vec::new(),
+            completion: None,
+            error: None,
+            maximized: false,
+            waiting: None,
         }
     }
 
+    /// Go through the pipelines run earlier, in the session or before with
+    /// `explore.try.history`.
+    pub fn with_history(mut self, history: &[String]) -> Self {
+        self.history = history.to_vec();
+        self
+    }
+
+    /// Offer the texts copied so far for pasting, as listed by `:registers`.
+    pub fn with_registers(mut self, registers: &[String]) -> Self {
+        self.registers = registers.to_vec();
+        self
+    }
+
     pub fn init(&mut self, command: String) {
         self.command = command;
     }
 
+    // show an earlier pipeline, `back` being the way to the oldest; going forward past the
+    // latest brings back what was typed
+    fn recall(&mut self, back: bool) -> bool {
+        let latest = match self.history.len() {
+            0 => return false,
+            len => len - 1,
+        };
+        let index = match (&self.recalled, back) {
+            (None, true) => latest,
+            (None, false) => return false,
+            (Some((index, _)), true) => index.saturating_sub(1),
+            (Some((index, _)), false) if *index < latest => index + 1,
+            (Some(_), false) => {
+                if let Some((_, typed)) = self.recalled.take() {
+                    self.command = typed;
+                }
+                return true;
+            }
+        };
+
+        let typed = match self.recalled.take() {
+            Some((_, typed)) => typed,
+            None => std::mem::take(&mut self.command),
+        };
+        self.command.clone_from(&self.history[index]);
+        self.recalled = Some((index, typed));
+        true
+    }
+
+    // the pipeline is kept, even when it fails, to be fixed
+    fn remember(&mut self, info: &mut ViewInfo) {
+        self.recalled = None;
+        let command = self.command.trim();
+        if command.is_empty() || self.history.last().is_some_and(|last| last == command) {
+            return;
+        }
+        self.history.push(command.to_string());
+        info.tried.push(command.to_string());
+    }
+
+    // the command was changed: it's highlighted again, and run if pipelines are run as
+    // they're typed
+    fn edited(&mut self, engine_state: &EngineState, stack: &mut Stack, info: &mut ViewInfo) {
+        if !self.immediate {
+            self.highlights = highlight_pipeline(engine_state, stack, &self.command);
+            return;
+        }
+
+        match self.try_run(engine_state, stack) {
+            Ok(_) => info.report = Some(Report::default()),
+            Err(err) => info.report = Some(Report::error(tr!("Error: {err}", err = err))),
+        }
+    }
+
+    // complete the word typed last, the next candidate on each press; false if nothing does
+    fn complete(&mut self, engine_state: &EngineState) -> bool {
+        let start = pipeline_word_start(&self.command);
+        let completion = match self.completion.take() {
+            Some((candidates, index)) => {
+                let next = (index + 1) % candidates.len();
+                Some((candidates, next))
+            }
+            None => match self.input.value() {
+                Ok(input) => {
+                    let candidates = complete_pipeline(engine_state, &self.command, input);
+                    (!candidates.is_empty()).then_some((candidates, 0))
+                }
+                Err(_) => None,
+            },
+        };
+        let Some((candidates, index)) = completion else {
+            return false;
+        };
+
+        self.command.truncate(start);
+        self.command.push_str(&candidates[index]);
+        self.completion = Some((candidates, index));
+        true
+    }
+
+    /// Run the pipeline on the input, or once the input is collected if it's still being.
     pub fn try_run(&mut self, engine_state: &EngineState, stack: &mut Stack) -> Result<()> {
-        let view = run_command(
+        self.highlights = highlight_pipeline(engine_state, stack, &self.command);
+        self.input.poll();
+        if self.input.collecting().is_some() {
+            self.waiting = Some((engine_state.clone(), stack.clone()));
+            return Ok(());
+        }
+        self.waiting = None;
+
+        let result = run_command(
             &self.command,
-            &self.input,
+            self.input.value()?,
             engine_state,
             stack,
             &self.config,
-        )?;
-        self.table = Some(view);
-        Ok(())
+        );
+        match result {
+            Ok(view) => {
+                self.table = Some(view);
+                self.error = None;
+                Ok(())
+            }
+            Err(err) => {
+                // spans count from the end of what the engine parsed before the pipeline
+                self.error = err.downcast_ref::<ShellError>().map(|error| {
+                    ErrorPane::new(error, &self.command, engine_state.next_span_start())
+                });
+                Err(err)
+            }
+        }
     }
-}
 
-impl View for TryView {
-    fn draw(&mut self, f: &mut Frame, area: Rect, cfg: ViewConfig<'_>, layout: &mut Layout) {
-        let border_color = self.border_color;
+    // Ctrl-L switches the layout, Ctrl and the arrows move the split and Ctrl-O maximizes
+    // the results, whether the command or the results are focused
+    fn handle_layout_key(&mut self, key: KeyEvent, info: &mut ViewInfo) -> bool {
+        if key.modifiers != KeyModifiers::CONTROL {
+            return false;
+        }
 
-        // Calculate areas with better proportions
-        let cmd_height: u16 = 3;
-        let margin: u16 = 1;
+        match key.code {
+            KeyCode::Char('l') => {
+                self.layout = match self.layout {
+                    TryLayout::Stacked => TryLayout::SideBySide,
+                    TryLayout::SideBySide => TryLayout::Stacked,
+                };
+                self.maximized = false;
+            }
+            KeyCode::Left | KeyCode::Up => {
+                self.split = self
+                    .split
+                    .saturating_sub(SPLIT_STEP)
+                    .max(TryLayout::MIN_SPLIT as u16);
+            }
+            KeyCode::Right | KeyCode::Down => {
+                self.split = (self.split + SPLIT_STEP).min(TryLayout::MAX_SPLIT as u16);
+            }
+            KeyCode::Char('o') if self.maximized => self.maximized = false,
+            KeyCode::Char('o') if self.table.is_some() => {
+                self.maximized = true;
+                self.view_mode = true;
+            }
+            KeyCode::Char('o') => {
+                info.report = Some(Report::info(tr!("No results yet, run a pipeline first")));
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    // back to editing the command, which shows again if the results were maximized
+    fn leave_results(&mut self) {
+        self.view_mode = false;
+        self.maximized = false;
+    }
+
+    // the areas of the command, of the error under it and of the results, as laid out
+    fn panes(&self, area: Rect) -> (Rect, Rect, Rect) {
+        let error_height = self.error.as_ref().map_or(0, ErrorPane::height);
+        let empty = Rect::new(area.x, area.y, area.width, 0);
+        if self.maximized {
+            return (empty, empty, area);
+        }
 
-        // Command input area at the top
+        let (pipeline_area, table_area) = match self.layout {
+            // the pipeline takes what its command and error need, up to the split
+            TryLayout::Stacked => {
+                let most = (share(area.height, self.split)).max(CMD_HEIGHT);
+                let height = min(CMD_HEIGHT + error_height, most).min(area.height);
+                let pipeline = Rect::new(area.x, area.y, area.width, height);
+                let table = Rect::new(area.x, area.y + height, area.width, area.height - height);
+                (pipeline, table)
+            }
+            TryLayout::SideBySide => {
+                let width = share(area.width, self.split);
+                let pipeline = Rect::new(area.x, area.y, width, area.height);
+                let table = Rect::new(area.x + width, area.y, area.width - width, area.height);
+                (pipeline, table)
+            }
+        };
+
+        let cmd_height = min(CMD_HEIGHT, pipeline_area.height);
         let cmd_area = Rect::new(
-            area.x + margin,
-            area.y,
-            area.width.saturating_sub(margin * 2),
+            pipeline_area.x,
+            pipeline_area.y,
+            pipeline_area.width,
             cmd_height,
         );
-
-        // Results area below
-        let table_area = Rect::new(
-            area.x + margin,
-            area.y + cmd_height,
-            area.width.saturating_sub(margin * 2),
-            area.height.saturating_sub(cmd_height),
+        let error_area = Rect::new(
+            pipeline_area.x,
+            pipeline_area.y + cmd_height,
+            pipeline_area.width,
+            min(error_height, pipeline_area.height - cmd_height),
         );
 
+        (cmd_area, error_area, table_area)
+    }
+
+    // the box of the command typed, with the cursor at its end while it's edited
+    fn draw_command(&self, f: &mut Frame, cmd_area: Rect) {
+        let border_color = self.border_color;
+
         // Draw command input block with rounded corners
         let cmd_block = if self.view_mode {
             Block::default()
@@ -135,10 +600,13 @@ impl View for TryView {
             input
         };
 
-        let cmd_line = Line::from(vec![
-            Span::styled(prompt, border_color.add_modifier(Modifier::BOLD)),
-            Span::raw(display_input),
-        ]);
+        let skip = input.len() - display_input.len();
+        let mut spans = vec![Span::styled(
+            prompt,
+            border_color.add_modifier(Modifier::BOLD),
+        )];
+        spans.extend(highlighted_spans(input, skip, &self.highlights));
+        let cmd_line = Line::from(spans);
         let cmd_input = Paragraph::new(cmd_line);
         f.render_widget(cmd_input, cmd_input_area);
 
@@ -151,6 +619,30 @@ impl View for TryView {
                 f.set_cursor_position((cursor_x, cmd_input_area.y));
             }
         }
+    }
+}
+
+impl View for TryView {
+    fn draw(&mut self, f: &mut Frame, area: Rect, cfg: ViewConfig<'_>, layout: &mut Layout) {
+        let border_color = self.border_color;
+
+        let margin: u16 = 1;
+        let area = Rect::new(
+            area.x + margin,
+            area.y,
+            area.width.saturating_sub(margin * 2),
+            area.height,
+        );
+        let (cmd_area, error_area, table_area) = self.panes(area);
+
+        if cmd_area.height > 0 {
+            self.draw_command(f, cmd_area);
+        }
+        if let Some(error) = &self.error
+            && error_area.height > 0
+        {
+            f.render_widget(error, error_area);
+        }
 
         // Draw results block with rounded corners
         let table_block = if self.view_mode {
@@ -220,6 +712,10 @@ impl View for TryView {
         info: &mut ViewInfo,
         key: KeyEvent,
     ) -> Transition {
+        if self.handle_layout_key(key, info) {
+            return Transition::Ok;
+        }
+
         if self.view_mode {
             let table = self
                 .table
@@ -229,12 +725,12 @@ impl View for TryView {
             let was_at_the_top = table.get_cursor_position().row == 0;
 
             if was_at_the_top && matches!(key.code, KeyCode::Up | KeyCode::PageUp) {
-                self.view_mode = false;
+                self.leave_results();
                 return Transition::Ok;
             }
 
             if let KeyCode::Tab = key.code {
-                self.view_mode = false;
+                self.leave_results();
                 return Transition::Ok;
             }
 
@@ -243,38 +739,74 @@ impl View for TryView {
             return match result {
                 Transition::Ok | Transition::Cmd { .. } => Transition::Ok,
                 Transition::Exit => {
-                    self.view_mode = false;
+                    self.leave_results();
                     Transition::Ok
                 }
+                Transition::Prompt(text) => Transition::Prompt(text),
+                Transition::Suspend => Transition::Suspend,
                 Transition::None => Transition::None,
             };
         }
 
+        if std::mem::take(&mut self.pasting) {
+            let register = match key.code {
+                KeyCode::Char(c) => c.to_digit(10).and_then(|n| self.registers.get(n as usize)),
+                _ => None,
+            };
+            match register {
+                Some(text) => {
+                    self.command.push_str(text);
+                    self.edited(engine_state, stack, info);
+                }
+                None => info.report = Some(Report::default()),
+            }
+
+            return Transition::Ok;
+        }
+        if key.code == KeyCode::Char('r') && key.modifiers == KeyModifiers::CONTROL {
+            if self.registers.is_empty() {
+                info.report = Some(Report::info(tr!("Nothing was copied yet")));
+            } else {
+                self.pasting = true;
+                info.report = Some(Report::info(tr!(
+                    "Paste register 0-{last}, see :registers",
+                    last = self.registers.len() - 1
+                )));
+            }
+
+            return Transition::Ok;
+        }
+
+        // Tab goes on to the next candidate, any other key keeps the one completed
+        if key.code != KeyCode::Tab {
+            self.completion = None;
+        }
+
         match &key.code {
             KeyCode::Esc => Transition::Exit,
+            KeyCode::Tab if self.complete(engine_state) => {
+                self.edited(engine_state, stack, info);
+
+                Transition::Ok
+            }
             KeyCode::Backspace => {
+                self.recalled = None;
                 if !self.command.is_empty() {
                     self.command.pop();
-
-                    if self.immediate {
-                        match self.try_run(engine_state, stack) {
-                            Ok(_) => info.report = Some(Report::default()),
-                            Err(err) => info.report = Some(Report::error(format!("Error: {err}"))),
-                        }
-                    }
+                    self.edited(engine_state, stack, info);
                 }
 
                 Transition::Ok
             }
             KeyCode::Char(c) => {
+                self.recalled = None;
                 self.command.push(*c);
+                self.edited(engine_state, stack, info);
 
-                if self.immediate {
-                    match self.try_run(engine_state, stack) {
-                        Ok(_) => info.report = Some(Report::default()),
-                        Err(err) => info.report = Some(Report::error(format!("Error: {err}"))),
-                    }
-                }
+                Transition::Ok
+            }
+            KeyCode::Up | KeyCode::Down if self.recall(key.code == KeyCode::Up) => {
+                self.edited(engine_state, stack, info);
 
                 Transition::Ok
             }
@@ -286,9 +818,10 @@ impl View for TryView {
                 Transition::Ok
             }
             KeyCode::Enter => {
+                self.remember(info);
                 match self.try_run(engine_state, stack) {
                     Ok(_) => info.report = Some(Report::default()),
-                    Err(err) => info.report = Some(Report::error(format!("Error: {err}"))),
+                    Err(err) => info.report = Some(Report::error(tr!("Error: {err}", err = err))),
                 }
 
                 Transition::Ok
@@ -297,8 +830,44 @@ impl View for TryView {
         }
     }
 
+    fn update(&mut self, info: &mut ViewInfo) -> bool {
+        if !self.input.poll() {
+            let Some(rows) = self.input.collecting() else {
+                return false;
+            };
+            let message = match (rows, self.waiting.is_some()) {
+                (0, false) => tr!("Collecting the input"),
+                (0, true) => tr!("Collecting the input, the pipeline runs once it's done"),
+                (rows, false) => tr!("Collecting the input: {rows} rows", rows = rows),
+                (rows, true) => tr!(
+                    "Collecting the input: {rows} rows, the pipeline runs once it's done",
+                    rows = rows
+                ),
+            };
+            info.status = Some(Report::info(message));
+            return true;
+        }
+
+        info.report = Some(match &self.input {
+            TryInput::Failed(err) => {
+                Report::error(tr!("The input could not be collected: {err}", err = err))
+            }
+            _ => Report::info(tr!("The input is collected")),
+        });
+        if let Some((engine_state, mut stack)) = self.waiting.take()
+            && let Err(err) = self.try_run(&engine_state, &mut stack)
+        {
+            info.report = Some(Report::error(tr!("Error: {err}", err = err)));
+        }
+        false
+    }
+
     fn exit(&mut self) -> Option<Value> {
-        self.table.as_mut().and_then(|v| v.exit())
+        match &mut self.table {
+            Some(table) => table.exit(),
+            // nothing was run yet, so what follows gets the input itself
+            None => self.input.wait().ok().cloned(),
+        }
     }
 
     fn collect_data(&self) -> Vec<super::super::nu_common::NuText> {
@@ -310,6 +879,79 @@ impl View for TryView {
     fn show_data(&mut self, i: usize) -> bool {
         self.table.as_mut().is_some_and(|v| v.show_data(i))
     }
+
+    fn key_hints(&self) -> Vec<KeyHint> {
+        if self.view_mode
+            && let Some(table) = &self.table
+        {
+            let mut hints = vec![KeyHint::new("Tab", "edit command")];
+            let maximize = if self.maximized {
+                "restore"
+            } else {
+                "maximize"
+            };
+            hints.push(KeyHint::new("Ctrl-O", maximize));
+            hints.extend(table.key_hints());
+            return hints;
+        }
+
+        let mut hints = vec![
+            KeyHint::new("Enter", "run"),
+            KeyHint::new("Tab", "complete, or to results"),
+            KeyHint::new("Esc", "cancel"),
+        ];
+        if !self.history.is_empty() {
+            hints.push(KeyHint::new("↑↓", "history"));
+        }
+        if !self.registers.is_empty() {
+            hints.push(KeyHint::new("Ctrl-R", "paste"));
+        }
+        hints.push(KeyHint::new("Ctrl-L", "layout"));
+
+        hints
+    }
+
+    fn as_record_view(&mut self) -> Option<&mut RecordView> {
+        self.table.as_mut()
+    }
+
+    fn kind(&self) -> &'static str {
+        "try"
+    }
+}
+
+// the spans of `line` from the byte `skip` on, styled as `highlights` say; the text
+// between the parts highlighted is left as it is
+fn highlighted_spans<'a>(
+    line: &'a str,
+    skip: usize,
+    highlights: &[(Range<usize>, NuStyle)],
+) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    let mut at = skip;
+    for (range, style) in highlights {
+        // the parts of a command edited since it was highlighted may not fit it anymore
+        let start = range.start.max(at);
+        let end = range.end.min(line.len());
+        if start >= end || !line.is_char_boundary(start) || !line.is_char_boundary(end) {
+            continue;
+        }
+        if at < start {
+            spans.push(Span::raw(&line[at..start]));
+        }
+        spans.push(Span::styled(&line[start..end], nu_style_to_tui(*style)));
+        at = end;
+    }
+    if at < line.len() {
+        spans.push(Span::raw(&line[at..]));
+    }
+
+    spans
+}
+
+// `percent` of `length`
+fn share(length: u16, percent: u16) -> u16 {
+    (u32::from(length) * u32::from(percent) / 100) as u16
 }
 
 fn run_command(
@@ -327,8 +969,279 @@ fn run_command(
 
     let mut view = RecordView::new(columns, values, config.clone());
     if is_record {
-        view.set_top_layer_orientation(Orientation::Left);
+        view.set_top_layer_record();
     }
 
     Ok(view)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_protocol::{ByteStream, ByteStreamType, ListStream, Signals};
+
+    #[test]
+    fn stream_is_collected_on_a_thread() {
+        let span = NuSpan::test_data();
+        let values = (0..3).map(move |i| Value::int(i, span));
+        let stream = ListStream::new(values, span, Signals::empty());
+        let mut input =
+            TryInput::collect(PipelineData::list_stream(stream, None), &Signals::empty());
+
+        let value = input.wait().unwrap();
+        assert_eq!(value.as_list().unwrap().len(), 3);
+        assert!(matches!(input, TryInput::Collected(_)));
+
+        let mut empty = TryInput::collect(PipelineData::empty(), &Signals::empty());
+        assert!(empty.value().unwrap().is_nothing());
+    }
+
+    #[test]
+    fn error_collecting_is_kept() {
+        struct Broken;
+        impl std::io::Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+        }
+        let span = NuSpan::test_data();
+        let stream = ByteStream::read(Broken, span, Signals::empty(), ByteStreamType::Unknown);
+        let mut input =
+            TryInput::collect(PipelineData::byte_stream(stream, None), &Signals::empty());
+
+        assert!(input.wait().is_err());
+        assert!(matches!(input, TryInput::Failed(_)));
+        // every run after the first fails with it too
+        assert!(input.value().is_err());
+    }
+
+    #[test]
+    fn collecting_stops_with_the_view() {
+        // tells when the stream is dropped, once the thread is done with it
+        struct Dropped(Arc<AtomicBool>);
+        impl Drop for Dropped {
+            fn drop(&mut self) {
+                self.0.store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = Dropped(dropped.clone());
+        let span = NuSpan::test_data();
+        let endless = std::iter::repeat_with(move || {
+            let _ = &guard;
+            Value::int(1, span)
+        });
+        let stream = ListStream::new(endless, span, Signals::empty());
+        let mut input =
+            TryInput::collect(PipelineData::list_stream(stream, None), &Signals::empty());
+
+        while input.collecting() == Some(0) {
+            input.poll();
+        }
+        drop(input);
+        while !dropped.load(std::sync::atomic::Ordering::Relaxed) {
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn pipeline_waits_for_the_input() {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        let mut info = ViewInfo::default();
+        let (sender, receiver) = mpsc::channel();
+        let span = NuSpan::test_data();
+        let values = receiver.into_iter().map(move |i| Value::int(i, span));
+        let stream = ListStream::new(values, span, Signals::empty());
+        let mut view = TryView::from_pipeline(
+            PipelineData::list_stream(stream, None),
+            &Signals::empty(),
+            ExploreConfig::default(),
+        );
+        view.init("$in".to_string());
+
+        sender.send(1).unwrap();
+        view.try_run(&engine_state, &mut stack).unwrap();
+        assert!(view.table.is_none());
+        assert!(view.update(&mut info));
+
+        sender.send(2).unwrap();
+        drop(sender);
+        while view.update(&mut info) {}
+        let table = view.table.as_ref().unwrap();
+        assert_eq!(table.get_top_layer().record_values.len(), 2);
+    }
+
+    #[test]
+    fn paste_a_register() {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        let mut info = ViewInfo::default();
+        let registers = ["ls".to_string(), "get name".to_string()];
+        let mut view = TryView::new(Value::test_nothing(), ExploreConfig::default())
+            .with_registers(&registers);
+
+        let mut press = |view: &mut TryView, code, modifiers| {
+            let key = KeyEvent::new(code, modifiers);
+            view.handle_input(
+                &engine_state,
+                &mut stack,
+                &Layout::default(),
+                &mut info,
+                key,
+            );
+        };
+        press(&mut view, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        press(&mut view, KeyCode::Char('1'), KeyModifiers::NONE);
+        assert_eq!(view.command, "get name");
+
+        // a register that isn't there pastes nothing
+        press(&mut view, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        press(&mut view, KeyCode::Char('7'), KeyModifiers::NONE);
+        assert_eq!(view.command, "get name");
+    }
+
+    #[test]
+    fn go_through_the_pipelines_run() {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        let mut info = ViewInfo::default();
+        let history = ["ls".to_string()];
+        let mut view =
+            TryView::new(Value::test_nothing(), ExploreConfig::default()).with_history(&history);
+
+        let mut press = |view: &mut TryView, info: &mut ViewInfo, code| {
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            view.handle_input(&engine_state, &mut stack, &Layout::default(), info, key);
+        };
+        for c in "get name".chars() {
+            press(&mut view, &mut info, KeyCode::Char(c));
+        }
+        press(&mut view, &mut info, KeyCode::Enter);
+        assert_eq!(info.tried, ["get name"]);
+
+        press(&mut view, &mut info, KeyCode::Char('x'));
+        press(&mut view, &mut info, KeyCode::Up);
+        assert_eq!(view.command, "get name");
+        press(&mut view, &mut info, KeyCode::Up);
+        press(&mut view, &mut info, KeyCode::Up);
+        assert_eq!(view.command, "ls");
+        press(&mut view, &mut info, KeyCode::Down);
+        assert_eq!(view.command, "get name");
+
+        // past the latest, what was typed comes back
+        press(&mut view, &mut info, KeyCode::Down);
+        assert_eq!(view.command, "get namex");
+
+        // running the latest again doesn't repeat it
+        press(&mut view, &mut info, KeyCode::Up);
+        press(&mut view, &mut info, KeyCode::Enter);
+        assert_eq!(view.history, ["ls", "get name"]);
+        assert_eq!(info.tried, ["get name"]);
+    }
+
+    #[test]
+    fn tab_completes_cell_paths() {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        let mut info = ViewInfo::default();
+        let input = Value::test_record(nu_protocol::record! {
+            "name" => Value::test_string("a"),
+            "size" => Value::test_int(1),
+        });
+        let mut view = TryView::new(input, ExploreConfig::default());
+
+        let mut press = |view: &mut TryView, code| {
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            view.handle_input(
+                &engine_state,
+                &mut stack,
+                &Layout::default(),
+                &mut info,
+                key,
+            );
+        };
+        for c in "$in.".chars() {
+            press(&mut view, KeyCode::Char(c));
+        }
+        press(&mut view, KeyCode::Tab);
+        assert_eq!(view.command, "$in.name");
+        press(&mut view, KeyCode::Tab);
+        assert_eq!(view.command, "$in.size");
+        assert!(!view.highlights.is_empty());
+
+        // nothing completes it, and there are no results to go to
+        press(&mut view, KeyCode::Char(' '));
+        press(&mut view, KeyCode::Tab);
+        assert_eq!(view.command, "$in.size ");
+        assert!(!view.view_mode);
+    }
+
+    #[test]
+    fn the_error_stays_until_a_pipeline_runs() {
+        let engine_state = EngineState::new();
+        let mut stack = Stack::new();
+        let mut info = ViewInfo::default();
+        let mut view = TryView::new(Value::test_nothing(), ExploreConfig::default());
+
+        let mut press = |view: &mut TryView, code| {
+            let key = KeyEvent::new(code, KeyModifiers::NONE);
+            view.handle_input(
+                &engine_state,
+                &mut stack,
+                &Layout::default(),
+                &mut info,
+                key,
+            );
+        };
+        for c in "1 + (".chars() {
+            press(&mut view, KeyCode::Char(c));
+        }
+        press(&mut view, KeyCode::Enter);
+        // the message, the pipeline, its underline and the border
+        let error = view.error.as_ref().expect("the pipeline doesn't parse");
+        assert!(error.height() >= 5);
+
+        press(&mut view, KeyCode::Backspace);
+        press(&mut view, KeyCode::Char('2'));
+        assert!(view.error.is_some());
+        press(&mut view, KeyCode::Enter);
+        assert!(view.error.is_none());
+    }
+
+    #[test]
+    fn lay_the_panes_out() {
+        let mut info = ViewInfo::default();
+        let mut view = TryView::new(Value::test_nothing(), ExploreConfig::default());
+        let area = Rect::new(0, 0, 100, 40);
+
+        let (cmd, error, table) = view.panes(area);
+        assert_eq!((cmd.height, error.height), (CMD_HEIGHT, 0));
+        assert_eq!((table.y, table.height), (CMD_HEIGHT, 40 - CMD_HEIGHT));
+
+        let mut press = |view: &mut TryView, code| {
+            let key = KeyEvent::new(code, KeyModifiers::CONTROL);
+            assert!(view.handle_layout_key(key, &mut info));
+        };
+        press(&mut view, KeyCode::Char('l'));
+        press(&mut view, KeyCode::Right);
+        let (cmd, _, table) = view.panes(area);
+        assert_eq!((cmd.width, cmd.height), (45, CMD_HEIGHT));
+        assert_eq!((table.x, table.width, table.height), (45, 55, 40));
+
+        // nothing to maximize before a pipeline ran
+        press(&mut view, KeyCode::Char('o'));
+        assert!(!view.maximized);
+        view.table = Some(RecordView::new(
+            Vec::new(),
+            Vec::new(),
+            ExploreConfig::default(),
+        ));
+        press(&mut view, KeyCode::Char('o'));
+        assert_eq!(view.panes(area).2, area);
+        assert!(view.view_mode);
+
+        view.leave_results();
+        assert_eq!(view.panes(area).2.width, 55);
+    }
+}
@@ -4,6 +4,10 @@ mod explore;
 mod explore_config;
 mod explore_regex;
 
-pub use default_context::add_explore_context;
-pub use explore::{Explore, ExploreConfig};
+pub use default_context::{ExploreContext, add_explore_context, add_explore_context_with};
+#[doc(hidden)]
+pub use explore::bench;
+#[doc(hidden)]
+pub use explore::deterministic;
+pub use explore::{Explore, ExploreCommand, ExploreConfig};
 pub use explore_regex::ExploreRegex;
@@ -2,7 +2,7 @@ use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet, hash_map::Entry},
     sync::{
         Arc, Mutex,
-        mpsc::{Receiver, RecvTimeoutError, Sender, TryRecvError},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError},
     },
 };
 
@@ -13,7 +13,7 @@ use std::time::Duration;
 
 use nu_system::{UnfreezeHandle, kill_by_pid};
 
-use crate::{PipelineData, Signals, shell_error};
+use crate::{PipelineData, ShellError, Signals, Span, Value, shell_error};
 
 use crate::JobId;
 
@@ -150,6 +150,10 @@ pub struct ThreadJob {
     pids: Arc<Mutex<HashSet<u32>>>,
     description: Option<String>,
     pub sender: Sender<Mail>,
+    // where the values the job outputs go while something follows it
+    follower: Arc<Mutex<Option<Sender<Value>>>>,
+    // how the job ended, once it did
+    outcome: Arc<Mutex<Option<Result<(), ShellError>>>>,
 }
 
 impl ThreadJob {
@@ -159,9 +163,70 @@ impl ThreadJob {
             pids: Arc::new(Mutex::new(HashSet::default())),
             sender,
             description,
+            follower: Arc::new(Mutex::new(None)),
+            outcome: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Receive the values the job outputs from now on, in place of whatever followed it before.
+    pub fn follow(&self) -> Receiver<Value> {
+        let (sender, receiver) = mpsc::channel();
+        *self.follower.lock().expect("follower lock was poisoned") = Some(sender);
+        receiver
+    }
+
+    /// Consume the output of the job, handing its values to whatever follows the job.
+    ///
+    /// Like [`PipelineData::drain`], it returns the error the output has, if any.
+    pub fn output(&self, data: PipelineData) -> Result<(), ShellError> {
+        let hand_on = |value: Value| {
+            if let Some(follower) = &*self.follower.lock().expect("follower lock was poisoned") {
+                let _ = follower.send(value);
+            }
+        };
+
+        match data {
+            PipelineData::ListStream(stream, ..) => {
+                for value in stream {
+                    if let Value::Error { error, .. } = value {
+                        return Err(*error);
+                    }
+                    hand_on(value);
+                }
+                Ok(())
+            }
+            PipelineData::Empty => Ok(()),
+            data if self.is_followed() => match data.into_value(Span::unknown())? {
+                Value::Error { error, .. } => Err(*error),
+                value => {
+                    hand_on(value);
+                    Ok(())
+                }
+            },
+            data => data.drain(),
+        }
+    }
+
+    fn is_followed(&self) -> bool {
+        self.follower
+            .lock()
+            .expect("follower lock was poisoned")
+            .is_some()
+    }
+
+    /// Keep how the job ended, for [`ThreadJob::outcome`].
+    pub fn finish(&self, outcome: Result<(), ShellError>) {
+        *self.outcome.lock().expect("outcome lock was poisoned") = Some(outcome);
+    }
+
+    /// How the job ended, if it did and told with [`ThreadJob::finish`].
+    pub fn outcome(&self) -> Option<Result<(), ShellError>> {
+        self.outcome
+            .lock()
+            .expect("outcome lock was poisoned")
+            .clone()
+    }
+
     /// Tries to add the provided pid to the active pid set of the current job.
     ///
     /// Returns true if the pid was added successfully, or false if the